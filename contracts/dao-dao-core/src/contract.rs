@@ -642,6 +642,7 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::Cw20Balances { start_after, limit } => {
             query_cw20_balances(deps, env, start_after, limit)
         }
+        QueryMsg::Snip20Balance { token } => query_snip20_balance(deps, env, token),
         QueryMsg::Cw721TokenList { start_after, limit } => {
             query_cw721_list(deps, start_after, limit)
         }
@@ -1004,6 +1005,26 @@ pub fn query_cw20_balances(
     to_binary(&balances)
 }
 
+pub fn query_snip20_balance(deps: Deps, env: Env, token: String) -> StdResult<Binary> {
+    let token = deps.api.addr_validate(&token)?;
+    let viewing_key = TOKEN_VIEWING_KEY
+        .get(deps.storage, &token)
+        .unwrap_or_default();
+    let snip20_code_hash = SNIP20_CODE_HASH.load(deps.storage)?;
+    let balance: secret_toolkit::snip20::query::Balance = deps.querier.query_wasm_smart(
+        snip20_code_hash,
+        token.to_string(),
+        &snip20_reference_impl::msg::QueryMsg::Balance {
+            address: env.contract.address.to_string(),
+            key: viewing_key,
+        },
+    )?;
+    to_binary(&Snip20BalanceResponse {
+        addr: token.into_string(),
+        balance: balance.amount,
+    })
+}
+
 pub fn query_list_sub_daos(
     deps: Deps,
     start_after: Option<String>,
@@ -0,0 +1,673 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    to_binary, Addr, BankMsg, Binary, Coin, CosmosMsg, Deps, DepsMut, Empty, Env, MessageInfo,
+    Response, StdError, StdResult, Uint128, Uint256,
+};
+use dao_interface::voting::InfoResponse;
+use secret_cw2::set_contract_version;
+use secret_utils::Expiration;
+
+use crate::error::ContractError;
+use crate::msg::{
+    ApprovalStatusResponse, ExecuteMsg, InstantiateMsg, ParticipantInfo, ProposalListResponse,
+    ProposalResponse, QueryMsg,
+};
+use crate::state::{
+    amount_of, Approval, JointProposal, Participant, Status, PROPOSALS, PROPOSAL_COUNT,
+};
+
+pub(crate) const CONTRACT_NAME: &str = "crates.io:dao-joint-proposal";
+pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+const DEFAULT_LIMIT: u64 = 30;
+/// The contract name DAO core contracts report in `Info` queries.
+const DAO_CORE_CONTRACT_NAME: &str = "crates.io:dao-dao-core";
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    _msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    PROPOSAL_COUNT.save(deps.storage, &0)?;
+    Ok(Response::new()
+        .add_attribute("method", "instantiate")
+        .add_attribute("creator", info.sender))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Create {
+            title,
+            description,
+            participants,
+            quorum,
+            expiration,
+            msgs,
+        } => execute_create(
+            deps,
+            env,
+            info,
+            title,
+            description,
+            participants,
+            quorum,
+            expiration,
+            msgs,
+        ),
+        ExecuteMsg::Approve { proposal_id } => execute_approve(deps, env, info, proposal_id),
+        ExecuteMsg::Withdraw { proposal_id } => execute_withdraw(deps, env, info, proposal_id),
+        ExecuteMsg::Close { proposal_id } => execute_close(deps, env, info, proposal_id),
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn execute_create(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    title: String,
+    description: String,
+    participants: Vec<ParticipantInfo>,
+    quorum: Option<u32>,
+    expiration: Expiration,
+    msgs: Vec<CosmosMsg<Empty>>,
+) -> Result<Response, ContractError> {
+    if participants.len() < 2 {
+        return Err(ContractError::TooFewParticipants {});
+    }
+    let mut validated: Vec<Participant> = Vec::with_capacity(participants.len());
+    for ParticipantInfo { addr, code_hash } in participants {
+        let dao = deps.api.addr_validate(&addr)?;
+        if validated.iter().any(|p| p.dao == dao) {
+            return Err(ContractError::DuplicateParticipant {
+                dao: dao.into_string(),
+            });
+        }
+        if !is_dao_core(deps.as_ref(), &dao, code_hash) {
+            return Err(ContractError::NotDao {
+                addr: dao.into_string(),
+            });
+        }
+        validated.push(Participant {
+            dao,
+            approval: Approval::Pending,
+        });
+    }
+
+    let count = validated.len() as u32;
+    let quorum = quorum.unwrap_or(count);
+    if quorum == 0 || quorum > count {
+        return Err(ContractError::InvalidQuorum {
+            quorum,
+            participants: count,
+        });
+    }
+    if expiration.is_expired(&env.block) {
+        return Err(ContractError::AlreadyExpired {});
+    }
+    // What these spend can't be determined, so they could spend
+    // other proposals' contributions.
+    if msgs
+        .iter()
+        .any(|msg| matches!(msg, CosmosMsg::Stargate { .. }))
+    {
+        return Err(ContractError::UnsupportedMessage {});
+    }
+
+    let proposal = JointProposal {
+        title,
+        description,
+        proposer: info.sender.clone(),
+        participants: validated,
+        quorum,
+        expiration,
+        msgs,
+        status: Status::Open,
+    };
+    let id = PROPOSAL_COUNT.load(deps.storage)? + 1;
+    PROPOSAL_COUNT.save(deps.storage, &id)?;
+    PROPOSALS.insert(deps.storage, &id, &proposal)?;
+
+    Ok(Response::default()
+        .add_attribute("action", "create")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", id.to_string())
+        .add_attribute("quorum", quorum.to_string()))
+}
+
+pub fn execute_approve(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+) -> Result<Response, ContractError> {
+    let mut prop = load_open_proposal(deps.as_ref(), &env, proposal_id)?;
+
+    let participant = prop
+        .participant_mut(&info.sender)
+        .ok_or(ContractError::NotParticipant {})?;
+    match participant.approval {
+        Approval::Pending => (),
+        Approval::Approved { .. } => return Err(ContractError::AlreadyApproved {}),
+        Approval::Withdrawn => return Err(ContractError::AlreadyWithdrawn {}),
+    }
+    participant.approval = Approval::Approved {
+        funds: info.funds.clone(),
+    };
+
+    let response = if prop.approvals() >= prop.quorum {
+        // The contract holds contributions to every proposal, so
+        // the messages may only spend what was contributed to this
+        // one.
+        let escrow = prop.escrow();
+        let spend = prop.spend();
+        for coin in &spend {
+            let held = amount_of(&escrow, &coin.denom);
+            if coin.amount > held {
+                return Err(ContractError::InsufficientEscrow {
+                    denom: coin.denom.clone(),
+                    spend: coin.amount,
+                    escrow: held,
+                });
+            }
+        }
+        prop.status = Status::Executed;
+        Response::default()
+            .add_messages(prop.msgs.clone())
+            .add_messages(unspent_refunds(&prop, &escrow, &spend))
+    } else {
+        Response::default()
+    };
+    PROPOSALS.insert(deps.storage, &proposal_id, &prop)?;
+
+    Ok(response
+        .add_attribute("action", "approve")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("approvals", prop.approvals().to_string())
+        .add_attribute("status", prop.status.to_string()))
+}
+
+pub fn execute_withdraw(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+) -> Result<Response, ContractError> {
+    let mut prop = load_open_proposal(deps.as_ref(), &env, proposal_id)?;
+
+    let participant = prop
+        .participant_mut(&info.sender)
+        .ok_or(ContractError::NotParticipant {})?;
+    let refund = match std::mem::replace(&mut participant.approval, Approval::Withdrawn) {
+        Approval::Pending => vec![],
+        Approval::Approved { funds } => funds,
+        Approval::Withdrawn => return Err(ContractError::AlreadyWithdrawn {}),
+    };
+
+    // The proposal fails once it can no longer reach its quorum.
+    prop.update_status(&env.block);
+    PROPOSALS.insert(deps.storage, &proposal_id, &prop)?;
+
+    Ok(Response::default()
+        .add_messages(refund_message(&info.sender, refund))
+        .add_attribute("action", "withdraw")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("status", prop.status.to_string()))
+}
+
+pub fn execute_close(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+) -> Result<Response, ContractError> {
+    let mut prop = PROPOSALS
+        .get(deps.storage, &proposal_id)
+        .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+
+    prop.update_status(&env.block);
+    if prop.status != Status::Failed {
+        return Err(ContractError::NotFailed {});
+    }
+    prop.status = Status::Closed;
+
+    let refunds: Vec<BankMsg> = prop
+        .participants
+        .iter()
+        .filter_map(|p| match &p.approval {
+            Approval::Approved { funds } => refund_message(&p.dao, funds.clone()),
+            _ => None,
+        })
+        .collect();
+    PROPOSALS.insert(deps.storage, &proposal_id, &prop)?;
+
+    Ok(Response::default()
+        .add_messages(refunds)
+        .add_attribute("action", "close")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string()))
+}
+
+/// Loads a proposal, erroring if it is no longer open.
+fn load_open_proposal(
+    deps: Deps,
+    env: &Env,
+    proposal_id: u64,
+) -> Result<JointProposal, ContractError> {
+    let mut prop = PROPOSALS
+        .get(deps.storage, &proposal_id)
+        .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+    prop.update_status(&env.block);
+    if prop.status != Status::Open {
+        return Err(ContractError::NotOpen { id: proposal_id });
+    }
+    Ok(prop)
+}
+
+/// Whether `addr` is a DAO's core contract.
+fn is_dao_core(deps: Deps, addr: &Addr, code_hash: String) -> bool {
+    deps.querier
+        .query_wasm_smart::<InfoResponse>(code_hash, addr, &dao_interface::msg::QueryMsg::Info {})
+        .map_or(false, |res| res.info.contract == DAO_CORE_CONTRACT_NAME)
+}
+
+/// Messages returning what is left of an executed proposal's
+/// contributions after its messages `spend` from the `escrow`. Each
+/// DAO bears the spending in proportion to what it contributed,
+/// rounded against the DAO so that refunds never exceed what is
+/// left.
+fn unspent_refunds(prop: &JointProposal, escrow: &[Coin], spend: &[Coin]) -> Vec<BankMsg> {
+    prop.participants
+        .iter()
+        .filter_map(|p| match &p.approval {
+            Approval::Approved { funds } => {
+                let refund = funds
+                    .iter()
+                    .filter_map(|coin| {
+                        let spent = amount_of(spend, &coin.denom);
+                        let total = amount_of(escrow, &coin.denom);
+                        let share = coin.amount.full_mul(spent);
+                        let mut borne = coin.amount.multiply_ratio(spent, total);
+                        if !(share % Uint256::from(total)).is_zero() {
+                            borne += Uint128::one();
+                        }
+                        let amount = coin.amount - borne;
+                        (!amount.is_zero()).then(|| Coin {
+                            denom: coin.denom.clone(),
+                            amount,
+                        })
+                    })
+                    .collect();
+                refund_message(&p.dao, refund)
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// A message returning `funds` to `dao`, if there are any.
+fn refund_message(dao: &Addr, funds: Vec<Coin>) -> Option<BankMsg> {
+    if funds.is_empty() {
+        None
+    } else {
+        Some(BankMsg::Send {
+            to_address: dao.to_string(),
+            amount: funds,
+        })
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Proposal { proposal_id } => {
+            let proposal = load_proposal(deps, &env, proposal_id)?;
+            to_binary(&ProposalResponse {
+                id: proposal_id,
+                proposal,
+            })
+        }
+        QueryMsg::ListProposals { start_after, limit } => {
+            let limit = limit.unwrap_or(DEFAULT_LIMIT) as usize;
+            let proposals = PROPOSALS
+                .iter(deps.storage)?
+                .filter(|item| match (item, start_after) {
+                    (Ok((id, _)), Some(start_after)) => *id > start_after,
+                    _ => true,
+                })
+                .take(limit)
+                .map(|item| {
+                    item.map(|(id, mut proposal)| {
+                        proposal.update_status(&env.block);
+                        ProposalResponse { id, proposal }
+                    })
+                })
+                .collect::<StdResult<Vec<_>>>()?;
+            to_binary(&ProposalListResponse { proposals })
+        }
+        QueryMsg::ApprovalStatus { proposal_id } => {
+            let proposal = load_proposal(deps, &env, proposal_id)?;
+            to_binary(&ApprovalStatusResponse {
+                status: proposal.status,
+                approvals: proposal.approvals(),
+                quorum: proposal.quorum,
+                participants: proposal.participants,
+            })
+        }
+        QueryMsg::ProposalCount {} => to_binary(&PROPOSAL_COUNT.load(deps.storage)?),
+    }
+}
+
+/// Loads a proposal with its status brought up to date.
+fn load_proposal(deps: Deps, env: &Env, proposal_id: u64) -> StdResult<JointProposal> {
+    let mut proposal = PROPOSALS
+        .get(deps.storage, &proposal_id)
+        .ok_or_else(|| StdError::not_found("proposal"))?;
+    proposal.update_status(&env.block);
+    Ok(proposal)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::{
+        mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+    };
+    use cosmwasm_std::{
+        coin, coins, from_binary, ContractResult, OwnedDeps, SystemResult, WasmQuery,
+    };
+    use secret_cw2::ContractVersion;
+
+    /// Mock dependencies in which "dao_a", "dao_b", and "dao_c" are
+    /// DAO core contracts.
+    fn setup() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+        let mut deps = mock_dependencies();
+        deps.querier.update_wasm(|query| {
+            let WasmQuery::Smart { contract_addr, .. } = query else {
+                panic!("unexpected query");
+            };
+            let contract = match contract_addr.as_str() {
+                "dao_a" | "dao_b" | "dao_c" => DAO_CORE_CONTRACT_NAME,
+                _ => "crates.io:snip20",
+            };
+            SystemResult::Ok(ContractResult::Ok(
+                to_binary(&InfoResponse {
+                    info: ContractVersion {
+                        contract: contract.to_string(),
+                        version: "2.4.0".to_string(),
+                    },
+                })
+                .unwrap(),
+            ))
+        });
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &[]),
+            InstantiateMsg {},
+        )
+        .unwrap();
+        deps
+    }
+
+    fn create_with(
+        deps: DepsMut,
+        participants: &[&str],
+        quorum: Option<u32>,
+        msgs: Vec<CosmosMsg<Empty>>,
+    ) -> Result<Response, ContractError> {
+        let env = mock_env();
+        execute(
+            deps,
+            env.clone(),
+            mock_info("proposer", &[]),
+            ExecuteMsg::Create {
+                title: "grant".to_string(),
+                description: "a co-funded grant".to_string(),
+                participants: participants
+                    .iter()
+                    .map(|addr| ParticipantInfo {
+                        addr: addr.to_string(),
+                        code_hash: "code_hash".to_string(),
+                    })
+                    .collect(),
+                quorum,
+                expiration: Expiration::AtHeight(env.block.height + 10),
+                msgs,
+            },
+        )
+    }
+
+    fn create(deps: DepsMut, quorum: Option<u32>) -> Result<Response, ContractError> {
+        create_with(
+            deps,
+            &["dao_a", "dao_b", "dao_c"],
+            quorum,
+            vec![BankMsg::Send {
+                to_address: "grantee".to_string(),
+                amount: coins(30, "uscrt"),
+            }
+            .into()],
+        )
+    }
+
+    fn approval_status(deps: Deps) -> ApprovalStatusResponse {
+        from_binary(
+            &query(
+                deps,
+                mock_env(),
+                QueryMsg::ApprovalStatus { proposal_id: 1 },
+            )
+            .unwrap(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_executes_on_quorum() {
+        let mut deps = setup();
+        create(deps.as_mut(), Some(2)).unwrap();
+
+        let res = execute_approve(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("dao_a", &coins(15, "uscrt")),
+            1,
+        )
+        .unwrap();
+        assert!(res.messages.is_empty());
+        let err =
+            execute_approve(deps.as_mut(), mock_env(), mock_info("dao_a", &[]), 1).unwrap_err();
+        assert_eq!(err, ContractError::AlreadyApproved {});
+        let err =
+            execute_approve(deps.as_mut(), mock_env(), mock_info("other", &[]), 1).unwrap_err();
+        assert_eq!(err, ContractError::NotParticipant {});
+
+        let res = execute_approve(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("dao_b", &coins(15, "uscrt")),
+            1,
+        )
+        .unwrap();
+        assert_eq!(res.messages.len(), 1);
+
+        let status = approval_status(deps.as_ref());
+        assert_eq!(status.status, Status::Executed);
+        assert_eq!(status.approvals, 2);
+    }
+
+    #[test]
+    fn test_withdraw_and_close() {
+        let mut deps = setup();
+        create(deps.as_mut(), None).unwrap();
+
+        execute_approve(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("dao_a", &coins(10, "uscrt")),
+            1,
+        )
+        .unwrap();
+        execute_approve(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("dao_b", &coins(20, "uscrt")),
+            1,
+        )
+        .unwrap();
+
+        // Withdrawing refunds the DAO's contribution. As every DAO
+        // must approve, the proposal fails.
+        let res = execute_withdraw(deps.as_mut(), mock_env(), mock_info("dao_b", &[]), 1).unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: "dao_b".to_string(),
+                amount: coins(20, "uscrt"),
+            })
+        );
+        assert_eq!(approval_status(deps.as_ref()).status, Status::Failed);
+
+        let err =
+            execute_approve(deps.as_mut(), mock_env(), mock_info("dao_c", &[]), 1).unwrap_err();
+        assert_eq!(err, ContractError::NotOpen { id: 1 });
+
+        // Closing refunds the remaining contributions.
+        let res = execute_close(deps.as_mut(), mock_env(), mock_info("anyone", &[]), 1).unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: "dao_a".to_string(),
+                amount: coins(10, "uscrt"),
+            })
+        );
+        assert_eq!(approval_status(deps.as_ref()).status, Status::Closed);
+    }
+
+    #[test]
+    fn test_invalid_quorum() {
+        let mut deps = setup();
+        let err = create(deps.as_mut(), Some(4)).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::InvalidQuorum {
+                quorum: 4,
+                participants: 3
+            }
+        );
+    }
+
+    #[test]
+    fn test_participants_must_be_daos() {
+        let mut deps = setup();
+        let err = create_with(deps.as_mut(), &["dao_a", "token"], None, vec![]).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::NotDao {
+                addr: "token".to_string()
+            }
+        );
+        let err = create_with(
+            deps.as_mut(),
+            &["dao_a", "dao_b"],
+            None,
+            vec![CosmosMsg::Stargate {
+                type_url: "/cosmos.bank.v1beta1.MsgSend".to_string(),
+                value: Binary::default(),
+            }],
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::UnsupportedMessage {});
+    }
+
+    #[test]
+    fn test_spending_limited_to_escrow() {
+        let mut deps = setup();
+        // Another proposal's contributions are held by the contract.
+        create(deps.as_mut(), None).unwrap();
+        execute_approve(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("dao_c", &coins(100, "uscrt")),
+            1,
+        )
+        .unwrap();
+
+        create(deps.as_mut(), Some(2)).unwrap();
+        execute_approve(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("dao_a", &coins(10, "uscrt")),
+            2,
+        )
+        .unwrap();
+        let err = execute_approve(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("dao_b", &coins(10, "uscrt")),
+            2,
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::InsufficientEscrow {
+                denom: "uscrt".to_string(),
+                spend: Uint128::new(30),
+                escrow: Uint128::new(20),
+            }
+        );
+    }
+
+    #[test]
+    fn test_unspent_escrow_refunded() {
+        let mut deps = setup();
+        create(deps.as_mut(), Some(2)).unwrap();
+
+        execute_approve(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("dao_a", &[coin(10, "uscrt"), coin(7, "uatom")]),
+            1,
+        )
+        .unwrap();
+        let res = execute_approve(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("dao_b", &coins(30, "uscrt")),
+            1,
+        )
+        .unwrap();
+
+        // The 30uscrt sent is borne a quarter by dao_a and three
+        // quarters by dao_b, rounded against each. Nothing spends
+        // uatom.
+        assert_eq!(res.messages.len(), 3);
+        assert_eq!(
+            res.messages[1].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: "dao_a".to_string(),
+                amount: vec![coin(2, "uscrt"), coin(7, "uatom")],
+            })
+        );
+        assert_eq!(
+            res.messages[2].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: "dao_b".to_string(),
+                amount: coins(7, "uscrt"),
+            })
+        );
+    }
+}
@@ -0,0 +1,51 @@
+use cosmwasm_std::{StdError, Uint128};
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error(transparent)]
+    Std(#[from] StdError),
+
+    #[error("no such proposal ({id})")]
+    NoSuchProposal { id: u64 },
+
+    #[error("joint proposals must have at least two participants")]
+    TooFewParticipants {},
+
+    #[error("DAO ({dao}) is listed more than once")]
+    DuplicateParticipant { dao: String },
+
+    #[error("quorum ({quorum}) must be non-zero and at most the number of participants ({participants})")]
+    InvalidQuorum { quorum: u32, participants: u32 },
+
+    #[error("the proposal's expiration has already passed")]
+    AlreadyExpired {},
+
+    #[error("sender is not a participant in this proposal")]
+    NotParticipant {},
+
+    #[error("proposal ({id}) is not open")]
+    NotOpen { id: u64 },
+
+    #[error("already approved this proposal")]
+    AlreadyApproved {},
+
+    #[error("already withdrew from this proposal")]
+    AlreadyWithdrawn {},
+
+    #[error("proposal is not in 'failed' state")]
+    NotFailed {},
+
+    #[error("({addr}) is not a DAO core contract")]
+    NotDao { addr: String },
+
+    #[error("stargate messages are not supported as their spending can't be checked against contributions")]
+    UnsupportedMessage {},
+
+    #[error("messages spend ({spend}{denom}) but only ({escrow}{denom}) was contributed")]
+    InsufficientEscrow {
+        denom: String,
+        spend: Uint128,
+        escrow: Uint128,
+    },
+}
@@ -0,0 +1,92 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{CosmosMsg, Empty};
+use secret_utils::Expiration;
+
+use crate::state::{JointProposal, Participant, Status};
+
+#[cw_serde]
+pub struct InstantiateMsg {}
+
+/// A DAO invited to participate in a joint proposal.
+#[cw_serde]
+pub struct ParticipantInfo {
+    /// The DAO's core contract.
+    pub addr: String,
+    /// The code hash of the DAO's core contract.
+    pub code_hash: String,
+}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Creates a joint proposal between the listed DAOs.
+    Create {
+        title: String,
+        description: String,
+        /// The core contracts of the participating DAOs.
+        participants: Vec<ParticipantInfo>,
+        /// The number of participants that must approve the proposal
+        /// for its messages to be executed. Defaults to all of them.
+        quorum: Option<u32>,
+        /// The time after which the proposal may no longer be
+        /// approved.
+        expiration: Expiration,
+        /// The messages this contract executes once the proposal is
+        /// approved. They may only spend the native tokens
+        /// contributed by the DAOs that approved it.
+        msgs: Vec<CosmosMsg<Empty>>,
+    },
+    /// Approves a proposal. Must be sent by a participating DAO,
+    /// normally by one of its proposals. Native tokens sent with the
+    /// approval are held towards the proposal's execution and
+    /// refunded should it fail. If this approval reaches the
+    /// proposal's quorum, its messages are executed and whatever
+    /// they leave of the contributions is refunded.
+    Approve { proposal_id: u64 },
+    /// Withdraws a participating DAO from a proposal, refunding any
+    /// contribution it made when approving.
+    Withdraw { proposal_id: u64 },
+    /// Closes a failed proposal, refunding the contributions of the
+    /// DAOs that approved it.
+    Close { proposal_id: u64 },
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    /// Gets information about a proposal.
+    #[returns(ProposalResponse)]
+    Proposal { proposal_id: u64 },
+    /// Lists the proposals that have been created.
+    #[returns(ProposalListResponse)]
+    ListProposals {
+        start_after: Option<u64>,
+        limit: Option<u64>,
+    },
+    /// Gets where each participating DAO stands on a proposal.
+    #[returns(ApprovalStatusResponse)]
+    ApprovalStatus { proposal_id: u64 },
+    /// Returns the number of proposals that have been created.
+    #[returns(::std::primitive::u64)]
+    ProposalCount {},
+}
+
+#[cw_serde]
+pub struct ProposalResponse {
+    pub id: u64,
+    pub proposal: JointProposal,
+}
+
+#[cw_serde]
+pub struct ProposalListResponse {
+    pub proposals: Vec<ProposalResponse>,
+}
+
+#[cw_serde]
+pub struct ApprovalStatusResponse {
+    pub status: Status,
+    /// The number of participants that have approved the proposal.
+    pub approvals: u32,
+    /// The number of approvals needed for the proposal to execute.
+    pub quorum: u32,
+    pub participants: Vec<Participant>,
+}
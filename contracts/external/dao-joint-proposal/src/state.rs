@@ -0,0 +1,273 @@
+use cosmwasm_std::{
+    Addr, BankMsg, BlockInfo, Coin, CosmosMsg, Empty, IbcMsg, StakingMsg, Uint128, WasmMsg,
+};
+use schemars::JsonSchema;
+use secret_storage_plus::Item;
+use secret_toolkit::{serialization::Json, storage::Keymap};
+use secret_utils::Expiration;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum Status {
+    /// The proposal is waiting for approvals.
+    Open,
+    /// The proposal expired or enough participants withdrew that it
+    /// can no longer be approved. It may be closed to refund
+    /// contributions.
+    Failed,
+    /// Enough participants approved the proposal and its messages
+    /// were executed.
+    Executed,
+    /// The failed proposal has been closed and contributions
+    /// refunded.
+    Closed,
+}
+
+impl std::fmt::Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Status::Open => write!(f, "open"),
+            Status::Failed => write!(f, "failed"),
+            Status::Executed => write!(f, "executed"),
+            Status::Closed => write!(f, "closed"),
+        }
+    }
+}
+
+/// Where a participating DAO stands on a joint proposal.
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum Approval {
+    /// The DAO has not yet decided.
+    Pending,
+    /// The DAO approved the proposal, contributing `funds` towards
+    /// its execution.
+    Approved { funds: Vec<Coin> },
+    /// The DAO withdrew from the proposal. Any contribution it made
+    /// has been refunded.
+    Withdrawn,
+}
+
+/// A DAO participating in a joint proposal.
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub struct Participant {
+    /// The participating DAO's core contract.
+    pub dao: Addr,
+    pub approval: Approval,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub struct JointProposal {
+    /// The title of the proposal
+    pub title: String,
+    /// The main body of the proposal text
+    pub description: String,
+    /// The address that created this proposal.
+    pub proposer: Addr,
+    /// The DAOs participating in the proposal.
+    pub participants: Vec<Participant>,
+    /// The number of participants that must approve the proposal for
+    /// its messages to be executed.
+    pub quorum: u32,
+    /// The time after which the proposal may no longer be approved.
+    pub expiration: Expiration,
+    /// The messages executed by this contract once the proposal is
+    /// approved.
+    pub msgs: Vec<CosmosMsg<Empty>>,
+    /// The proposal status
+    pub status: Status,
+}
+
+impl JointProposal {
+    /// The number of participants that have approved the proposal.
+    pub fn approvals(&self) -> u32 {
+        self.participants
+            .iter()
+            .filter(|p| matches!(p.approval, Approval::Approved { .. }))
+            .count() as u32
+    }
+
+    /// The number of participants that have approved the proposal or
+    /// may still do so.
+    pub fn possible_approvals(&self) -> u32 {
+        self.participants
+            .iter()
+            .filter(|p| p.approval != Approval::Withdrawn)
+            .count() as u32
+    }
+
+    /// The participant entry for `dao`, if it participates in the
+    /// proposal.
+    pub fn participant_mut(&mut self, dao: &Addr) -> Option<&mut Participant> {
+        self.participants.iter_mut().find(|p| &p.dao == dao)
+    }
+
+    /// The native tokens held for this proposal by the DAOs that
+    /// approved it. Only these may be spent by its messages.
+    pub fn escrow(&self) -> Vec<Coin> {
+        let mut escrow = vec![];
+        for participant in &self.participants {
+            if let Approval::Approved { funds } = &participant.approval {
+                for coin in funds {
+                    add_coin(&mut escrow, coin);
+                }
+            }
+        }
+        escrow
+    }
+
+    /// The native tokens this proposal's messages spend from this
+    /// contract's balance.
+    pub fn spend(&self) -> Vec<Coin> {
+        let mut spend = vec![];
+        for msg in &self.msgs {
+            for coin in msg_spend(msg) {
+                add_coin(&mut spend, coin);
+            }
+        }
+        spend
+    }
+
+    /// Gets the current status of the proposal.
+    pub fn current_status(&self, block: &BlockInfo) -> Status {
+        if self.status == Status::Open
+            && (self.expiration.is_expired(block) || self.possible_approvals() < self.quorum)
+        {
+            Status::Failed
+        } else {
+            self.status
+        }
+    }
+
+    /// Sets a proposals status to its current status.
+    pub fn update_status(&mut self, block: &BlockInfo) {
+        self.status = self.current_status(block);
+    }
+}
+
+/// The native tokens `msg` moves out of the sender's balance.
+fn msg_spend(msg: &CosmosMsg<Empty>) -> Vec<&Coin> {
+    match msg {
+        CosmosMsg::Bank(BankMsg::Send { amount, .. } | BankMsg::Burn { amount }) => {
+            amount.iter().collect()
+        }
+        CosmosMsg::Wasm(WasmMsg::Execute { funds, .. } | WasmMsg::Instantiate { funds, .. }) => {
+            funds.iter().collect()
+        }
+        CosmosMsg::Staking(StakingMsg::Delegate { amount, .. }) => vec![amount],
+        CosmosMsg::Ibc(IbcMsg::Transfer { amount, .. }) => vec![amount],
+        _ => vec![],
+    }
+}
+
+/// Adds `coin` to the matching denom in `coins`.
+fn add_coin(coins: &mut Vec<Coin>, coin: &Coin) {
+    match coins.iter_mut().find(|c| c.denom == coin.denom) {
+        Some(c) => c.amount = c.amount.saturating_add(coin.amount),
+        None => coins.push(coin.clone()),
+    }
+}
+
+/// The amount of `denom` in `coins`.
+pub fn amount_of(coins: &[Coin], denom: &str) -> Uint128 {
+    coins
+        .iter()
+        .find(|c| c.denom == denom)
+        .map(|c| c.amount)
+        .unwrap_or_default()
+}
+
+/// The number of proposals that have been created.
+pub const PROPOSAL_COUNT: Item<u64> = Item::new("proposal_count");
+pub static PROPOSALS: Keymap<u64, JointProposal, Json> = Keymap::new(b"proposals");
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::mock_env;
+    use cosmwasm_std::{coin, coins};
+
+    fn proposal(quorum: u32) -> JointProposal {
+        let env = mock_env();
+        JointProposal {
+            title: "title".to_string(),
+            description: "description".to_string(),
+            proposer: Addr::unchecked("proposer"),
+            participants: ["dao_a", "dao_b", "dao_c"]
+                .iter()
+                .map(|dao| Participant {
+                    dao: Addr::unchecked(*dao),
+                    approval: Approval::Pending,
+                })
+                .collect(),
+            quorum,
+            expiration: Expiration::AtHeight(env.block.height + 10),
+            msgs: vec![],
+            status: Status::Open,
+        }
+    }
+
+    #[test]
+    fn test_withdrawals_fail_proposal() {
+        let env = mock_env();
+        let mut prop = proposal(2);
+
+        prop.participant_mut(&Addr::unchecked("dao_a"))
+            .unwrap()
+            .approval = Approval::Withdrawn;
+        assert_eq!(prop.current_status(&env.block), Status::Open);
+        prop.participant_mut(&Addr::unchecked("dao_b"))
+            .unwrap()
+            .approval = Approval::Withdrawn;
+        assert_eq!(prop.possible_approvals(), 1);
+        assert_eq!(prop.current_status(&env.block), Status::Failed);
+    }
+
+    #[test]
+    fn test_escrow_and_spend() {
+        let mut prop = proposal(2);
+        prop.participant_mut(&Addr::unchecked("dao_a"))
+            .unwrap()
+            .approval = Approval::Approved {
+            funds: vec![coin(10, "uscrt"), coin(5, "uatom")],
+        };
+        prop.participant_mut(&Addr::unchecked("dao_b"))
+            .unwrap()
+            .approval = Approval::Approved {
+            funds: coins(20, "uscrt"),
+        };
+        assert_eq!(prop.escrow(), vec![coin(30, "uscrt"), coin(5, "uatom")]);
+
+        prop.msgs = vec![
+            BankMsg::Send {
+                to_address: "grantee".to_string(),
+                amount: coins(12, "uscrt"),
+            }
+            .into(),
+            WasmMsg::Execute {
+                contract_addr: "contract".to_string(),
+                code_hash: "code_hash".to_string(),
+                msg: Default::default(),
+                funds: vec![coin(8, "uscrt"), coin(1, "uatom")],
+            }
+            .into(),
+        ];
+        assert_eq!(prop.spend(), vec![coin(20, "uscrt"), coin(1, "uatom")]);
+    }
+
+    #[test]
+    fn test_expiry_fails_proposal() {
+        let mut env = mock_env();
+        let mut prop = proposal(2);
+        prop.participant_mut(&Addr::unchecked("dao_a"))
+            .unwrap()
+            .approval = Approval::Approved { funds: vec![] };
+        assert_eq!(prop.approvals(), 1);
+
+        env.block.height += 10;
+        assert_eq!(prop.current_status(&env.block), Status::Failed);
+    }
+}
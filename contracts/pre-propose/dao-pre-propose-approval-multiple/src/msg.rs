@@ -0,0 +1,69 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::Addr;
+use dao_pre_propose_base::msg::{
+    ExecuteMsg as ExecuteBase, InstantiateMsg as InstantiateBase, QueryMsg as QueryBase,
+};
+use dao_voting::{
+    condition::ExecutionCondition, dependency::ProposalDependency,
+    multiple_choice::MultipleChoiceOptions, proposal::MultipleChoiceProposeMsg as ProposeMsg,
+};
+
+pub use dao_pre_propose_approval_single::msg::{
+    ApprovalProposeMessage, ApproverProposeMessage, InstantiateExt, QueryExt, UncheckedApprover,
+};
+
+#[cw_serde]
+pub enum ProposeMessage {
+    Propose {
+        title: String,
+        description: String,
+        choices: MultipleChoiceOptions,
+        /// Conditions that must hold when the proposal is executed.
+        #[serde(default)]
+        conditions: Vec<ExecutionCondition>,
+        /// Proposals, in this or other proposal modules, that must be
+        /// executed before the proposal may be executed.
+        #[serde(default)]
+        dependencies: Vec<ProposalDependency>,
+    },
+}
+
+impl ApprovalProposeMessage for ProposeMessage {
+    type ProposeMsg = ProposeMsg;
+
+    fn title(&self) -> &str {
+        match self {
+            ProposeMessage::Propose { title, .. } => title,
+        }
+    }
+
+    fn description(&self) -> &str {
+        match self {
+            ProposeMessage::Propose { description, .. } => description,
+        }
+    }
+
+    fn into_propose_msg(self, proposer: &Addr) -> ProposeMsg {
+        match self {
+            ProposeMessage::Propose {
+                title,
+                description,
+                choices,
+                conditions,
+                dependencies,
+            } => ProposeMsg {
+                title,
+                description,
+                choices,
+                proposer: Some(proposer.to_string()),
+                conditions,
+                dependencies,
+            },
+        }
+    }
+}
+
+pub type ExecuteExt = dao_pre_propose_approval_single::msg::ApprovalExecuteExt<ProposeMessage>;
+pub type InstantiateMsg = InstantiateBase<InstantiateExt>;
+pub type ExecuteMsg = ExecuteBase<ProposeMessage, ExecuteExt>;
+pub type QueryMsg = QueryBase<QueryExt>;
@@ -0,0 +1,13 @@
+use dao_voting::proposal::MultipleChoiceProposeMsg as ProposeMsg;
+
+pub use dao_pre_propose_approval_single::state::{
+    ApprovalProgress, Approver, Committee, ProposalStatus, Rejection, COMMITTEE,
+    CREATED_PROPOSAL_TO_COMPLETED_PROPOSAL, HELD_DEPOSITS, PENDING_TIMEOUT,
+};
+
+/// A multiple choice proposal pending approval, or completed.
+pub type Proposal = dao_pre_propose_approval_single::state::Proposal<ProposeMsg>;
+
+/// A multiple choice proposal pending approval and its approval
+/// progress.
+pub type PendingProposal = dao_pre_propose_approval_single::state::PendingProposal<ProposeMsg>;
@@ -0,0 +1,342 @@
+use cosmwasm_std::testing::{
+    mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+};
+use cosmwasm_std::{
+    from_binary, to_binary, Addr, Binary, ContractResult, CosmosMsg, Env, OwnedDeps, QuerierResult,
+    Response, SystemResult, WasmMsg, WasmQuery,
+};
+use dao_interface::state::{AnyContractInfo, ModuleInstantiateCallback};
+use dao_interface::voting::Query as VotingQuery;
+use dao_pre_propose_approver::msg::{
+    ExecuteMsg as ApproverExecuteMsg, InstantiateMsg as ApproverInstantiateMsg,
+};
+use dao_pre_propose_base::error::PreProposeError;
+use dao_proposal_multiple::msg::ExecuteMsg as ProposalExecuteMsg;
+use dao_voting::multiple_choice::{MultipleChoiceOption, MultipleChoiceOptions};
+use dao_voting::status::Status;
+
+use crate::contract::{execute, instantiate, query};
+use crate::msg::{
+    ApproverProposeMessage, ExecuteExt, ExecuteMsg, InstantiateExt, InstantiateMsg, ProposeMessage,
+    QueryExt, QueryMsg,
+};
+use crate::state::{Proposal, ProposalStatus};
+
+type Deps = OwnedDeps<MockStorage, MockApi, MockQuerier>;
+
+/// Answers the queries of both the approval module and the approver
+/// DAO's pre-propose module. Both proposal modules belong to "dao",
+/// their next proposal ID is 1, and every approval is pending.
+fn querier(query: &WasmQuery) -> QuerierResult {
+    let WasmQuery::Smart { msg, .. } = query else {
+        panic!("unexpected query");
+    };
+    let res = if let Ok(VotingQuery::Dao {}) = from_binary(msg) {
+        to_binary(&AnyContractInfo {
+            addr: Addr::unchecked("dao"),
+            code_hash: "dao_hash".to_string(),
+        })
+    } else if let Ok(dao_interface::proposal::Query::NextProposalId {}) = from_binary(msg) {
+        to_binary(&1u64)
+    } else if let Ok(QueryMsg::QueryExtension {
+        msg: QueryExt::IsPending { .. },
+    }) = from_binary(msg)
+    {
+        to_binary(&true)
+    } else {
+        panic!("unexpected query")
+    };
+    SystemResult::Ok(ContractResult::Ok(res.unwrap()))
+}
+
+fn env_of(contract: &str) -> Env {
+    let mut env = mock_env();
+    env.contract.address = Addr::unchecked(contract);
+    env
+}
+
+/// Sets up the module for "module", with "approver" as its approver.
+/// Anyone may submit and there is no deposit.
+fn setup() -> Deps {
+    let mut deps = mock_dependencies();
+    deps.querier.update_wasm(querier);
+    instantiate(
+        deps.as_mut(),
+        env_of("approval"),
+        mock_info("module", &[]),
+        InstantiateMsg {
+            deposit_info: None,
+            open_proposal_submission: true,
+            proposal_module_code_hash: "module_hash".to_string(),
+            extension: InstantiateExt {
+                approver: "approver".to_string(),
+                pending_timeout: None,
+            },
+        },
+    )
+    .unwrap();
+    deps
+}
+
+fn choices(titles: &[&str]) -> MultipleChoiceOptions {
+    MultipleChoiceOptions {
+        options: titles
+            .iter()
+            .map(|title| MultipleChoiceOption {
+                title: title.to_string(),
+                description: "description".to_string(),
+                msgs: vec![],
+            })
+            .collect(),
+    }
+}
+
+fn propose_message(choices: MultipleChoiceOptions) -> ProposeMessage {
+    ProposeMessage::Propose {
+        title: "title".to_string(),
+        description: "description".to_string(),
+        choices,
+        conditions: vec![],
+        dependencies: vec![],
+    }
+}
+
+fn propose(deps: &mut Deps, proposer: &str, choices: MultipleChoiceOptions) -> Response {
+    execute(
+        deps.as_mut(),
+        env_of("approval"),
+        mock_info(proposer, &[]),
+        ExecuteMsg::Propose {
+            key: "key".to_string(),
+            msg: propose_message(choices),
+            co_sponsors: vec![],
+        },
+    )
+    .unwrap()
+}
+
+fn execute_ext(
+    deps: &mut Deps,
+    sender: &str,
+    msg: ExecuteExt,
+) -> Result<Response, PreProposeError> {
+    execute(
+        deps.as_mut(),
+        env_of("approval"),
+        mock_info(sender, &[]),
+        ExecuteMsg::Extension { msg },
+    )
+}
+
+fn proposal(deps: &Deps, id: u64) -> Proposal {
+    from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::QueryExtension {
+                msg: QueryExt::Proposal { id },
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap()
+}
+
+/// Returns the messages RES sends to CONTRACT.
+fn sent_to(res: &Response, contract: &str) -> Vec<Binary> {
+    res.messages
+        .iter()
+        .filter_map(|sub| match &sub.msg {
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr, msg, ..
+            }) if contract_addr == contract => Some(msg.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Returns the choices and proposer of the proposal RES creates in
+/// the proposal module.
+fn created_proposal(res: &Response) -> (MultipleChoiceOptions, Option<String>) {
+    let msgs = sent_to(res, "module");
+    assert_eq!(msgs.len(), 1);
+    match from_binary(&msgs[0]).unwrap() {
+        ProposalExecuteMsg::Propose {
+            choices, proposer, ..
+        } => (choices, proposer),
+        _ => panic!("expected a proposal to be created"),
+    }
+}
+
+#[test]
+fn test_approve_multiple_choice_proposal() {
+    let mut deps = setup();
+    let res = propose(&mut deps, "alice", choices(&["yes", "no"]));
+    assert!(sent_to(&res, "module").is_empty());
+
+    let pending = proposal(&deps, 1);
+    assert_eq!(pending.status, ProposalStatus::Pending {});
+    assert_eq!(pending.msg.choices, choices(&["yes", "no"]));
+    assert_eq!(pending.msg.proposer, Some("alice".to_string()));
+
+    let err = execute_ext(&mut deps, "alice", ExecuteExt::Approve { id: 1 }).unwrap_err();
+    assert_eq!(err, PreProposeError::Unauthorized {});
+
+    let res = execute_ext(&mut deps, "approver", ExecuteExt::Approve { id: 1 }).unwrap();
+    assert_eq!(
+        created_proposal(&res),
+        (choices(&["yes", "no"]), Some("alice".to_string()))
+    );
+    assert_eq!(
+        proposal(&deps, 1).status,
+        ProposalStatus::Approved {
+            created_proposal_id: 1
+        }
+    );
+}
+
+#[test]
+fn test_resubmit_multiple_choice_proposal() {
+    let mut deps = setup();
+    propose(&mut deps, "alice", choices(&["yes", "no"]));
+    execute_ext(
+        &mut deps,
+        "approver",
+        ExecuteExt::Reject {
+            id: 1,
+            reason: Some("needs an abstain option".to_string()),
+        },
+    )
+    .unwrap();
+    assert_eq!(proposal(&deps, 1).status, ProposalStatus::Rejected {});
+
+    let resubmit = ExecuteExt::Resubmit {
+        id: 1,
+        key: "key".to_string(),
+        msg: propose_message(choices(&["yes", "no", "abstain"])),
+        co_sponsors: vec![],
+    };
+    let err = execute_ext(&mut deps, "bob", resubmit.clone()).unwrap_err();
+    assert_eq!(err, PreProposeError::Unauthorized {});
+    execute_ext(&mut deps, "alice", resubmit).unwrap();
+
+    let resubmission = proposal(&deps, 2);
+    assert_eq!(resubmission.resubmitted_from, Some(1));
+    assert_eq!(resubmission.msg.choices, choices(&["yes", "no", "abstain"]));
+
+    let res = execute_ext(&mut deps, "approver", ExecuteExt::Approve { id: 2 }).unwrap();
+    assert_eq!(created_proposal(&res).0, choices(&["yes", "no", "abstain"]));
+}
+
+/// Sets up the module with a dao-pre-propose-approver, "approver", as
+/// its approver, and has alice submit a proposal. The approver's
+/// proposal module is "approver_module". Returns the approval module
+/// and the approver.
+fn setup_approver() -> (Deps, Deps) {
+    let mut deps = setup();
+    let mut approver = mock_dependencies();
+    approver.querier.update_wasm(querier);
+
+    let res = dao_pre_propose_approver::contract::instantiate(
+        approver.as_mut(),
+        env_of("approver"),
+        mock_info("approver_module", &[]),
+        ApproverInstantiateMsg {
+            pre_propose_approval_contract: "approval".to_string(),
+            pre_propose_approval_contract_code_hash: "approval_hash".to_string(),
+            proposal_module_code_hash: "approver_module_hash".to_string(),
+        },
+    )
+    .unwrap();
+    // The DAO executes the approver's setup messages.
+    let callback: ModuleInstantiateCallback = from_binary(&res.data.unwrap()).unwrap();
+    for msg in callback.msgs {
+        let CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr, msg, ..
+        }) = msg
+        else {
+            panic!("unexpected callback message");
+        };
+        assert_eq!(contract_addr, "approval");
+        execute(
+            deps.as_mut(),
+            env_of("approval"),
+            mock_info("dao", &[]),
+            from_binary(&msg).unwrap(),
+        )
+        .unwrap();
+    }
+
+    // The approver is notified of the proposal, and proposes
+    // approving it to its DAO.
+    let res = propose(&mut deps, "alice", choices(&["yes", "no"]));
+    let hooks = sent_to(&res, "approver");
+    assert_eq!(hooks.len(), 1);
+    let hook: ApproverExecuteMsg = from_binary(&hooks[0]).unwrap();
+    assert!(matches!(
+        &hook,
+        ApproverExecuteMsg::Propose {
+            msg: ApproverProposeMessage::Propose { approval_id: 1, .. },
+            ..
+        }
+    ));
+    let res = dao_pre_propose_approver::contract::execute(
+        approver.as_mut(),
+        env_of("approver"),
+        mock_info("approval", &[]),
+        hook,
+    )
+    .unwrap();
+    assert_eq!(sent_to(&res, "approver_module").len(), 1);
+
+    (deps, approver)
+}
+
+/// Completes the approver DAO's proposal with STATUS, and executes
+/// the resulting message on the approval module.
+fn complete_approver_proposal(deps: &mut Deps, approver: &mut Deps, status: Status) -> Response {
+    let res = dao_pre_propose_approver::contract::execute(
+        approver.as_mut(),
+        env_of("approver"),
+        mock_info("approver_module", &[]),
+        ApproverExecuteMsg::ProposalCompletedHook {
+            proposal_id: 1,
+            new_status: status,
+            quorum_met: None,
+        },
+    )
+    .unwrap();
+    let msgs = sent_to(&res, "approval");
+    assert_eq!(msgs.len(), 1);
+    execute(
+        deps.as_mut(),
+        env_of("approval"),
+        mock_info("approver", &[]),
+        from_binary(&msgs[0]).unwrap(),
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_approver_dao_approves() {
+    let (mut deps, mut approver) = setup_approver();
+    let res = complete_approver_proposal(&mut deps, &mut approver, Status::Executed);
+    assert_eq!(
+        created_proposal(&res),
+        (choices(&["yes", "no"]), Some("alice".to_string()))
+    );
+    assert_eq!(
+        proposal(&deps, 1).status,
+        ProposalStatus::Approved {
+            created_proposal_id: 1
+        }
+    );
+}
+
+#[test]
+fn test_approver_dao_rejects() {
+    let (mut deps, mut approver) = setup_approver();
+    let res = complete_approver_proposal(&mut deps, &mut approver, Status::Closed);
+    assert!(sent_to(&res, "module").is_empty());
+    assert_eq!(proposal(&deps, 1).status, ProposalStatus::Rejected {});
+}
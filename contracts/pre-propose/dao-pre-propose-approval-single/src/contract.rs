@@ -1,29 +1,37 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_binary, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdResult, SubMsg, WasmMsg,
+    to_binary, Addr, Binary, CosmosMsg, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdError,
+    StdResult, SubMsg, WasmMsg,
 };
 use cw_hooks::HookItem;
+use dao_interface::msg::Snip20ReceiveMsg;
 use dao_pre_propose_base::{
-    error::PreProposeError, msg::ExecuteMsg as ExecuteBase, state::PreProposeContract,
+    error::PreProposeError,
+    msg::{CoSponsor, ExecuteMsg as ExecuteBase, ReceiveMsg},
+    state::PreProposeContract,
 };
-use dao_voting::deposit::DepositRefundPolicy;
-use dao_voting::proposal::SingleChoiceProposeMsg as ProposeMsg;
+use dao_voting::deposit::CheckedDepositInfo;
+use dao_voting::status::Status;
 use secret_cw2::set_contract_version;
+use secret_utils::Duration;
 
 use crate::msg::{
-    ApproverProposeMessage, ExecuteExt, ExecuteMsg, InstantiateExt, InstantiateMsg, ProposeMessage,
-    ProposeMessageInternal, QueryExt, QueryMsg,
+    ApprovalExecuteExt, ApprovalProposeMessage, ApproverProposeMessage, ExecuteMsg, InstantiateExt,
+    InstantiateMsg, ProposeMessage, ProposeMessageInternal, QueryExt, QueryMsg, UncheckedApprover,
 };
 use crate::state::{
-    advance_approval_id, Proposal, ProposalStatus, APPROVER, COMPLETED_PROPOSALS,
-    CREATED_PROPOSAL_TO_COMPLETED_PROPOSAL, PENDING_PROPOSALS,
+    advance_approval_id, completed_proposals, pending_proposals, Approver, Committee,
+    PendingProposal, Proposal, ProposalStatus, Rejection, COMMITTEE,
+    CREATED_PROPOSAL_TO_COMPLETED_PROPOSAL, HELD_DEPOSITS, PENDING_TIMEOUT,
 };
 
 pub(crate) const CONTRACT_NAME: &str = "crates.io:dao-pre-propose-approval-single";
 pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
-type PrePropose = PreProposeContract<InstantiateExt, ExecuteExt, QueryExt, ProposeMessage>;
+/// The pre-propose module of an approval module whose proposals are
+/// submitted with P.
+type PrePropose<P> = PreProposeContract<InstantiateExt, ApprovalExecuteExt<P>, QueryExt, P>;
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
@@ -32,12 +40,9 @@ pub fn instantiate(
     info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, PreProposeError> {
-    let approver = deps.api.addr_validate(&msg.extension.approver)?;
-    APPROVER.save(deps.storage, &approver)?;
-
-    let resp = PrePropose::default().instantiate(deps.branch(), env, info, msg)?;
+    let resp = instantiate_approval::<ProposeMessage>(deps.branch(), env, info, msg)?;
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
-    Ok(resp.add_attribute("approver", approver.to_string()))
+    Ok(resp)
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -47,37 +52,94 @@ pub fn execute(
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, PreProposeError> {
-    match msg {
-        ExecuteMsg::Propose { msg, key } => execute_propose(deps, env, info, msg, key),
+    execute_approval(deps, env, info, msg)
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    query_approval::<ProposeMessage>(deps, env, msg)
+}
+
+/// Instantiates an approval module whose proposals are submitted with
+/// P. The caller sets the contract version.
+pub fn instantiate_approval<P: ApprovalProposeMessage>(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, PreProposeError> {
+    let approver = deps.api.addr_validate(&msg.extension.approver)?;
+    COMMITTEE.save(deps.storage, &Committee::single(approver.clone()))?;
+    if let Some(timeout) = msg.extension.pending_timeout {
+        PENDING_TIMEOUT.save(deps.storage, &timeout)?;
+    }
 
-        ExecuteMsg::AddProposalSubmittedHook { address, code_hash } => {
-            execute_add_approver_hook(deps, info, address, code_hash)
+    let resp = PrePropose::<P>::default().instantiate(deps, env, info, msg)?;
+    Ok(resp.add_attribute("approver", approver.to_string()))
+}
+
+/// Executes a message sent to an approval module whose proposals are
+/// submitted with P.
+pub fn execute_approval<P: ApprovalProposeMessage>(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteBase<P, ApprovalExecuteExt<P>>,
+) -> Result<Response, PreProposeError> {
+    match msg {
+        ExecuteBase::Propose {
+            msg,
+            key,
+            co_sponsors,
+        } => execute_propose(deps, env, info, msg, key, co_sponsors),
+        ExecuteBase::Receive(wrapper) => execute_receive::<P>(deps, env, info, wrapper),
+
+        ExecuteBase::AddProposalSubmittedHook { address, code_hash } => {
+            execute_add_approver_hook::<P>(deps, info, address, code_hash)
         }
-        ExecuteMsg::RemoveProposalSubmittedHook { address, code_hash } => {
-            execute_remove_approver_hook(deps, info, address, code_hash)
+        ExecuteBase::RemoveProposalSubmittedHook { address, code_hash } => {
+            execute_remove_approver_hook::<P>(deps, info, address, code_hash)
         }
 
-        ExecuteMsg::Extension { msg } => match msg {
-            ExecuteExt::Approve { id } => execute_approve(deps, info, id),
-            ExecuteExt::Reject { id } => execute_reject(deps, info, id),
-            ExecuteExt::UpdateApprover { address } => execute_update_approver(deps, info, address),
+        ExecuteBase::Extension { msg } => match msg {
+            ApprovalExecuteExt::Approve { id } => execute_approve::<P>(deps, env, info, id),
+            ApprovalExecuteExt::Reject { id, reason } => {
+                execute_reject::<P>(deps, env, info, id, reason)
+            }
+            ApprovalExecuteExt::UpdateApprover { address } => {
+                execute_update_approver::<P>(deps, info, address)
+            }
+            ApprovalExecuteExt::UpdateCommittee {
+                approvers,
+                threshold,
+            } => execute_update_committee::<P>(deps, info, approvers, threshold),
+            ApprovalExecuteExt::Expire { id } => execute_expire::<P>(deps, env, id),
+            ApprovalExecuteExt::Finalize { id } => execute_finalize::<P>(deps, env, id),
+            ApprovalExecuteExt::Resubmit {
+                id,
+                key,
+                msg,
+                co_sponsors,
+            } => execute_resubmit(deps, env, info, id, msg, key, co_sponsors),
+            ApprovalExecuteExt::ClaimDeposit { id } => execute_claim_deposit::<P>(deps, info, id),
+            ApprovalExecuteExt::UpdatePendingTimeout { timeout } => {
+                execute_update_pending_timeout::<P>(deps, info, timeout)
+            }
         },
         // Default pre-propose-base behavior for all other messages
-        _ => PrePropose::default().execute(deps, env, info, msg),
+        _ => PrePropose::<P>::default().execute(deps, env, info, msg),
     }
 }
 
-pub fn execute_propose(
+pub fn execute_propose<P: ApprovalProposeMessage>(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
-    msg: ProposeMessage,
+    msg: P,
     key: String,
+    co_sponsors: Vec<CoSponsor>,
 ) -> Result<Response, PreProposeError> {
-    let pre_propose_base = PrePropose::default();
-    let config = pre_propose_base.config.load(deps.storage)?;
-
-    pre_propose_base.check_can_submit(deps.as_ref(), info.sender.clone(), key.clone())?;
+    let config = PrePropose::<P>::default().config.load(deps.storage)?;
 
     // Take deposit, if configured.
     let deposit_messages = if let Some(ref deposit_info) = config.deposit_info {
@@ -87,20 +149,86 @@ pub fn execute_propose(
         vec![]
     };
 
-    let approval_id = advance_approval_id(deps.storage)?;
+    submit_for_approval(
+        deps,
+        env,
+        info.sender,
+        msg,
+        key,
+        co_sponsors,
+        Submission {
+            deposit: config.deposit_info,
+            deposit_messages,
+            resubmitted_from: None,
+        },
+    )
+}
 
-    let propose_msg_internal = match msg {
-        ProposeMessage::Propose {
-            title,
-            description,
-            msgs,
-        } => ProposeMsg {
-            title,
-            description,
-            msgs,
-            proposer: Some(info.sender.to_string()),
+pub fn execute_receive<P: ApprovalProposeMessage>(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    wrapper: Snip20ReceiveMsg,
+) -> Result<Response, PreProposeError> {
+    let ReceiveMsg::Propose {
+        key,
+        msg,
+        co_sponsors,
+    } = PrePropose::<P>::default().check_receive::<P>(deps.as_ref(), &info, &wrapper)?;
+    let config = PrePropose::<P>::default().config.load(deps.storage)?;
+    submit_for_approval(
+        deps,
+        env,
+        wrapper.from,
+        msg,
+        key,
+        co_sponsors,
+        Submission {
+            deposit: config.deposit_info,
+            // The deposit was paid by the send.
+            deposit_messages: vec![],
+            resubmitted_from: None,
         },
-    };
+    )
+}
+
+/// How a proposal submitted for approval pays its deposit.
+struct Submission {
+    /// The deposit the proposal holds.
+    deposit: Option<CheckedDepositInfo>,
+    /// Messages that take the deposit, if it has not been paid.
+    deposit_messages: Vec<CosmosMsg>,
+    /// The rejected proposal this resubmits, if any.
+    resubmitted_from: Option<u64>,
+}
+
+/// Saves a proposal as pending approval.
+fn submit_for_approval<P: ApprovalProposeMessage>(
+    deps: DepsMut,
+    env: Env,
+    proposer: Addr,
+    msg: P,
+    key: String,
+    co_sponsors: Vec<CoSponsor>,
+    submission: Submission,
+) -> Result<Response, PreProposeError> {
+    let pre_propose_base = PrePropose::<P>::default();
+
+    pre_propose_base.check_can_submit(
+        deps.as_ref(),
+        &env,
+        proposer.clone(),
+        key.clone(),
+        &co_sponsors,
+    )?;
+    // Pending proposals count towards the submission limits.
+    pre_propose_base.record_submission(deps.storage, &env.block, &proposer)?;
+
+    let approval_id = advance_approval_id(deps.storage)?;
+
+    let title = msg.title().to_string();
+    let description = msg.description().to_string();
+    let propose_msg_internal = msg.into_propose_msg(&proposer);
 
     // Prepare proposal submitted hooks msg to notify approver.  Make
     // a proposal on the approver DAO to approve this pre-proposal
@@ -113,189 +241,472 @@ pub fn execute_propose(
                     code_hash: a.code_hash,
                     msg: to_binary(&ExecuteBase::<ApproverProposeMessage, Empty>::Propose {
                         msg: ApproverProposeMessage::Propose {
-                            title: propose_msg_internal.title.clone(),
-                            description: propose_msg_internal.description.clone(),
+                            title: title.clone(),
+                            description: description.clone(),
                             approval_id,
                         },
                         key: key.clone(),
+                        co_sponsors: vec![],
                     })?,
                     funds: vec![],
                 };
                 Ok(SubMsg::new(execute_msg))
             })?;
 
+    if let Some(rejected_id) = submission.resubmitted_from {
+        let rejected = completed_proposals::<P::ProposeMsg>()
+            .get(deps.storage, &rejected_id)
+            .ok_or(PreProposeError::ProposalNotFound {})?;
+        completed_proposals().insert(
+            deps.storage,
+            &rejected_id,
+            &Proposal {
+                status: ProposalStatus::Resubmitted {
+                    resubmission_id: approval_id,
+                },
+                ..rejected
+            },
+        )?;
+    }
+
     // Save the proposal and its information as pending.
-    PENDING_PROPOSALS.insert(
+    let expiration = PENDING_TIMEOUT
+        .may_load(deps.storage)?
+        .map(|timeout| timeout.after(&env.block));
+    pending_proposals().insert(
         deps.storage,
         &approval_id,
         &Proposal {
             status: ProposalStatus::Pending {},
             approval_id,
-            proposer: info.sender,
+            proposer,
             msg: propose_msg_internal,
-            deposit: config.deposit_info,
+            deposit: submission.deposit,
+            approvals: vec![],
+            rejections: vec![],
+            expiration,
+            resubmitted_from: submission.resubmitted_from,
         },
     )?;
 
     Ok(Response::default()
-        .add_messages(deposit_messages)
+        .add_messages(submission.deposit_messages)
         .add_submessages(hooks_msgs)
         .add_attribute("method", "pre-propose")
         .add_attribute("id", approval_id.to_string()))
 }
 
-pub fn execute_approve(
+pub fn execute_approve<P: ApprovalProposeMessage>(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
     id: u64,
 ) -> Result<Response, PreProposeError> {
-    // Check sender is the approver
-    let approver = APPROVER.load(deps.storage)?;
-    if approver != info.sender {
+    // Check sender is an approver
+    let committee = COMMITTEE.load(deps.storage)?;
+    if committee.weight(&info.sender).is_none() {
         return Err(PreProposeError::Unauthorized {});
     }
 
-    // Load proposal and send propose message to the proposal module
-    let proposal = PENDING_PROPOSALS.get(deps.storage, &id);
-    match proposal {
-        Some(proposal) => {
-            let proposal_module = PrePropose::default().proposal_module.load(deps.storage)?;
-
-            // Snapshot the deposit for the proposal that we're about
-            // to create.
-            let proposal_id = deps.querier.query_wasm_smart(
-                &proposal_module.code_hash.clone(),
-                proposal_module.addr.clone().to_string(),
-                &dao_interface::proposal::Query::NextProposalId {},
-            )?;
-            PrePropose::default().deposits.insert(
-                deps.storage,
-                &proposal_id,
-                &(proposal.deposit.clone(), proposal.proposer.clone()),
-            )?;
-
-            let propose_messsage = WasmMsg::Execute {
-                contract_addr: proposal_module.addr.into_string(),
-                code_hash: proposal_module.code_hash,
-                msg: to_binary(&ProposeMessageInternal::Propose(proposal.msg.clone()))?,
-                funds: vec![],
-            };
-
-            COMPLETED_PROPOSALS.insert(
-                deps.storage,
-                &id,
-                &Proposal {
-                    status: ProposalStatus::Approved {
-                        created_proposal_id: proposal_id,
-                    },
-                    approval_id: proposal.approval_id,
-                    proposer: proposal.proposer,
-                    msg: proposal.msg,
-                    deposit: proposal.deposit,
-                },
-            )?;
-            CREATED_PROPOSAL_TO_COMPLETED_PROPOSAL.insert(deps.storage, &proposal_id, &id)?;
-            PENDING_PROPOSALS.remove(deps.storage, &id)?;
-
-            Ok(Response::default()
-                .add_message(propose_messsage)
-                .add_attribute("method", "proposal_approved")
-                .add_attribute("approval_id", id.to_string())
-                .add_attribute("proposal_id", proposal_id.to_string()))
-        }
-        None => Err(PreProposeError::ProposalNotFound {}),
+    let mut proposal = pending_proposals::<P::ProposeMsg>()
+        .get(deps.storage, &id)
+        .ok_or(PreProposeError::ProposalNotFound {})?;
+    // Votes arriving after the proposal expired close it instead, so
+    // that an approver DAO's proposal completing late does not fail.
+    if proposal.is_expired(&env.block) {
+        return close_expired::<P>(deps, id, proposal);
+    }
+    if has_voted(&proposal, &info.sender) {
+        return Err(PreProposeError::AlreadyVoted {});
     }
+    proposal.approvals.push(info.sender.clone());
+
+    if !committee.progress(&proposal).is_approved() {
+        pending_proposals().insert(deps.storage, &id, &proposal)?;
+        return Ok(Response::default()
+            .add_attribute("method", "approval_recorded")
+            .add_attribute("approval_id", id.to_string())
+            .add_attribute("approver", info.sender));
+    }
+
+    approve::<P>(deps, id, proposal)
+}
+
+/// Creates PROPOSAL in the proposal module and completes it as
+/// approved.
+fn approve<P: ApprovalProposeMessage>(
+    deps: DepsMut,
+    id: u64,
+    proposal: Proposal<P::ProposeMsg>,
+) -> Result<Response, PreProposeError> {
+    let pre_propose_base = PrePropose::<P>::default();
+
+    // Send propose message to the proposal module
+    let proposal_module = pre_propose_base.proposal_module.load(deps.storage)?;
+
+    // Snapshot the deposit for the proposal that we're about
+    // to create.
+    let proposal_id = deps.querier.query_wasm_smart(
+        &proposal_module.code_hash.clone(),
+        proposal_module.addr.clone().to_string(),
+        &dao_interface::proposal::Query::NextProposalId {},
+    )?;
+    pre_propose_base.deposits.insert(
+        deps.storage,
+        &proposal_id,
+        &(proposal.deposit.clone(), proposal.proposer.clone()),
+    )?;
+    // The proposal counts towards the proposer's submission
+    // limits until it completes in the proposal module.
+    pre_propose_base
+        .open_proposals
+        .insert(deps.storage, &proposal_id, &proposal.proposer)?;
+
+    let propose_messsage = WasmMsg::Execute {
+        contract_addr: proposal_module.addr.into_string(),
+        code_hash: proposal_module.code_hash,
+        msg: to_binary(&ProposeMessageInternal::Propose(proposal.msg.clone()))?,
+        funds: vec![],
+    };
+
+    completed_proposals().insert(
+        deps.storage,
+        &id,
+        &Proposal {
+            status: ProposalStatus::Approved {
+                created_proposal_id: proposal_id,
+            },
+            ..proposal
+        },
+    )?;
+    CREATED_PROPOSAL_TO_COMPLETED_PROPOSAL.insert(deps.storage, &proposal_id, &id)?;
+    pending_proposals::<P::ProposeMsg>().remove(deps.storage, &id)?;
+
+    Ok(Response::default()
+        .add_message(propose_messsage)
+        .add_attribute("method", "proposal_approved")
+        .add_attribute("approval_id", id.to_string())
+        .add_attribute("proposal_id", proposal_id.to_string()))
 }
 
-pub fn execute_reject(
+pub fn execute_reject<P: ApprovalProposeMessage>(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
     id: u64,
+    reason: Option<String>,
 ) -> Result<Response, PreProposeError> {
-    // Check sender is the approver
-    let approver = APPROVER.load(deps.storage)?;
-    if approver != info.sender {
+    // Check sender is an approver
+    let committee = COMMITTEE.load(deps.storage)?;
+    if committee.weight(&info.sender).is_none() {
         return Err(PreProposeError::Unauthorized {});
     }
 
-    let Proposal {
-        approval_id,
-        proposer,
-        msg,
-        deposit,
-        ..
-    } = PENDING_PROPOSALS
+    let mut proposal = pending_proposals::<P::ProposeMsg>()
         .get(deps.storage, &id)
         .ok_or(PreProposeError::ProposalNotFound {})?;
+    if proposal.is_expired(&env.block) {
+        return close_expired::<P>(deps, id, proposal);
+    }
+    if has_voted(&proposal, &info.sender) {
+        return Err(PreProposeError::AlreadyVoted {});
+    }
+    proposal.rejections.push(Rejection {
+        approver: info.sender.clone(),
+        reason: reason.clone(),
+    });
+
+    let response = Response::default()
+        .add_attribute("proposal", id.to_string())
+        .add_attribute("approver", info.sender)
+        .add_attribute("reason", reason.unwrap_or_default());
+
+    if !committee.progress(&proposal).is_rejected() {
+        pending_proposals().insert(deps.storage, &id, &proposal)?;
+        return Ok(response.add_attribute("method", "rejection_recorded"));
+    }
 
-    COMPLETED_PROPOSALS.insert(
+    reject::<P>(deps, id, proposal, response)
+}
+
+/// Completes PROPOSAL as rejected, holding its deposit.
+fn reject<P: ApprovalProposeMessage>(
+    deps: DepsMut,
+    id: u64,
+    proposal: Proposal<P::ProposeMsg>,
+    response: Response,
+) -> Result<Response, PreProposeError> {
+    let proposer = proposal.proposer.clone();
+    let deposit = proposal.deposit.clone();
+    completed_proposals().insert(
         deps.storage,
         &id,
         &Proposal {
             status: ProposalStatus::Rejected {},
-            approval_id,
-            proposer: proposer.clone(),
-            msg: msg.clone(),
-            deposit: deposit.clone(),
+            ..proposal
         },
     )?;
-    PENDING_PROPOSALS.remove(deps.storage, &id)?;
+    pending_proposals::<P::ProposeMsg>().remove(deps.storage, &id)?;
+    PrePropose::<P>::default().record_completion(deps.storage, &proposer)?;
 
+    // Hold the deposit so that the proposer may resubmit the
+    // proposal without paying it again.
+    if let Some(ref deposit_info) = deposit {
+        HELD_DEPOSITS.insert(deps.storage, &id, deposit_info)?;
+    }
+
+    Ok(response
+        .add_attribute("method", "proposal_rejected")
+        .add_attribute("deposit_info", to_binary(&deposit)?.to_string()))
+}
+
+pub fn execute_expire<P: ApprovalProposeMessage>(
+    deps: DepsMut,
+    env: Env,
+    id: u64,
+) -> Result<Response, PreProposeError> {
+    let proposal = pending_proposals::<P::ProposeMsg>()
+        .get(deps.storage, &id)
+        .ok_or(PreProposeError::ProposalNotFound {})?;
+    if !proposal.is_expired(&env.block) {
+        return Err(PreProposeError::PendingProposalNotExpired {});
+    }
+    close_expired::<P>(deps, id, proposal)
+}
+
+/// Approves or rejects a pending proposal whose votes decide it under
+/// the current committee. A proposal voted on before the committee
+/// changed may be decided by votes it already has, and would
+/// otherwise wait for a vote that may never come.
+pub fn execute_finalize<P: ApprovalProposeMessage>(
+    deps: DepsMut,
+    env: Env,
+    id: u64,
+) -> Result<Response, PreProposeError> {
+    let proposal = pending_proposals::<P::ProposeMsg>()
+        .get(deps.storage, &id)
+        .ok_or(PreProposeError::ProposalNotFound {})?;
+    if proposal.is_expired(&env.block) {
+        return close_expired::<P>(deps, id, proposal);
+    }
+
+    let progress = COMMITTEE.load(deps.storage)?.progress(&proposal);
+    if progress.is_approved() {
+        approve::<P>(deps, id, proposal)
+    } else if progress.is_rejected() {
+        let response = Response::default().add_attribute("proposal", id.to_string());
+        reject::<P>(deps, id, proposal, response)
+    } else {
+        Err(PreProposeError::NotDecided {})
+    }
+}
+
+/// Completes PROPOSAL as expired and refunds its deposit.
+fn close_expired<P: ApprovalProposeMessage>(
+    deps: DepsMut,
+    id: u64,
+    proposal: Proposal<P::ProposeMsg>,
+) -> Result<Response, PreProposeError> {
+    let proposer = proposal.proposer.clone();
+    let deposit = proposal.deposit.clone();
+    completed_proposals().insert(
+        deps.storage,
+        &id,
+        &Proposal {
+            status: ProposalStatus::Expired {},
+            ..proposal
+        },
+    )?;
+    pending_proposals::<P::ProposeMsg>().remove(deps.storage, &id)?;
+    PrePropose::<P>::default().record_completion(deps.storage, &proposer)?;
+
+    // The approvers never decided on the proposal, so the deposit is
+    // refunded regardless of the refund policy.
     let messages = if let Some(ref deposit_info) = deposit {
-        // Refund can be issued if proposal if deposits are always
-        // refunded. `OnlyPassed` and `Never` refund deposit policies
-        // do not apply here.
-        if deposit_info.refund_policy == DepositRefundPolicy::Always {
-            deposit_info.get_return_deposit_message(&proposer)?
-        } else {
-            // If the proposer doesn't get the deposit, the DAO does.
-            let dao = PrePropose::default().dao.load(deps.storage)?;
-            deposit_info.get_return_deposit_message(&dao.addr)?
-        }
+        deposit_info.get_return_deposit_message(&proposer)?
     } else {
         vec![]
     };
 
     Ok(Response::default()
-        .add_attribute("method", "proposal_rejected")
-        .add_attribute("proposal", id.to_string())
-        .add_attribute("deposit_info", to_binary(&deposit)?.to_string())
-        .add_messages(messages))
+        .add_messages(messages)
+        .add_attribute("method", "proposal_expired")
+        .add_attribute("approval_id", id.to_string()))
 }
 
-pub fn execute_update_approver(
+pub fn execute_resubmit<P: ApprovalProposeMessage>(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
-    address: String,
+    id: u64,
+    msg: P,
+    key: String,
+    co_sponsors: Vec<CoSponsor>,
+) -> Result<Response, PreProposeError> {
+    let rejected = completed_proposals::<P::ProposeMsg>()
+        .get(deps.storage, &id)
+        .ok_or(PreProposeError::ProposalNotFound {})?;
+    if rejected.proposer != info.sender {
+        return Err(PreProposeError::Unauthorized {});
+    }
+    if rejected.status != (ProposalStatus::Rejected {}) {
+        return Err(PreProposeError::NotRejected {});
+    }
+
+    // Keep the deposit held since the rejection. If it has been
+    // claimed, or the proposal was rejected before deposits were
+    // held, take a new one.
+    let submission = match HELD_DEPOSITS.get(deps.storage, &id) {
+        Some(deposit) => {
+            HELD_DEPOSITS.remove(deps.storage, &id)?;
+            Submission {
+                deposit: Some(deposit),
+                deposit_messages: vec![],
+                resubmitted_from: Some(id),
+            }
+        }
+        None => {
+            let config = PrePropose::<P>::default().config.load(deps.storage)?;
+            let deposit_messages = if let Some(ref deposit_info) = config.deposit_info {
+                deposit_info.check_native_deposit_paid(&info)?;
+                deposit_info.get_take_deposit_messages(&info.sender, &env.contract.address)?
+            } else {
+                vec![]
+            };
+            Submission {
+                deposit: config.deposit_info,
+                deposit_messages,
+                resubmitted_from: Some(id),
+            }
+        }
+    };
+
+    submit_for_approval(deps, env, info.sender, msg, key, co_sponsors, submission)
+        .map(|resp| resp.add_attribute("resubmitted_from", id.to_string()))
+}
+
+pub fn execute_claim_deposit<P: ApprovalProposeMessage>(
+    deps: DepsMut,
+    info: MessageInfo,
+    id: u64,
 ) -> Result<Response, PreProposeError> {
-    // Check sender is the approver
-    let approver = APPROVER.load(deps.storage)?;
-    if approver != info.sender {
+    let proposal = completed_proposals::<P::ProposeMsg>()
+        .get(deps.storage, &id)
+        .ok_or(PreProposeError::ProposalNotFound {})?;
+    let dao = PrePropose::<P>::default().dao.load(deps.storage)?;
+    if info.sender != proposal.proposer && info.sender != dao.addr {
         return Err(PreProposeError::Unauthorized {});
     }
 
+    let deposit = HELD_DEPOSITS
+        .get(deps.storage, &id)
+        .ok_or(PreProposeError::NoHeldDeposit {})?;
+    HELD_DEPOSITS.remove(deps.storage, &id)?;
+
+    // Rejection by the approvers is treated as a rejected proposal:
+    // `Always` refunds the deposit, `OnlyPassed` and `Never` send it
+    // to the DAO, and `Graded` refunds its rejected share. Whether
+    // the approvers reached quorum is not known here.
+    let messages =
+        deposit.get_completion_messages(Status::Closed, None, &proposal.proposer, &dao.addr)?;
+
+    Ok(Response::default()
+        .add_messages(messages)
+        .add_attribute("method", "claim_deposit")
+        .add_attribute("approval_id", id.to_string()))
+}
+
+pub fn execute_update_pending_timeout<P: ApprovalProposeMessage>(
+    deps: DepsMut,
+    info: MessageInfo,
+    timeout: Option<Duration>,
+) -> Result<Response, PreProposeError> {
+    let dao = PrePropose::<P>::default().dao.load(deps.storage)?;
+    if info.sender != dao.addr {
+        return Err(PreProposeError::NotDao {});
+    }
+
+    match timeout {
+        Some(timeout) => PENDING_TIMEOUT.save(deps.storage, &timeout)?,
+        None => PENDING_TIMEOUT.remove(deps.storage),
+    }
+
+    Ok(Response::default().add_attribute("method", "update_pending_timeout"))
+}
+
+fn has_voted<M>(proposal: &Proposal<M>, approver: &Addr) -> bool {
+    proposal.approvals.contains(approver)
+        || proposal.rejections.iter().any(|r| r.approver == *approver)
+}
+
+/// Checks that SENDER may change the committee and its hooks: the
+/// DAO, or an approver who meets the threshold alone.
+fn check_can_manage<P: ApprovalProposeMessage>(
+    deps: Deps,
+    sender: &Addr,
+) -> Result<(), PreProposeError> {
+    let dao = PrePropose::<P>::default().dao.load(deps.storage)?;
+    let committee = COMMITTEE.load(deps.storage)?;
+    if dao.addr != *sender && !committee.is_sole_approver(sender) {
+        return Err(PreProposeError::Unauthorized {});
+    }
+    Ok(())
+}
+
+pub fn execute_update_approver<P: ApprovalProposeMessage>(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: String,
+) -> Result<Response, PreProposeError> {
+    check_can_manage::<P>(deps.as_ref(), &info.sender)?;
+
     // Validate address and save new approver
     let addr = deps.api.addr_validate(&address)?;
-    APPROVER.save(deps.storage, &addr)?;
+    COMMITTEE.save(deps.storage, &Committee::single(addr))?;
 
     Ok(Response::default())
 }
 
-pub fn execute_add_approver_hook(
+pub fn execute_update_committee<P: ApprovalProposeMessage>(
+    deps: DepsMut,
+    info: MessageInfo,
+    approvers: Vec<UncheckedApprover>,
+    threshold: u64,
+) -> Result<Response, PreProposeError> {
+    check_can_manage::<P>(deps.as_ref(), &info.sender)?;
+
+    let mut checked: Vec<Approver> = Vec::with_capacity(approvers.len());
+    for UncheckedApprover { address, weight } in approvers {
+        let address = deps.api.addr_validate(&address)?;
+        if weight == 0 || checked.iter().any(|a| a.address == address) {
+            return Err(PreProposeError::InvalidCommittee {});
+        }
+        checked.push(Approver { address, weight });
+    }
+    let committee = Committee {
+        approvers: checked,
+        threshold,
+    };
+    if threshold == 0 || threshold > committee.total_weight() {
+        return Err(PreProposeError::InvalidCommittee {});
+    }
+    COMMITTEE.save(deps.storage, &committee)?;
+
+    Ok(Response::default()
+        .add_attribute("method", "update_committee")
+        .add_attribute("threshold", threshold.to_string()))
+}
+
+pub fn execute_add_approver_hook<P: ApprovalProposeMessage>(
     deps: DepsMut,
     info: MessageInfo,
     address: String,
     code_hash: String,
 ) -> Result<Response, PreProposeError> {
-    let pre_propose_base = PrePropose::default();
+    let pre_propose_base = PrePropose::<P>::default();
 
-    let dao = pre_propose_base.dao.load(deps.storage)?;
-    let approver = APPROVER.load(deps.storage)?;
-
-    // Check sender is the approver or the parent DAO
-    if approver != info.sender && dao.addr != info.sender {
-        return Err(PreProposeError::Unauthorized {});
-    }
+    // Check sender is the DAO or may act for the committee alone
+    check_can_manage::<P>(deps.as_ref(), &info.sender)?;
 
     let addr = deps.api.addr_validate(&address)?;
     pre_propose_base
@@ -305,21 +716,16 @@ pub fn execute_add_approver_hook(
     Ok(Response::default())
 }
 
-pub fn execute_remove_approver_hook(
+pub fn execute_remove_approver_hook<P: ApprovalProposeMessage>(
     deps: DepsMut,
     info: MessageInfo,
     address: String,
     code_hash: String,
 ) -> Result<Response, PreProposeError> {
-    let pre_propose_base = PrePropose::default();
+    let pre_propose_base = PrePropose::<P>::default();
 
-    let dao = pre_propose_base.dao.load(deps.storage)?;
-    let approver = APPROVER.load(deps.storage)?;
-
-    // Check sender is the approver or the parent DAO
-    if approver != info.sender && dao.addr != info.sender {
-        return Err(PreProposeError::Unauthorized {});
-    }
+    // Check sender is the DAO or may act for the committee alone
+    check_can_manage::<P>(deps.as_ref(), &info.sender)?;
 
     // Validate address
     let addr = deps.api.addr_validate(&address)?;
@@ -332,37 +738,56 @@ pub fn execute_remove_approver_hook(
     Ok(Response::default())
 }
 
-#[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+/// Answers a query sent to an approval module whose proposals are
+/// submitted with P.
+pub fn query_approval<P: ApprovalProposeMessage>(
+    deps: Deps,
+    env: Env,
+    msg: QueryMsg,
+) -> StdResult<Binary> {
+    let pending_proposals = pending_proposals::<P::ProposeMsg>();
+    let completed_proposals = completed_proposals::<P::ProposeMsg>();
     match msg {
         QueryMsg::QueryExtension { msg } => match msg {
-            QueryExt::Approver {} => to_binary(&APPROVER.load(deps.storage)?),
+            QueryExt::Approver {} => match COMMITTEE.load(deps.storage)?.approvers.as_slice() {
+                [approver] => to_binary(&approver.address),
+                _ => Err(StdError::generic_err(
+                    "Committee has more than one approver",
+                )),
+            },
+            QueryExt::Committee {} => to_binary(&COMMITTEE.load(deps.storage)?),
             QueryExt::IsPending { id } => {
-                let pending = PENDING_PROPOSALS.get(deps.storage, &id).is_some();
+                let pending = pending_proposals.get(deps.storage, &id).is_some();
                 // Force load completed proposal if not pending, throwing error
                 // if not found.
                 if !pending {
-                    COMPLETED_PROPOSALS.get(deps.storage, &id);
+                    completed_proposals.get(deps.storage, &id);
                 }
 
                 to_binary(&pending)
             }
             QueryExt::Proposal { id } => {
-                if let Some(pending) = PENDING_PROPOSALS.get(deps.storage, &id) {
+                if let Some(pending) = pending_proposals.get(deps.storage, &id) {
                     to_binary(&pending)
                 } else {
                     // Force load completed proposal if not pending, throwing
                     // error if not found.
-                    to_binary(&COMPLETED_PROPOSALS.get(deps.storage, &id))
+                    to_binary(&completed_proposals.get(deps.storage, &id))
                 }
             }
             QueryExt::PendingProposal { id } => {
-                to_binary(&PENDING_PROPOSALS.get(deps.storage, &id))
+                let committee = COMMITTEE.load(deps.storage)?;
+                to_binary(
+                    &pending_proposals
+                        .get(deps.storage, &id)
+                        .map(|proposal| with_progress(&committee, proposal)),
+                )
             }
             QueryExt::PendingProposals { start_after, limit } => {
-                let mut res: Vec<Proposal> = Vec::new();
+                let committee = COMMITTEE.load(deps.storage)?;
+                let mut res: Vec<PendingProposal<P::ProposeMsg>> = Vec::new();
                 let mut start = start_after.clone();
-                let binding = &PENDING_PROPOSALS;
+                let binding = &pending_proposals;
                 let iter = binding.iter(deps.storage)?;
                 for item in iter {
                     let (id, proposal) = item?;
@@ -373,7 +798,7 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
                         }
                     }
                     if start.is_none() {
-                        res.push(proposal);
+                        res.push(with_progress(&committee, proposal));
                         if res.len() >= limit.unwrap_or_default() as usize {
                             break; // Break out of loop if limit reached
                         }
@@ -385,9 +810,10 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
                 start_before,
                 limit,
             } => {
-                let mut res: Vec<Proposal> = Vec::new();
+                let committee = COMMITTEE.load(deps.storage)?;
+                let mut res: Vec<PendingProposal<P::ProposeMsg>> = Vec::new();
                 let mut start = start_before.clone();
-                let binding = &PENDING_PROPOSALS;
+                let binding = &pending_proposals;
                 let iter = binding.iter(deps.storage)?.rev(); // Iterate in reverse
                 for item in iter {
                     let (id, proposal) = item?;
@@ -398,7 +824,7 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
                         }
                     }
                     if start.is_none() {
-                        res.push(proposal);
+                        res.push(with_progress(&committee, proposal));
                         if res.len() >= limit.unwrap_or_default() as usize {
                             break; // Break out of loop if limit reached
                         }
@@ -408,12 +834,12 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
             }
 
             QueryExt::CompletedProposal { id } => {
-                to_binary(&COMPLETED_PROPOSALS.get(deps.storage, &id))
+                to_binary(&completed_proposals.get(deps.storage, &id))
             }
             QueryExt::CompletedProposals { start_after, limit } => {
-                let mut res: Vec<Proposal> = Vec::new();
+                let mut res: Vec<Proposal<P::ProposeMsg>> = Vec::new();
                 let mut start = start_after.clone();
-                let binding = &COMPLETED_PROPOSALS;
+                let binding = &completed_proposals;
                 let iter = binding.iter(deps.storage)?;
                 for item in iter {
                     let (id, proposal) = item?;
@@ -436,9 +862,9 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
                 start_before,
                 limit,
             } => {
-                let mut res: Vec<Proposal> = Vec::new();
+                let mut res: Vec<Proposal<P::ProposeMsg>> = Vec::new();
                 let mut start = start_before.clone();
-                let binding = &COMPLETED_PROPOSALS;
+                let binding = &completed_proposals;
                 let iter = binding.iter(deps.storage)?.rev(); // Iterate in reverse
                 for item in iter {
                     let (id, proposal) = item?;
@@ -460,7 +886,16 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
             QueryExt::CompletedProposalIdForCreatedProposalId { id } => {
                 to_binary(&CREATED_PROPOSAL_TO_COMPLETED_PROPOSAL.get(deps.storage, &id))
             }
+            QueryExt::PendingTimeout {} => to_binary(&PENDING_TIMEOUT.may_load(deps.storage)?),
+            QueryExt::HeldDeposit { id } => to_binary(&HELD_DEPOSITS.get(deps.storage, &id)),
         },
-        _ => PrePropose::default().query(deps, env, msg),
+        _ => PrePropose::<P>::default().query(deps, env, msg),
+    }
+}
+
+fn with_progress<M>(committee: &Committee, proposal: Proposal<M>) -> PendingProposal<M> {
+    PendingProposal {
+        progress: committee.progress(&proposal),
+        proposal,
     }
 }
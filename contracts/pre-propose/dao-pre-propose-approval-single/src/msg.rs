@@ -1,9 +1,17 @@
+use std::fmt::Debug;
+
+use cosmwasm_schema::schemars::JsonSchema;
+use cosmwasm_schema::serde::{de::DeserializeOwned, Serialize};
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{CosmosMsg, Empty};
+use cosmwasm_std::{Addr, CosmosMsg, Empty};
 use dao_pre_propose_base::msg::{
-    ExecuteMsg as ExecuteBase, InstantiateMsg as InstantiateBase, QueryMsg as QueryBase,
+    CoSponsor, ExecuteMsg as ExecuteBase, InstantiateMsg as InstantiateBase, QueryMsg as QueryBase,
+};
+use dao_voting::{
+    condition::ExecutionCondition, dependency::ProposalDependency,
+    proposal::SingleChoiceProposeMsg as ProposeMsg,
 };
-use dao_voting::proposal::SingleChoiceProposeMsg as ProposeMsg;
+use secret_utils::Duration;
 
 #[cw_serde]
 pub enum ApproverProposeMessage {
@@ -20,46 +28,172 @@ pub enum ProposeMessage {
         title: String,
         description: String,
         msgs: Vec<CosmosMsg<Empty>>,
+        /// Conditions that must hold when the proposal is executed.
+        #[serde(default)]
+        conditions: Vec<ExecutionCondition>,
+        /// Proposals, in this or other proposal modules, that must be
+        /// executed before the proposal may be executed.
+        #[serde(default)]
+        dependencies: Vec<ProposalDependency>,
+        /// Whether this is an emergency proposal. Only the proposal
+        /// module's emergency proposers may create them.
+        #[serde(default)]
+        emergency: bool,
     },
 }
 
+/// A message proposing a proposal for approval. The approval logic of
+/// this module is generic over it, so that approval modules for other
+/// proposal modules need only implement it for their own propose
+/// message.
+pub trait ApprovalProposeMessage:
+    Serialize + DeserializeOwned + Clone + Debug + PartialEq + JsonSchema
+{
+    /// The proposal module's propose message, sent to it once the
+    /// proposal is approved.
+    type ProposeMsg: Serialize + DeserializeOwned + Clone + Debug + PartialEq + JsonSchema;
+
+    fn title(&self) -> &str;
+    fn description(&self) -> &str;
+
+    /// Returns the proposal module's propose message for this
+    /// proposal, made on behalf of PROPOSER.
+    fn into_propose_msg(self, proposer: &Addr) -> Self::ProposeMsg;
+}
+
+impl ApprovalProposeMessage for ProposeMessage {
+    type ProposeMsg = ProposeMsg;
+
+    fn title(&self) -> &str {
+        match self {
+            ProposeMessage::Propose { title, .. } => title,
+        }
+    }
+
+    fn description(&self) -> &str {
+        match self {
+            ProposeMessage::Propose { description, .. } => description,
+        }
+    }
+
+    fn into_propose_msg(self, proposer: &Addr) -> ProposeMsg {
+        match self {
+            ProposeMessage::Propose {
+                title,
+                description,
+                msgs,
+                conditions,
+                dependencies,
+                emergency,
+            } => ProposeMsg {
+                title,
+                description,
+                msgs,
+                proposer: Some(proposer.to_string()),
+                conditions,
+                dependencies,
+                emergency,
+            },
+        }
+    }
+}
+
 #[cw_serde]
 pub struct InstantiateExt {
     pub approver: String,
+    /// How long proposals may wait for approval before they expire.
+    /// If unset, proposals wait indefinitely.
+    #[serde(default)]
+    pub pending_timeout: Option<Duration>,
 }
 
 #[cw_serde]
-pub enum ExecuteExt {
-    /// Approve a proposal, only callable by approver
+pub struct UncheckedApprover {
+    pub address: String,
+    pub weight: u64,
+}
+
+/// Extension messages of approval modules, generic over the message
+/// proposals are resubmitted with.
+#[cw_serde]
+pub enum ApprovalExecuteExt<P> {
+    /// Approve a proposal, only callable by approvers. The proposal
+    /// is created once approvers meeting the committee's threshold
+    /// have approved it.
     Approve { id: u64 },
-    /// Reject a proposal, only callable by approver
-    Reject { id: u64 },
-    /// Updates the approver, can only be called the current approver
+    /// Reject a proposal, only callable by approvers. The proposal is
+    /// rejected once it can no longer meet the committee's
+    /// threshold. REASON is recorded on the proposal for the
+    /// proposer to see.
+    Reject {
+        id: u64,
+        #[serde(default)]
+        reason: Option<String>,
+    },
+    /// Replaces the committee with a single approver. Callable by the
+    /// DAO, or an approver who meets the threshold alone.
     UpdateApprover { address: String },
+    /// Replaces the committee. Callable by the DAO, or an approver who
+    /// meets the threshold alone. Pending proposals are counted
+    /// using the new committee, and those it decides may be
+    /// finalized.
+    UpdateCommittee {
+        approvers: Vec<UncheckedApprover>,
+        threshold: u64,
+    },
+    /// Closes a pending proposal that expired before it was approved
+    /// or rejected, refunding its deposit. Callable by anyone.
+    Expire { id: u64 },
+    /// Approves or rejects a pending proposal that the votes it has
+    /// already decide under the current committee, for example after
+    /// the threshold was lowered. Callable by anyone.
+    Finalize { id: u64 },
+    /// Resubmits a rejected proposal in edited form, only callable by
+    /// its proposer. The new proposal keeps the rejected proposal's
+    /// deposit, or takes a new one if that deposit was claimed.
+    Resubmit {
+        id: u64,
+        key: String,
+        msg: P,
+        #[serde(default)]
+        co_sponsors: Vec<CoSponsor>,
+    },
+    /// Settles the deposit held for a rejected proposal according to
+    /// its refund policy. Callable by the proposer or the DAO. Once
+    /// claimed, resubmitting the proposal takes a new deposit.
+    ClaimDeposit { id: u64 },
+    /// Updates the pending timeout used for new proposals. Only
+    /// callable by the DAO.
+    UpdatePendingTimeout { timeout: Option<Duration> },
 }
 
 #[cw_serde]
 #[derive(QueryResponses)]
 pub enum QueryExt {
-    /// List the approver address
+    /// The approver address. Errors if the committee has more than
+    /// one approver.
     #[returns(cosmwasm_std::Addr)]
     Approver {},
+    /// The approval committee.
+    #[returns(crate::state::Committee)]
+    Committee {},
     /// Return whether or not the proposal is pending
     #[returns(bool)]
     IsPending { id: u64 },
     /// A proposal, pending or completed.
     #[returns(crate::state::Proposal)]
     Proposal { id: u64 },
-    /// A pending proposal
-    #[returns(crate::state::Proposal)]
+    /// A pending proposal and its approval progress
+    #[returns(::std::option::Option<crate::state::PendingProposal>)]
     PendingProposal { id: u64 },
-    /// List of proposals awaiting approval
-    #[returns(Vec<crate::state::Proposal>)]
+    /// List of proposals awaiting approval, with their approval
+    /// progress
+    #[returns(Vec<crate::state::PendingProposal>)]
     PendingProposals {
         start_after: Option<u64>,
         limit: Option<u32>,
     },
-    #[returns(Vec<crate::state::Proposal>)]
+    #[returns(Vec<crate::state::PendingProposal>)]
     ReversePendingProposals {
         start_before: Option<u64>,
         limit: Option<u32>,
@@ -81,8 +215,16 @@ pub enum QueryExt {
     /// The completed approval ID for a created proposal ID.
     #[returns(::std::option::Option<u64>)]
     CompletedProposalIdForCreatedProposalId { id: u64 },
+    /// How long new proposals may wait for approval before they
+    /// expire.
+    #[returns(::std::option::Option<secret_utils::Duration>)]
+    PendingTimeout {},
+    /// The deposit held for a rejected proposal, if any.
+    #[returns(::std::option::Option<dao_voting::deposit::CheckedDepositInfo>)]
+    HeldDeposit { id: u64 },
 }
 
+pub type ExecuteExt = ApprovalExecuteExt<ProposeMessage>;
 pub type InstantiateMsg = InstantiateBase<InstantiateExt>;
 pub type ExecuteMsg = ExecuteBase<ProposeMessage, ExecuteExt>;
 pub type QueryMsg = QueryBase<QueryExt>;
@@ -91,6 +233,6 @@ pub type QueryMsg = QueryBase<QueryExt>;
 /// `proposer` field. The module will fill this in based on the sender
 /// of the external message.
 #[cw_serde]
-pub(crate) enum ProposeMessageInternal {
-    Propose(ProposeMsg),
+pub(crate) enum ProposeMessageInternal<M> {
+    Propose(M),
 }
@@ -1,8 +1,10 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, StdResult, Storage};
+use cosmwasm_schema::serde::{de::DeserializeOwned, Serialize};
+use cosmwasm_std::{Addr, BlockInfo, StdResult, Storage};
 use secret_storage_plus::Item;
 use secret_toolkit::serialization::Json;
 use secret_toolkit::storage::Keymap;
+use secret_utils::{Duration, Expiration};
 
 use dao_voting::deposit::CheckedDepositInfo;
 use dao_voting::proposal::SingleChoiceProposeMsg as ProposeMsg;
@@ -16,12 +18,23 @@ pub enum ProposalStatus {
         /// The created proposal ID.
         created_proposal_id: u64,
     },
-    /// The proposal has been rejected.
+    /// The proposal has been rejected. Its deposit is held until it
+    /// is resubmitted or claimed.
     Rejected {},
+    /// The proposal was not approved or rejected before it expired.
+    /// Its deposit was refunded.
+    Expired {},
+    /// The proposal was rejected, then resubmitted by its proposer.
+    Resubmitted {
+        /// The approval ID of the resubmitted proposal.
+        resubmission_id: u64,
+    },
 }
 
+/// A proposal submitted for approval. M is the propose message of
+/// the proposal module it is created in once approved.
 #[cw_serde]
-pub struct Proposal {
+pub struct Proposal<M = ProposeMsg> {
     /// The status of a completed proposal.
     pub status: ProposalStatus,
     /// The approval ID used to identify this pending proposal.
@@ -30,17 +43,154 @@ pub struct Proposal {
     pub proposer: Addr,
     /// The propose message that ought to be executed on the proposal
     /// message if this proposal is approved.
-    pub msg: ProposeMsg,
+    pub msg: M,
     /// Snapshot of the deposit info at the time of proposal
     /// submission.
     pub deposit: Option<CheckedDepositInfo>,
+    /// Approvers that have approved the proposal.
+    #[serde(default)]
+    pub approvals: Vec<Addr>,
+    /// Approvers that have rejected the proposal, and why.
+    #[serde(default)]
+    pub rejections: Vec<Rejection>,
+    /// When the proposal expires if it has not been approved or
+    /// rejected. None if no pending timeout was configured when it was
+    /// submitted.
+    #[serde(default)]
+    pub expiration: Option<Expiration>,
+    /// The approval ID of the rejected proposal this resubmits, if
+    /// any.
+    #[serde(default)]
+    pub resubmitted_from: Option<u64>,
+}
+
+impl<M> Proposal<M> {
+    pub fn is_expired(&self, block: &BlockInfo) -> bool {
+        self.expiration
+            .map_or(false, |expiration| expiration.is_expired(block))
+    }
+}
+
+#[cw_serde]
+pub struct Rejection {
+    pub approver: Addr,
+    /// The reason given for the rejection, visible to the proposer.
+    pub reason: Option<String>,
+}
+
+#[cw_serde]
+pub struct Approver {
+    pub address: Addr,
+    pub weight: u64,
+}
+
+/// The approvers of proposals. A proposal is approved once approvers
+/// with a combined weight of at least `threshold` approve it, and
+/// rejected once enough reject it that this is no longer possible.
+#[cw_serde]
+pub struct Committee {
+    pub approvers: Vec<Approver>,
+    pub threshold: u64,
+}
+
+/// How close a pending proposal is to being approved or rejected.
+#[cw_serde]
+pub struct ApprovalProgress {
+    /// The combined weight of approvers that approved the proposal.
+    pub approved: u64,
+    /// The combined weight of approvers that rejected the proposal.
+    pub rejected: u64,
+    /// The weight needed to approve the proposal.
+    pub threshold: u64,
+    /// The combined weight of all approvers.
+    pub total: u64,
+}
+
+impl Committee {
+    /// A committee of a single approver.
+    pub fn single(address: Addr) -> Self {
+        Committee {
+            approvers: vec![Approver { address, weight: 1 }],
+            threshold: 1,
+        }
+    }
+
+    /// The weight of WHO, or None if they are not an approver.
+    pub fn weight(&self, who: &Addr) -> Option<u64> {
+        self.approvers
+            .iter()
+            .find(|a| a.address == *who)
+            .map(|a| a.weight)
+    }
+
+    pub fn total_weight(&self) -> u64 {
+        self.approvers.iter().map(|a| a.weight).sum()
+    }
+
+    /// Returns true if WHO may act for the committee on their own.
+    pub fn is_sole_approver(&self, who: &Addr) -> bool {
+        self.weight(who)
+            .map_or(false, |weight| weight >= self.threshold)
+    }
+
+    /// The progress of PROPOSAL. Votes from addresses that are no
+    /// longer approvers are not counted.
+    pub fn progress<M>(&self, proposal: &Proposal<M>) -> ApprovalProgress {
+        let weigh = |who: &Addr| self.weight(who).unwrap_or_default();
+        ApprovalProgress {
+            approved: proposal.approvals.iter().map(weigh).sum(),
+            rejected: proposal.rejections.iter().map(|r| weigh(&r.approver)).sum(),
+            threshold: self.threshold,
+            total: self.total_weight(),
+        }
+    }
+}
+
+impl ApprovalProgress {
+    pub fn is_approved(&self) -> bool {
+        self.approved >= self.threshold
+    }
+
+    /// Returns true if the proposal can no longer be approved.
+    pub fn is_rejected(&self) -> bool {
+        self.total.saturating_sub(self.rejected) < self.threshold
+    }
+}
+
+/// A pending proposal and its approval progress.
+#[cw_serde]
+pub struct PendingProposal<M = ProposeMsg> {
+    pub proposal: Proposal<M>,
+    pub progress: ApprovalProgress,
+}
+
+pub const COMMITTEE: Item<Committee> = Item::new("committee");
+/// How long proposals may wait for approval before they expire. If
+/// unset, proposals wait indefinitely.
+pub const PENDING_TIMEOUT: Item<Duration> = Item::new("pending_timeout");
+
+/// Proposals pending approval, keyed by approval ID.
+pub fn pending_proposals<M>() -> Keymap<'static, u64, Proposal<M>, Json>
+where
+    M: Serialize + DeserializeOwned,
+{
+    Keymap::new(b"pending_proposals")
+}
+
+/// Proposals that have been approved, rejected or have expired, keyed
+/// by approval ID.
+pub fn completed_proposals<M>() -> Keymap<'static, u64, Proposal<M>, Json>
+where
+    M: Serialize + DeserializeOwned,
+{
+    Keymap::new(b"completed_proposals")
 }
 
-pub const APPROVER: Item<Addr> = Item::new("approver");
-pub const PENDING_PROPOSALS: Keymap<u64, Proposal, Json> = Keymap::new(b"pending_proposals");
-pub const COMPLETED_PROPOSALS: Keymap<u64, Proposal, Json> = Keymap::new(b"completed_proposals");
 pub const CREATED_PROPOSAL_TO_COMPLETED_PROPOSAL: Keymap<u64, u64, Json> =
     Keymap::new(b"created_to_completed_proposal");
+/// Deposits of rejected proposals, held until the proposal is
+/// resubmitted or the deposit is claimed.
+pub const HELD_DEPOSITS: Keymap<u64, CheckedDepositInfo, Json> = Keymap::new(b"held_deposits");
 
 /// Used internally to track the current approval_id.
 const CURRENT_ID: Item<u64> = Item::new("current_id");
@@ -50,3 +200,104 @@ pub(crate) fn advance_approval_id(store: &mut dyn Storage) -> StdResult<u64> {
     CURRENT_ID.save(store, &id)?;
     Ok(id)
 }
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::testing::mock_env;
+
+    use super::*;
+
+    #[test]
+    fn test_committee_progress() {
+        let approver = |address: &str, weight| Approver {
+            address: Addr::unchecked(address),
+            weight,
+        };
+        let committee = Committee {
+            approvers: vec![approver("a", 2), approver("b", 1), approver("c", 1)],
+            threshold: 3,
+        };
+        assert!(!committee.is_sole_approver(&Addr::unchecked("a")));
+        assert_eq!(committee.total_weight(), 4);
+
+        let mut proposal = Proposal {
+            status: ProposalStatus::Pending {},
+            approval_id: 1,
+            proposer: Addr::unchecked("ekez"),
+            msg: ProposeMsg {
+                title: "title".to_string(),
+                description: "description".to_string(),
+                msgs: vec![],
+                proposer: Some("ekez".to_string()),
+                conditions: vec![],
+                dependencies: vec![],
+                emergency: false,
+            },
+            deposit: None,
+            approvals: vec![Addr::unchecked("a")],
+            rejections: vec![Rejection {
+                approver: Addr::unchecked("b"),
+                reason: Some("too vague".to_string()),
+            }],
+            expiration: None,
+            resubmitted_from: None,
+        };
+        let progress = committee.progress(&proposal);
+        assert_eq!(
+            progress,
+            ApprovalProgress {
+                approved: 2,
+                rejected: 1,
+                threshold: 3,
+                total: 4,
+            }
+        );
+        assert!(!progress.is_approved());
+        assert!(!progress.is_rejected());
+
+        // Votes from former approvers are not counted.
+        proposal.approvals.push(Addr::unchecked("ekez"));
+        assert!(!committee.progress(&proposal).is_approved());
+
+        proposal.rejections.push(Rejection {
+            approver: Addr::unchecked("c"),
+            reason: None,
+        });
+        assert!(committee.progress(&proposal).is_rejected());
+
+        let single = Committee::single(Addr::unchecked("a"));
+        assert!(single.is_sole_approver(&Addr::unchecked("a")));
+    }
+
+    #[test]
+    fn test_is_expired() {
+        let mut proposal = Proposal {
+            status: ProposalStatus::Pending {},
+            approval_id: 1,
+            proposer: Addr::unchecked("ekez"),
+            msg: ProposeMsg {
+                title: "title".to_string(),
+                description: "description".to_string(),
+                msgs: vec![],
+                proposer: Some("ekez".to_string()),
+                conditions: vec![],
+                dependencies: vec![],
+                emergency: false,
+            },
+            deposit: None,
+            approvals: vec![],
+            rejections: vec![],
+            expiration: None,
+            resubmitted_from: None,
+        };
+        let mut block = mock_env().block;
+        // Proposals submitted without a timeout never expire.
+        assert!(!proposal.is_expired(&block));
+
+        proposal.expiration = Some(Duration::Height(10).after(&block));
+        block.height += 9;
+        assert!(!proposal.is_expired(&block));
+        block.height += 1;
+        assert!(proposal.is_expired(&block));
+    }
+}
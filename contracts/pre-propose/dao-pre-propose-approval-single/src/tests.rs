@@ -68,6 +68,7 @@ fn get_default_proposal_module_instantiate(
                     open_proposal_submission,
                     extension: InstantiateExt {
                         approver: "approver".to_string(),
+                        pending_timeout: None,
                     },
                 })
                 .unwrap(),
@@ -409,7 +410,22 @@ fn reject_proposal(app: &mut App, module: Addr, sender: &str, proposal_id: u64)
         Addr::unchecked(sender),
         module,
         &ExecuteMsg::Extension {
-            msg: ExecuteExt::Reject { id: proposal_id },
+            msg: ExecuteExt::Reject {
+                id: proposal_id,
+                reason: None,
+            },
+        },
+        &[],
+    )
+    .unwrap();
+}
+
+fn claim_deposit(app: &mut App, module: Addr, sender: &str, proposal_id: u64) {
+    app.execute_contract(
+        Addr::unchecked(sender),
+        module,
+        &ExecuteMsg::Extension {
+            msg: ExecuteExt::ClaimDeposit { id: proposal_id },
         },
         &[],
     )
@@ -488,7 +504,12 @@ fn test_native_permutation(
         ApprovalStatus::Rejected => {
             // Proposal is rejected by approver
             // No proposal is created so there is no voting
-            reject_proposal(&mut app, pre_propose, "approver", pre_propose_id);
+            reject_proposal(&mut app, pre_propose.clone(), "approver", pre_propose_id);
+
+            // The deposit is held until it is claimed.
+            let balance = get_balance_native(&app, pre_propose.as_str(), "ujuno");
+            assert_eq!(balance, Uint128::new(10));
+            claim_deposit(&mut app, pre_propose, "ekez", pre_propose_id);
         }
     };
 
@@ -568,6 +589,11 @@ fn test_cw20_permutation(
             // Proposal is rejected by approver
             // No proposal is created so there is no voting
             reject_proposal(&mut app, pre_propose.clone(), "approver", pre_propose_id);
+
+            // The deposit is held until it is claimed.
+            let balance = get_balance_cw20(&app, &cw20_address, pre_propose.clone());
+            assert_eq!(balance, Uint128::new(10));
+            claim_deposit(&mut app, pre_propose.clone(), "ekez", pre_propose_id);
         }
     };
 
@@ -1137,6 +1163,7 @@ fn test_permissions() {
             &ExecuteMsg::ProposalCompletedHook {
                 proposal_id: 1,
                 new_status: Status::Closed,
+                quorum_met: None,
             },
             &[],
         )
@@ -1215,7 +1242,10 @@ fn test_approval_and_rejection_permissions() {
             Addr::unchecked("nonmember"),
             pre_propose,
             &ExecuteMsg::Extension {
-                msg: ExecuteExt::Reject { id: pre_propose_id },
+                msg: ExecuteExt::Reject {
+                    id: pre_propose_id,
+                    reason: None,
+                },
             },
             &[],
         )
@@ -1225,6 +1255,330 @@ fn test_approval_and_rejection_permissions() {
     assert_eq!(err, PreProposeError::Unauthorized {});
 }
 
+fn update_committee(app: &mut App, module: Addr, sender: &str, approvers: &[&str], threshold: u64) {
+    app.execute_contract(
+        Addr::unchecked(sender),
+        module,
+        &ExecuteMsg::Extension {
+            msg: ExecuteExt::UpdateCommittee {
+                approvers: approvers
+                    .iter()
+                    .map(|address| UncheckedApprover {
+                        address: address.to_string(),
+                        weight: 1,
+                    })
+                    .collect(),
+                threshold,
+            },
+        },
+        &[],
+    )
+    .unwrap();
+}
+
+fn finalize(app: &mut App, module: Addr, id: u64) -> Result<(), PreProposeError> {
+    app.execute_contract(
+        Addr::unchecked("anyone"),
+        module,
+        &ExecuteMsg::Extension {
+            msg: ExecuteExt::Finalize { id },
+        },
+        &[],
+    )
+    .map(|_| ())
+    .map_err(|err| err.downcast().unwrap())
+}
+
+fn completed_status(app: &App, module: Addr, id: u64) -> ProposalStatus {
+    let proposal: Option<Proposal> = app
+        .wrap()
+        .query_wasm_smart(
+            module,
+            &QueryMsg::QueryExtension {
+                msg: QueryExt::CompletedProposal { id },
+            },
+        )
+        .unwrap();
+    proposal.unwrap().status
+}
+
+#[test]
+fn test_finalize_after_committee_change() {
+    let mut app = App::default();
+    let DefaultTestSetup {
+        core_addr,
+        proposal_single: _,
+        pre_propose,
+    } = setup_default_test(&mut app, None, false);
+    update_committee(
+        &mut app,
+        pre_propose.clone(),
+        "approver",
+        &["a", "b", "c"],
+        2,
+    );
+
+    let approved = make_pre_proposal(&mut app, pre_propose.clone(), "ekez", &[]);
+    let rejected = make_pre_proposal(&mut app, pre_propose.clone(), "ekez", &[]);
+    approve_proposal(&mut app, pre_propose.clone(), "a", approved);
+    reject_proposal(&mut app, pre_propose.clone(), "b", rejected);
+
+    // Neither proposal is decided by the committee that voted on it.
+    let err = finalize(&mut app, pre_propose.clone(), approved).unwrap_err();
+    assert_eq!(err, PreProposeError::NotDecided {});
+    let err = finalize(&mut app, pre_propose.clone(), rejected).unwrap_err();
+    assert_eq!(err, PreProposeError::NotDecided {});
+
+    // With one vote needed, a's approval approves the first
+    // proposal. With c removed, b's rejection leaves the second out
+    // of reach.
+    update_committee(
+        &mut app,
+        pre_propose.clone(),
+        core_addr.as_str(),
+        &["a", "b"],
+        1,
+    );
+    finalize(&mut app, pre_propose.clone(), approved).unwrap();
+    assert_eq!(
+        completed_status(&app, pre_propose.clone(), approved),
+        ProposalStatus::Approved {
+            created_proposal_id: 1
+        }
+    );
+    update_committee(
+        &mut app,
+        pre_propose.clone(),
+        core_addr.as_str(),
+        &["a", "b"],
+        2,
+    );
+    finalize(&mut app, pre_propose.clone(), rejected).unwrap();
+    assert_eq!(
+        completed_status(&app, pre_propose.clone(), rejected),
+        ProposalStatus::Rejected {}
+    );
+
+    let err = finalize(&mut app, pre_propose, approved).unwrap_err();
+    assert_eq!(err, PreProposeError::ProposalNotFound {});
+}
+
+fn native_deposit(refund_policy: DepositRefundPolicy) -> Option<UncheckedDepositInfo> {
+    Some(UncheckedDepositInfo {
+        denom: DepositToken::Token {
+            denom: UncheckedDenom::Native("ujuno".to_string()),
+        },
+        amount: Uint128::new(10),
+        refund_policy,
+    })
+}
+
+fn execute_ext(
+    app: &mut App,
+    module: Addr,
+    sender: &str,
+    msg: ExecuteExt,
+    funds: &[Coin],
+) -> Result<(), PreProposeError> {
+    app.execute_contract(
+        Addr::unchecked(sender),
+        module,
+        &ExecuteMsg::Extension { msg },
+        funds,
+    )
+    .map(|_| ())
+    .map_err(|err| err.downcast().unwrap())
+}
+
+fn resubmit(
+    app: &mut App,
+    module: Addr,
+    sender: &str,
+    id: u64,
+    funds: &[Coin],
+) -> Result<(), PreProposeError> {
+    execute_ext(
+        app,
+        module,
+        sender,
+        ExecuteExt::Resubmit {
+            id,
+            key: "key".to_string(),
+            msg: ProposeMessage::Propose {
+                title: "edited title".to_string(),
+                description: "edited description".to_string(),
+                msgs: vec![],
+                conditions: vec![],
+                dependencies: vec![],
+                emergency: false,
+            },
+            co_sponsors: vec![],
+        },
+        funds,
+    )
+}
+
+fn held_deposit(app: &App, module: Addr, id: u64) -> Option<CheckedDepositInfo> {
+    app.wrap()
+        .query_wasm_smart(
+            module,
+            &QueryMsg::QueryExtension {
+                msg: QueryExt::HeldDeposit { id },
+            },
+        )
+        .unwrap()
+}
+
+#[test]
+fn test_pending_proposal_expiry() {
+    let mut app = App::default();
+    let DefaultTestSetup {
+        core_addr,
+        proposal_single: _,
+        pre_propose,
+    } = setup_default_test(&mut app, native_deposit(DepositRefundPolicy::Never), false);
+    execute_ext(
+        &mut app,
+        pre_propose.clone(),
+        core_addr.as_str(),
+        ExecuteExt::UpdatePendingTimeout {
+            timeout: Some(Duration::Height(5)),
+        },
+        &[],
+    )
+    .unwrap();
+
+    mint_natives(&mut app, "ekez", coins(20, "ujuno"));
+    let expired = make_pre_proposal(&mut app, pre_propose.clone(), "ekez", &coins(10, "ujuno"));
+    let voted = make_pre_proposal(&mut app, pre_propose.clone(), "ekez", &coins(10, "ujuno"));
+
+    let err = execute_ext(
+        &mut app,
+        pre_propose.clone(),
+        "anyone",
+        ExecuteExt::Expire { id: expired },
+        &[],
+    )
+    .unwrap_err();
+    assert_eq!(err, PreProposeError::PendingProposalNotExpired {});
+
+    app.update_block(|block| block.height += 5);
+
+    // Expired proposals are refunded in full, whatever the refund
+    // policy, whether closed by anyone or by a late vote.
+    execute_ext(
+        &mut app,
+        pre_propose.clone(),
+        "anyone",
+        ExecuteExt::Expire { id: expired },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(get_balance_native(&app, "ekez", "ujuno"), Uint128::new(10));
+    execute_ext(
+        &mut app,
+        pre_propose.clone(),
+        "approver",
+        ExecuteExt::Approve { id: voted },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(get_balance_native(&app, "ekez", "ujuno"), Uint128::new(20));
+
+    for id in [expired, voted] {
+        assert_eq!(
+            completed_status(&app, pre_propose.clone(), id),
+            ProposalStatus::Expired {}
+        );
+    }
+}
+
+#[test]
+fn test_resubmit_rejected_proposal() {
+    let mut app = App::default();
+    let DefaultTestSetup {
+        core_addr,
+        proposal_single: _,
+        pre_propose,
+    } = setup_default_test(
+        &mut app,
+        native_deposit(DepositRefundPolicy::OnlyPassed),
+        false,
+    );
+
+    mint_natives(&mut app, "ekez", coins(20, "ujuno"));
+    let rejected = make_pre_proposal(&mut app, pre_propose.clone(), "ekez", &coins(10, "ujuno"));
+    reject_proposal(&mut app, pre_propose.clone(), "approver", rejected);
+    assert!(held_deposit(&app, pre_propose.clone(), rejected).is_some());
+
+    // Only the proposer may resubmit.
+    let err = resubmit(&mut app, pre_propose.clone(), "keze", rejected, &[]).unwrap_err();
+    assert_eq!(err, PreProposeError::Unauthorized {});
+
+    // The resubmission keeps the held deposit.
+    resubmit(&mut app, pre_propose.clone(), "ekez", rejected, &[]).unwrap();
+    let resubmission = rejected + 1;
+    assert_eq!(
+        completed_status(&app, pre_propose.clone(), rejected),
+        ProposalStatus::Resubmitted {
+            resubmission_id: resubmission
+        }
+    );
+    assert!(held_deposit(&app, pre_propose.clone(), rejected).is_none());
+    assert_eq!(get_balance_native(&app, "ekez", "ujuno"), Uint128::new(10));
+    let proposal: Proposal = app
+        .wrap()
+        .query_wasm_smart(
+            pre_propose.clone(),
+            &QueryMsg::QueryExtension {
+                msg: QueryExt::Proposal { id: resubmission },
+            },
+        )
+        .unwrap();
+    assert_eq!(proposal.resubmitted_from, Some(rejected));
+    assert_eq!(proposal.msg.title, "edited title");
+
+    let err = resubmit(&mut app, pre_propose.clone(), "ekez", rejected, &[]).unwrap_err();
+    assert_eq!(err, PreProposeError::NotRejected {});
+
+    // Once the DAO claims the deposit under `OnlyPassed`, it is
+    // slashed, and resubmitting takes a new one.
+    reject_proposal(&mut app, pre_propose.clone(), "approver", resubmission);
+    claim_deposit(
+        &mut app,
+        pre_propose.clone(),
+        core_addr.as_str(),
+        resubmission,
+    );
+    assert_eq!(
+        get_balance_native(&app, core_addr.as_str(), "ujuno"),
+        Uint128::new(10)
+    );
+    let err = execute_ext(
+        &mut app,
+        pre_propose.clone(),
+        "ekez",
+        ExecuteExt::ClaimDeposit { id: resubmission },
+        &[],
+    )
+    .unwrap_err();
+    assert_eq!(err, PreProposeError::NoHeldDeposit {});
+
+    resubmit(
+        &mut app,
+        pre_propose.clone(),
+        "ekez",
+        resubmission,
+        &coins(10, "ujuno"),
+    )
+    .unwrap();
+    assert_eq!(get_balance_native(&app, "ekez", "ujuno"), Uint128::zero());
+    assert_eq!(
+        get_balance_native(&app, pre_propose.as_str(), "ujuno"),
+        Uint128::new(10)
+    );
+}
+
 #[test]
 fn test_propose_open_proposal_submission() {
     let mut app = App::default();
@@ -1354,6 +1708,7 @@ fn test_instantiate_with_zero_native_deposit() {
                         open_proposal_submission: false,
                         extension: InstantiateExt {
                             approver: "approver".to_string(),
+                            pending_timeout: None,
                         },
                     })
                     .unwrap(),
@@ -1419,6 +1774,7 @@ fn test_instantiate_with_zero_cw20_deposit() {
                         open_proposal_submission: false,
                         extension: InstantiateExt {
                             approver: "approver".to_string(),
+                            pending_timeout: None,
                         },
                     })
                     .unwrap(),
@@ -9,6 +9,7 @@ use secret_cw2::set_contract_version;
 use dao_interface::state::{AnyContractInfo, ModuleInstantiateCallback};
 use dao_pre_propose_approval_single::msg::{
     ApproverProposeMessage, ExecuteExt as ApprovalExt, ExecuteMsg as PreProposeApprovalExecuteMsg,
+    QueryExt as ApprovalQueryExt, QueryMsg as PreProposeApprovalQueryMsg,
 };
 use dao_pre_propose_base::{error::PreProposeError, state::PreProposeContract};
 use dao_voting::status::Status;
@@ -95,9 +96,13 @@ pub fn execute(
     match msg {
         // Override default pre-propose-base behavior
         ExecuteMsg::Propose { msg, .. } => execute_propose(deps, info, msg),
+        // Proposals are only created by the approval contract, which
+        // does not pay deposits.
+        ExecuteMsg::Receive(_) => Err(PreProposeError::Unauthorized {}),
         ExecuteMsg::ProposalCompletedHook {
             proposal_id,
             new_status,
+            ..
         } => execute_proposal_completed(deps, info, proposal_id, new_status),
         ExecuteMsg::Extension { msg } => match msg {
             ExecuteExt::ResetApprover {} => execute_reset_approver(deps, env, info),
@@ -171,6 +176,25 @@ pub fn execute_proposal_completed(
     // Get approval contract address
     let approval_contract = PRE_PROPOSE_APPROVAL_CONTRACT.load(deps.storage)?;
 
+    // The proposal may have expired, or been decided by other
+    // approvers, while this DAO voted on it. Skip it rather than fail
+    // the hook.
+    if let Some(id) = pre_propose_id {
+        let pending: bool = deps.querier.query_wasm_smart(
+            approval_contract.code_hash.clone(),
+            approval_contract.addr.to_string(),
+            &PreProposeApprovalQueryMsg::QueryExtension {
+                msg: ApprovalQueryExt::IsPending { id },
+            },
+        )?;
+        if !pending {
+            return Ok(Response::default()
+                .add_attribute("method", "execute_proposal_completed_hook")
+                .add_attribute("proposal", proposal_id.to_string())
+                .add_attribute("skipped", "not_pending"));
+        }
+    }
+
     // On completion send rejection or approval message
     let msg = match new_status {
         Status::Closed => Some(WasmMsg::Execute {
@@ -179,6 +203,7 @@ pub fn execute_proposal_completed(
             msg: to_binary(&PreProposeApprovalExecuteMsg::Extension {
                 msg: ApprovalExt::Reject {
                     id: pre_propose_id.unwrap(),
+                    reason: None,
                 },
             })?,
             funds: vec![],
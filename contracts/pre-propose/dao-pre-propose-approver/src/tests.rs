@@ -95,6 +95,7 @@ fn get_proposal_module_approval_single_instantiate(
                     open_proposal_submission,
                     extension: InstantiateExt {
                         approver: APPROVER.to_string(),
+                        pending_timeout: None,
                     },
                 })
                 .unwrap(),
@@ -1132,6 +1133,7 @@ fn test_permissions() {
             &ExecuteMsg::ProposalCompletedHook {
                 proposal_id: 1,
                 new_status: Status::Closed,
+                quorum_met: None,
             },
             &[],
         )
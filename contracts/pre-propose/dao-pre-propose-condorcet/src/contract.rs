@@ -0,0 +1,141 @@
+use cosmwasm_schema::cw_serde;
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{Addr, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdResult};
+use secret_cw2::set_contract_version;
+
+use dao_pre_propose_base::{
+    error::PreProposeError,
+    msg::{
+        ExecuteMsg as ExecuteBase, InstantiateMsg as InstantiateBase, QueryMsg as QueryBase,
+        ReceiveMsg,
+    },
+    state::PreProposeContract,
+};
+use dao_proposal_condorcet::msg::Choice;
+
+pub(crate) const CONTRACT_NAME: &str = "crates.io:dao-pre-propose-condorcet";
+pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[cw_serde]
+pub enum ProposeMessage {
+    /// The propose message used to make a proposal to this
+    /// module. Note that this is identical to the propose message
+    /// used by dao-proposal-condorcet, except that it omits the `key`
+    /// and `proposer` fields. This module checks the sender may
+    /// propose and fills in the proposer.
+    Propose { choices: Vec<Choice> },
+}
+
+pub type InstantiateMsg = InstantiateBase<Empty>;
+pub type ExecuteMsg = ExecuteBase<ProposeMessage, Empty>;
+pub type QueryMsg = QueryBase<Empty>;
+
+/// Internal version of the propose message that includes the
+/// `proposer` field. The module will fill this in based on the sender
+/// of the external message.
+#[cw_serde]
+enum ProposeMessageInternal {
+    Propose {
+        choices: Vec<Choice>,
+        proposer: Option<String>,
+    },
+}
+
+type PrePropose = PreProposeContract<Empty, Empty, Empty, ProposeMessageInternal>;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, PreProposeError> {
+    let resp = PrePropose::default().instantiate(deps.branch(), env, info, msg)?;
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    Ok(resp)
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, PreProposeError> {
+    // We don't want to expose the `proposer` field on the propose
+    // message externally as that is to be set by this module. Here,
+    // we transform an external message which omits that field into an
+    // internal message which sets it.
+    type ExecuteInternal = ExecuteBase<ProposeMessageInternal, Empty>;
+    let internalized = match msg {
+        ExecuteMsg::Propose {
+            msg,
+            key,
+            co_sponsors,
+        } => ExecuteInternal::Propose {
+            msg: internalize(msg, &info.sender),
+            key,
+            co_sponsors,
+        },
+        // The base module can not internalize the message sent with a
+        // SNIP-20 deposit, so proposals paid for this way are created
+        // here.
+        ExecuteMsg::Receive(wrapper) => {
+            let pre_propose = PrePropose::default();
+            let ReceiveMsg::Propose {
+                key,
+                msg,
+                co_sponsors,
+            } = pre_propose.check_receive(deps.as_ref(), &info, &wrapper)?;
+            let msg = internalize(msg, &wrapper.from);
+            return pre_propose.propose(deps, env, wrapper.from, key, msg, co_sponsors);
+        }
+        ExecuteMsg::Extension { msg } => ExecuteInternal::Extension { msg },
+        ExecuteMsg::UpdateSubmissionLimits { limits } => {
+            ExecuteInternal::UpdateSubmissionLimits { limits }
+        }
+        ExecuteMsg::UpdateSubmissionPolicy { policy } => {
+            ExecuteInternal::UpdateSubmissionPolicy { policy }
+        }
+        ExecuteMsg::Withdraw { denom, key } => ExecuteInternal::Withdraw { denom, key },
+        ExecuteMsg::UpdateConfig {
+            deposit_info,
+            open_proposal_submission,
+        } => ExecuteInternal::UpdateConfig {
+            deposit_info,
+            open_proposal_submission,
+        },
+        ExecuteMsg::AddProposalSubmittedHook { address, code_hash } => {
+            ExecuteInternal::AddProposalSubmittedHook { address, code_hash }
+        }
+        ExecuteMsg::RemoveProposalSubmittedHook { address, code_hash } => {
+            ExecuteInternal::RemoveProposalSubmittedHook { address, code_hash }
+        }
+        ExecuteMsg::ProposalCompletedHook {
+            proposal_id,
+            new_status,
+            quorum_met,
+        } => ExecuteInternal::ProposalCompletedHook {
+            proposal_id,
+            new_status,
+            quorum_met,
+        },
+    };
+
+    PrePropose::default().execute(deps, env, info, internalized)
+}
+
+fn internalize(msg: ProposeMessage, proposer: &Addr) -> ProposeMessageInternal {
+    match msg {
+        ProposeMessage::Propose { choices } => ProposeMessageInternal::Propose {
+            proposer: Some(proposer.to_string()),
+            choices,
+        },
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    PrePropose::default().query(deps, env, msg)
+}
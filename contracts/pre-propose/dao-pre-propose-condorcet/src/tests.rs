@@ -0,0 +1,504 @@
+use cosmwasm_std::testing::{
+    mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+};
+use cosmwasm_std::{
+    coins, from_binary, to_binary, Addr, BankMsg, Binary, ContractInfo, ContractResult, CosmosMsg,
+    Decimal, Deps, DepsMut, Empty, Env, Event, MessageInfo, OwnedDeps, Reply, Response, StdResult,
+    SubMsgResponse, SubMsgResult, SystemResult, Uint128, WasmMsg, WasmQuery,
+};
+use cw_denom::UncheckedDenom;
+use dao_interface::state::{AnyContractInfo, ModuleInstantiateInfo};
+use dao_interface::voting::{
+    Query as VotingQuery, TotalPowerAtHeightResponse, VotingPowerAtHeightResponse,
+};
+use dao_pre_propose_base::error::PreProposeError;
+use dao_pre_propose_base::msg::{DepositInfoResponse, ReceiveMsg};
+use dao_proposal_condorcet::msg::{
+    Choice, ExecuteMsg as ModuleExecuteMsg, InstantiateMsg as ModuleInstantiateMsg,
+    QueryMsg as ModuleQueryMsg,
+};
+use dao_proposal_condorcet::proposal::ProposalResponse;
+use dao_proposal_condorcet::resolution::CycleResolution;
+use dao_voting::{
+    deposit::{DepositError, DepositRefundPolicy, DepositToken, UncheckedDepositInfo},
+    pre_propose::PreProposeInfo,
+    threshold::PercentageThreshold,
+};
+use secret_multi_test::{App, AppResponse, Contract, ContractWrapper, Executor};
+use secret_utils::Duration;
+use snip20_reference_impl::msg::{
+    ExecuteMsg as Snip20ExecuteMsg, InitialBalance, InstantiateMsg as Snip20InstantiateMsg,
+};
+
+use crate::contract::{
+    execute, instantiate, query, ExecuteMsg, InstantiateMsg, ProposeMessage, QueryMsg,
+};
+
+type Deps = OwnedDeps<MockStorage, MockApi, MockQuerier>;
+
+/// The height proposals are created at.
+const START: u64 = 12_345;
+
+/// The proposal module and this module. Messages between them are
+/// passed along by the tests.
+struct Suite {
+    module: Deps,
+    pre_propose: Deps,
+}
+
+fn env_at(height: u64) -> Env {
+    let mut env = mock_env();
+    env.block.height = height;
+    env
+}
+
+/// Returns the messages RES sends to the contract at ADDR.
+fn msgs_to(res: &Response, addr: &str) -> Vec<Binary> {
+    res.messages
+        .iter()
+        .filter_map(|sub| match &sub.msg {
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr, msg, ..
+            }) if contract_addr == addr => Some(msg.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Sets up a condorcet proposal module with a 10 block voting period
+/// that instantiates this module with a 10 uscrt deposit refunded
+/// only for passed proposals. Alice has 600 of the DAO's 1000 voting
+/// power.
+fn setup() -> Suite {
+    let mut module = mock_dependencies();
+    module.querier.update_wasm(|query| {
+        let WasmQuery::Smart { msg, .. } = query else {
+            panic!("unexpected query");
+        };
+        let res = match from_binary(msg).unwrap() {
+            VotingQuery::VotingPowerAtHeight {
+                address, height, ..
+            } => to_binary(&VotingPowerAtHeightResponse {
+                power: Uint128::new(if address == "alice" { 600 } else { 0 }),
+                height: height.unwrap_or(START),
+            }),
+            VotingQuery::TotalPowerAtHeight { height } => to_binary(&TotalPowerAtHeightResponse {
+                power: Uint128::new(1000),
+                height: height.unwrap_or(START),
+            }),
+            _ => panic!("unexpected query"),
+        };
+        SystemResult::Ok(ContractResult::Ok(res.unwrap()))
+    });
+
+    let instantiate_msg = InstantiateMsg {
+        deposit_info: Some(UncheckedDepositInfo {
+            denom: DepositToken::Token {
+                denom: UncheckedDenom::Native("uscrt".to_string()),
+            },
+            amount: Uint128::new(10),
+            refund_policy: DepositRefundPolicy::OnlyPassed,
+        }),
+        open_proposal_submission: true,
+        proposal_module_code_hash: "module_hash".to_string(),
+        extension: Empty::default(),
+    };
+    let res = dao_proposal_condorcet::contract::instantiate(
+        module.as_mut(),
+        env_at(START),
+        mock_info("dao", &[]),
+        ModuleInstantiateMsg {
+            quorum: PercentageThreshold::Percent(Decimal::percent(15)),
+            voting_period: Duration::Height(10),
+            min_voting_period: None,
+            close_proposals_on_execution_failure: false,
+            veto: None,
+            cycle_resolution: CycleResolution::None,
+            pre_propose_info: PreProposeInfo::ModuleMayPropose {
+                info: ModuleInstantiateInfo {
+                    code_id: 1,
+                    code_hash: "pre_propose_hash".to_string(),
+                    msg: to_binary(&instantiate_msg).unwrap(),
+                    admin: None,
+                    funds: vec![],
+                    label: "pre-propose".to_string(),
+                },
+            },
+            dao_code_hash: "dao_hash".to_string(),
+        },
+    )
+    .unwrap();
+
+    // The proposal module instantiates this module, which asks it for
+    // its DAO.
+    let CosmosMsg::Wasm(WasmMsg::Instantiate { msg, .. }) = &res.messages[0].msg else {
+        panic!("expected pre-propose instantiation");
+    };
+    let mut pre_propose = mock_dependencies();
+    pre_propose.querier.update_wasm(|query| {
+        let WasmQuery::Smart { msg, .. } = query else {
+            panic!("unexpected query");
+        };
+        let res = match from_binary(msg).unwrap() {
+            dao_interface::proposal::Query::Dao {} => to_binary(&AnyContractInfo {
+                addr: Addr::unchecked("dao"),
+                code_hash: "dao_hash".to_string(),
+            }),
+            dao_interface::proposal::Query::NextProposalId {} => to_binary(&1u64),
+            _ => panic!("unexpected query"),
+        };
+        SystemResult::Ok(ContractResult::Ok(res.unwrap()))
+    });
+    instantiate(
+        pre_propose.as_mut(),
+        env_at(START),
+        mock_info("module", &[]),
+        from_binary(msg).unwrap(),
+    )
+    .unwrap();
+    dao_proposal_condorcet::contract::reply(
+        module.as_mut(),
+        env_at(START),
+        Reply {
+            id: res.messages[0].id,
+            result: SubMsgResult::Ok(SubMsgResponse {
+                events: vec![
+                    Event::new("instantiate").add_attribute("contract_address", "pre_propose")
+                ],
+                data: None,
+            }),
+        },
+    )
+    .unwrap();
+
+    Suite {
+        module,
+        pre_propose,
+    }
+}
+
+impl Suite {
+    /// Creates a proposal on behalf of PROPOSER through the
+    /// pre-propose module.
+    fn propose(&mut self, proposer: &str, deposit: u128) -> Result<(), PreProposeError> {
+        let res = execute(
+            self.pre_propose.as_mut(),
+            env_at(START),
+            mock_info(proposer, &coins(deposit, "uscrt")),
+            ExecuteMsg::Propose {
+                key: "key".to_string(),
+                msg: ProposeMessage::Propose {
+                    choices: vec![Choice { msgs: vec![] }],
+                },
+                co_sponsors: vec![],
+            },
+        )?;
+        for msg in msgs_to(&res, "module") {
+            dao_proposal_condorcet::contract::execute(
+                self.module.as_mut(),
+                env_at(START),
+                mock_info("pre_propose", &[]),
+                from_binary(&msg).unwrap(),
+            )
+            .unwrap();
+        }
+        Ok(())
+    }
+
+    /// Executes MSG on the proposal module and passes any proposal
+    /// completed hooks on to the pre-propose module, returning the
+    /// messages it sends in response.
+    fn execute_module(
+        &mut self,
+        height: u64,
+        sender: &str,
+        msg: ModuleExecuteMsg,
+    ) -> Vec<CosmosMsg> {
+        let res = dao_proposal_condorcet::contract::execute(
+            self.module.as_mut(),
+            env_at(height),
+            mock_info(sender, &[]),
+            msg,
+        )
+        .unwrap();
+        msgs_to(&res, "pre_propose")
+            .into_iter()
+            .flat_map(|hook| {
+                execute(
+                    self.pre_propose.as_mut(),
+                    env_at(height),
+                    mock_info("module", &[]),
+                    from_binary(&hook).unwrap(),
+                )
+                .unwrap()
+                .messages
+                .into_iter()
+                .map(|sub| sub.msg)
+            })
+            .collect()
+    }
+}
+
+fn refund(to: &str) -> CosmosMsg {
+    CosmosMsg::Bank(BankMsg::Send {
+        to_address: to.to_string(),
+        amount: coins(10, "uscrt"),
+    })
+}
+
+#[test]
+fn test_propose_sets_proposer() {
+    let mut suite = setup();
+
+    let err = suite.propose("alice", 5).unwrap_err();
+    assert_eq!(
+        err,
+        PreProposeError::Deposit(DepositError::InvalidDeposit {
+            actual: Uint128::new(5),
+            expected: Uint128::new(10),
+        })
+    );
+
+    suite.propose("alice", 10).unwrap();
+    let ProposalResponse { proposal, .. } = from_binary(
+        &dao_proposal_condorcet::contract::query(
+            suite.module.as_ref(),
+            env_at(START),
+            ModuleQueryMsg::Proposal { id: 1 },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(proposal.proposer, Addr::unchecked("alice"));
+}
+
+#[test]
+fn test_deposit_refunded_on_execute() {
+    let mut suite = setup();
+    suite.propose("alice", 10).unwrap();
+
+    let msgs = suite.execute_module(
+        START + 1,
+        "alice",
+        ModuleExecuteMsg::Vote {
+            proposal_id: 1,
+            vote: vec![0],
+            key: "key".to_string(),
+            rationale: None,
+        },
+    );
+    assert!(msgs.is_empty());
+
+    let msgs = suite.execute_module(
+        START + 1,
+        "alice",
+        ModuleExecuteMsg::Execute {
+            proposal_id: 1,
+            key: "key".to_string(),
+        },
+    );
+    assert_eq!(msgs, vec![refund("alice")]);
+}
+
+#[test]
+fn test_deposit_slashed_on_close() {
+    let mut suite = setup();
+    suite.propose("alice", 10).unwrap();
+
+    // Nobody votes, so the proposal is rejected without reaching
+    // quorum once voting closes.
+    let msgs = suite.execute_module(
+        START + 10,
+        "bob",
+        ModuleExecuteMsg::Close { proposal_id: 1 },
+    );
+    assert_eq!(msgs, vec![refund("dao")]);
+}
+
+/// A proposal module that accepts every proposal and reports its
+/// proposer.
+fn stub_module() -> Box<dyn Contract<Empty>> {
+    fn instantiate(_: DepsMut, _: Env, _: MessageInfo, _: Empty) -> StdResult<Response> {
+        Ok(Response::default())
+    }
+    fn execute(_: DepsMut, _: Env, _: MessageInfo, msg: ModuleExecuteMsg) -> StdResult<Response> {
+        let ModuleExecuteMsg::Propose { proposer, .. } = msg else {
+            panic!("unexpected message");
+        };
+        Ok(Response::default().add_attribute("proposer", proposer.unwrap()))
+    }
+    fn query(_: Deps, _: Env, msg: dao_interface::proposal::Query) -> StdResult<Binary> {
+        match msg {
+            dao_interface::proposal::Query::Dao {} => to_binary(&AnyContractInfo {
+                addr: Addr::unchecked("dao"),
+                code_hash: "dao_hash".to_string(),
+            }),
+            dao_interface::proposal::Query::NextProposalId {} => to_binary(&1u64),
+            _ => panic!("unexpected query"),
+        }
+    }
+    Box::new(ContractWrapper::new(execute, instantiate, query))
+}
+
+/// A SNIP-20 token and this module, instantiated by a stub proposal
+/// module.
+struct Chain {
+    app: App,
+    token: ContractInfo,
+    pre_propose: ContractInfo,
+}
+
+fn snip20_deposit(token: &ContractInfo) -> UncheckedDepositInfo {
+    UncheckedDepositInfo {
+        denom: DepositToken::Token {
+            denom: UncheckedDenom::Cw20(token.address.to_string(), token.code_hash.clone()),
+        },
+        amount: Uint128::new(10),
+        refund_policy: DepositRefundPolicy::Always,
+    }
+}
+
+/// Sets up a token that Alice holds 100 of, and this module with a
+/// deposit paid in it if SNIP20_DEPOSIT_INFO is set.
+fn setup_chain(snip20_deposit_info: bool) -> Chain {
+    let mut app = App::default();
+    let token_code = app.store_code(Box::new(ContractWrapper::new(
+        snip20_reference_impl::contract::execute,
+        snip20_reference_impl::contract::instantiate,
+        snip20_reference_impl::contract::query,
+    )));
+    let module_code = app.store_code(stub_module());
+    let pre_propose_code =
+        app.store_code(Box::new(ContractWrapper::new(execute, instantiate, query)));
+
+    let token = app
+        .instantiate_contract(
+            token_code,
+            Addr::unchecked("dao"),
+            &Snip20InstantiateMsg {
+                name: "Token".to_string(),
+                admin: None,
+                symbol: "TKN".to_string(),
+                decimals: 6,
+                initial_balances: Some(vec![InitialBalance {
+                    address: "alice".to_string(),
+                    amount: Uint128::new(100),
+                }]),
+                prng_seed: to_binary("seed").unwrap(),
+                config: None,
+                supported_denoms: None,
+            },
+            &[],
+            "token",
+            None,
+        )
+        .unwrap();
+    let module = app
+        .instantiate_contract(
+            module_code,
+            Addr::unchecked("dao"),
+            &Empty::default(),
+            &[],
+            "module",
+            None,
+        )
+        .unwrap();
+    let pre_propose = app
+        .instantiate_contract(
+            pre_propose_code,
+            module.address,
+            &InstantiateMsg {
+                deposit_info: snip20_deposit_info.then(|| snip20_deposit(&token)),
+                open_proposal_submission: true,
+                proposal_module_code_hash: module.code_hash,
+                extension: Empty::default(),
+            },
+            &[],
+            "pre-propose",
+            None,
+        )
+        .unwrap();
+
+    Chain {
+        app,
+        token,
+        pre_propose,
+    }
+}
+
+impl Chain {
+    /// Has Alice pay a deposit of AMOUNT with `Send`, without naming
+    /// the receiver's code hash, so the token only calls the receiver
+    /// if it has registered.
+    fn send_proposal(&mut self, amount: u128) -> AppResponse {
+        let msg = ReceiveMsg::Propose {
+            key: "key".to_string(),
+            msg: ProposeMessage::Propose {
+                choices: vec![Choice { msgs: vec![] }],
+            },
+            co_sponsors: vec![],
+        };
+        self.app
+            .execute_contract(
+                Addr::unchecked("alice"),
+                &self.token,
+                &Snip20ExecuteMsg::Send {
+                    recipient: self.pre_propose.address.to_string(),
+                    recipient_code_hash: None,
+                    amount: Uint128::new(amount),
+                    msg: Some(to_binary(&msg).unwrap()),
+                    memo: None,
+                    decoys: None,
+                    entropy: None,
+                    padding: None,
+                },
+                &[],
+            )
+            .unwrap()
+    }
+
+    fn assert_proposed_by_alice(&self, res: &AppResponse) {
+        assert!(res.events.iter().any(|event| event
+            .attributes
+            .iter()
+            .any(|attr| attr.key == "proposer" && attr.value == "alice")));
+        let DepositInfoResponse { proposer, .. } = self
+            .app
+            .wrap()
+            .query_wasm_smart(
+                self.pre_propose.code_hash.clone(),
+                self.pre_propose.address.clone(),
+                &QueryMsg::DepositInfo { proposal_id: 1 },
+            )
+            .unwrap();
+        assert_eq!(proposer, Addr::unchecked("alice"));
+    }
+}
+
+#[test]
+fn test_snip20_deposit_sent() {
+    let mut chain = setup_chain(true);
+    let res = chain.send_proposal(10);
+    chain.assert_proposed_by_alice(&res);
+}
+
+#[test]
+fn test_update_config_registers_receive() {
+    let mut chain = setup_chain(false);
+    let deposit_info = snip20_deposit(&chain.token);
+    chain
+        .app
+        .execute_contract(
+            Addr::unchecked("dao"),
+            &chain.pre_propose,
+            &ExecuteMsg::UpdateConfig {
+                deposit_info: Some(deposit_info),
+                open_proposal_submission: true,
+            },
+            &[],
+        )
+        .unwrap();
+
+    let res = chain.send_proposal(10);
+    chain.assert_proposed_by_alice(&res);
+}
@@ -1,15 +1,21 @@
 use cosmwasm_schema::cw_serde;
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
-use cosmwasm_std::{Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdResult};
+use cosmwasm_std::{Addr, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdResult};
 use secret_cw2::set_contract_version;
 
 use dao_pre_propose_base::{
     error::PreProposeError,
-    msg::{ExecuteMsg as ExecuteBase, InstantiateMsg as InstantiateBase, QueryMsg as QueryBase},
+    msg::{
+        ExecuteMsg as ExecuteBase, InstantiateMsg as InstantiateBase, QueryMsg as QueryBase,
+        ReceiveMsg,
+    },
     state::PreProposeContract,
 };
-use dao_voting::multiple_choice::MultipleChoiceOptions;
+use dao_voting::{
+    condition::ExecutionCondition, dependency::ProposalDependency,
+    multiple_choice::MultipleChoiceOptions,
+};
 
 pub(crate) const CONTRACT_NAME: &str = "crates.io:dao-pre-propose-multiple";
 pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -20,6 +26,13 @@ pub enum ProposeMessage {
         title: String,
         description: String,
         choices: MultipleChoiceOptions,
+        /// Conditions that must hold when the proposal is executed.
+        #[serde(default)]
+        conditions: Vec<ExecutionCondition>,
+        /// Proposals, in this or other proposal modules, that must be
+        /// executed before the proposal may be executed.
+        #[serde(default)]
+        dependencies: Vec<ProposalDependency>,
     },
 }
 
@@ -37,6 +50,10 @@ enum ProposeMessageInternal {
         description: String,
         choices: MultipleChoiceOptions,
         proposer: Option<String>,
+        #[serde(default)]
+        conditions: Vec<ExecutionCondition>,
+        #[serde(default)]
+        dependencies: Vec<ProposalDependency>,
     },
 }
 
@@ -68,23 +85,34 @@ pub fn execute(
     type ExecuteInternal = ExecuteBase<ProposeMessageInternal, Empty>;
     let internalized = match msg {
         ExecuteMsg::Propose {
-            msg:
-                ProposeMessage::Propose {
-                    title,
-                    description,
-                    choices,
-                },
+            msg,
             key,
+            co_sponsors,
         } => ExecuteInternal::Propose {
-            msg: ProposeMessageInternal::Propose {
-                proposer: Some(info.sender.to_string()),
-                title,
-                description,
-                choices,
-            },
+            msg: internalize(msg, &info.sender),
             key,
+            co_sponsors,
         },
+        // The base module can not internalize the message sent with a
+        // SNIP-20 deposit, so proposals paid for this way are created
+        // here.
+        ExecuteMsg::Receive(wrapper) => {
+            let pre_propose = PrePropose::default();
+            let ReceiveMsg::Propose {
+                key,
+                msg,
+                co_sponsors,
+            } = pre_propose.check_receive(deps.as_ref(), &info, &wrapper)?;
+            let msg = internalize(msg, &wrapper.from);
+            return pre_propose.propose(deps, env, wrapper.from, key, msg, co_sponsors);
+        }
         ExecuteMsg::Extension { msg } => ExecuteInternal::Extension { msg },
+        ExecuteMsg::UpdateSubmissionLimits { limits } => {
+            ExecuteInternal::UpdateSubmissionLimits { limits }
+        }
+        ExecuteMsg::UpdateSubmissionPolicy { policy } => {
+            ExecuteInternal::UpdateSubmissionPolicy { policy }
+        }
         ExecuteMsg::Withdraw { denom, key } => ExecuteInternal::Withdraw { denom, key },
         ExecuteMsg::UpdateConfig {
             deposit_info,
@@ -102,15 +130,36 @@ pub fn execute(
         ExecuteBase::ProposalCompletedHook {
             proposal_id,
             new_status,
+            quorum_met,
         } => ExecuteInternal::ProposalCompletedHook {
             proposal_id,
             new_status,
+            quorum_met,
         },
     };
 
     PrePropose::default().execute(deps, env, info, internalized)
 }
 
+fn internalize(msg: ProposeMessage, proposer: &Addr) -> ProposeMessageInternal {
+    match msg {
+        ProposeMessage::Propose {
+            title,
+            description,
+            choices,
+            conditions,
+            dependencies,
+        } => ProposeMessageInternal::Propose {
+            proposer: Some(proposer.to_string()),
+            title,
+            description,
+            choices,
+            conditions,
+            dependencies,
+        },
+    }
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     PrePropose::default().query(deps, env, msg)
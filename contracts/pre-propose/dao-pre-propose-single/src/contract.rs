@@ -2,16 +2,24 @@ use cosmwasm_schema::cw_serde;
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    Binary, CosmosMsg, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdResult,
+    Addr, Binary, CosmosMsg, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdResult,
 };
 use secret_cw2::set_contract_version;
 
+use dao_interface::state::ProposalModuleStatus;
 use dao_pre_propose_base::{
     error::PreProposeError,
-    msg::{ExecuteMsg as ExecuteBase, InstantiateMsg as InstantiateBase, QueryMsg as QueryBase},
+    msg::{
+        ExecuteMsg as ExecuteBase, InstantiateMsg as InstantiateBase, QueryMsg as QueryBase,
+        ReceiveMsg,
+    },
     state::PreProposeContract,
 };
-use dao_voting::proposal::SingleChoiceProposeMsg as ProposeMsg;
+use dao_voting::{
+    condition::ExecutionCondition,
+    dependency::{query_dao_proposal_module, ProposalDependency},
+    proposal::SingleChoiceProposeMsg as ProposeMsg,
+};
 
 pub(crate) const CONTRACT_NAME: &str = "crates.io:dao-pre-propose-single";
 pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -26,11 +34,38 @@ pub enum ProposeMessage {
         title: String,
         description: String,
         msgs: Vec<CosmosMsg<Empty>>,
+        /// Conditions that must hold when the proposal is executed.
+        #[serde(default)]
+        conditions: Vec<ExecutionCondition>,
+        /// Proposals, in this or other proposal modules, that must be
+        /// executed before the proposal may be executed.
+        #[serde(default)]
+        dependencies: Vec<ProposalDependency>,
+        /// Whether this is an emergency proposal. Only the proposal
+        /// module's emergency proposers may create them.
+        #[serde(default)]
+        emergency: bool,
+    },
+}
+
+#[cw_serde]
+pub enum ExecuteExt {
+    /// Creates a proposal on behalf of PROPOSER, who is recorded as
+    /// its proposer and is refunded its deposit. Only the DAO's
+    /// enabled proposal modules may do this, for example
+    /// dao-proposal-optimistic when escalating a challenged
+    /// proposal. KEY is PROPOSER's viewing key. A native deposit must
+    /// be sent with this message, while a SNIP-20 deposit is taken
+    /// from PROPOSER's allowance.
+    ProposeOnBehalf {
+        proposer: String,
+        key: String,
+        msg: ProposeMessage,
     },
 }
 
 pub type InstantiateMsg = InstantiateBase<Empty>;
-pub type ExecuteMsg = ExecuteBase<ProposeMessage, Empty>;
+pub type ExecuteMsg = ExecuteBase<ProposeMessage, ExecuteExt>;
 pub type QueryMsg = QueryBase<Empty>;
 
 /// Internal version of the propose message that includes the
@@ -69,23 +104,36 @@ pub fn execute(
     type ExecuteInternal = ExecuteBase<ProposeMessageInternal, Empty>;
     let internalized = match msg {
         ExecuteMsg::Propose {
-            msg:
-                ProposeMessage::Propose {
-                    title,
-                    description,
-                    msgs,
-                },
+            msg,
             key,
+            co_sponsors,
         } => ExecuteInternal::Propose {
-            msg: ProposeMessageInternal::Propose(ProposeMsg {
-                proposer: Some(info.sender.to_string()),
-                title,
-                description,
-                msgs,
-            }),
+            msg: internalize(msg, &info.sender),
             key,
+            co_sponsors,
         },
-        ExecuteMsg::Extension { msg } => ExecuteInternal::Extension { msg },
+        // The base module can not internalize the message sent with a
+        // SNIP-20 deposit, so proposals paid for this way are created
+        // here.
+        ExecuteMsg::Receive(wrapper) => {
+            let pre_propose = PrePropose::default();
+            let ReceiveMsg::Propose {
+                key,
+                msg,
+                co_sponsors,
+            } = pre_propose.check_receive(deps.as_ref(), &info, &wrapper)?;
+            let msg = internalize(msg, &wrapper.from);
+            return pre_propose.propose(deps, env, wrapper.from, key, msg, co_sponsors);
+        }
+        ExecuteMsg::Extension {
+            msg: ExecuteExt::ProposeOnBehalf { proposer, key, msg },
+        } => return execute_propose_on_behalf(deps, env, info, proposer, key, msg),
+        ExecuteMsg::UpdateSubmissionLimits { limits } => {
+            ExecuteInternal::UpdateSubmissionLimits { limits }
+        }
+        ExecuteMsg::UpdateSubmissionPolicy { policy } => {
+            ExecuteInternal::UpdateSubmissionPolicy { policy }
+        }
         ExecuteMsg::Withdraw { denom, key } => ExecuteInternal::Withdraw { denom, key },
         ExecuteMsg::UpdateConfig {
             deposit_info,
@@ -103,15 +151,69 @@ pub fn execute(
         ExecuteMsg::ProposalCompletedHook {
             proposal_id,
             new_status,
+            quorum_met,
         } => ExecuteInternal::ProposalCompletedHook {
             proposal_id,
             new_status,
+            quorum_met,
         },
     };
 
     PrePropose::default().execute(deps, env, info, internalized)
 }
 
+pub fn execute_propose_on_behalf(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposer: String,
+    key: String,
+    msg: ProposeMessage,
+) -> Result<Response, PreProposeError> {
+    let pre_propose = PrePropose::default();
+    let dao = pre_propose.dao.load(deps.storage)?;
+    let module = query_dao_proposal_module(deps.as_ref(), &dao, &info.sender)?;
+    if !matches!(module, Some(module) if module.status == ProposalModuleStatus::Enabled) {
+        return Err(PreProposeError::Unauthorized {});
+    }
+    let proposer = deps.api.addr_validate(&proposer)?;
+
+    let config = pre_propose.config.load(deps.storage)?;
+    let deposit_messages = if let Some(ref deposit_info) = config.deposit_info {
+        deposit_info.check_native_deposit_paid(&info)?;
+        deposit_info.get_take_deposit_messages(&proposer, &env.contract.address)?
+    } else {
+        vec![]
+    };
+
+    let msg = internalize(msg, &proposer);
+    Ok(pre_propose
+        .propose(deps, env, proposer, key, msg, vec![])?
+        .add_messages(deposit_messages)
+        .add_attribute("submitted_by", info.sender))
+}
+
+fn internalize(msg: ProposeMessage, proposer: &Addr) -> ProposeMessageInternal {
+    match msg {
+        ProposeMessage::Propose {
+            title,
+            description,
+            msgs,
+            conditions,
+            dependencies,
+            emergency,
+        } => ProposeMessageInternal::Propose(ProposeMsg {
+            proposer: Some(proposer.to_string()),
+            title,
+            description,
+            msgs,
+            conditions,
+            dependencies,
+            emergency,
+        }),
+    }
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     PrePropose::default().query(deps, env, msg)
@@ -5,7 +5,7 @@ pub mod contract;
 #[cfg(test)]
 mod tests;
 
-pub use contract::{ExecuteMsg, InstantiateMsg, ProposeMessage, QueryMsg};
+pub use contract::{ExecuteExt, ExecuteMsg, InstantiateMsg, ProposeMessage, QueryMsg};
 
 // Exporting these means that contracts interacting with this one don't
 // need an explicit dependency on the base contract to read queries.
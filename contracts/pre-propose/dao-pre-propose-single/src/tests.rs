@@ -188,6 +188,9 @@ fn make_proposal(
                 title: "title".to_string(),
                 description: "description".to_string(),
                 msgs: vec![],
+                conditions: vec![],
+                dependencies: vec![],
+                emergency: false,
             },
         },
         funds,
@@ -810,6 +813,7 @@ fn test_permissions() {
             &ExecuteMsg::ProposalCompletedHook {
                 proposal_id: 1,
                 new_status: Status::Closed,
+                quorum_met: None,
             },
             &[],
         )
@@ -829,6 +833,9 @@ fn test_permissions() {
                     title: "I would like to join the DAO".to_string(),
                     description: "though, I am currently not a member.".to_string(),
                     msgs: vec![],
+                    conditions: vec![],
+                    dependencies: vec![],
+                    emergency: false,
                 },
             },
             &[],
@@ -917,6 +924,9 @@ fn test_no_deposit_required_members_submission() {
                     title: "I would like to join the DAO".to_string(),
                     description: "though, I am currently not a member.".to_string(),
                     msgs: vec![],
+                    conditions: vec![],
+                    dependencies: vec![],
+                    emergency: false,
                 },
             },
             &[],
@@ -0,0 +1,591 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    from_binary, to_binary, Addr, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Response,
+    StdResult, SubMsg, Uint128, WasmMsg,
+};
+use cw_denom::CheckedDenom;
+use dao_interface::msg::Snip20ReceiveMsg;
+use dao_pre_propose_base::{error::PreProposeError, msg::CoSponsor, state::PreProposeContract};
+use dao_voting::{
+    deposit::{CheckedDepositInfo, DepositError},
+    proposal::SingleChoiceProposeMsg as ProposeMsg,
+    voting::get_voting_power,
+};
+use secret_cw2::set_contract_version;
+use secret_utils::{may_pay, nonpayable};
+
+use crate::error::ContractError;
+use crate::msg::{
+    ExecuteExt, ExecuteMsg, InstantiateExt, InstantiateMsg, ProposeMessage, ProposeMessageInternal,
+    QueryExt, QueryMsg, ReceiveMsg,
+};
+use crate::state::{
+    advance_draft_id, Draft, DraftStatus, Sponsor, SponsorshipConfig, DRAFTS, PROPOSAL_TO_DRAFT,
+    SPONSORSHIP,
+};
+
+pub(crate) const CONTRACT_NAME: &str = "crates.io:dao-pre-propose-sponsored";
+pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+const DEFAULT_LIMIT: u32 = 30;
+
+type PrePropose = PreProposeContract<InstantiateExt, ExecuteExt, QueryExt, ProposeMessage>;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    if !msg.extension.sponsorship.is_valid() {
+        return Err(ContractError::InvalidThreshold {});
+    }
+    SPONSORSHIP.save(deps.storage, &msg.extension.sponsorship)?;
+
+    let resp = PrePropose::default().instantiate(deps.branch(), env, info, msg)?;
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    Ok(resp)
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Propose {
+            msg,
+            key,
+            co_sponsors,
+        } => execute_propose(deps, env, info, msg, key, co_sponsors),
+        ExecuteMsg::Receive(wrapper) => execute_receive(deps, env, info, wrapper),
+
+        ExecuteMsg::Extension { msg } => match msg {
+            ExecuteExt::Sponsor { id, key } => execute_sponsor(deps, env, info, id, key),
+            ExecuteExt::WithdrawDraft { id } => execute_withdraw_draft(deps, info, id),
+            ExecuteExt::CloseExpiredDraft { id } => execute_close_expired_draft(deps, env, id),
+            ExecuteExt::UpdateSponsorship { config } => {
+                execute_update_sponsorship(deps, info, config)
+            }
+        },
+        // Default pre-propose-base behavior for all other messages
+        _ => Ok(PrePropose::default().execute(deps, env, info, msg)?),
+    }
+}
+
+pub fn execute_propose(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ProposeMessage,
+    key: String,
+    co_sponsors: Vec<CoSponsor>,
+) -> Result<Response, ContractError> {
+    let config = PrePropose::default().config.load(deps.storage)?;
+    let sponsorship = SPONSORSHIP.load(deps.storage)?;
+
+    // Unless the deposit is split, the author pays all of it now.
+    let (paid, deposit_messages) = take_payment(
+        &config.deposit_info,
+        &info,
+        &env.contract.address,
+        !sponsorship.split_deposit,
+    )?;
+
+    create_draft(
+        deps,
+        env,
+        info.sender,
+        key,
+        msg,
+        co_sponsors,
+        paid,
+        deposit_messages,
+    )
+}
+
+pub fn execute_receive(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    wrapper: Snip20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let msg = wrapper
+        .msg
+        .as_ref()
+        .ok_or(PreProposeError::NoReceiveMsg {})?;
+    match from_binary(msg)? {
+        ReceiveMsg::Propose {
+            key,
+            msg,
+            co_sponsors,
+        } => {
+            let config = PrePropose::default().config.load(deps.storage)?;
+            check_deposit_token(&config.deposit_info, &info.sender)?;
+            create_draft(
+                deps,
+                env,
+                wrapper.from,
+                key,
+                msg,
+                co_sponsors,
+                wrapper.amount,
+                vec![],
+            )
+        }
+        ReceiveMsg::Sponsor { id, key } => {
+            let draft = DRAFTS
+                .get(deps.storage, &id)
+                .ok_or(ContractError::DraftNotFound {})?;
+            check_deposit_token(&draft.deposit, &info.sender)?;
+            sponsor(deps, env, wrapper.from, id, key, wrapper.amount)
+        }
+    }
+}
+
+pub fn execute_sponsor(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+    key: String,
+) -> Result<Response, ContractError> {
+    let draft = DRAFTS
+        .get(deps.storage, &id)
+        .ok_or(ContractError::DraftNotFound {})?;
+    let (paid, _) = take_payment(&draft.deposit, &info, &env.contract.address, false)?;
+
+    sponsor(deps, env, info.sender, id, key, paid)
+}
+
+pub fn execute_withdraw_draft(
+    deps: DepsMut,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response, ContractError> {
+    let draft = load_open_draft(deps.as_ref(), id)?;
+    if draft.author != info.sender {
+        return Err(PreProposeError::Unauthorized {}.into());
+    }
+
+    let messages = close_draft(deps, draft, DraftStatus::Withdrawn {})?;
+    Ok(Response::default()
+        .add_messages(messages)
+        .add_attribute("method", "withdraw_draft")
+        .add_attribute("draft_id", id.to_string()))
+}
+
+pub fn execute_close_expired_draft(
+    deps: DepsMut,
+    env: Env,
+    id: u64,
+) -> Result<Response, ContractError> {
+    let draft = load_open_draft(deps.as_ref(), id)?;
+    if !draft.expiration.is_expired(&env.block) {
+        return Err(ContractError::DraftNotExpired {});
+    }
+
+    let messages = close_draft(deps, draft, DraftStatus::Expired {})?;
+    Ok(Response::default()
+        .add_messages(messages)
+        .add_attribute("method", "close_expired_draft")
+        .add_attribute("draft_id", id.to_string()))
+}
+
+pub fn execute_update_sponsorship(
+    deps: DepsMut,
+    info: MessageInfo,
+    config: SponsorshipConfig,
+) -> Result<Response, ContractError> {
+    let dao = PrePropose::default().dao.load(deps.storage)?;
+    if info.sender != dao.addr {
+        return Err(PreProposeError::NotDao {}.into());
+    }
+    if !config.is_valid() {
+        return Err(ContractError::InvalidThreshold {});
+    }
+    SPONSORSHIP.save(deps.storage, &config)?;
+
+    Ok(Response::default()
+        .add_attribute("method", "update_sponsorship")
+        .add_attribute("sender", info.sender))
+}
+
+/// Creates a draft authored by AUTHOR, who has paid PAID towards its
+/// deposit. DEPOSIT_MESSAGES take the payment, if it has not already
+/// been made. CO_SPONSORS count towards the submission policy as they
+/// would for a proposal submitted directly.
+#[allow(clippy::too_many_arguments)]
+fn create_draft(
+    deps: DepsMut,
+    env: Env,
+    author: Addr,
+    key: String,
+    msg: ProposeMessage,
+    co_sponsors: Vec<CoSponsor>,
+    paid: Uint128,
+    deposit_messages: Vec<CosmosMsg>,
+) -> Result<Response, ContractError> {
+    let pre_propose = PrePropose::default();
+
+    // Drafts may only be created by those who could submit the
+    // proposal directly.
+    pre_propose.check_can_submit(
+        deps.as_ref(),
+        &env,
+        author.clone(),
+        key.clone(),
+        &co_sponsors,
+    )?;
+    let dao = pre_propose.dao.load(deps.storage)?;
+    let author_power = get_voting_power(
+        deps.as_ref(),
+        dao.code_hash,
+        author.clone(),
+        key,
+        &dao.addr,
+        Some(env.block.height),
+    )?;
+    // Open drafts count towards the submission limits.
+    pre_propose.record_submission(deps.storage, &env.block, &author)?;
+
+    let config = pre_propose.config.load(deps.storage)?;
+    let sponsorship = SPONSORSHIP.load(deps.storage)?;
+    if let Some(ref deposit) = config.deposit_info {
+        if !sponsorship.split_deposit && paid != deposit.amount {
+            return Err(DepositError::InvalidDeposit {
+                actual: paid,
+                expected: deposit.amount,
+            }
+            .into());
+        }
+        if paid > deposit.amount {
+            return Err(ContractError::ExcessPayment {
+                remaining: deposit.amount,
+            });
+        }
+    }
+
+    let id = advance_draft_id(deps.storage)?;
+    let mut draft = Draft {
+        id,
+        status: DraftStatus::Open {},
+        author: author.clone(),
+        author_power,
+        msg: internalize(msg, &author),
+        sponsors: vec![],
+        created_height: env.block.height,
+        expiration: sponsorship.draft_duration.after(&env.block),
+        sponsorship,
+        deposit: config.deposit_info,
+        contributions: vec![],
+    };
+    draft.contribute(&author, paid);
+
+    let response = Response::default()
+        .add_messages(deposit_messages)
+        .add_attribute("method", "create_draft")
+        .add_attribute("draft_id", id.to_string())
+        .add_attribute("author", author);
+    save_draft(deps, draft, response)
+}
+
+/// Adds SPONSOR, who has paid PAID towards the deposit, to the
+/// sponsors of draft ID.
+fn sponsor(
+    deps: DepsMut,
+    env: Env,
+    sponsor: Addr,
+    id: u64,
+    key: String,
+    paid: Uint128,
+) -> Result<Response, ContractError> {
+    let mut draft = load_open_draft(deps.as_ref(), id)?;
+    if draft.expiration.is_expired(&env.block) {
+        return Err(ContractError::DraftExpired {
+            expiration: draft.expiration,
+        });
+    }
+    // The author and existing sponsors may not sponsor the draft
+    // again, but may pay more of a split deposit.
+    let top_up = draft.author == sponsor || draft.sponsors.iter().any(|s| s.address == sponsor);
+    if top_up && paid.is_zero() {
+        return Err(if draft.author == sponsor {
+            ContractError::AuthorCannotSponsor {}
+        } else {
+            ContractError::AlreadySponsored {}
+        });
+    }
+    if !paid.is_zero() {
+        if !draft.sponsorship.split_deposit {
+            return Err(ContractError::DepositNotSplit {});
+        }
+        let remaining = draft.deposit_remaining();
+        if paid > remaining {
+            return Err(ContractError::ExcessPayment { remaining });
+        }
+    }
+
+    if top_up {
+        draft.contribute(&sponsor, paid);
+        let response = Response::default()
+            .add_attribute("method", "top_up_deposit")
+            .add_attribute("draft_id", id.to_string())
+            .add_attribute("sender", sponsor)
+            .add_attribute("amount", paid);
+        return save_draft(deps, draft, response);
+    }
+
+    // Every sponsor's power is measured when the draft was created,
+    // so that power may not be moved between accounts to sponsor a
+    // draft more than once.
+    let dao = PrePropose::default().dao.load(deps.storage)?;
+    let power = get_voting_power(
+        deps.as_ref(),
+        dao.code_hash,
+        sponsor.clone(),
+        key,
+        &dao.addr,
+        Some(draft.created_height),
+    )?;
+    if power.is_zero() {
+        return Err(PreProposeError::NotMember {}.into());
+    }
+    draft.sponsors.push(Sponsor {
+        address: sponsor.clone(),
+        power,
+    });
+    draft.contribute(&sponsor, paid);
+
+    let response = Response::default()
+        .add_attribute("method", "sponsor_draft")
+        .add_attribute("draft_id", id.to_string())
+        .add_attribute("sponsor", sponsor);
+    save_draft(deps, draft, response)
+}
+
+/// Saves DRAFT, first proposing it if it has enough sponsors and its
+/// deposit is paid.
+fn save_draft(
+    mut deps: DepsMut,
+    mut draft: Draft,
+    response: Response,
+) -> Result<Response, ContractError> {
+    let response = if draft.is_ready() {
+        let (proposal_id, messages) = propose_draft(deps.branch(), &draft)?;
+        draft.status = DraftStatus::Proposed { proposal_id };
+        response
+            .add_submessages(messages)
+            .add_attribute("proposal_id", proposal_id.to_string())
+    } else {
+        response
+    };
+    DRAFTS.insert(deps.storage, &draft.id, &draft)?;
+    Ok(response)
+}
+
+/// Creates a proposal in the proposal module from DRAFT. Returns the
+/// created proposal ID and the messages that create it.
+fn propose_draft(deps: DepsMut, draft: &Draft) -> Result<(u64, Vec<SubMsg>), ContractError> {
+    let pre_propose = PrePropose::default();
+    let proposal_module = pre_propose.proposal_module.load(deps.storage)?;
+
+    // Snapshot the deposit using the ID of the proposal that we
+    // will create.
+    let proposal_id: u64 = deps.querier.query_wasm_smart(
+        proposal_module.code_hash.clone(),
+        proposal_module.addr.to_string(),
+        &dao_interface::proposal::Query::NextProposalId {},
+    )?;
+    pre_propose.deposits.insert(
+        deps.storage,
+        &proposal_id,
+        &(draft.deposit.clone(), draft.author.clone()),
+    )?;
+    // Refunds are split between everyone who paid towards the
+    // deposit.
+    if draft
+        .contributions
+        .iter()
+        .any(|(who, _)| *who != draft.author)
+    {
+        pre_propose.deposit_contributions.insert(
+            deps.storage,
+            &proposal_id,
+            &draft.contributions,
+        )?;
+    }
+    // The proposal counts towards the author's submission limits
+    // until it completes in the proposal module.
+    pre_propose
+        .open_proposals
+        .insert(deps.storage, &proposal_id, &draft.author)?;
+    PROPOSAL_TO_DRAFT.insert(deps.storage, &proposal_id, &draft.id)?;
+
+    let msg = ProposeMessageInternal::Propose(draft.msg.clone());
+    let propose_message = SubMsg::new(WasmMsg::Execute {
+        contract_addr: proposal_module.addr.into_string(),
+        code_hash: proposal_module.code_hash,
+        msg: to_binary(&msg)?,
+        funds: vec![],
+    });
+    let hooks_msgs =
+        pre_propose
+            .proposal_submitted_hooks
+            .prepare_hooks(deps.storage, |hook_item| {
+                let execute = WasmMsg::Execute {
+                    contract_addr: hook_item.addr.into_string(),
+                    code_hash: hook_item.code_hash,
+                    msg: to_binary(&msg)?,
+                    funds: vec![],
+                };
+                Ok(SubMsg::new(execute))
+            })?;
+
+    // The propose message must be first so that hook receivers can
+    // not create a proposal before us and invalidate our
+    // `NextProposalId {}` query.
+    let mut messages = vec![propose_message];
+    messages.extend(hooks_msgs);
+    Ok((proposal_id, messages))
+}
+
+/// Closes DRAFT with STATUS. Returns messages refunding everything
+/// paid towards its deposit.
+fn close_draft(
+    deps: DepsMut,
+    mut draft: Draft,
+    status: DraftStatus,
+) -> Result<Vec<CosmosMsg>, ContractError> {
+    let messages = match draft.deposit {
+        Some(ref deposit) => draft
+            .contributions
+            .iter()
+            .map(|(who, amount)| deposit.denom.get_transfer_to_message(who, *amount))
+            .collect::<StdResult<Vec<_>>>()?,
+        None => vec![],
+    };
+
+    PrePropose::default().record_completion(deps.storage, &draft.author)?;
+    draft.status = status;
+    DRAFTS.insert(deps.storage, &draft.id, &draft)?;
+    Ok(messages)
+}
+
+fn load_open_draft(deps: Deps, id: u64) -> Result<Draft, ContractError> {
+    let draft = DRAFTS
+        .get(deps.storage, &id)
+        .ok_or(ContractError::DraftNotFound {})?;
+    if draft.status != (DraftStatus::Open {}) {
+        return Err(ContractError::DraftNotOpen {});
+    }
+    Ok(draft)
+}
+
+/// Returns how much of DEPOSIT was paid by INFO, and any messages
+/// needed to take the payment. If IN_FULL, SNIP-20 deposits are taken
+/// in full with an allowance. Otherwise, SNIP-20 payments are made
+/// with `Send`, and handled by `execute_receive`. Native funds are
+/// rejected unless the deposit is paid in them.
+fn take_payment(
+    deposit: &Option<CheckedDepositInfo>,
+    info: &MessageInfo,
+    contract: &Addr,
+    in_full: bool,
+) -> Result<(Uint128, Vec<CosmosMsg>), ContractError> {
+    match deposit {
+        None => {
+            nonpayable(info)?;
+            Ok((Uint128::zero(), vec![]))
+        }
+        Some(deposit) => match deposit.denom {
+            CheckedDenom::Native(ref denom) => Ok((may_pay(info, denom)?, vec![])),
+            CheckedDenom::Cw20(..) => {
+                nonpayable(info)?;
+                if in_full {
+                    Ok((
+                        deposit.amount,
+                        deposit.get_take_deposit_messages(&info.sender, contract)?,
+                    ))
+                } else {
+                    Ok((Uint128::zero(), vec![]))
+                }
+            }
+        },
+    }
+}
+
+/// Checks that TOKEN, received with a SNIP-20 `Send`, is the token
+/// DEPOSIT is paid in.
+fn check_deposit_token(
+    deposit: &Option<CheckedDepositInfo>,
+    token: &Addr,
+) -> Result<(), ContractError> {
+    match deposit {
+        Some(deposit) if deposit.denom.is_cw20(token) => Ok(()),
+        _ => Err(DepositError::InvalidDepositToken {
+            token: token.clone(),
+        }
+        .into()),
+    }
+}
+
+fn internalize(msg: ProposeMessage, proposer: &Addr) -> ProposeMsg {
+    match msg {
+        ProposeMessage::Propose {
+            title,
+            description,
+            msgs,
+            conditions,
+            dependencies,
+            emergency,
+        } => ProposeMsg {
+            proposer: Some(proposer.to_string()),
+            title,
+            description,
+            msgs,
+            conditions,
+            dependencies,
+            emergency,
+        },
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::QueryExtension { msg } => match msg {
+            QueryExt::Sponsorship {} => to_binary(&SPONSORSHIP.load(deps.storage)?),
+            QueryExt::Draft { id } => to_binary(&DRAFTS.get(deps.storage, &id)),
+            QueryExt::Drafts { start_after, limit } => {
+                let limit = limit.unwrap_or(DEFAULT_LIMIT) as usize;
+                let mut drafts: Vec<Draft> = Vec::with_capacity(limit);
+                // Drafts are inserted in order of ID.
+                for item in DRAFTS.iter(deps.storage)? {
+                    if drafts.len() >= limit {
+                        break;
+                    }
+                    let (id, draft) = item?;
+                    if start_after.map_or(true, |start| id > start) {
+                        drafts.push(draft);
+                    }
+                }
+                to_binary(&drafts)
+            }
+            QueryExt::Sponsors { id } => to_binary(
+                &DRAFTS
+                    .get(deps.storage, &id)
+                    .map(|draft| draft.sponsors)
+                    .unwrap_or_default(),
+            ),
+            QueryExt::DraftIdForProposalId { id } => {
+                to_binary(&PROPOSAL_TO_DRAFT.get(deps.storage, &id))
+            }
+        },
+        _ => PrePropose::default().query(deps, env, msg),
+    }
+}
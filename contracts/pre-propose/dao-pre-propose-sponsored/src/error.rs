@@ -0,0 +1,47 @@
+use cosmwasm_std::{StdError, Uint128};
+use dao_pre_propose_base::error::PreProposeError;
+use dao_voting::deposit::DepositError;
+use secret_utils::{Expiration, PaymentError};
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error(transparent)]
+    Std(#[from] StdError),
+
+    #[error(transparent)]
+    PrePropose(#[from] PreProposeError),
+
+    #[error(transparent)]
+    Deposit(#[from] DepositError),
+
+    #[error(transparent)]
+    Payment(#[from] PaymentError),
+
+    #[error("Sponsorship threshold must be non-zero")]
+    InvalidThreshold {},
+
+    #[error("Draft not found")]
+    DraftNotFound {},
+
+    #[error("Draft is no longer open")]
+    DraftNotOpen {},
+
+    #[error("Draft expired ({expiration})")]
+    DraftExpired { expiration: Expiration },
+
+    #[error("Draft has not expired")]
+    DraftNotExpired {},
+
+    #[error("Authors may not sponsor their own drafts")]
+    AuthorCannotSponsor {},
+
+    #[error("Sender has already sponsored this draft")]
+    AlreadySponsored {},
+
+    #[error("Only the author pays the deposit of this draft")]
+    DepositNotSplit {},
+
+    #[error("Payment exceeds the remaining deposit ({remaining})")]
+    ExcessPayment { remaining: Uint128 },
+}
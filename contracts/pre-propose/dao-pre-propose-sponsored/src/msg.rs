@@ -0,0 +1,112 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{CosmosMsg, Empty};
+use dao_pre_propose_base::msg::{
+    CoSponsor, ExecuteMsg as ExecuteBase, InstantiateMsg as InstantiateBase, QueryMsg as QueryBase,
+};
+use dao_voting::{
+    condition::ExecutionCondition, dependency::ProposalDependency,
+    proposal::SingleChoiceProposeMsg as ProposeMsg,
+};
+
+use crate::state::SponsorshipConfig;
+
+#[cw_serde]
+pub enum ProposeMessage {
+    /// Creates a draft. Note that this is identical to the propose
+    /// message used by dao-proposal-single, except that it omits the
+    /// `proposer` field which it fills in for the sender.
+    Propose {
+        title: String,
+        description: String,
+        msgs: Vec<CosmosMsg<Empty>>,
+        /// Conditions that must hold when the proposal is executed.
+        #[serde(default)]
+        conditions: Vec<ExecutionCondition>,
+        /// Proposals, in this or other proposal modules, that must be
+        /// executed before the proposal may be executed.
+        #[serde(default)]
+        dependencies: Vec<ProposalDependency>,
+        /// Whether this is an emergency proposal. Only the proposal
+        /// module's emergency proposers may create them.
+        #[serde(default)]
+        emergency: bool,
+    },
+}
+
+#[cw_serde]
+pub struct InstantiateExt {
+    pub sponsorship: SponsorshipConfig,
+}
+
+#[cw_serde]
+pub enum ExecuteExt {
+    /// Sponsors a draft. KEY is the sender's viewing key, used to
+    /// query their voting power. If the deposit is split, native
+    /// tokens sent with this message are paid towards it, and the
+    /// author or an existing sponsor may send this message again to
+    /// pay more of it.
+    Sponsor { id: u64, key: String },
+    /// Withdraws a draft that has not been proposed, refunding
+    /// everything paid towards its deposit. Only callable by the
+    /// draft's author.
+    WithdrawDraft { id: u64 },
+    /// Closes a draft that expired before it found enough sponsors,
+    /// refunding everything paid towards its deposit. Callable by
+    /// anyone.
+    CloseExpiredDraft { id: u64 },
+    /// Updates the sponsorship config used for new drafts. Only
+    /// callable by the DAO.
+    UpdateSponsorship { config: SponsorshipConfig },
+}
+
+/// Messages that may be sent with a SNIP-20 `Send` of the deposit
+/// token. The tokens sent are paid towards the draft's deposit.
+#[cw_serde]
+pub enum ReceiveMsg {
+    /// Creates a draft.
+    Propose {
+        key: String,
+        msg: ProposeMessage,
+        #[serde(default)]
+        co_sponsors: Vec<CoSponsor>,
+    },
+    /// Sponsors a draft, or pays more of its deposit if the sender
+    /// is its author or already sponsors it.
+    Sponsor { id: u64, key: String },
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryExt {
+    /// The sponsorship config used for new drafts.
+    #[returns(crate::state::SponsorshipConfig)]
+    Sponsorship {},
+    /// A draft, including its sponsors and expiration.
+    #[returns(::std::option::Option<crate::state::Draft>)]
+    Draft { id: u64 },
+    /// Lists drafts in ascending order of ID. If no limit is set a
+    /// max of 30 drafts will be returned.
+    #[returns(Vec<crate::state::Draft>)]
+    Drafts {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// The sponsors of a draft.
+    #[returns(Vec<crate::state::Sponsor>)]
+    Sponsors { id: u64 },
+    /// The draft ID for a created proposal ID.
+    #[returns(::std::option::Option<u64>)]
+    DraftIdForProposalId { id: u64 },
+}
+
+pub type InstantiateMsg = InstantiateBase<InstantiateExt>;
+pub type ExecuteMsg = ExecuteBase<ProposeMessage, ExecuteExt>;
+pub type QueryMsg = QueryBase<QueryExt>;
+
+/// Internal version of the propose message that includes the
+/// `proposer` field. The module will fill this in based on the
+/// draft's author.
+#[cw_serde]
+pub(crate) enum ProposeMessageInternal {
+    Propose(ProposeMsg),
+}
@@ -0,0 +1,236 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, StdResult, Storage, Uint128};
+use secret_storage_plus::Item;
+use secret_toolkit::serialization::Json;
+use secret_toolkit::storage::Keymap;
+use secret_utils::{Duration, Expiration};
+
+use dao_voting::deposit::CheckedDepositInfo;
+use dao_voting::proposal::SingleChoiceProposeMsg as ProposeMsg;
+
+/// How much sponsorship a draft needs before it is proposed.
+#[cw_serde]
+pub enum SponsorThreshold {
+    /// A number of sponsors, not counting the draft's author.
+    Count { count: u32 },
+    /// The combined voting power of the draft's author and sponsors.
+    Power { power: Uint128 },
+}
+
+#[cw_serde]
+pub struct SponsorshipConfig {
+    /// How much sponsorship a draft needs before it is proposed.
+    pub threshold: SponsorThreshold,
+    /// How long a draft has to find sponsors before it expires.
+    pub draft_duration: Duration,
+    /// If true, the proposal deposit may be paid by the author and
+    /// sponsors together, and each is refunded in proportion to what
+    /// they paid. Otherwise, the author pays the whole deposit when
+    /// they create the draft.
+    pub split_deposit: bool,
+}
+
+impl SponsorshipConfig {
+    /// Returns true if the threshold is non-zero.
+    pub fn is_valid(&self) -> bool {
+        match self.threshold {
+            SponsorThreshold::Count { count } => count > 0,
+            SponsorThreshold::Power { power } => !power.is_zero(),
+        }
+    }
+}
+
+#[cw_serde]
+pub enum DraftStatus {
+    /// The draft is looking for sponsors.
+    Open {},
+    /// The draft found enough sponsors and was proposed.
+    Proposed {
+        /// The created proposal ID.
+        proposal_id: u64,
+    },
+    /// The draft was withdrawn by its author.
+    Withdrawn {},
+    /// The draft did not find enough sponsors before it expired.
+    Expired {},
+}
+
+#[cw_serde]
+pub struct Sponsor {
+    pub address: Addr,
+    /// The sponsor's voting power when the draft was created.
+    pub power: Uint128,
+}
+
+#[cw_serde]
+pub struct Draft {
+    /// The ID used to identify this draft.
+    pub id: u64,
+    pub status: DraftStatus,
+    /// The address that created the draft. The proposal is created
+    /// on their behalf.
+    pub author: Addr,
+    /// The author's voting power when they created the draft.
+    pub author_power: Uint128,
+    /// The propose message that will be sent to the proposal module
+    /// once the draft has enough sponsors.
+    pub msg: ProposeMsg,
+    pub sponsors: Vec<Sponsor>,
+    /// The block height the draft was created at. Sponsors' voting
+    /// power is measured at this height.
+    pub created_height: u64,
+    /// When the draft stops accepting sponsors.
+    pub expiration: Expiration,
+    /// Snapshot of the sponsorship config at the time the draft was
+    /// created.
+    pub sponsorship: SponsorshipConfig,
+    /// Snapshot of the deposit info at the time the draft was
+    /// created.
+    pub deposit: Option<CheckedDepositInfo>,
+    /// Who has paid towards the deposit, and how much.
+    pub contributions: Vec<(Addr, Uint128)>,
+}
+
+impl Draft {
+    /// Returns true if the draft has enough sponsors to be proposed.
+    pub fn threshold_met(&self) -> bool {
+        match self.sponsorship.threshold {
+            SponsorThreshold::Count { count } => self.sponsors.len() >= count as usize,
+            SponsorThreshold::Power { power } => {
+                let sponsor_power: Uint128 = self.sponsors.iter().map(|s| s.power).sum();
+                self.author_power + sponsor_power >= power
+            }
+        }
+    }
+
+    /// The amount of the deposit not yet paid.
+    pub fn deposit_remaining(&self) -> Uint128 {
+        let paid: Uint128 = self.contributions.iter().map(|(_, amount)| *amount).sum();
+        self.deposit
+            .as_ref()
+            .map(|deposit| deposit.amount.saturating_sub(paid))
+            .unwrap_or_default()
+    }
+
+    /// Returns true if the draft has enough sponsors and its deposit
+    /// is paid.
+    pub fn is_ready(&self) -> bool {
+        self.threshold_met() && self.deposit_remaining().is_zero()
+    }
+
+    /// Records that WHO paid AMOUNT towards the deposit.
+    pub fn contribute(&mut self, who: &Addr, amount: Uint128) {
+        if amount.is_zero() {
+            return;
+        }
+        match self.contributions.iter_mut().find(|(a, _)| a == who) {
+            Some((_, paid)) => *paid += amount,
+            None => self.contributions.push((who.clone(), amount)),
+        }
+    }
+}
+
+pub const SPONSORSHIP: Item<SponsorshipConfig> = Item::new("sponsorship");
+pub const DRAFTS: Keymap<u64, Draft, Json> = Keymap::new(b"drafts");
+pub const PROPOSAL_TO_DRAFT: Keymap<u64, u64, Json> = Keymap::new(b"proposal_to_draft");
+
+/// Used internally to track the current draft ID.
+const CURRENT_ID: Item<u64> = Item::new("current_id");
+
+pub(crate) fn advance_draft_id(store: &mut dyn Storage) -> StdResult<u64> {
+    let id: u64 = CURRENT_ID.may_load(store)?.unwrap_or_default() + 1;
+    CURRENT_ID.save(store, &id)?;
+    Ok(id)
+}
+
+#[cfg(test)]
+mod tests {
+    use cw_denom::CheckedDenom;
+    use dao_voting::deposit::DepositRefundPolicy;
+
+    use super::*;
+
+    fn draft(threshold: SponsorThreshold) -> Draft {
+        Draft {
+            id: 1,
+            status: DraftStatus::Open {},
+            author: Addr::unchecked("ekez"),
+            author_power: Uint128::new(5),
+            msg: ProposeMsg {
+                title: "title".to_string(),
+                description: "description".to_string(),
+                msgs: vec![],
+                proposer: Some("ekez".to_string()),
+                conditions: vec![],
+                dependencies: vec![],
+                emergency: false,
+            },
+            sponsors: vec![],
+            created_height: 1,
+            expiration: Expiration::Never {},
+            sponsorship: SponsorshipConfig {
+                threshold,
+                draft_duration: Duration::Height(10),
+                split_deposit: true,
+            },
+            deposit: Some(CheckedDepositInfo {
+                denom: CheckedDenom::Native("uekez".to_string()),
+                amount: Uint128::new(10),
+                refund_policy: DepositRefundPolicy::Always,
+            }),
+            contributions: vec![],
+        }
+    }
+
+    #[test]
+    fn test_threshold() {
+        let mut d = draft(SponsorThreshold::Count { count: 2 });
+        d.sponsors.push(Sponsor {
+            address: Addr::unchecked("a"),
+            power: Uint128::new(1),
+        });
+        assert!(!d.threshold_met());
+        d.sponsors.push(Sponsor {
+            address: Addr::unchecked("b"),
+            power: Uint128::new(1),
+        });
+        assert!(d.threshold_met());
+
+        // The author's power counts towards a power threshold.
+        let mut d = draft(SponsorThreshold::Power {
+            power: Uint128::new(8),
+        });
+        d.sponsors.push(Sponsor {
+            address: Addr::unchecked("a"),
+            power: Uint128::new(2),
+        });
+        assert!(!d.threshold_met());
+        d.sponsors.push(Sponsor {
+            address: Addr::unchecked("b"),
+            power: Uint128::new(1),
+        });
+        assert!(d.threshold_met());
+    }
+
+    #[test]
+    fn test_contributions() {
+        let mut d = draft(SponsorThreshold::Count { count: 0 });
+        let ekez = Addr::unchecked("ekez");
+        assert_eq!(d.deposit_remaining(), Uint128::new(10));
+        assert!(!d.is_ready());
+
+        d.contribute(&ekez, Uint128::new(4));
+        d.contribute(&Addr::unchecked("a"), Uint128::zero());
+        d.contribute(&Addr::unchecked("b"), Uint128::new(3));
+        d.contribute(&ekez, Uint128::new(3));
+        assert_eq!(
+            d.contributions,
+            vec![
+                (ekez, Uint128::new(7)),
+                (Addr::unchecked("b"), Uint128::new(3))
+            ]
+        );
+        assert_eq!(d.deposit_remaining(), Uint128::zero());
+        assert!(d.is_ready());
+    }
+}
@@ -0,0 +1,356 @@
+use cosmwasm_std::testing::{
+    mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+};
+use cosmwasm_std::{
+    coins, from_binary, to_binary, Addr, BankMsg, Coin, ContractResult, CosmosMsg, Env, OwnedDeps,
+    Response, SystemResult, Uint128, WasmMsg, WasmQuery,
+};
+use cw_denom::UncheckedDenom;
+use dao_interface::state::AnyContractInfo;
+use dao_interface::voting::{
+    Query as VotingQuery, TotalPowerAtHeightResponse, VotingPowerAtHeightResponse,
+};
+use dao_pre_propose_base::error::PreProposeError;
+use dao_pre_propose_base::msg::{CoSponsor, PowerAuth};
+use dao_pre_propose_base::state::{MinimumPower, PreProposeSubmissionPolicy};
+use dao_voting::deposit::{DepositRefundPolicy, DepositToken, UncheckedDepositInfo};
+use secret_utils::{Duration, PaymentError};
+
+use crate::contract::{execute, instantiate, query};
+use crate::error::ContractError;
+use crate::msg::{
+    ExecuteExt, ExecuteMsg, InstantiateExt, InstantiateMsg, ProposeMessage, QueryExt, QueryMsg,
+};
+use crate::state::{Draft, DraftStatus, SponsorThreshold, SponsorshipConfig};
+
+type Deps = OwnedDeps<MockStorage, MockApi, MockQuerier>;
+
+/// The height drafts are created at.
+const START: u64 = 12_345;
+
+fn env_at(height: u64) -> Env {
+    let mut env = mock_env();
+    env.block.height = height;
+    env
+}
+
+/// Sets up the module with a draft needing one sponsor and a 10
+/// uscrt deposit, if DEPOSIT. Only members may submit. Alice has 600
+/// and bob 300 of the DAO's 1000 voting power, and carol gains 100
+/// after START.
+fn setup(deposit: bool, split_deposit: bool) -> Deps {
+    let mut deps = mock_dependencies();
+    deps.querier.update_wasm(|query| {
+        let WasmQuery::Smart { msg, .. } = query else {
+            panic!("unexpected query");
+        };
+        let res = match from_binary(msg) {
+            Ok(VotingQuery::VotingPowerAtHeight {
+                address, height, ..
+            }) => {
+                let height = height.unwrap_or(START);
+                let power = match address.as_str() {
+                    "alice" => 600,
+                    "bob" => 300,
+                    "carol" if height > START => 100,
+                    _ => 0,
+                };
+                to_binary(&VotingPowerAtHeightResponse {
+                    power: Uint128::new(power),
+                    height,
+                })
+            }
+            Ok(VotingQuery::TotalPowerAtHeight { height }) => {
+                to_binary(&TotalPowerAtHeightResponse {
+                    power: Uint128::new(1000),
+                    height: height.unwrap_or(START),
+                })
+            }
+            Ok(VotingQuery::Dao {}) => to_binary(&AnyContractInfo {
+                addr: Addr::unchecked("dao"),
+                code_hash: "dao_hash".to_string(),
+            }),
+            Ok(_) => panic!("unexpected query"),
+            Err(_) => match from_binary(msg).unwrap() {
+                dao_interface::proposal::Query::NextProposalId {} => to_binary(&1u64),
+                _ => panic!("unexpected query"),
+            },
+        };
+        SystemResult::Ok(ContractResult::Ok(res.unwrap()))
+    });
+
+    instantiate(
+        deps.as_mut(),
+        env_at(START),
+        mock_info("module", &[]),
+        InstantiateMsg {
+            deposit_info: deposit.then(|| UncheckedDepositInfo {
+                denom: DepositToken::Token {
+                    denom: UncheckedDenom::Native("uscrt".to_string()),
+                },
+                amount: Uint128::new(10),
+                refund_policy: DepositRefundPolicy::OnlyPassed,
+            }),
+            open_proposal_submission: false,
+            proposal_module_code_hash: "module_hash".to_string(),
+            extension: InstantiateExt {
+                sponsorship: SponsorshipConfig {
+                    threshold: SponsorThreshold::Count { count: 1 },
+                    draft_duration: Duration::Height(10),
+                    split_deposit,
+                },
+            },
+        },
+    )
+    .unwrap();
+    deps
+}
+
+fn funds(amount: u128) -> Vec<Coin> {
+    if amount == 0 {
+        vec![]
+    } else {
+        coins(amount, "uscrt")
+    }
+}
+
+fn propose(
+    deps: &mut Deps,
+    author: &str,
+    paid: u128,
+    co_sponsors: Vec<CoSponsor>,
+) -> Result<Response, ContractError> {
+    execute(
+        deps.as_mut(),
+        env_at(START),
+        mock_info(author, &funds(paid)),
+        ExecuteMsg::Propose {
+            key: "key".to_string(),
+            msg: ProposeMessage::Propose {
+                title: "title".to_string(),
+                description: "description".to_string(),
+                msgs: vec![],
+                conditions: vec![],
+                dependencies: vec![],
+                emergency: false,
+            },
+            co_sponsors,
+        },
+    )
+}
+
+fn sponsor(
+    deps: &mut Deps,
+    sponsor: &str,
+    height: u64,
+    paid: u128,
+) -> Result<Response, ContractError> {
+    execute(
+        deps.as_mut(),
+        env_at(height),
+        mock_info(sponsor, &funds(paid)),
+        ExecuteMsg::Extension {
+            msg: ExecuteExt::Sponsor {
+                id: 1,
+                key: "key".to_string(),
+            },
+        },
+    )
+}
+
+fn draft(deps: &Deps) -> Draft {
+    let draft: Option<Draft> = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::QueryExtension {
+                msg: QueryExt::Draft { id: 1 },
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    draft.unwrap()
+}
+
+/// Returns true if RES creates a proposal in the proposal module.
+fn proposes(res: &Response) -> bool {
+    res.messages.iter().any(|sub| {
+        matches!(
+            &sub.msg,
+            CosmosMsg::Wasm(WasmMsg::Execute { contract_addr, .. }) if contract_addr == "module"
+        )
+    })
+}
+
+#[test]
+fn test_sponsor_power_at_draft_creation() {
+    let mut deps = setup(true, false);
+
+    // Carol has no voting power when the draft is created, so may
+    // neither create it nor sponsor it.
+    let err = propose(&mut deps, "carol", 10, vec![]).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::PrePropose(PreProposeError::NotMember {})
+    );
+
+    let res = propose(&mut deps, "alice", 10, vec![]).unwrap();
+    assert!(!proposes(&res));
+    assert_eq!(draft(&deps).created_height, START);
+
+    let err = sponsor(&mut deps, "carol", START + 1, 0).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::PrePropose(PreProposeError::NotMember {})
+    );
+
+    let res = sponsor(&mut deps, "bob", START + 1, 0).unwrap();
+    assert!(proposes(&res));
+    let draft = draft(&deps);
+    assert_eq!(draft.status, DraftStatus::Proposed { proposal_id: 1 });
+    assert_eq!(draft.sponsors[0].power, Uint128::new(300));
+}
+
+#[test]
+fn test_propose_checks_co_sponsors() {
+    let mut deps = setup(true, false);
+    execute(
+        deps.as_mut(),
+        env_at(START),
+        mock_info("dao", &[]),
+        ExecuteMsg::UpdateSubmissionPolicy {
+            policy: Some(PreProposeSubmissionPolicy::MinimumPower {
+                minimum: MinimumPower::Absolute {
+                    power: Uint128::new(800),
+                },
+            }),
+        },
+    )
+    .unwrap();
+
+    let err = propose(&mut deps, "bob", 10, vec![]).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::PrePropose(PreProposeError::InsufficientPower {
+            power: Uint128::new(300)
+        })
+    );
+
+    let alice = CoSponsor {
+        address: "alice".to_string(),
+        auth: PowerAuth::ViewingKey {
+            key: "key".to_string(),
+        },
+    };
+    propose(&mut deps, "bob", 10, vec![alice]).unwrap();
+    assert_eq!(draft(&deps).author, Addr::unchecked("bob"));
+}
+
+#[test]
+fn test_funds_rejected_without_native_deposit() {
+    let mut deps = setup(false, false);
+    let err = propose(&mut deps, "alice", 10, vec![]).unwrap_err();
+    assert_eq!(err, ContractError::Payment(PaymentError::NonPayable {}));
+
+    propose(&mut deps, "alice", 0, vec![]).unwrap();
+    let err = sponsor(&mut deps, "bob", START, 10).unwrap_err();
+    assert_eq!(err, ContractError::Payment(PaymentError::NonPayable {}));
+}
+
+#[test]
+fn test_split_deposit() {
+    let mut deps = setup(true, true);
+    propose(&mut deps, "alice", 4, vec![]).unwrap();
+
+    let err = sponsor(&mut deps, "bob", START, 7).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::ExcessPayment {
+            remaining: Uint128::new(6)
+        }
+    );
+
+    let res = sponsor(&mut deps, "bob", START, 6).unwrap();
+    assert!(proposes(&res));
+    assert_eq!(
+        draft(&deps).contributions,
+        vec![
+            (Addr::unchecked("alice"), Uint128::new(4)),
+            (Addr::unchecked("bob"), Uint128::new(6))
+        ]
+    );
+}
+
+#[test]
+fn test_expired_draft_refunded() {
+    let mut deps = setup(true, false);
+    propose(&mut deps, "alice", 10, vec![]).unwrap();
+
+    let err = sponsor(&mut deps, "bob", START + 10, 0).unwrap_err();
+    assert!(matches!(err, ContractError::DraftExpired { .. }));
+
+    let close = |deps: &mut Deps, height| {
+        execute(
+            deps.as_mut(),
+            env_at(height),
+            mock_info("anyone", &[]),
+            ExecuteMsg::Extension {
+                msg: ExecuteExt::CloseExpiredDraft { id: 1 },
+            },
+        )
+    };
+    assert_eq!(
+        close(&mut deps, START + 9).unwrap_err(),
+        ContractError::DraftNotExpired {}
+    );
+    let res = close(&mut deps, START + 10).unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: "alice".to_string(),
+            amount: coins(10, "uscrt"),
+        })
+    );
+    assert_eq!(draft(&deps).status, DraftStatus::Expired {});
+}
+
+#[test]
+fn test_top_up_deposit() {
+    let mut deps = setup(true, true);
+    propose(&mut deps, "alice", 2, vec![]).unwrap();
+
+    // Without a payment the author and sponsors may not sponsor the
+    // draft again.
+    let err = sponsor(&mut deps, "alice", START, 0).unwrap_err();
+    assert_eq!(err, ContractError::AuthorCannotSponsor {});
+
+    let res = sponsor(&mut deps, "alice", START, 3).unwrap();
+    assert!(!proposes(&res));
+    let res = sponsor(&mut deps, "bob", START, 1).unwrap();
+    assert!(!proposes(&res));
+    assert_eq!(draft(&deps).sponsors.len(), 1);
+
+    let err = sponsor(&mut deps, "bob", START, 0).unwrap_err();
+    assert_eq!(err, ContractError::AlreadySponsored {});
+    let err = sponsor(&mut deps, "bob", START, 5).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::ExcessPayment {
+            remaining: Uint128::new(4)
+        }
+    );
+
+    let res = sponsor(&mut deps, "bob", START + 1, 4).unwrap();
+    assert!(proposes(&res));
+    let draft = draft(&deps);
+    assert_eq!(draft.status, DraftStatus::Proposed { proposal_id: 1 });
+    assert_eq!(draft.sponsors.len(), 1);
+    assert_eq!(
+        draft.contributions,
+        vec![
+            (Addr::unchecked("alice"), Uint128::new(5)),
+            (Addr::unchecked("bob"), Uint128::new(5))
+        ]
+    );
+}
@@ -0,0 +1,79 @@
+use cosmwasm_std::StdError;
+use cw_hooks::HookError;
+use dao_voting::{error::VotingError, threshold::ThresholdError};
+use secret_cw_controllers::ReplyError;
+use secret_utils::ParseReplyError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error(transparent)]
+    Std(#[from] StdError),
+
+    #[error(transparent)]
+    ParseReplyError(#[from] ParseReplyError),
+
+    #[error(transparent)]
+    HookError(#[from] HookError),
+
+    #[error(transparent)]
+    ReplyUdError(#[from] ReplyError),
+
+    #[error(transparent)]
+    ThresholdError(#[from] ThresholdError),
+
+    #[error(transparent)]
+    VotingError(#[from] VotingError),
+
+    #[error("unauthorized")]
+    Unauthorized {},
+
+    #[error("no such proposal ({id})")]
+    NoSuchProposal { id: u64 },
+
+    #[error("An unknown reply ID was received.")]
+    UnknownReplyID {},
+
+    #[error("received a reply failure with an invalid ID: ({id})")]
+    InvalidReplyID { id: u64 },
+
+    #[error(
+        "pre-propose modules must specify a proposer. lacking one, no proposer should be specified"
+    )]
+    InvalidProposer {},
+
+    #[error("proposals must be voted on by between two and {max} houses")]
+    InvalidHouseCount { max: usize },
+
+    #[error("voting module ({addr}) is used by more than one house")]
+    DuplicateHouse { addr: String },
+
+    #[error("dynamic quorums are not supported by this module")]
+    DynamicQuorumUnsupported {},
+
+    #[error("the max voting period must be non-zero")]
+    ZeroVotingPeriod {},
+
+    #[error("no such house ({house})")]
+    NoSuchHouse { house: u32 },
+
+    #[error(
+        "not registered to vote (no voting power) in house ({house}) at time of proposal creation"
+    )]
+    NotRegistered { house: u32 },
+
+    #[error("already voted on this proposal in house ({house})")]
+    AlreadyVoted { house: u32 },
+
+    #[error("proposal is not open ({id})")]
+    NotOpen { id: u64 },
+
+    #[error("proposal is not in 'passed' state")]
+    NotPassed {},
+
+    #[error("proposal is not in 'rejected' state")]
+    NotRejected {},
+
+    #[error("proposal is ({size}) bytes, must be <= ({max}) bytes")]
+    ProposalTooLarge { size: u64, max: u64 },
+}
@@ -0,0 +1,184 @@
+use cosmwasm_schema::QueryResponses;
+use cosmwasm_std::{Addr, Api, CosmosMsg, Empty, StdResult};
+use dao_dao_macros::proposal_module_query;
+use dao_voting::{pre_propose::PreProposeInfo, voting::Vote};
+use schemars::JsonSchema;
+use secret_utils::Duration;
+use serde::{Deserialize, Serialize};
+
+use crate::state::House;
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub struct InstantiateMsg {
+    /// The houses proposals must pass in. Between two and five houses
+    /// may be configured, each with a different voting module.
+    pub houses: Vec<House>,
+    /// The default maximum amount of time a proposal may be voted on
+    /// before expiring.
+    pub max_voting_period: Duration,
+    /// The minimum amount of time a proposal must be open before
+    /// passing.
+    pub min_voting_period: Option<Duration>,
+    /// Information about what addresses may create proposals.
+    pub pre_propose_info: PreProposeInfo,
+    /// If set to true proposals will be closed if their execution
+    /// fails. Otherwise, proposals will remain open after execution
+    /// failure.
+    pub close_proposal_on_execution_failure: bool,
+
+    pub dao_code_hash: String,
+}
+
+/// The message used to create a proposal. This matches the fields
+/// used by dao-pre-propose-single, so that module may be used to
+/// take deposits for this one.
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub struct ProposeMsg {
+    /// The title of the proposal.
+    pub title: String,
+    /// A description of the proposal.
+    pub description: String,
+    /// The messages that should be executed in response to this
+    /// proposal passing.
+    pub msgs: Vec<CosmosMsg<Empty>>,
+    /// The address creating the proposal. If no pre-propose
+    /// module is attached to this module this must always be None
+    /// as the proposer is the sender of the propose message. If a
+    /// pre-propose module is attached, this must be Some and will
+    /// set the proposer of the proposal it creates.
+    pub proposer: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Creates a proposal in the module.
+    Propose(ProposeMsg),
+    /// Votes on a proposal in one of its houses. Voting power is
+    /// determined by that house's voting module. Members with power
+    /// in several houses vote in each separately.
+    Vote {
+        /// The viewing key of the sender, as checked by the house's
+        /// voting module.
+        key: String,
+        /// The ID of the proposal to vote on.
+        proposal_id: u64,
+        /// The index of the house to vote in.
+        house: u32,
+        /// The senders position on the proposal.
+        vote: Vote,
+    },
+    /// Causes the messages associated with a passed proposal to be
+    /// executed by the DAO.
+    Execute { proposal_id: u64 },
+    /// Closes a proposal that has failed (either not passed or timed
+    /// out).
+    Close { proposal_id: u64 },
+    /// Updates the module's configuration. Only the DAO may call
+    /// this method. Houses and voting periods apply to proposals
+    /// created after the update.
+    UpdateConfig {
+        houses: Vec<House>,
+        max_voting_period: Duration,
+        min_voting_period: Option<Duration>,
+        close_proposal_on_execution_failure: bool,
+    },
+    /// Update's the proposal creation policy used for this
+    /// module. Only the DAO may call this method.
+    UpdatePreProposeInfo { info: PreProposeInfo },
+    /// Adds an address as a consumer of proposal hooks. Consumers of
+    /// proposal hooks have hook messages executed on them whenever
+    /// the status of a proposal changes or a proposal is created. If
+    /// a consumer contract errors when handling a hook message it
+    /// will be removed from the list of consumers.
+    AddProposalHook { address: String, code_hash: String },
+    /// Removes a consumer of proposal hooks.
+    RemoveProposalHook { address: String, code_hash: String },
+    /// Adds an address as a consumer of vote hooks. Consumers of vote
+    /// hooks have hook messages executed on them whenever a vote
+    /// is cast in any house. If a consumer contract errors when
+    /// handling a hook message it will be removed from the list of
+    /// consumers.
+    AddVoteHook { address: String, code_hash: String },
+    /// Removed a consumer of vote hooks.
+    RemoveVoteHook { address: String, code_hash: String },
+    CreateViewingKey {
+        entropy: String,
+        padding: Option<String>,
+    },
+    SetViewingKey {
+        key: String,
+        padding: Option<String>,
+    },
+}
+
+#[proposal_module_query]
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    /// Gets the proposal module's config.
+    #[returns(crate::state::Config)]
+    Config {},
+    /// Gets information about a proposal.
+    #[returns(crate::query::ProposalResponse)]
+    Proposal { proposal_id: u64 },
+    /// Lists all the proposals that have been created in this
+    /// module.
+    #[returns(crate::query::ProposalListResponse)]
+    ListProposals {
+        /// The proposal ID to start listing proposals after. For
+        /// example, if this is set to 2 proposals with IDs 3 and
+        /// higher will be returned.
+        start_after: Option<u64>,
+        /// The maximum number of proposals to return as part of this
+        /// query. If no limit is set a max of 30 proposals will be
+        /// returned.
+        limit: Option<u64>,
+    },
+    /// Returns a voter's position on a proposal in one of its
+    /// houses. Requires the voter's viewing key for this module.
+    #[returns(crate::query::VoteResponse)]
+    GetVote {
+        proposal_id: u64,
+        house: u32,
+        voter: String,
+        key: String,
+    },
+    /// Returns the number of proposals that have been created in this module.
+    #[returns(::std::primitive::u64)]
+    ProposalCount {},
+    /// Gets the current proposal creation policy for this module.
+    #[returns(::dao_voting::pre_propose::ProposalCreationPolicy)]
+    ProposalCreationPolicy {},
+    /// Lists all of the consumers of proposal hooks for this module.
+    #[returns(::cw_hooks::HooksResponse)]
+    ProposalHooks {},
+    /// Lists all of the consumers of vote hooks for this module.
+    #[returns(::cw_hooks::HooksResponse)]
+    VoteHooks {},
+}
+
+impl QueryMsg {
+    pub fn get_validation_params(&self, api: &dyn Api) -> StdResult<(Vec<Addr>, String)> {
+        match self {
+            Self::GetVote { voter, key, .. } => {
+                let address = api.addr_validate(voter.as_str())?;
+                Ok((vec![address], key.clone()))
+            }
+            _ => panic!("This query type does not require authentication"),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct CreateViewingKey {
+    pub key: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct ViewingKeyError {
+    pub msg: String,
+}
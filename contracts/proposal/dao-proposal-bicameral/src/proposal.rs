@@ -0,0 +1,220 @@
+use cosmwasm_std::{Addr, BlockInfo, CosmosMsg, Empty, StdResult, Storage, Uint128};
+use dao_interface::state::AnyContractInfo;
+use dao_voting::status::Status;
+use dao_voting::threshold::Threshold;
+use dao_voting::voting::{is_threshold_passed, is_threshold_rejected, Votes};
+use schemars::JsonSchema;
+use secret_utils::Expiration;
+use serde::{Deserialize, Serialize};
+
+use crate::query::ProposalResponse;
+use crate::state::PROPOSAL_COUNT;
+
+/// A proposal's votes in one of its houses.
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub struct HouseTally {
+    /// The voting module voting power in this house is queried from.
+    pub voting_module: AnyContractInfo,
+    /// The threshold the proposal must reach in this house.
+    pub threshold: Threshold,
+    /// The total voting power in this house at the time of the
+    /// proposal's creation.
+    pub total_power: Uint128,
+    /// Votes cast in this house.
+    pub votes: Votes,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub struct BicameralProposal {
+    /// The title of the proposal
+    pub title: String,
+    /// The main body of the proposal text
+    pub description: String,
+    /// The address that created this proposal.
+    pub proposer: Addr,
+    /// The block height at which this proposal was created. Voting
+    /// power queries should query for voting power at this block
+    /// height.
+    pub start_height: u64,
+    /// The minimum amount of time this proposal must remain open for
+    /// voting. The proposal may not pass unless this is expired or
+    /// None.
+    pub min_voting_period: Option<Expiration>,
+    /// The the time at which this proposal will expire and close for
+    /// additional votes.
+    pub expiration: Expiration,
+    /// The proposal's votes in each of its houses. The proposal
+    /// passes once it has passed in every house.
+    pub houses: Vec<HouseTally>,
+    /// The messages that will be executed should this proposal pass.
+    pub msgs: Vec<CosmosMsg<Empty>>,
+    /// The proposal status
+    pub status: Status,
+}
+
+pub fn next_proposal_id(store: &dyn Storage) -> StdResult<u64> {
+    Ok(PROPOSAL_COUNT.may_load(store)?.unwrap_or_default() + 1)
+}
+
+pub fn advance_proposal_id(store: &mut dyn Storage) -> StdResult<u64> {
+    let id: u64 = next_proposal_id(store)?;
+    PROPOSAL_COUNT.save(store, &id)?;
+    Ok(id)
+}
+
+impl BicameralProposal {
+    /// Consumes the proposal and returns a version which may be used
+    /// in a query response. Statuses are only updated on vote,
+    /// execute, and close events, so the stored status of an expired
+    /// proposal may be out of date.
+    pub fn into_response(mut self, block: &BlockInfo, id: u64) -> ProposalResponse {
+        self.update_status(block);
+        ProposalResponse { id, proposal: self }
+    }
+
+    /// Gets the current status of the proposal.
+    pub fn current_status(&self, block: &BlockInfo) -> Status {
+        match self.status {
+            Status::Open if self.is_passed(block) => Status::Passed,
+            Status::Open if self.expiration.is_expired(block) || self.is_rejected(block) => {
+                Status::Rejected
+            }
+            status => status,
+        }
+    }
+
+    /// Sets a proposals status to its current status.
+    pub fn update_status(&mut self, block: &BlockInfo) {
+        self.status = self.current_status(block);
+    }
+
+    /// Returns true iff this proposal is sure to pass in every house.
+    pub fn is_passed(&self, block: &BlockInfo) -> bool {
+        if let Some(min) = self.min_voting_period {
+            if !min.is_expired(block) {
+                return false;
+            }
+        }
+        let expired = self.expiration.is_expired(block);
+        self.houses.iter().all(|house| house.is_passed(expired))
+    }
+
+    /// Returns true iff this proposal can no longer pass in at least
+    /// one of its houses.
+    pub fn is_rejected(&self, block: &BlockInfo) -> bool {
+        let expired = self.expiration.is_expired(block);
+        self.houses.iter().any(|house| house.is_rejected(expired))
+    }
+}
+
+impl HouseTally {
+    /// Returns true iff the proposal is sure to pass in this house.
+    pub fn is_passed(&self, expired: bool) -> bool {
+        is_threshold_passed(&self.threshold, &self.votes, self.total_power, expired)
+    }
+
+    /// Returns true iff the proposal can no longer pass in this
+    /// house.
+    pub fn is_rejected(&self, expired: bool) -> bool {
+        is_threshold_rejected(&self.threshold, &self.votes, self.total_power, expired)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::mock_env;
+    use cosmwasm_std::Decimal;
+    use dao_voting::threshold::PercentageThreshold;
+    use dao_voting::voting::Vote;
+
+    fn house(threshold: Threshold, total_power: u128) -> HouseTally {
+        HouseTally {
+            voting_module: AnyContractInfo {
+                addr: Addr::unchecked("voting"),
+                code_hash: "code_hash".to_string(),
+            },
+            threshold,
+            total_power: Uint128::new(total_power),
+            votes: Votes::zero(),
+        }
+    }
+
+    fn proposal(houses: Vec<HouseTally>) -> BicameralProposal {
+        let env = mock_env();
+        BicameralProposal {
+            title: "title".to_string(),
+            description: "description".to_string(),
+            proposer: Addr::unchecked("proposer"),
+            start_height: env.block.height,
+            min_voting_period: None,
+            expiration: Expiration::AtHeight(env.block.height + 10),
+            houses,
+            msgs: vec![],
+            status: Status::Open,
+        }
+    }
+
+    fn majority() -> Threshold {
+        Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Majority {},
+        }
+    }
+
+    #[test]
+    fn test_passes_in_every_house() {
+        let env = mock_env();
+        let mut prop = proposal(vec![
+            house(majority(), 100),
+            house(
+                Threshold::AbsoluteCount {
+                    threshold: Uint128::new(2),
+                },
+                3,
+            ),
+        ]);
+
+        prop.houses[0].votes.add_vote(Vote::Yes, Uint128::new(51));
+        assert_eq!(prop.current_status(&env.block), Status::Open);
+
+        prop.houses[1].votes.add_vote(Vote::Yes, Uint128::new(1));
+        assert_eq!(prop.current_status(&env.block), Status::Open);
+        prop.houses[1].votes.add_vote(Vote::Yes, Uint128::new(1));
+        assert_eq!(prop.current_status(&env.block), Status::Passed);
+    }
+
+    #[test]
+    fn test_rejected_in_one_house() {
+        let env = mock_env();
+        let mut prop = proposal(vec![house(majority(), 100), house(majority(), 10)]);
+
+        prop.houses[0].votes.add_vote(Vote::Yes, Uint128::new(100));
+        prop.houses[1].votes.add_vote(Vote::No, Uint128::new(5));
+        assert_eq!(prop.current_status(&env.block), Status::Rejected);
+    }
+
+    #[test]
+    fn test_expires_without_passing() {
+        let mut env = mock_env();
+        let mut prop = proposal(vec![
+            house(majority(), 100),
+            house(
+                Threshold::ThresholdQuorum {
+                    threshold: PercentageThreshold::Majority {},
+                    quorum: PercentageThreshold::Percent(Decimal::percent(20)),
+                },
+                100,
+            ),
+        ]);
+
+        prop.houses[0].votes.add_vote(Vote::Yes, Uint128::new(60));
+        prop.houses[1].votes.add_vote(Vote::Yes, Uint128::new(10));
+        assert_eq!(prop.current_status(&env.block), Status::Open);
+
+        // The second house never reached quorum.
+        env.block.height += 10;
+        assert_eq!(prop.current_status(&env.block), Status::Rejected);
+    }
+}
@@ -0,0 +1,28 @@
+use crate::proposal::BicameralProposal;
+use crate::state::Ballot;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Information about a proposal returned by proposal queries.
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub struct ProposalResponse {
+    /// The ID of the proposal being returned.
+    pub id: u64,
+    pub proposal: BicameralProposal,
+}
+
+/// A list of proposals returned by `ListProposals`.
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub struct ProposalListResponse {
+    pub proposals: Vec<ProposalResponse>,
+}
+
+/// A voter's ballot in one house of a proposal.
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub struct VoteResponse {
+    /// None if no such vote, Some otherwise.
+    pub vote: Option<Ballot>,
+}
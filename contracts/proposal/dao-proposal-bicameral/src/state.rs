@@ -0,0 +1,70 @@
+use cosmwasm_std::{Addr, Uint128};
+use cw_hooks::Hooks;
+use dao_interface::state::AnyContractInfo;
+use dao_voting::{pre_propose::ProposalCreationPolicy, threshold::Threshold, voting::Vote};
+use schemars::JsonSchema;
+use secret_cw_controllers::ReplyIds;
+use secret_storage_plus::Item;
+use secret_toolkit::{serialization::Json, storage::Keymap};
+use secret_utils::Duration;
+use serde::{Deserialize, Serialize};
+
+use crate::proposal::BicameralProposal;
+
+/// The largest number of houses a proposal may be voted on by.
+pub const MAX_HOUSES: usize = 5;
+
+/// A voting body, made up of the holders of voting power in a voting
+/// module, and the threshold proposals must reach in it.
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub struct House {
+    /// The voting module voting power in this house is queried from.
+    pub voting_module: AnyContractInfo,
+    /// The threshold a proposal must reach in this house.
+    pub threshold: Threshold,
+}
+
+/// The bicameral proposal module's configuration.
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub struct Config {
+    /// The houses a proposal must pass in.
+    pub houses: Vec<House>,
+    /// The default maximum amount of time a proposal may be voted on
+    /// before expiring.
+    pub max_voting_period: Duration,
+    /// The minimum amount of time a proposal must be open before
+    /// passing.
+    pub min_voting_period: Option<Duration>,
+    /// If set to true proposals will be closed if their execution
+    /// fails. Otherwise, proposals will remain open after execution
+    /// failure.
+    pub close_proposal_on_execution_failure: bool,
+}
+
+/// A vote cast in one house of a proposal.
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub struct Ballot {
+    /// The amount of voting power behind the vote.
+    pub power: Uint128,
+    /// The position.
+    pub vote: Vote,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+/// The number of proposals that have been created.
+pub const PROPOSAL_COUNT: Item<u64> = Item::new("proposal_count");
+pub static PROPOSALS: Keymap<u64, BicameralProposal, Json> = Keymap::new(b"proposals");
+/// Ballots keyed by proposal ID, house index, and voter.
+pub static BALLOTS: Keymap<(u64, u32, Addr), Ballot, Json> = Keymap::new(b"ballots");
+/// Consumers of proposal state change hooks.
+pub const PROPOSAL_HOOKS: Hooks = Hooks::new("proposal_hooks");
+/// Consumers of vote hooks.
+pub const VOTE_HOOKS: Hooks = Hooks::new("vote_hooks");
+/// The address of the pre-propose module associated with this
+/// proposal module (if any).
+pub const CREATION_POLICY: Item<ProposalCreationPolicy> = Item::new("creation_policy");
+pub const DAO: Item<AnyContractInfo> = Item::new("dao");
+pub const REPLY_IDS: ReplyIds = ReplyIds::new(b"reply_ids", b"reply_ids_count");
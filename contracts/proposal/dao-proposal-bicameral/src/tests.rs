@@ -0,0 +1,397 @@
+use cosmwasm_std::testing::{
+    mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+};
+use cosmwasm_std::{
+    coins, from_binary, to_binary, Addr, BankMsg, ContractResult, CosmosMsg, Env, OwnedDeps,
+    Response, SystemResult, Uint128, WasmMsg, WasmQuery,
+};
+use dao_hooks::proposal::{PreProposeHookMsg, ProposalHookExecuteMsg, ProposalHookMsg};
+use dao_hooks::vote::{VoteHookExecuteMsg, VoteHookMsg};
+use dao_interface::state::AnyContractInfo;
+use dao_interface::voting::{
+    Query as VotingQuery, TotalPowerAtHeightResponse, VotingPowerAtHeightResponse,
+};
+use dao_voting::pre_propose::{PreProposeInfo, ProposalCreationPolicy};
+use dao_voting::status::Status;
+use dao_voting::threshold::{PercentageThreshold, Threshold};
+use dao_voting::voting::Vote;
+use secret_utils::Duration;
+
+use crate::contract::{execute, instantiate, query};
+use crate::msg::{ExecuteMsg, InstantiateMsg, ProposeMsg, QueryMsg};
+use crate::proposal::BicameralProposal;
+use crate::query::{ProposalResponse, VoteResponse};
+use crate::state::{Ballot, House, CREATION_POLICY};
+use crate::ContractError;
+
+type Deps = OwnedDeps<MockStorage, MockApi, MockQuerier>;
+
+/// The height proposals are created at.
+const START: u64 = 12_345;
+
+/// Answers the houses' voting power queries. In the "stakers" house,
+/// Alice has 60 of the 100 voting power and Bob 40. In the "council"
+/// house, Alice, Carol, and Dave each have one of three. Queries made
+/// with a key other than "key" fail.
+fn mock_houses(deps: &mut Deps) {
+    deps.querier.update_wasm(|query| {
+        let WasmQuery::Smart {
+            contract_addr, msg, ..
+        } = query
+        else {
+            panic!("unexpected query");
+        };
+        let res = match from_binary(msg).unwrap() {
+            VotingQuery::VotingPowerAtHeight {
+                address,
+                key,
+                height,
+            } => {
+                if key != "key" {
+                    return SystemResult::Ok(ContractResult::Err("wrong viewing key".to_string()));
+                }
+                let power = match (contract_addr.as_str(), address.as_str()) {
+                    ("stakers", "alice") => 60,
+                    ("stakers", "bob") => 40,
+                    ("council", "alice" | "carol" | "dave") => 1,
+                    _ => 0,
+                };
+                to_binary(&VotingPowerAtHeightResponse {
+                    power: Uint128::new(power),
+                    height: height.unwrap_or(START),
+                })
+            }
+            VotingQuery::TotalPowerAtHeight { height } => {
+                let power = match contract_addr.as_str() {
+                    "stakers" => 100,
+                    "council" => 3,
+                    _ => panic!("unexpected query"),
+                };
+                to_binary(&TotalPowerAtHeightResponse {
+                    power: Uint128::new(power),
+                    height: height.unwrap_or(START),
+                })
+            }
+            _ => panic!("unexpected query"),
+        };
+        SystemResult::Ok(ContractResult::Ok(res.unwrap()))
+    });
+}
+
+fn house(addr: &str, threshold: Threshold) -> House {
+    House {
+        voting_module: AnyContractInfo {
+            addr: Addr::unchecked(addr),
+            code_hash: format!("{addr}_hash"),
+        },
+        threshold,
+    }
+}
+
+/// Sets up a module whose proposals must pass by a majority in the
+/// "stakers" house, house 0, and be approved by two members of the
+/// "council" house, house 1, within 10 blocks. Proposals are created
+/// by the "pre_propose" module, and "proposal_hook" and "vote_hook"
+/// receive hooks. Alice has set her viewing key to "vk".
+fn setup() -> Deps {
+    let mut deps = mock_dependencies();
+    mock_houses(&mut deps);
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("dao", &[]),
+        InstantiateMsg {
+            houses: vec![
+                house(
+                    "stakers",
+                    Threshold::AbsolutePercentage {
+                        percentage: PercentageThreshold::Majority {},
+                    },
+                ),
+                house(
+                    "council",
+                    Threshold::AbsoluteCount {
+                        threshold: Uint128::new(2),
+                    },
+                ),
+            ],
+            max_voting_period: Duration::Height(10),
+            min_voting_period: None,
+            pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
+            close_proposal_on_execution_failure: false,
+            dao_code_hash: "dao_hash".to_string(),
+        },
+    )
+    .unwrap();
+    CREATION_POLICY
+        .save(
+            deps.as_mut().storage,
+            &ProposalCreationPolicy::Module {
+                addr: Addr::unchecked("pre_propose"),
+                code_hash: "pre_propose_hash".to_string(),
+            },
+        )
+        .unwrap();
+    for msg in [
+        ExecuteMsg::AddProposalHook {
+            address: "proposal_hook".to_string(),
+            code_hash: "hook_hash".to_string(),
+        },
+        ExecuteMsg::AddVoteHook {
+            address: "vote_hook".to_string(),
+            code_hash: "hook_hash".to_string(),
+        },
+    ] {
+        execute(deps.as_mut(), mock_env(), mock_info("dao", &[]), msg).unwrap();
+    }
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("alice", &[]),
+        ExecuteMsg::SetViewingKey {
+            key: "vk".to_string(),
+            padding: None,
+        },
+    )
+    .unwrap();
+
+    execute(
+        deps.as_mut(),
+        env_at(START),
+        mock_info("pre_propose", &[]),
+        ExecuteMsg::Propose(ProposeMsg {
+            title: "title".to_string(),
+            description: "description".to_string(),
+            msgs: proposal_msgs(),
+            proposer: Some("alice".to_string()),
+        }),
+    )
+    .unwrap();
+    deps
+}
+
+fn env_at(height: u64) -> Env {
+    let mut env = mock_env();
+    env.block.height = height;
+    env
+}
+
+fn proposal_msgs() -> Vec<CosmosMsg> {
+    vec![CosmosMsg::Bank(BankMsg::Send {
+        to_address: "alice".to_string(),
+        amount: coins(1, "ujuno"),
+    })]
+}
+
+fn vote(deps: &mut Deps, voter: &str, house: u32, vote: Vote) -> Result<Response, ContractError> {
+    execute(
+        deps.as_mut(),
+        env_at(START + 1),
+        mock_info(voter, &[]),
+        ExecuteMsg::Vote {
+            key: "key".to_string(),
+            proposal_id: 1,
+            house,
+            vote,
+        },
+    )
+}
+
+fn proposal(deps: &Deps, height: u64) -> BicameralProposal {
+    let ProposalResponse { proposal, .. } = from_binary(
+        &query(
+            deps.as_ref(),
+            env_at(height),
+            QueryMsg::Proposal { proposal_id: 1 },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    proposal
+}
+
+/// Alice's ballot in HOUSE.
+fn ballot(deps: &Deps, house: u32) -> Option<Ballot> {
+    let VoteResponse { vote } = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::GetVote {
+                proposal_id: 1,
+                house,
+                voter: "alice".to_string(),
+                key: "vk".to_string(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    vote
+}
+
+/// Returns the hook messages sent to HOOK in RES.
+fn hook_msgs<T: serde::de::DeserializeOwned>(res: &Response, hook: &str) -> Vec<T> {
+    res.messages
+        .iter()
+        .filter_map(|sub| match &sub.msg {
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr, msg, ..
+            }) if contract_addr == hook => Some(from_binary(msg).unwrap()),
+            _ => None,
+        })
+        .collect()
+}
+
+fn status_changed(old_status: Status, new_status: Status) -> ProposalHookExecuteMsg {
+    ProposalHookExecuteMsg::ProposalHook(ProposalHookMsg::ProposalStatusChanged {
+        id: 1,
+        old_status: old_status.to_string(),
+        new_status: new_status.to_string(),
+    })
+}
+
+#[test]
+fn test_vote_in_each_house() {
+    let mut deps = setup();
+
+    // Alice takes a different position in each house, each with her
+    // power in that house.
+    let res = vote(&mut deps, "alice", 0, Vote::Yes).unwrap();
+    assert_eq!(
+        hook_msgs::<VoteHookExecuteMsg>(&res, "vote_hook"),
+        vec![VoteHookExecuteMsg::VoteHook(VoteHookMsg::NewVote {
+            proposal_id: 1,
+            voter: "alice".to_string(),
+            vote: Vote::Yes.to_string(),
+        })]
+    );
+    vote(&mut deps, "alice", 1, Vote::No).unwrap();
+
+    assert_eq!(
+        ballot(&deps, 0),
+        Some(Ballot {
+            power: Uint128::new(60),
+            vote: Vote::Yes,
+        })
+    );
+    assert_eq!(
+        ballot(&deps, 1),
+        Some(Ballot {
+            power: Uint128::new(1),
+            vote: Vote::No,
+        })
+    );
+    let prop = proposal(&deps, START + 1);
+    assert_eq!(prop.houses[0].votes.yes, Uint128::new(60));
+    assert_eq!(prop.houses[0].votes.no, Uint128::zero());
+    assert_eq!(prop.houses[1].votes.yes, Uint128::zero());
+    assert_eq!(prop.houses[1].votes.no, Uint128::new(1));
+
+    // A ballot in one house does not prevent voting in another, but
+    // may not be changed.
+    let err = vote(&mut deps, "alice", 0, Vote::No).unwrap_err();
+    assert_eq!(err, ContractError::AlreadyVoted { house: 0 });
+    let err = vote(&mut deps, "alice", 1, Vote::Yes).unwrap_err();
+    assert_eq!(err, ContractError::AlreadyVoted { house: 1 });
+}
+
+#[test]
+fn test_vote_errors() {
+    let mut deps = setup();
+
+    let err = vote(&mut deps, "alice", 2, Vote::Yes).unwrap_err();
+    assert_eq!(err, ContractError::NoSuchHouse { house: 2 });
+
+    // Carol sits on the council but holds no stake, and Bob the
+    // reverse.
+    let err = vote(&mut deps, "carol", 0, Vote::Yes).unwrap_err();
+    assert_eq!(err, ContractError::NotRegistered { house: 0 });
+    let err = vote(&mut deps, "bob", 1, Vote::Yes).unwrap_err();
+    assert_eq!(err, ContractError::NotRegistered { house: 1 });
+    vote(&mut deps, "carol", 1, Vote::Yes).unwrap();
+    vote(&mut deps, "bob", 0, Vote::Yes).unwrap();
+
+    assert_eq!(ballot(&deps, 0), None);
+}
+
+#[test]
+fn test_execute_once_every_house_passes() {
+    let mut deps = setup();
+    let execute_proposal = |deps: &mut Deps| {
+        execute(
+            deps.as_mut(),
+            env_at(START + 1),
+            mock_info("anyone", &[]),
+            ExecuteMsg::Execute { proposal_id: 1 },
+        )
+    };
+
+    // The proposal has passed among the stakers, but not the
+    // council.
+    vote(&mut deps, "alice", 0, Vote::Yes).unwrap();
+    assert!(proposal(&deps, START + 1).houses[0].is_passed(false));
+    vote(&mut deps, "carol", 1, Vote::Yes).unwrap();
+    assert_eq!(proposal(&deps, START + 1).status, Status::Open);
+    let err = execute_proposal(&mut deps).unwrap_err();
+    assert_eq!(err, ContractError::NotPassed {});
+
+    let res = vote(&mut deps, "dave", 1, Vote::Yes).unwrap();
+    assert_eq!(
+        hook_msgs::<ProposalHookExecuteMsg>(&res, "proposal_hook"),
+        vec![status_changed(Status::Open, Status::Passed)]
+    );
+    assert_eq!(proposal(&deps, START + 1).status, Status::Passed);
+
+    let res = execute_proposal(&mut deps).unwrap();
+    assert_eq!(
+        hook_msgs::<dao_interface::msg::ExecuteMsg>(&res, "dao"),
+        vec![dao_interface::msg::ExecuteMsg::ExecuteProposalHook {
+            msgs: proposal_msgs()
+        }]
+    );
+    assert_eq!(
+        hook_msgs::<ProposalHookExecuteMsg>(&res, "proposal_hook"),
+        vec![status_changed(Status::Passed, Status::Executed)]
+    );
+    assert_eq!(
+        hook_msgs::<PreProposeHookMsg>(&res, "pre_propose"),
+        vec![PreProposeHookMsg::ProposalCompletedHook {
+            proposal_id: 1,
+            new_status: Status::Executed,
+            quorum_met: None,
+        }]
+    );
+    assert_eq!(proposal(&deps, START + 1).status, Status::Executed);
+}
+
+#[test]
+fn test_rejected_in_one_house() {
+    let mut deps = setup();
+    vote(&mut deps, "alice", 0, Vote::Yes).unwrap();
+    vote(&mut deps, "alice", 1, Vote::No).unwrap();
+
+    // With two of the council against it, the proposal can no longer
+    // pass in that house.
+    let res = vote(&mut deps, "carol", 1, Vote::No).unwrap();
+    assert_eq!(
+        hook_msgs::<ProposalHookExecuteMsg>(&res, "proposal_hook"),
+        vec![status_changed(Status::Open, Status::Rejected)]
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        env_at(START + 1),
+        mock_info("anyone", &[]),
+        ExecuteMsg::Close { proposal_id: 1 },
+    )
+    .unwrap();
+    assert_eq!(
+        hook_msgs::<PreProposeHookMsg>(&res, "pre_propose"),
+        vec![PreProposeHookMsg::ProposalCompletedHook {
+            proposal_id: 1,
+            new_status: Status::Closed,
+            quorum_met: None,
+        }]
+    );
+    assert_eq!(proposal(&deps, START + 1).status, Status::Closed);
+}
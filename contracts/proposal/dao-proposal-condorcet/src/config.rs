@@ -1,11 +1,13 @@
 use cosmwasm_schema::cw_serde;
+use cosmwasm_std::Deps;
 use dao_voting::{
     threshold::{validate_quorum, PercentageThreshold},
+    veto::VetoConfig,
     voting::validate_voting_period,
 };
 use secret_utils::Duration;
 
-use crate::ContractError;
+use crate::{resolution::CycleResolution, ContractError};
 
 #[cw_serde]
 pub struct UncheckedConfig {
@@ -13,7 +15,14 @@ pub struct UncheckedConfig {
     pub voting_period: Duration,
     pub min_voting_period: Option<Duration>,
     pub close_proposals_on_execution_failure: bool,
-    pub dao_code_hash: String,
+    /// Optional veto configuration. If set, passed proposals are
+    /// timelocked after voting closes, during which the vetoer may
+    /// veto them.
+    pub veto: Option<VetoConfig>,
+    /// How a winner is chosen when voting closes without a Condorcet
+    /// winner. Defaults to rejecting the proposal.
+    #[serde(default)]
+    pub cycle_resolution: CycleResolution,
 }
 
 #[cw_serde]
@@ -22,18 +31,25 @@ pub(crate) struct Config {
     pub voting_period: Duration,
     pub min_voting_period: Option<Duration>,
     pub close_proposals_on_execution_failure: bool,
+    pub veto: Option<VetoConfig>,
+    pub cycle_resolution: CycleResolution,
 }
 
 impl UncheckedConfig {
-    pub(crate) fn into_checked(self) -> Result<Config, ContractError> {
+    pub(crate) fn into_checked(self, deps: Deps) -> Result<Config, ContractError> {
         validate_quorum(&self.quorum)?;
         let (min_voting_period, voting_period) =
             validate_voting_period(self.min_voting_period, self.voting_period)?;
+        if let Some(ref veto) = self.veto {
+            veto.validate(&deps, &voting_period)?;
+        }
         Ok(Config {
             quorum: self.quorum,
             close_proposals_on_execution_failure: self.close_proposals_on_execution_failure,
             voting_period,
             min_voting_period,
+            veto: self.veto,
+            cycle_resolution: self.cycle_resolution,
         })
     }
 }
@@ -1,26 +1,47 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Reply, Response, StdError, StdResult,
-    SubMsgResult,
+    to_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Reply, Response, StdError, StdResult,
+    Storage, SubMsg, SubMsgResult,
 };
 
+use cw_hooks::{HookItem, Hooks};
+use dao_hooks::proposal::{
+    new_proposal_hooks, proposal_closed_hooks, proposal_completed_hooks,
+    proposal_status_changed_hooks,
+};
+use dao_hooks::vote::new_vote_hooks;
 use dao_interface::state::AnyContractInfo;
+use dao_voting::pre_propose::{PreProposeInfo, ProposalCreationPolicy};
+use dao_voting::proposal::DEFAULT_LIMIT;
+use dao_voting::veto::{withdraw_veto, VetoError};
 use dao_voting::voting::{get_total_power, get_voting_power};
 use secret_cw2::set_contract_version;
 use secret_cw_controllers::ReplyEvent;
+use secret_toolkit::permit::{Permit, RevokedPermits, TokenPermissions};
+use secret_toolkit::viewing_key::{ViewingKey, ViewingKeyStore};
+use secret_utils::parse_reply_event_for_contract_address;
 
 use crate::config::UncheckedConfig;
 use crate::error::ContractError;
-use crate::msg::{Choice, ExecuteMsg, InstantiateMsg, QueryMsg};
-use crate::proposal::{Proposal, ProposalResponse, Status};
-use crate::state::{next_proposal_id, CONFIG, DAO, PROPOSAL, REPLY_IDS, TALLY, VOTE};
+use crate::msg::{
+    Choice, CreateViewingKey, ExecuteMsg, InstantiateMsg, QueryMsg, QueryWithPermit,
+    ViewingKeyError,
+};
+use crate::proposal::{Proposal, ProposalListResponse, ProposalResponse, Status};
+use crate::resolution::{CycleResolution, MAX_RESOLVABLE_CANDIDATES};
+use crate::state::{
+    next_proposal_id, proposal_count, Ballot, BALLOT, CONFIG, CREATION_POLICY, DAO, PROPOSAL,
+    PROPOSAL_HOOKS, REPLY_IDS, TALLY, VOTE_HOOKS,
+};
 use crate::tally::Tally;
-use crate::vote::Vote;
+use crate::vote::{Vote, VoteInfo, VoteResponse};
 
 pub(crate) const CONTRACT_NAME: &str = "crates.io:dao-proposal-condorcet";
 pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+pub const PREFIX_REVOKED_PERMITS: &str = "revoked_permits";
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
@@ -37,9 +58,15 @@ pub fn instantiate(
             code_hash: msg.dao_code_hash.clone(),
         },
     )?;
-    CONFIG.save(deps.storage, &msg.into_checked()?)?;
+    CONFIG.save(deps.storage, &msg.config().into_checked(deps.as_ref())?)?;
+
+    let (initial_policy, pre_propose_messages) = msg
+        .pre_propose_info
+        .into_initial_policy_and_messages(deps.storage, info.sender.clone(), REPLY_IDS)?;
+    CREATION_POLICY.save(deps.storage, &initial_policy)?;
 
     Ok(Response::default()
+        .add_submessages(pre_propose_messages)
         .add_attribute("method", "instantiate")
         .add_attribute("creator", info.sender))
 }
@@ -66,18 +93,25 @@ pub fn instantiate(
 //
 // this is true so long as a vote is smaller than a proposal in
 // storage which is true because proposals store `choices =
-// Vec<Vec<CosmosMsg>>`, `choices.len() = vote.len()`, vote is a
+// Vec<Vec<CosmosMsg>>`, `choices.len() >= vote.len()`, vote is a
 // `Vec<u32>`, even an empty vec must contain it's length which is a
 // usize, so `sizeof(Vec<u32>) <= sizeof(Vec<usize>) <=
 // sizeof(Vec<Vec<CosmosMsg>) => sizeof(vote) <= sizeof(proposal)`.
+// a vote's rationale is chosen by the voter, so a long rationale
+// only makes that voter's vote more expensive.
 //
 // in terms of other costs:
 //
 // propose: query_voting_power + compute_winner [2]
-// execute: query_voting_power
+// execute: query_voting_power + resolve_cycle [3]
 // vote:    query_voting_power + compute_winner
 //
-// so we're good there as well.
+// so we're good there as well. proposal and vote hooks cost the same
+// in each case.
+//
+// for the same reason, voting does not load the proposal and so does
+// not update its status. proposal status change hooks are fired when
+// a proposal is executed, closed, or vetoed.
 //
 // [1] we need to be gas efficent in this way because the size of the
 //     Tally type grows with candidates^2 and thus can be too large to
@@ -87,6 +121,10 @@ pub fn instantiate(
 //     be voted on or executed.
 // [2] Tally::new computes the winner over the new matrix so that this
 //     is the case.
+// [3] resolving a cycle costs more than computing the winner, so if
+//     a cycle resolution method is configured proposals may have at
+//     most `MAX_RESOLVABLE_CANDIDATES` candidates. this bounds the
+//     cost of resolution to well within gas limits.
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
@@ -96,18 +134,61 @@ pub fn execute(
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
     match msg {
-        ExecuteMsg::Propose { choices, key } => execute_propose(deps, env, info, choices, key),
+        ExecuteMsg::Propose {
+            choices,
+            key,
+            proposer,
+        } => execute_propose(deps, env, info, choices, key, proposer),
         ExecuteMsg::Vote {
             proposal_id,
             vote,
             key,
-        } => execute_vote(deps, env, info, proposal_id, vote, key),
+            rationale,
+        } => execute_vote(deps, env, info, proposal_id, vote, key, rationale),
+        ExecuteMsg::UpdateRationale {
+            proposal_id,
+            rationale,
+        } => execute_update_rationale(deps, info, proposal_id, rationale),
         ExecuteMsg::Execute { proposal_id, key } => {
             execute_execute(deps, env, info, proposal_id, key)
         }
         ExecuteMsg::Close { proposal_id } => execute_close(deps, env, info, proposal_id),
+        ExecuteMsg::Veto { proposal_id } => execute_veto(deps, env, info, proposal_id),
+        ExecuteMsg::WithdrawVeto { proposal_id } => {
+            execute_withdraw_veto(deps, env, info, proposal_id)
+        }
 
         ExecuteMsg::SetConfig(config) => execute_set_config(deps, info, config),
+        ExecuteMsg::UpdatePreProposeInfo { info: new_info } => {
+            execute_update_proposal_creation_policy(deps, info, new_info)
+        }
+        ExecuteMsg::AddProposalHook { address, code_hash } => execute_update_hook(
+            deps,
+            info,
+            PROPOSAL_HOOKS,
+            "proposal",
+            address,
+            code_hash,
+            true,
+        ),
+        ExecuteMsg::RemoveProposalHook { address, code_hash } => execute_update_hook(
+            deps,
+            info,
+            PROPOSAL_HOOKS,
+            "proposal",
+            address,
+            code_hash,
+            false,
+        ),
+        ExecuteMsg::AddVoteHook { address, code_hash } => {
+            execute_update_hook(deps, info, VOTE_HOOKS, "vote", address, code_hash, true)
+        }
+        ExecuteMsg::RemoveVoteHook { address, code_hash } => {
+            execute_update_hook(deps, info, VOTE_HOOKS, "vote", address, code_hash, false)
+        }
+        ExecuteMsg::CreateViewingKey { entropy, .. } => try_create_key(deps, env, info, entropy),
+        ExecuteMsg::SetViewingKey { key, .. } => try_set_key(deps, info, key),
+        ExecuteMsg::RevokePermit { permit_name, .. } => revoke_permit(deps, info, permit_name),
     }
 }
 
@@ -117,21 +198,40 @@ fn execute_propose(
     info: MessageInfo,
     choices: Vec<Choice>,
     key: String,
+    proposer: Option<String>,
 ) -> Result<Response, ContractError> {
     let dao = DAO.load(deps.storage)?;
     let config = CONFIG.load(deps.storage)?;
-    let sender_voting_power = get_voting_power(
-        deps.as_ref(),
-        dao.code_hash.clone(),
-        info.sender.clone(),
-        key,
-        &dao.addr.clone(),
-        None,
-    )?;
-    if sender_voting_power.is_zero() {
-        return Err(ContractError::ZeroVotingPower {});
+    let proposal_creation_policy = CREATION_POLICY.load(deps.storage)?;
+
+    if !proposal_creation_policy.is_permitted(&info.sender) {
+        return Err(ContractError::Unauthorized {});
     }
 
+    let proposer = match (proposer, &proposal_creation_policy) {
+        (None, ProposalCreationPolicy::Anyone {}) => {
+            let sender_voting_power = get_voting_power(
+                deps.as_ref(),
+                dao.code_hash.clone(),
+                info.sender.clone(),
+                key,
+                &dao.addr.clone(),
+                None,
+            )?;
+            if sender_voting_power.is_zero() {
+                return Err(ContractError::ZeroVotingPower {});
+            }
+            info.sender
+        }
+        // The pre-propose module checks that the proposer may create
+        // proposals, and `is_permitted` above checks that it is the
+        // sender.
+        (Some(proposer), ProposalCreationPolicy::Module { .. }) => {
+            deps.api.addr_validate(&proposer)?
+        }
+        _ => return Err(ContractError::InvalidProposer {}),
+    };
+
     let id = next_proposal_id(deps.storage)?;
     let total_power = get_total_power(deps.as_ref(), dao.code_hash.clone(), &dao.addr, None)?;
 
@@ -143,6 +243,14 @@ fn execute_propose(
     let mut choices = choices;
     choices.push(none_of_the_above);
 
+    if config.cycle_resolution != CycleResolution::None
+        && choices.len() as u32 > MAX_RESOLVABLE_CANDIDATES
+    {
+        return Err(ContractError::TooManyCandidates {
+            max: MAX_RESOLVABLE_CANDIDATES,
+        });
+    }
+
     let tally = Tally::new(
         choices.len() as u32,
         total_power,
@@ -151,11 +259,19 @@ fn execute_propose(
     );
     TALLY.insert(deps.storage, &id, &tally)?;
 
-    let mut proposal = Proposal::new(&env.block, &config, info.sender, id, choices, total_power);
-    proposal.update_status(&env.block, &tally);
+    let mut proposal = Proposal::new(&env.block, &config, proposer, id, choices, total_power);
+    proposal.update_status(&env.block, &tally)?;
     PROPOSAL.insert(deps.storage, &id, &proposal)?;
 
+    let hooks = new_proposal_hooks(
+        PROPOSAL_HOOKS,
+        deps.storage,
+        id as u64,
+        proposal.proposer.as_str(),
+    )?;
+
     Ok(Response::default()
+        .add_submessages(hooks)
         .add_attribute("method", "propose")
         .add_attribute("proposal_id", proposal.id.to_string())
         .add_attribute("proposer", proposal.proposer))
@@ -168,38 +284,85 @@ fn execute_vote(
     proposal_id: u32,
     vote: Vec<u32>,
     key: String,
+    rationale: Option<String>,
 ) -> Result<Response, ContractError> {
-    let tally = TALLY.get(deps.storage, &proposal_id);
+    let dao = DAO.load(deps.storage)?;
+    let mut tally = TALLY
+        .get(deps.storage, &proposal_id)
+        .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
     let sender_power = get_voting_power(
         deps.as_ref(),
-        DAO.load(deps.storage)?.code_hash.clone(),
+        dao.code_hash.clone(),
         info.sender.clone(),
         key,
-        &DAO.load(deps.storage)?.addr,
-        Some(tally.clone().unwrap().start_height),
+        &dao.addr,
+        Some(tally.start_height),
     )?;
     if sender_power.is_zero() {
         Err(ContractError::ZeroVotingPower {})
-    } else if VOTE.contains(deps.storage, &(proposal_id, info.sender.clone())) {
+    } else if BALLOT.contains(deps.storage, &(proposal_id, info.sender.clone())) {
         Err(ContractError::Voted {})
-    } else if tally.clone().unwrap().expired(&env.block) {
+    } else if tally.expired(&env.block) {
         Err(ContractError::Expired {})
     } else {
-        let vote = Vote::new(vote, tally.clone().unwrap().candidates())?;
-        VOTE.insert(deps.storage, &(proposal_id, info.sender.clone()), &vote)?;
+        let vote = Vote::new(vote, tally.candidates())?;
+        BALLOT.insert(
+            deps.storage,
+            &(proposal_id, info.sender.clone()),
+            &Ballot {
+                power: sender_power,
+                vote: vote.clone(),
+                rationale: rationale.clone(),
+            },
+        )?;
+
+        let position = vote.to_string();
+        tally.add_vote(vote, sender_power);
+        TALLY.insert(deps.storage, &proposal_id, &tally)?;
 
-        let tally = tally;
-        tally.clone().unwrap().add_vote(vote, sender_power);
-        TALLY.insert(deps.storage, &proposal_id, &tally.clone().unwrap())?;
+        let hooks = new_vote_hooks(
+            VOTE_HOOKS,
+            deps.storage,
+            proposal_id as u64,
+            info.sender.to_string(),
+            position,
+        )?;
 
         Ok(Response::default()
+            .add_submessages(hooks)
             .add_attribute("method", "vote")
             .add_attribute("proposal_id", proposal_id.to_string())
             .add_attribute("voter", info.sender)
-            .add_attribute("power", sender_power))
+            .add_attribute("power", sender_power)
+            .add_attribute("rationale", rationale.as_deref().unwrap_or("_none")))
     }
 }
 
+fn execute_update_rationale(
+    deps: DepsMut,
+    info: MessageInfo,
+    proposal_id: u32,
+    rationale: Option<String>,
+) -> Result<Response, ContractError> {
+    // info.sender can't be forged so we implicitly access control
+    // with the key.
+    let key = (proposal_id, info.sender.clone());
+    let mut ballot = BALLOT
+        .get(deps.storage, &key)
+        .ok_or_else(|| ContractError::NoSuchVote {
+            id: proposal_id,
+            voter: info.sender.to_string(),
+        })?;
+    ballot.rationale = rationale.clone();
+    BALLOT.insert(deps.storage, &key, &ballot)?;
+
+    Ok(Response::default()
+        .add_attribute("method", "update_rationale")
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("voter", info.sender)
+        .add_attribute("rationale", rationale.as_deref().unwrap_or("_none")))
+}
+
 fn execute_execute(
     deps: DepsMut,
     env: Env,
@@ -207,42 +370,78 @@ fn execute_execute(
     proposal_id: u32,
     key: String,
 ) -> Result<Response, ContractError> {
-    let tally = TALLY.get(deps.storage, &proposal_id);
     let dao = DAO.load(deps.storage)?;
-    let sender_power = get_voting_power(
-        deps.as_ref(),
-        dao.code_hash.clone(),
-        info.sender.clone(),
-        key,
-        &dao.addr.clone(),
-        Some(tally.clone().unwrap().start_height),
-    )?;
-    if sender_power.is_zero() {
-        return Err(ContractError::ZeroVotingPower {});
-    }
+    let tally = TALLY
+        .get(deps.storage, &proposal_id)
+        .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+    let mut proposal = PROPOSAL
+        .get(deps.storage, &proposal_id)
+        .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
 
-    let proposal = PROPOSAL.get(deps.storage, &proposal_id);
-    if let Status::Passed { winner } = proposal
-        .clone()
-        .unwrap()
-        .update_status(&env.block, &tally.clone().unwrap())
-    {
-        let msgs = proposal.clone().unwrap().set_executed(
-            deps.storage,
-            dao.addr,
-            dao.code_hash.clone(),
-            winner,
-        )?;
-        PROPOSAL.insert(deps.storage, &proposal_id, &proposal.clone().unwrap())?;
+    let old_status = proposal.update_status(&env.block, &tally)?;
+    let winner = match old_status {
+        Status::Passed { winner } => {
+            let sender_power = get_voting_power(
+                deps.as_ref(),
+                dao.code_hash.clone(),
+                info.sender.clone(),
+                key,
+                &dao.addr.clone(),
+                Some(tally.start_height),
+            )?;
+            if sender_power.is_zero() {
+                return Err(ContractError::ZeroVotingPower {});
+            }
+            winner
+        }
+        Status::VetoTimelock { winner, .. } => {
+            // During the timelock only vetoers may execute, and only
+            // if early execution is enabled and no veto has been
+            // cast.
+            let veto = proposal
+                .veto
+                .as_ref()
+                .ok_or(VetoError::NoVetoConfiguration {})?;
+            if !veto.is_vetoer(&info.sender) {
+                return Err(ContractError::Veto(VetoError::Timelocked {}));
+            }
+            veto.check_early_execute_enabled()?;
+            veto.check_no_vetoes_cast(&proposal.vetoes)?;
+            winner
+        }
+        _ => return Err(ContractError::Unexecutable {}),
+    };
 
-        Ok(Response::default()
-            .add_attribute("method", "execute")
-            .add_attribute("proposal_id", proposal_id.to_string())
-            .add_attribute("executor", info.sender)
-            .add_submessage(msgs))
+    let msgs = proposal.set_executed(deps.storage, dao.addr, dao.code_hash.clone(), winner)?;
+    PROPOSAL.insert(deps.storage, &proposal_id, &proposal)?;
+
+    // If the proposal is closed should its execution fail, whether
+    // it completed as executed is only known once execution replies,
+    // so the deposit module is told then.
+    let hooks = if proposal.close_on_execution_failure {
+        proposal_status_changed_hooks(
+            PROPOSAL_HOOKS,
+            deps.storage,
+            proposal_id as u64,
+            old_status.to_string(),
+            Status::Executed.to_string(),
+        )?
     } else {
-        Err(ContractError::Unexecutable {})
-    }
+        status_change_hooks(
+            deps.storage,
+            proposal_id,
+            old_status,
+            Status::Executed,
+            None,
+        )?
+    };
+
+    Ok(Response::default()
+        .add_attribute("method", "execute")
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("executor", info.sender)
+        .add_submessage(msgs)
+        .add_submessages(hooks))
 }
 
 fn execute_close(
@@ -251,17 +450,26 @@ fn execute_close(
     info: MessageInfo,
     proposal_id: u32,
 ) -> Result<Response, ContractError> {
-    let tally = TALLY.get(deps.storage, &proposal_id);
-    let proposal = PROPOSAL.get(deps.storage, &proposal_id);
-    if let Status::Rejected = proposal
-        .clone()
-        .unwrap()
-        .update_status(&env.block, &tally.unwrap())
-    {
-        proposal.clone().unwrap().set_closed();
-        PROPOSAL.insert(deps.storage, &proposal_id, &proposal.clone().unwrap())?;
+    let tally = TALLY
+        .get(deps.storage, &proposal_id)
+        .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+    let mut proposal = PROPOSAL
+        .get(deps.storage, &proposal_id)
+        .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+    if let Status::Rejected = proposal.update_status(&env.block, &tally)? {
+        proposal.set_closed();
+        PROPOSAL.insert(deps.storage, &proposal_id, &proposal)?;
+
+        let hooks = status_change_hooks(
+            deps.storage,
+            proposal_id,
+            Status::Rejected,
+            Status::Closed,
+            Some(proposal.quorum_met(&tally)),
+        )?;
 
         Ok(Response::default()
+            .add_submessages(hooks)
             .add_attribute("method", "close")
             .add_attribute("proposal_id", proposal_id.to_string())
             .add_attribute("closer", info.sender))
@@ -270,6 +478,93 @@ fn execute_close(
     }
 }
 
+fn execute_veto(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u32,
+) -> Result<Response, ContractError> {
+    let tally = TALLY
+        .get(deps.storage, &proposal_id)
+        .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+    let mut proposal = PROPOSAL
+        .get(deps.storage, &proposal_id)
+        .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+
+    let old_status = proposal.update_status(&env.block, &tally)?;
+    let veto = proposal
+        .veto
+        .as_ref()
+        .ok_or(VetoError::NoVetoConfiguration {})?;
+    veto.check_is_vetoer(&info)?;
+
+    match old_status {
+        // Open proposals may only be vetoed if veto before passed is
+        // enabled.
+        Status::Open => veto.check_veto_before_passed_enabled()?,
+        Status::VetoTimelock { .. } => (),
+        // A passed proposal with veto configured has left its
+        // timelock.
+        Status::Passed { .. } => return Err(ContractError::Veto(VetoError::TimelockExpired {})),
+        status => {
+            return Err(ContractError::Veto(VetoError::InvalidProposalStatus {
+                status: status.to_string(),
+            }))
+        }
+    }
+
+    // The proposal is vetoed once the veto threshold is reached.
+    if !veto.cast_veto(&mut proposal.vetoes, &info.sender)? {
+        PROPOSAL.insert(deps.storage, &proposal_id, &proposal)?;
+        return Ok(Response::default()
+            .add_attribute("method", "veto")
+            .add_attribute("proposal_id", proposal_id.to_string())
+            .add_attribute("vetoes", proposal.vetoes.len().to_string()));
+    }
+
+    proposal.set_vetoed();
+    PROPOSAL.insert(deps.storage, &proposal_id, &proposal)?;
+
+    let hooks = status_change_hooks(deps.storage, proposal_id, old_status, Status::Vetoed, None)?;
+
+    Ok(Response::default()
+        .add_submessages(hooks)
+        .add_attribute("method", "veto")
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("vetoer", info.sender))
+}
+
+fn execute_withdraw_veto(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u32,
+) -> Result<Response, ContractError> {
+    let tally = TALLY
+        .get(deps.storage, &proposal_id)
+        .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+    let mut proposal = PROPOSAL
+        .get(deps.storage, &proposal_id)
+        .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+
+    // Vetoes may not be withdrawn once the proposal has been vetoed.
+    let status = proposal.status(&env.block, &tally)?;
+    if status == Status::Vetoed {
+        return Err(ContractError::Veto(VetoError::InvalidProposalStatus {
+            status: status.to_string(),
+        }));
+    }
+
+    withdraw_veto(&mut proposal.vetoes, &info.sender)?;
+    PROPOSAL.insert(deps.storage, &proposal_id, &proposal)?;
+
+    Ok(Response::default()
+        .add_attribute("method", "withdraw_veto")
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("vetoer", info.sender)
+        .add_attribute("vetoes", proposal.vetoes.len().to_string()))
+}
+
 fn execute_set_config(
     deps: DepsMut,
     info: MessageInfo,
@@ -278,29 +573,156 @@ fn execute_set_config(
     if info.sender != DAO.load(deps.storage)?.addr {
         Err(ContractError::NotDao {})
     } else {
-        CONFIG.save(deps.storage, &config.into_checked()?)?;
+        CONFIG.save(deps.storage, &config.into_checked(deps.as_ref())?)?;
         Ok(Response::default()
             .add_attribute("method", "update_config")
             .add_attribute("updater", info.sender))
     }
 }
 
+fn execute_update_proposal_creation_policy(
+    deps: DepsMut,
+    info: MessageInfo,
+    new_info: PreProposeInfo,
+) -> Result<Response, ContractError> {
+    let dao = DAO.load(deps.storage)?;
+    if info.sender != dao.addr {
+        return Err(ContractError::NotDao {});
+    }
+
+    let (initial_policy, messages) =
+        new_info.into_initial_policy_and_messages(deps.storage, dao.addr, REPLY_IDS)?;
+    CREATION_POLICY.save(deps.storage, &initial_policy)?;
+
+    Ok(Response::default()
+        .add_submessages(messages)
+        .add_attribute("method", "update_proposal_creation_policy")
+        .add_attribute("updater", info.sender)
+        .add_attribute("new_policy", format!("{initial_policy:?}")))
+}
+
+fn execute_update_hook(
+    deps: DepsMut,
+    info: MessageInfo,
+    hooks: Hooks,
+    kind: &str,
+    address: String,
+    code_hash: String,
+    add: bool,
+) -> Result<Response, ContractError> {
+    if info.sender != DAO.load(deps.storage)?.addr {
+        return Err(ContractError::NotDao {});
+    }
+
+    let hook = HookItem {
+        addr: deps.api.addr_validate(&address)?,
+        code_hash,
+    };
+    let action = if add {
+        hooks.add_hook(deps.storage, hook)?;
+        "add"
+    } else {
+        hooks.remove_hook(deps.storage, hook)?;
+        "remove"
+    };
+
+    Ok(Response::default()
+        .add_attribute("method", format!("{action}_{kind}_hook"))
+        .add_attribute("address", address))
+}
+
+fn try_set_key(deps: DepsMut, info: MessageInfo, key: String) -> Result<Response, ContractError> {
+    ViewingKey::set(deps.storage, info.sender.as_str(), key.as_str());
+    Ok(Response::default())
+}
+
+fn try_create_key(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    entropy: String,
+) -> Result<Response, ContractError> {
+    let key = ViewingKey::create(
+        deps.storage,
+        &info,
+        &env,
+        info.sender.as_str(),
+        entropy.as_ref(),
+    );
+
+    Ok(Response::default().set_data(to_binary(&CreateViewingKey { key })?))
+}
+
+fn revoke_permit(
+    deps: DepsMut,
+    info: MessageInfo,
+    permit_name: String,
+) -> Result<Response, ContractError> {
+    RevokedPermits::revoke_permit(
+        deps.storage,
+        PREFIX_REVOKED_PERMITS,
+        info.sender.as_str(),
+        &permit_name,
+    );
+
+    Ok(Response::default())
+}
+
+/// Prepares proposal status change hooks, and the proposal completed
+/// hook for the pre-propose module if the proposal is complete.
+/// QUORUM_MET is reported to the pre-propose module for closed
+/// proposals.
+fn status_change_hooks(
+    storage: &mut dyn Storage,
+    proposal_id: u32,
+    old_status: Status,
+    new_status: Status,
+    quorum_met: Option<bool>,
+) -> StdResult<Vec<SubMsg>> {
+    let mut hooks = proposal_status_changed_hooks(
+        PROPOSAL_HOOKS,
+        storage,
+        proposal_id as u64,
+        old_status.to_string(),
+        new_status.to_string(),
+    )?;
+    if matches!(
+        new_status,
+        Status::Executed | Status::Closed | Status::ExecutionFailed | Status::Vetoed
+    ) {
+        let proposal_creation_policy = CREATION_POLICY.load(storage)?;
+        hooks.extend(match (new_status, quorum_met) {
+            (Status::Closed, Some(quorum_met)) => {
+                proposal_closed_hooks(proposal_creation_policy, proposal_id as u64, quorum_met)?
+            }
+            _ => proposal_completed_hooks(
+                proposal_creation_policy,
+                proposal_id as u64,
+                new_status.into(),
+            )?,
+        });
+    }
+    Ok(hooks)
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
-        QueryMsg::Proposal { id } => {
-            let proposal = PROPOSAL.get(deps.storage, &id);
-            let tally = TALLY.get(deps.storage, &id);
-            proposal
-                .clone()
-                .unwrap()
-                .update_status(&env.block, &tally.clone().unwrap());
-            to_binary(&ProposalResponse {
-                proposal: proposal.unwrap(),
-                tally: tally.unwrap(),
-            })
-        }
+        QueryMsg::Proposal { id } => to_binary(&proposal_response(deps, &env, id)?),
         QueryMsg::Config {} => to_binary(&CONFIG.load(deps.storage)?),
+        QueryMsg::ListProposals { start_after, limit } => {
+            query_list_proposals(deps, env, start_after, limit)
+        }
+        QueryMsg::ReverseProposals {
+            start_before,
+            limit,
+        } => query_reverse_proposals(deps, env, start_before, limit),
+        QueryMsg::ProposalCount {} => to_binary(&proposal_count(deps.storage)?),
+        QueryMsg::ProposalCreationPolicy {} => to_binary(&CREATION_POLICY.load(deps.storage)?),
+        QueryMsg::ProposalHooks {} => to_binary(&PROPOSAL_HOOKS.query_hooks(deps)?),
+        QueryMsg::VoteHooks {} => to_binary(&VOTE_HOOKS.query_hooks(deps)?),
+        QueryMsg::WithPermit { permit, query } => permit_queries(deps, env, permit, query),
+        QueryMsg::GetVote { .. } => viewing_keys_queries(deps, msg),
         QueryMsg::NextProposalId {} => to_binary(&next_proposal_id(deps.storage)?),
         QueryMsg::Dao {} => to_binary(&DAO.load(deps.storage)?),
         QueryMsg::Info {} => to_binary(&dao_interface::voting::InfoResponse {
@@ -309,26 +731,215 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     }
 }
 
+fn permit_queries(
+    deps: Deps,
+    env: Env,
+    permit: Permit,
+    query: QueryWithPermit,
+) -> StdResult<Binary> {
+    let account = secret_toolkit::permit::validate(
+        deps,
+        PREFIX_REVOKED_PERMITS,
+        &permit,
+        env.contract.address.into_string(),
+        None,
+    )?;
+
+    match query {
+        QueryWithPermit::GetVote { proposal_id, voter } => {
+            if !permit.check_permission(&TokenPermissions::Balance) {
+                return Err(StdError::generic_err(format!(
+                    "No permission to query get vote, got permissions {:?}",
+                    permit.params.permissions
+                )));
+            }
+            // Ballots are private, so voters may only query their
+            // own.
+            let voter = deps.api.addr_validate(&voter)?;
+            if voter.as_str() != account {
+                return Err(StdError::generic_err(
+                    "Permit was not signed by the voter being queried",
+                ));
+            }
+
+            to_binary(&query_vote(deps, proposal_id, voter))
+        }
+    }
+}
+
+fn viewing_keys_queries(deps: Deps, msg: QueryMsg) -> StdResult<Binary> {
+    let (addresses, key) = msg.get_validation_params(deps.api)?;
+
+    for address in addresses {
+        let result = ViewingKey::check(deps.storage, address.as_str(), key.as_str());
+        if result.is_ok() {
+            return match msg {
+                QueryMsg::GetVote { proposal_id, .. } => {
+                    to_binary(&query_vote(deps, proposal_id, address))
+                }
+                _ => panic!("This query type does not require authentication"),
+            };
+        }
+    }
+
+    to_binary(&ViewingKeyError {
+        msg: "Wrong viewing key for this address or viewing key not set".to_string(),
+    })
+}
+
+fn query_vote(deps: Deps, proposal_id: u32, voter: Addr) -> VoteResponse {
+    let vote = BALLOT
+        .get(deps.storage, &(proposal_id, voter.clone()))
+        .map(|ballot| VoteInfo {
+            voter,
+            vote: ballot.vote,
+            power: ballot.power,
+            rationale: ballot.rationale,
+        });
+    VoteResponse { vote }
+}
+
+fn proposal_response(deps: Deps, env: &Env, id: u32) -> StdResult<ProposalResponse> {
+    let mut proposal = PROPOSAL
+        .get(deps.storage, &id)
+        .ok_or_else(|| StdError::not_found("proposal"))?;
+    let tally = TALLY
+        .get(deps.storage, &id)
+        .ok_or_else(|| StdError::not_found("tally"))?;
+    // Statuses are only stored on execute, close, and veto, so the
+    // stored status may be out of date.
+    proposal.update_status(&env.block, &tally)?;
+    let resolution = tally.resolve(proposal.cycle_resolution);
+    Ok(ProposalResponse {
+        proposal,
+        tally,
+        resolution,
+    })
+}
+
+// Proposals are never removed, so proposal IDs are exactly the range
+// `1..=proposal_count` and list queries need not iterate storage.
+
+fn query_list_proposals(
+    deps: Deps,
+    env: Env,
+    start_after: Option<u32>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let limit = limit.map_or(DEFAULT_LIMIT as usize, |l| l as usize);
+    let first = start_after.map_or(1, |id| id.saturating_add(1));
+    let proposals = (first..=proposal_count(deps.storage)?)
+        .take(limit)
+        .map(|id| proposal_response(deps, &env, id))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    to_binary(&ProposalListResponse { proposals })
+}
+
+fn query_reverse_proposals(
+    deps: Deps,
+    env: Env,
+    start_before: Option<u32>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let limit = limit.map_or(DEFAULT_LIMIT as usize, |l| l as usize);
+    let end = next_proposal_id(deps.storage)?;
+    let end = start_before.map_or(end, |id| id.min(end));
+    let proposals = (1..end)
+        .rev()
+        .take(limit)
+        .map(|id| proposal_response(deps, &env, id))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    to_binary(&ProposalListResponse { proposals })
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
     let repl = REPLY_IDS.get_event(deps.storage, msg.id)?;
-    match repl {
-        ReplyEvent::FailedProposalExecution { proposal_id } => match msg.clone().result {
-            SubMsgResult::Err(err) => Err(ContractError::Std(StdError::GenericErr { msg: err })),
-            SubMsgResult::Ok(_) => {
-                let proposal = PROPOSAL.get(deps.storage, &(proposal_id as u32));
-                proposal.clone().unwrap().set_execution_failed();
-                PROPOSAL.insert(
-                    deps.storage,
-                    &(proposal_id as u32),
-                    &proposal.clone().unwrap(),
-                )?;
-
-                Ok(Response::default()
-                    .add_attribute("proposal_execution_failed", proposal_id.to_string())
-                    .add_attribute("error", msg.result.into_result().err().unwrap_or_default()))
-            }
-        },
-        _ => unimplemented!("pre-propose and hooks not yet supported"),
+    match (repl, msg.result) {
+        // The proposal executed, so the deposit module may now be
+        // told it completed.
+        (ReplyEvent::FailedProposalExecution { proposal_id }, SubMsgResult::Ok(_)) => {
+            let proposal_creation_policy = CREATION_POLICY.load(deps.storage)?;
+            Ok(
+                Response::default().add_submessages(proposal_completed_hooks(
+                    proposal_creation_policy,
+                    proposal_id,
+                    Status::Executed.into(),
+                )?),
+            )
+        }
+        (ReplyEvent::FailedProposalExecution { proposal_id }, SubMsgResult::Err(err)) => {
+            let id = proposal_id as u32;
+            let mut proposal = PROPOSAL
+                .get(deps.storage, &id)
+                .ok_or(ContractError::NoSuchProposal { id })?;
+            proposal.set_execution_failed();
+            PROPOSAL.insert(deps.storage, &id, &proposal)?;
+
+            let hooks = status_change_hooks(
+                deps.storage,
+                id,
+                Status::Executed,
+                Status::ExecutionFailed,
+                None,
+            )?;
+
+            Ok(Response::default()
+                .add_submessages(hooks)
+                .add_attribute("proposal_execution_failed", proposal_id.to_string())
+                .add_attribute("error", err))
+        }
+        (ReplyEvent::FailedProposalHook { idx }, SubMsgResult::Err(_)) => {
+            let hook_item = PROPOSAL_HOOKS.remove_hook_by_index(deps.storage, idx)?;
+            Ok(Response::default().add_attribute(
+                "removed_proposal_hook",
+                format!("{0}:{idx}", hook_item.addr),
+            ))
+        }
+        (ReplyEvent::FailedVoteHook { idx }, SubMsgResult::Err(_)) => {
+            let hook_item = VOTE_HOOKS.remove_hook_by_index(deps.storage, idx)?;
+            Ok(Response::default()
+                .add_attribute("removed_vote_hook", format!("{0}:{idx}", hook_item.addr)))
+        }
+        (ReplyEvent::PreProposalModuleInstantiate { code_hash }, SubMsgResult::Ok(res)) => {
+            let contract_address = parse_reply_event_for_contract_address(res.events)?;
+            let module_addr = deps.api.addr_validate(&contract_address)?;
+            CREATION_POLICY.save(
+                deps.storage,
+                &ProposalCreationPolicy::Module {
+                    addr: module_addr.clone(),
+                    code_hash,
+                },
+            )?;
+
+            // Forward the pre-propose module's instantiation data.
+            let response =
+                Response::default().add_attribute("update_pre_propose_module", module_addr);
+            Ok(match res.data {
+                Some(data) => response.set_data(data),
+                None => response,
+            })
+        }
+        (ReplyEvent::FailedPreProposeModuleHook {}, _) => {
+            let addr = match CREATION_POLICY.load(deps.storage)? {
+                // Something is off if we're getting this reply and we
+                // don't have a pre-propose module installed. This
+                // should be unreachable.
+                ProposalCreationPolicy::Anyone {} => {
+                    return Err(ContractError::InvalidReplyID { id: msg.id })
+                }
+                // Our pre-propose module has errored while receiving
+                // a proposal hook, so open proposal creation to
+                // anyone.
+                ProposalCreationPolicy::Module { addr, .. } => {
+                    CREATION_POLICY.save(deps.storage, &ProposalCreationPolicy::Anyone {})?;
+                    addr
+                }
+            };
+            Ok(Response::default().add_attribute("failed_prepropose_hook", addr))
+        }
+        _ => Err(ContractError::UnknownReplyID {}),
     }
 }
@@ -1,5 +1,8 @@
 use cosmwasm_std::StdError;
-use dao_voting::{error::VotingError, reply::error::TagError, threshold::ThresholdError};
+use cw_hooks::HookError;
+use dao_voting::{
+    error::VotingError, reply::error::TagError, threshold::ThresholdError, veto::VetoError,
+};
 use secret_cw_controllers::ReplyError;
 use secret_utils::ParseReplyError;
 use thiserror::Error;
@@ -18,6 +21,10 @@ pub enum ContractError {
     Voting(#[from] VotingError),
     #[error(transparent)]
     Tag(#[from] TagError),
+    #[error(transparent)]
+    Hook(#[from] HookError),
+    #[error(transparent)]
+    Veto(#[from] VetoError),
 
     #[error(transparent)]
     ReplyUdError(#[from] ReplyError),
@@ -45,4 +52,25 @@ pub enum ContractError {
 
     #[error("must specify at least one choice for proposal")]
     ZeroChoices {},
+
+    #[error("proposals may have at most ({max}) candidates, including none of the above, if cycle resolution is configured")]
+    TooManyCandidates { max: u32 },
+
+    #[error("unauthorized")]
+    Unauthorized {},
+
+    #[error("the proposer must be set by the pre-propose module, if one is installed, and unset otherwise")]
+    InvalidProposer {},
+
+    #[error("no such proposal ({id})")]
+    NoSuchProposal { id: u32 },
+
+    #[error("no vote exists for proposal ({id}) and voter ({voter})")]
+    NoSuchVote { id: u32, voter: String },
+
+    #[error("invalid reply id ({id})")]
+    InvalidReplyID { id: u64 },
+
+    #[error("unknown reply id")]
+    UnknownReplyID {},
 }
@@ -5,9 +5,13 @@ mod error;
 mod m;
 pub mod msg;
 pub mod proposal;
+pub mod resolution;
 pub mod state;
 pub mod tally;
 
+#[cfg(test)]
+mod tests;
+
 // #[cfg(test)]
 // mod testing;
 
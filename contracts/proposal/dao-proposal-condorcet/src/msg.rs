@@ -1,11 +1,44 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{CosmosMsg, Empty};
+use cosmwasm_std::{Addr, Api, CosmosMsg, Empty, StdResult};
 
 use dao_dao_macros::proposal_module_query;
+use dao_voting::{pre_propose::PreProposeInfo, threshold::PercentageThreshold, veto::VetoConfig};
+use secret_toolkit::permit::Permit;
+use secret_utils::Duration;
 
-use crate::config::UncheckedConfig;
+use crate::{config::UncheckedConfig, resolution::CycleResolution};
 
-pub type InstantiateMsg = UncheckedConfig;
+#[cw_serde]
+pub struct InstantiateMsg {
+    pub quorum: PercentageThreshold,
+    pub voting_period: Duration,
+    pub min_voting_period: Option<Duration>,
+    pub close_proposals_on_execution_failure: bool,
+    /// Optional veto configuration. If set, passed proposals are
+    /// timelocked after voting closes, during which the vetoer may
+    /// veto them.
+    pub veto: Option<VetoConfig>,
+    /// How a winner is chosen when voting closes without a Condorcet
+    /// winner. Defaults to rejecting the proposal.
+    #[serde(default)]
+    pub cycle_resolution: CycleResolution,
+    /// Information about what addresses may create proposals.
+    pub pre_propose_info: PreProposeInfo,
+    pub dao_code_hash: String,
+}
+
+impl InstantiateMsg {
+    pub(crate) fn config(&self) -> UncheckedConfig {
+        UncheckedConfig {
+            quorum: self.quorum,
+            voting_period: self.voting_period,
+            min_voting_period: self.min_voting_period,
+            close_proposals_on_execution_failure: self.close_proposals_on_execution_failure,
+            veto: self.veto.clone(),
+            cycle_resolution: self.cycle_resolution,
+        }
+    }
+}
 
 #[cw_serde]
 pub struct Choice {
@@ -16,12 +49,32 @@ pub struct Choice {
 pub enum ExecuteMsg {
     Propose {
         choices: Vec<Choice>,
+        /// The viewing key of the sender, used to check that they
+        /// have voting power. Unused when proposals are created by a
+        /// pre-propose module.
+        #[serde(default)]
         key: String,
+        /// The address creating the proposal. Must be set by the
+        /// pre-propose module, if one is installed, and unset
+        /// otherwise.
+        proposer: Option<String>,
     },
     Vote {
         proposal_id: u32,
+        /// The sender's ranking of the proposal's candidates, most
+        /// preferred first. Candidates that are left out are ranked
+        /// equally below every ranked candidate.
         vote: Vec<u32>,
         key: String,
+        /// An optional rationale for why this vote was cast. This can
+        /// be updated, set, or removed later by the voter.
+        rationale: Option<String>,
+    },
+    /// Updates the sender's rationale for their vote on the specified
+    /// proposal. Errors if no vote has been cast.
+    UpdateRationale {
+        proposal_id: u32,
+        rationale: Option<String>,
     },
     Execute {
         proposal_id: u32,
@@ -30,15 +83,145 @@ pub enum ExecuteMsg {
     Close {
         proposal_id: u32,
     },
+    /// Vetoes a proposal. Callable only by the proposal's vetoers if
+    /// veto is configured.
+    Veto {
+        proposal_id: u32,
+    },
+    /// Withdraws the sender's veto of a proposal that has not yet
+    /// been vetoed by the veto council.
+    WithdrawVeto {
+        proposal_id: u32,
+    },
     SetConfig(UncheckedConfig),
+    /// Update's the proposal creation policy used for this
+    /// module. Only the DAO may call this method.
+    UpdatePreProposeInfo {
+        info: PreProposeInfo,
+    },
+    /// Adds an address as a consumer of proposal hooks. Consumers of
+    /// proposal hooks have hook messages executed on them whenever
+    /// the status of a proposal changes or a proposal is created. If
+    /// a consumer contract errors when handling a hook message it
+    /// will be removed from the list of consumers.
+    AddProposalHook {
+        address: String,
+        code_hash: String,
+    },
+    /// Removes a consumer of proposal hooks.
+    RemoveProposalHook {
+        address: String,
+        code_hash: String,
+    },
+    /// Adds an address as a consumer of vote hooks. Consumers of vote
+    /// hooks have hook messages executed on them whenever a vote is
+    /// cast. If a consumer contract errors when handling a hook
+    /// message it will be removed from the list of consumers.
+    AddVoteHook {
+        address: String,
+        code_hash: String,
+    },
+    /// Removes a consumer of vote hooks.
+    RemoveVoteHook {
+        address: String,
+        code_hash: String,
+    },
+    CreateViewingKey {
+        entropy: String,
+        padding: Option<String>,
+    },
+    SetViewingKey {
+        key: String,
+        padding: Option<String>,
+    },
+    RevokePermit {
+        permit_name: String,
+        padding: Option<String>,
+    },
 }
 
 #[proposal_module_query]
 #[cw_serde]
 #[derive(QueryResponses)]
 pub enum QueryMsg {
+    /// A proposal. `proposal_id` is accepted for `id` so that this
+    /// module may be queried like others for proposal dependencies.
     #[returns(crate::proposal::ProposalResponse)]
-    Proposal { id: u32 },
+    Proposal {
+        #[serde(alias = "proposal_id")]
+        id: u32,
+    },
     #[returns(crate::config::Config)]
     Config {},
+    /// Lists proposals in ascending order of proposal ID. If no
+    /// limit is set a max of 30 proposals will be returned.
+    #[returns(crate::proposal::ProposalListResponse)]
+    ListProposals {
+        start_after: Option<u32>,
+        limit: Option<u32>,
+    },
+    /// Lists proposals in descending order of proposal ID. If no
+    /// limit is set a max of 30 proposals will be returned.
+    #[returns(crate::proposal::ProposalListResponse)]
+    ReverseProposals {
+        start_before: Option<u32>,
+        limit: Option<u32>,
+    },
+    /// Returns a voter's ballot on a proposal. `key` must be the
+    /// voter's viewing key for this module.
+    #[returns(crate::vote::VoteResponse)]
+    GetVote {
+        proposal_id: u32,
+        voter: String,
+        key: String,
+    },
+    /// Returns the number of proposals that have been created in
+    /// this module.
+    #[returns(::std::primitive::u32)]
+    ProposalCount {},
+    /// Gets the current proposal creation policy for this module.
+    #[returns(::dao_voting::pre_propose::ProposalCreationPolicy)]
+    ProposalCreationPolicy {},
+    /// Lists all of the consumers of proposal hooks for this module.
+    #[returns(::cw_hooks::HooksResponse)]
+    ProposalHooks {},
+    /// Lists all of the consumers of vote hooks for this module.
+    #[returns(::cw_hooks::HooksResponse)]
+    VoteHooks {},
+    #[returns(())]
+    WithPermit {
+        permit: Permit,
+        query: QueryWithPermit,
+    },
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryWithPermit {
+    /// Returns a voter's ballot on a proposal. The permit must have
+    /// been signed by the voter.
+    #[returns(crate::vote::VoteResponse)]
+    GetVote { proposal_id: u32, voter: String },
+}
+
+impl QueryMsg {
+    pub fn get_validation_params(&self, api: &dyn Api) -> StdResult<(Vec<Addr>, String)> {
+        match self {
+            Self::GetVote { voter, key, .. } => {
+                let address = api.addr_validate(voter.as_str())?;
+                Ok((vec![address], key.clone()))
+            }
+            _ => panic!("This query type does not require authentication"),
+        }
+    }
+}
+
+#[cw_serde]
+pub struct CreateViewingKey {
+    pub key: String,
+}
+
+#[cw_serde]
+pub struct ViewingKeyError {
+    pub msg: String,
 }
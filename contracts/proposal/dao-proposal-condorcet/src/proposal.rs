@@ -1,13 +1,17 @@
+use std::ops::Add;
+
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{Addr, BlockInfo, StdResult, Storage, SubMsg, Uint128};
-use dao_voting::{threshold::PercentageThreshold, voting::does_vote_count_pass};
+use dao_voting::{threshold::PercentageThreshold, veto::VetoConfig, voting::does_vote_count_pass};
 use secret_cw_controllers::ReplyEvent;
 use secret_toolkit::utils::HandleCallback;
 use secret_utils::Expiration;
 
 use crate::{
     config::Config,
+    error::ContractError,
     msg::Choice,
+    resolution::{CycleResolution, Resolution},
     state::REPLY_IDS,
     tally::{Tally, Winner},
 };
@@ -15,6 +19,9 @@ use crate::{
 #[cw_serde]
 pub struct Proposal {
     last_status: Status,
+    /// `last_status` in the form used by other proposal modules, so
+    /// that their proposals may depend on this module's.
+    pub status: dao_voting::status::Status,
 
     pub proposer: Addr,
 
@@ -26,6 +33,18 @@ pub struct Proposal {
 
     pub id: u32,
     pub choices: Vec<Choice>,
+
+    /// The veto configuration at the time of the proposal's
+    /// creation, if any.
+    pub veto: Option<VetoConfig>,
+    /// The members of the veto council that have vetoed this
+    /// proposal. The proposal is vetoed once the council's veto
+    /// threshold is reached.
+    pub vetoes: Vec<Addr>,
+
+    /// How a winner is chosen if voting closes without a Condorcet
+    /// winner.
+    pub cycle_resolution: CycleResolution,
 }
 
 #[cw_serde]
@@ -44,63 +63,121 @@ pub enum Status {
     Closed,
     /// The proposal's execution failed.
     ExecutionFailed,
+    /// The proposal has passed and is timelocked. Only the vetoers
+    /// may execute or veto it until the timelock expires.
+    VetoTimelock { winner: u32, expiration: Expiration },
+    /// The proposal has been vetoed.
+    Vetoed,
+}
+
+impl From<Status> for dao_voting::status::Status {
+    fn from(status: Status) -> Self {
+        match status {
+            Status::Open => Self::Open,
+            Status::Rejected => Self::Rejected,
+            Status::Passed { .. } => Self::Passed,
+            Status::Executed => Self::Executed,
+            Status::Closed => Self::Closed,
+            Status::ExecutionFailed => Self::ExecutionFailed,
+            Status::VetoTimelock { expiration, .. } => Self::VetoTimelock { expiration },
+            Status::Vetoed => Self::Vetoed,
+        }
+    }
+}
+
+/// Formats statuses the same way as `dao-proposal-single` so that
+/// hook consumers may handle both.
+impl std::fmt::Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", dao_voting::status::Status::from(*self))
+    }
 }
 
 #[cw_serde]
 pub struct ProposalResponse {
     pub proposal: Proposal,
     pub tally: Tally,
+    /// The resolution of the cycle in the proposal's votes, if there
+    /// is no Condorcet winner and the proposal has a cycle
+    /// resolution method.
+    pub resolution: Option<Resolution>,
+}
+
+#[cw_serde]
+pub struct ProposalListResponse {
+    pub proposals: Vec<ProposalResponse>,
 }
 
-fn status(block: &BlockInfo, proposal: &Proposal, tally: &Tally) -> Status {
+fn status(block: &BlockInfo, proposal: &Proposal, tally: &Tally) -> StdResult<Status> {
     match proposal.last_status {
+        Status::Open => match voting_status(block, proposal, tally) {
+            Status::Passed { winner } => match proposal.veto {
+                // If veto is configured, the proposal is timelocked
+                // until the timelock following the end of voting
+                // expires.
+                Some(ref veto) => {
+                    let expiration = tally.expiration.add(veto.timelock_duration)?;
+                    if expiration.is_expired(block) {
+                        Ok(Status::Passed { winner })
+                    } else {
+                        Ok(Status::VetoTimelock { winner, expiration })
+                    }
+                }
+                None => Ok(Status::Passed { winner }),
+            },
+            status => Ok(status),
+        },
+        Status::VetoTimelock { winner, expiration } if expiration.is_expired(block) => {
+            Ok(Status::Passed { winner })
+        }
+        status => Ok(status),
+    }
+}
+
+/// The status of an open proposal according to its votes, ignoring
+/// any veto timelock.
+fn voting_status(block: &BlockInfo, proposal: &Proposal, tally: &Tally) -> Status {
+    if proposal
+        .min_voting_period
+        .map_or(false, |min| !min.is_expired(block))
+    {
+        return Status::Open;
+    }
+
+    let winner = tally.winner;
+    let expired = tally.expiration.is_expired(block);
+    let quorum = proposal.quorum_met(tally);
+
+    if expired && !quorum {
         Status::Rejected
-        | Status::Passed { .. }
-        | Status::Executed
-        | Status::Closed
-        | Status::ExecutionFailed => proposal.last_status,
-        Status::Open => {
-            if proposal
-                .min_voting_period
-                .map_or(false, |min| !min.is_expired(block))
-            {
-                return Status::Open;
+    } else {
+        match winner {
+            Winner::Never if proposal.cycle_resolution == CycleResolution::None => Status::Rejected,
+            // If a cycle resolution method is configured, a proposal
+            // without a Condorcet winner is resolved once voting
+            // closes.
+            Winner::Never | Winner::None => {
+                if expired {
+                    tally
+                        .resolve(proposal.cycle_resolution)
+                        .and_then(|resolution| resolution.winner())
+                        .map_or(Status::Rejected, |winner| Status::Passed { winner })
+                } else {
+                    Status::Open
+                }
             }
-
-            let winner = tally.winner;
-            let expired = tally.expiration.is_expired(block);
-            let quorum = does_vote_count_pass(
-                proposal.total_power - tally.power_outstanding,
-                proposal.total_power,
-                proposal.quorum,
-            );
-
-            if expired && !quorum {
-                Status::Rejected
-            } else {
-                match winner {
-                    Winner::Never => Status::Rejected,
-                    Winner::None => {
-                        if expired {
-                            Status::Rejected
-                        } else {
-                            Status::Open
-                        }
-                    }
-                    Winner::Some(winner) => {
-                        if expired && quorum {
-                            Status::Passed { winner }
-                        } else {
-                            Status::Open
-                        }
-                    }
-                    Winner::Undisputed(winner) => {
-                        if quorum {
-                            Status::Passed { winner }
-                        } else {
-                            Status::Open
-                        }
-                    }
+            Winner::Some(winner) => {
+                if expired && quorum {
+                    Status::Passed { winner }
+                } else {
+                    Status::Open
+                }
+            }
+            Winner::Undisputed(winner) => {
+                if quorum {
+                    Status::Passed { winner }
+                } else {
+                    Status::Open
                 }
             }
         }
@@ -118,6 +195,7 @@ impl Proposal {
     ) -> Self {
         Self {
             last_status: Status::Open,
+            status: dao_voting::status::Status::Open,
 
             min_voting_period: config.min_voting_period.map(|m| m.after(block)),
             quorum: config.quorum,
@@ -127,15 +205,35 @@ impl Proposal {
             proposer,
             choices,
             total_power,
+
+            veto: config.veto.clone(),
+            vetoes: vec![],
+
+            cycle_resolution: config.cycle_resolution,
         }
     }
 
-    pub(crate) fn update_status(&mut self, block: &BlockInfo, tally: &Tally) -> Status {
-        self.last_status = status(block, self, tally);
-        self.last_status
+    /// Returns whether the votes cast on this proposal meet its
+    /// quorum.
+    pub fn quorum_met(&self, tally: &Tally) -> bool {
+        does_vote_count_pass(
+            self.total_power - tally.power_outstanding,
+            self.total_power,
+            self.quorum,
+        )
     }
 
-    pub fn status(&self, block: &BlockInfo, tally: &Tally) -> Status {
+    pub(crate) fn update_status(&mut self, block: &BlockInfo, tally: &Tally) -> StdResult<Status> {
+        self.set_status(status(block, self, tally)?);
+        Ok(self.last_status)
+    }
+
+    fn set_status(&mut self, status: Status) {
+        self.last_status = status;
+        self.status = status.into();
+    }
+
+    pub fn status(&self, block: &BlockInfo, tally: &Tally) -> StdResult<Status> {
         status(block, self, tally)
     }
 
@@ -148,7 +246,11 @@ impl Proposal {
     pub(crate) fn set_closed(&mut self) {
         debug_assert_eq!(self.last_status, Status::Rejected);
 
-        self.last_status = Status::Closed;
+        self.set_status(Status::Closed);
+    }
+
+    pub(crate) fn set_vetoed(&mut self) {
+        self.set_status(Status::Vetoed);
     }
 
     /// Sets the proposal's status to executed and returns a
@@ -159,10 +261,13 @@ impl Proposal {
         dao: Addr,
         dao_code_hash: String,
         winner: u32,
-    ) -> StdResult<SubMsg> {
-        debug_assert_eq!(self.last_status, Status::Passed { winner });
+    ) -> Result<SubMsg, ContractError> {
+        debug_assert!(matches!(
+            self.last_status,
+            Status::Passed { winner: w } | Status::VetoTimelock { winner: w, .. } if w == winner
+        ));
 
-        self.last_status = Status::Executed;
+        self.set_status(Status::Executed);
 
         let msgs = self.choices[winner as usize].msgs.clone();
         let core_exec = dao_interface::msg::ExecuteMsg::ExecuteProposalHook { msgs };
@@ -173,10 +278,10 @@ impl Proposal {
                 ReplyEvent::FailedProposalExecution {
                     proposal_id: self.id as u64,
                 },
-            );
-            SubMsg::reply_on_error(
+            )?;
+            SubMsg::reply_always(
                 core_exec.to_cosmos_msg(dao_code_hash.clone(), dao.clone().to_string(), None)?,
-                reply_id.unwrap(),
+                reply_id,
             )
         } else {
             SubMsg::new(core_exec.to_cosmos_msg(dao_code_hash, dao.to_string(), None)?)
@@ -186,6 +291,140 @@ impl Proposal {
     pub(crate) fn set_execution_failed(&mut self) {
         debug_assert_eq!(self.last_status, Status::Executed);
 
-        self.last_status = Status::ExecutionFailed;
+        self.set_status(Status::ExecutionFailed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::{testing::mock_env, Decimal};
+    use secret_utils::Duration;
+
+    use crate::vote::Vote;
+
+    fn config(veto: Option<VetoConfig>) -> Config {
+        Config {
+            quorum: PercentageThreshold::Percent(Decimal::percent(15)),
+            voting_period: Duration::Height(10),
+            min_voting_period: None,
+            close_proposals_on_execution_failure: true,
+            veto,
+            cycle_resolution: CycleResolution::None,
+        }
+    }
+
+    fn veto() -> VetoConfig {
+        VetoConfig {
+            timelock_duration: Duration::Height(5),
+            vetoer: "vetoer".to_string(),
+            council: vec![],
+            veto_threshold: 1,
+            early_execute: false,
+            veto_before_passed: false,
+        }
+    }
+
+    fn passed_proposal(veto: Option<VetoConfig>) -> (Proposal, Tally) {
+        let env = mock_env();
+        let config = config(veto);
+        let choices = vec![Choice { msgs: vec![] }, Choice { msgs: vec![] }];
+        let mut tally = Tally::new(
+            2,
+            Uint128::new(10),
+            env.block.height,
+            config.voting_period.after(&env.block),
+        );
+        tally.add_vote(Vote::new(vec![0, 1], 2).unwrap(), Uint128::new(10));
+        let proposal = Proposal::new(
+            &env.block,
+            &config,
+            Addr::unchecked("proposer"),
+            1,
+            choices,
+            Uint128::new(10),
+        );
+        (proposal, tally)
+    }
+
+    #[test]
+    fn test_passes_without_veto() {
+        let env = mock_env();
+        let (mut proposal, tally) = passed_proposal(None);
+        assert_eq!(
+            proposal.update_status(&env.block, &tally).unwrap(),
+            Status::Passed { winner: 0 }
+        );
+    }
+
+    #[test]
+    fn test_veto_timelock() {
+        let mut env = mock_env();
+        let (mut proposal, tally) = passed_proposal(Some(veto()));
+        let expiration = Expiration::AtHeight(env.block.height + 15);
+
+        // The timelock begins once voting closes, even if the winner
+        // is known before then.
+        assert_eq!(
+            proposal.update_status(&env.block, &tally).unwrap(),
+            Status::VetoTimelock {
+                winner: 0,
+                expiration
+            }
+        );
+
+        env.block.height += 15;
+        assert_eq!(
+            proposal.update_status(&env.block, &tally).unwrap(),
+            Status::Passed { winner: 0 }
+        );
+        assert_eq!(proposal.last_status().to_string(), "passed");
+    }
+
+    #[test]
+    fn test_cycle_resolution() {
+        let mut env = mock_env();
+        let choices = vec![Choice { msgs: vec![] }; 3];
+        let mut tally = Tally::new(
+            3,
+            Uint128::new(11),
+            env.block.height,
+            Expiration::AtHeight(env.block.height + 10),
+        );
+        // 0 beats 1, 1 beats 2, and 2 beats 0.
+        for (vote, power) in [(vec![0, 1, 2], 5), (vec![1, 2, 0], 4), (vec![2, 0, 1], 2)] {
+            tally.add_vote(Vote::new(vote, 3).unwrap(), Uint128::new(power));
+        }
+        let proposal = |cycle_resolution| {
+            let config = Config {
+                cycle_resolution,
+                ..config(None)
+            };
+            Proposal::new(
+                &env.block,
+                &config,
+                Addr::unchecked("proposer"),
+                1,
+                choices.clone(),
+                Uint128::new(11),
+            )
+        };
+        let rejected = proposal(CycleResolution::None);
+        let resolved = proposal(CycleResolution::Schulze);
+
+        // Without a resolution method the cycle can never be broken,
+        // so the proposal is rejected early. Otherwise it stays open
+        // until voting closes.
+        assert_eq!(
+            rejected.status(&env.block, &tally).unwrap(),
+            Status::Rejected
+        );
+        assert_eq!(resolved.status(&env.block, &tally).unwrap(), Status::Open);
+
+        env.block.height += 10;
+        assert_eq!(
+            resolved.status(&env.block, &tally).unwrap(),
+            Status::Passed { winner: 0 }
+        );
     }
 }
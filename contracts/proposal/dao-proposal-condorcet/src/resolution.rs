@@ -0,0 +1,248 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::Uint128;
+
+use crate::{cell::Cell, m::M};
+
+/// The maximum number of candidates, including "none of the above",
+/// a proposal may have if a cycle resolution method is
+/// configured. Resolving a cycle costs O(candidates^3) for Schulze and
+/// O(candidates^4) in the worst case for ranked pairs, so this bounds
+/// the cost of executing a proposal.
+pub const MAX_RESOLVABLE_CANDIDATES: u32 = 32;
+
+/// How a winner is chosen when voting closes without a Condorcet
+/// winner, which happens when the voters' preferences form a cycle.
+#[cw_serde]
+#[derive(Copy, Default)]
+pub enum CycleResolution {
+    /// No winner is chosen and the proposal is rejected.
+    #[default]
+    None,
+    /// The Schulze beatpath method. The winner is the candidate
+    /// whose strongest paths to every other candidate are at least
+    /// as strong as the strongest paths back.
+    Schulze,
+    /// Tideman's ranked pairs method. Pairwise victories are locked
+    /// in from largest to smallest margin, skipping any that would
+    /// create a cycle, and the winner is the candidate no locked
+    /// victory points to.
+    RankedPairs,
+}
+
+/// The outcome of resolving a cycle.
+#[cw_serde]
+pub enum Resolution {
+    Schulze {
+        /// The winner, if any.
+        winner: Option<u32>,
+        /// `strongest_paths[x][y]` is the strength of the strongest
+        /// path from candidate x to candidate y, where a path's
+        /// strength is its weakest margin of victory.
+        strongest_paths: Vec<Vec<Uint128>>,
+    },
+    RankedPairs {
+        /// The winner, if any.
+        winner: Option<u32>,
+        /// The locked victories, as (winner, loser), in the order
+        /// they were locked.
+        locked: Vec<(u32, u32)>,
+    },
+}
+
+impl Resolution {
+    pub fn winner(&self) -> Option<u32> {
+        match self {
+            Resolution::Schulze { winner, .. } | Resolution::RankedPairs { winner, .. } => *winner,
+        }
+    }
+}
+
+impl CycleResolution {
+    /// Resolves a cycle in M. Returns None if no method is
+    /// configured.
+    pub(crate) fn resolve(self, m: &M) -> Option<Resolution> {
+        match self {
+            CycleResolution::None => None,
+            CycleResolution::Schulze => {
+                let strongest_paths = strongest_paths(m);
+                Some(Resolution::Schulze {
+                    winner: schulze_winner(&strongest_paths),
+                    strongest_paths,
+                })
+            }
+            CycleResolution::RankedPairs => {
+                let (locked, winner) = ranked_pairs(m);
+                Some(Resolution::RankedPairs { winner, locked })
+            }
+        }
+    }
+}
+
+// Ties are broken deterministically. The last candidate, "none of the
+// above", is preferred so that ties favor the status quo. After that,
+// candidates are preferred in the order they were proposed.
+
+/// A candidate's tie-breaking precedence. Lower is preferred.
+fn precedence(candidate: u32, n: u32) -> u32 {
+    if candidate == n - 1 {
+        0
+    } else {
+        candidate + 1
+    }
+}
+
+/// Candidates in order of tie-breaking precedence.
+fn by_precedence(n: u32) -> impl Iterator<Item = u32> {
+    std::iter::once(n - 1).chain(0..n - 1)
+}
+
+/// The margin by which x beats y, or zero if x does not beat y.
+fn margin(m: &M, (x, y): (u32, u32)) -> Uint128 {
+    match m.get((x, y)) {
+        Cell::Positive(margin) => margin,
+        Cell::Zero | Cell::Negative(_) => Uint128::zero(),
+    }
+}
+
+fn strongest_paths(m: &M) -> Vec<Vec<Uint128>> {
+    let n = m.n as usize;
+    let mut p = vec![vec![Uint128::zero(); n]; n];
+    for (x, row) in p.iter_mut().enumerate() {
+        for (y, strength) in row.iter_mut().enumerate() {
+            if x != y {
+                *strength = margin(m, (x as u32, y as u32));
+            }
+        }
+    }
+    // Floyd-Warshall, widening paths through each candidate in turn.
+    for k in 0..n {
+        for x in 0..n {
+            if x == k {
+                continue;
+            }
+            for y in 0..n {
+                if y == x || y == k {
+                    continue;
+                }
+                let through = p[x][k].min(p[k][y]);
+                if through > p[x][y] {
+                    p[x][y] = through;
+                }
+            }
+        }
+    }
+    p
+}
+
+fn schulze_winner(p: &[Vec<Uint128>]) -> Option<u32> {
+    let n = p.len() as u32;
+    by_precedence(n)
+        .find(|&x| (0..n).all(|y| p[x as usize][y as usize] >= p[y as usize][x as usize]))
+}
+
+fn ranked_pairs(m: &M) -> (Vec<(u32, u32)>, Option<u32>) {
+    let n = m.n;
+    let mut pairs: Vec<(Uint128, u32, u32)> = (0..n)
+        .flat_map(|x| (0..n).map(move |y| (x, y)))
+        .filter(|(x, y)| x != y)
+        .map(|(x, y)| (margin(m, (x, y)), x, y))
+        .filter(|(margin, _, _)| !margin.is_zero())
+        .collect();
+    // Largest margins first, with equal margins ordered by the
+    // precedence of the winner and then the loser.
+    pairs.sort_by(|(am, aw, al), (bm, bw, bl)| {
+        bm.cmp(am)
+            .then(precedence(*aw, n).cmp(&precedence(*bw, n)))
+            .then(precedence(*al, n).cmp(&precedence(*bl, n)))
+    });
+
+    // `reaches[x][y]` is true if there is a path of locked victories
+    // from x to y.
+    let n = n as usize;
+    let mut reaches = vec![vec![false; n]; n];
+    let mut locked = vec![];
+    let mut beaten = vec![false; n];
+    for (_, w, l) in pairs {
+        let (w, l) = (w as usize, l as usize);
+        if reaches[l][w] {
+            // Locking this victory would create a cycle.
+            continue;
+        }
+        locked.push((w as u32, l as u32));
+        beaten[l] = true;
+        let from: Vec<usize> = (0..n).filter(|&x| x == w || reaches[x][w]).collect();
+        let to: Vec<usize> = (0..n).filter(|&y| y == l || reaches[l][y]).collect();
+        for &x in &from {
+            for &y in &to {
+                reaches[x][y] = true;
+            }
+        }
+    }
+
+    let winner = by_precedence(n as u32).find(|&c| !beaten[c as usize]);
+    (locked, winner)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{tally::Tally, vote::Vote};
+    use secret_utils::Expiration;
+
+    fn new_tally(candidates: u32, votes: &[(&[u32], u128)]) -> Tally {
+        let total = votes.iter().map(|(_, p)| p).sum();
+        let mut tally = Tally::new(candidates, Uint128::new(total), 0, Expiration::Never {});
+        for (vote, power) in votes {
+            let vote = Vote::new(vote.to_vec(), candidates).unwrap();
+            tally.add_vote(vote, Uint128::new(*power));
+        }
+        tally
+    }
+
+    #[test]
+    fn test_resolves_cycle() {
+        // 0 beats 1 by 3, 1 beats 2 by 7, and 2 beats 0 by 1.
+        let tally = new_tally(3, &[(&[0, 1, 2], 5), (&[1, 2, 0], 4), (&[2, 0, 1], 2)]);
+
+        let schulze = tally.resolve(CycleResolution::Schulze).unwrap();
+        assert_eq!(schulze.winner(), Some(0));
+        let u = Uint128::new;
+        assert_eq!(
+            schulze,
+            Resolution::Schulze {
+                winner: Some(0),
+                strongest_paths: vec![
+                    vec![u(0), u(3), u(3)],
+                    vec![u(1), u(0), u(7)],
+                    vec![u(1), u(1), u(0)],
+                ],
+            }
+        );
+
+        // The weakest victory, 2 over 0, is not locked.
+        assert_eq!(
+            tally.resolve(CycleResolution::RankedPairs).unwrap(),
+            Resolution::RankedPairs {
+                winner: Some(0),
+                locked: vec![(1, 2), (0, 1)],
+            }
+        );
+
+        assert_eq!(tally.resolve(CycleResolution::None), None);
+    }
+
+    #[test]
+    fn test_ties_prefer_none_of_the_above() {
+        // Every candidate ties with every other.
+        let tally = new_tally(3, &[(&[0, 1, 2], 1), (&[2, 1, 0], 1)]);
+        for method in [CycleResolution::Schulze, CycleResolution::RankedPairs] {
+            assert_eq!(tally.resolve(method).unwrap().winner(), Some(2));
+        }
+
+        // 0 and 1 tie, and both beat "none of the above".
+        let tally = new_tally(3, &[(&[0, 1, 2], 1), (&[1, 0, 2], 1)]);
+        for method in [CycleResolution::Schulze, CycleResolution::RankedPairs] {
+            assert_eq!(tally.resolve(method).unwrap().winner(), Some(0));
+        }
+    }
+}
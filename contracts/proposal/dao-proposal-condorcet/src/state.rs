@@ -1,23 +1,47 @@
-use cosmwasm_std::{Addr, StdResult, Storage};
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, StdResult, Storage, Uint128};
+use cw_hooks::Hooks;
 use dao_interface::state::AnyContractInfo;
+use dao_voting::pre_propose::ProposalCreationPolicy;
 use secret_cw_controllers::ReplyIds;
 use secret_storage_plus::Item;
 use secret_toolkit::{serialization::Json, storage::Keymap};
 
 use crate::{config::Config, proposal::Proposal, tally::Tally, vote::Vote};
 
+/// A vote cast on a proposal.
+#[cw_serde]
+pub struct Ballot {
+    /// The voting power behind the vote.
+    pub power: Uint128,
+    /// The voter's ranking of the proposal's candidates.
+    pub vote: Vote,
+    /// An optional rationale for why this vote was cast.
+    pub rationale: Option<String>,
+}
+
 pub(crate) const DAO: Item<AnyContractInfo> = Item::new("dao");
 pub(crate) const CONFIG: Item<Config> = Item::new("config");
 
 pub(crate) const TALLY: Keymap<u32, Tally, Json> = Keymap::new(b"tallys");
 pub(crate) const PROPOSAL: Keymap<u32, Proposal, Json> = Keymap::new(b"proposals");
-pub(crate) const VOTE: Keymap<(u32, Addr), Vote, Json> = Keymap::new(b"votes");
+pub(crate) const BALLOT: Keymap<(u32, Addr), Ballot, Json> = Keymap::new(b"ballots");
 pub(crate) const REPLY_IDS: ReplyIds = ReplyIds::new(b"reply_ids", b"reply_ids_count");
 
+/// Consumers of proposal state change hooks.
+pub(crate) const PROPOSAL_HOOKS: Hooks = Hooks::new("proposal_hooks");
+/// Consumers of vote hooks.
+pub(crate) const VOTE_HOOKS: Hooks = Hooks::new("vote_hooks");
+/// The address of the pre-propose module associated with this
+/// proposal module (if any).
+pub(crate) const CREATION_POLICY: Item<ProposalCreationPolicy> = Item::new("creation_policy");
+
+pub(crate) fn proposal_count(storage: &dyn Storage) -> StdResult<u32> {
+    PROPOSAL.get_len(storage)
+}
+
 pub(crate) fn next_proposal_id(storage: &dyn Storage) -> StdResult<u32> {
-    PROPOSAL
-        .iter_keys(storage)?
-        .next()
-        .transpose()
-        .map(|id| id.unwrap_or(0) + 1)
+    // Proposals are never removed, so IDs are assigned sequentially
+    // from one.
+    proposal_count(storage).map(|count| count + 1)
 }
@@ -4,6 +4,7 @@ use secret_utils::Expiration;
 
 use crate::{
     m::{Stats, M},
+    resolution::{CycleResolution, Resolution},
     vote::Vote,
 };
 
@@ -74,7 +75,8 @@ impl Tally {
     /// Records a vote in the tally. The tally must not be expired.
     ///
     ///  - `vote` a list of candidates sorted in order from most to
-    ///    least favored
+    ///    least favored. candidates not in the list are unranked and
+    ///    tied below every ranked candidate.
     ///  - `power` the voting power of the voter
     ///
     /// Invariants:
@@ -82,6 +84,7 @@ impl Tally {
     /// - Voter has not already voted.
     /// - Tally is not expired.
     pub fn add_vote(&mut self, vote: Vote, power: Uint128) {
+        let mut ranked = vec![false; self.m.n as usize];
         for (index, preference) in vote.iter().enumerate() {
             // an interesting property of the symetry of M is that in
             // recording all the defeats, we also record all of the
@@ -89,11 +92,30 @@ impl Tally {
             for defeat in 0..index {
                 self.m.decrement((*preference, vote[defeat]), power)
             }
+            ranked[*preference as usize] = true;
+        }
+        // unranked candidates are defeated by every ranked
+        // candidate. they tie with each other, so the margins between
+        // them are unchanged.
+        for unranked in (0..self.m.n).filter(|c| !ranked[*c as usize]) {
+            for preference in vote.iter() {
+                self.m.decrement((unranked, *preference), power)
+            }
         }
         self.power_outstanding -= power;
         self.winner = self.winner();
     }
 
+    /// Resolves a cycle in the tally using `method`. Returns None if
+    /// there is no cycle to resolve because a candidate is the
+    /// Condorcet winner, or if `method` is `CycleResolution::None`.
+    pub fn resolve(&self, method: CycleResolution) -> Option<Resolution> {
+        match self.winner {
+            Winner::Never | Winner::None => method.resolve(&self.m),
+            Winner::Some(_) | Winner::Undisputed(_) => None,
+        }
+    }
+
     fn winner(&self) -> Winner {
         match self.m.stats(self.power_outstanding) {
             Stats::PositiveColumn { col, min_margin } => {
@@ -115,3 +137,23 @@ impl Tally {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cell::Cell;
+
+    #[test]
+    fn test_partial_ranking() {
+        let mut tally = Tally::new(3, Uint128::new(3), 0, Expiration::Never {});
+        tally.add_vote(Vote::new(vec![1], 3).unwrap(), Uint128::new(2));
+        tally.add_vote(Vote::new(vec![0, 1, 2], 3).unwrap(), Uint128::new(1));
+
+        // 1 beats 0 on the partial ballot and loses on the full one.
+        assert_eq!(tally.m.get((1, 0)), Cell::Positive(Uint128::new(1)));
+        assert_eq!(tally.m.get((1, 2)), Cell::Positive(Uint128::new(3)));
+        // 0 and 2 are tied on the partial ballot.
+        assert_eq!(tally.m.get((0, 2)), Cell::Positive(Uint128::new(1)));
+        assert_eq!(tally.winner, Winner::Undisputed(1));
+    }
+}
@@ -0,0 +1,469 @@
+use cosmwasm_std::testing::{
+    mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+};
+use cosmwasm_std::{
+    from_binary, to_binary, Addr, Binary, ContractResult, CosmosMsg, Decimal, Env, OwnedDeps,
+    Response, SystemResult, Uint128, WasmMsg, WasmQuery,
+};
+use cw_hooks::{HookItem, HooksResponse};
+use dao_hooks::proposal::{ProposalHookExecuteMsg, ProposalHookMsg};
+use dao_hooks::vote::{VoteHookExecuteMsg, VoteHookMsg};
+use dao_interface::voting::{
+    Query as VotingQuery, TotalPowerAtHeightResponse, VotingPowerAtHeightResponse,
+};
+use dao_voting::{
+    dependency::DependencyInfo, pre_propose::PreProposeInfo, status::Status as VotingStatus,
+    threshold::PercentageThreshold, veto::VetoConfig, veto::VetoError,
+};
+use secret_utils::Duration;
+
+use crate::contract::{execute, instantiate, query};
+use crate::msg::{Choice, ExecuteMsg, InstantiateMsg, QueryMsg, ViewingKeyError};
+use crate::proposal::{ProposalListResponse, ProposalResponse, Status};
+use crate::resolution::CycleResolution;
+use crate::vote::{Vote, VoteInfo, VoteResponse};
+use crate::ContractError;
+
+type Deps = OwnedDeps<MockStorage, MockApi, MockQuerier>;
+
+/// The height proposals are created at.
+const START: u64 = 12_345;
+
+/// Answers the DAO's voting power queries. Alice has 600 of the 1000
+/// voting power and Bob 300. Queries made with a key other than
+/// "key" fail.
+fn mock_dao(deps: &mut Deps) {
+    deps.querier.update_wasm(|query| {
+        let WasmQuery::Smart { msg, .. } = query else {
+            panic!("unexpected query");
+        };
+        let res = match from_binary(msg).unwrap() {
+            VotingQuery::VotingPowerAtHeight {
+                address,
+                key,
+                height,
+            } => {
+                if key != "key" {
+                    return SystemResult::Ok(ContractResult::Err("wrong viewing key".to_string()));
+                }
+                let power = match address.as_str() {
+                    "alice" => 600,
+                    "bob" => 300,
+                    _ => 0,
+                };
+                to_binary(&VotingPowerAtHeightResponse {
+                    power: Uint128::new(power),
+                    height: height.unwrap_or(START),
+                })
+            }
+            VotingQuery::TotalPowerAtHeight { height } => to_binary(&TotalPowerAtHeightResponse {
+                power: Uint128::new(1000),
+                height: height.unwrap_or(START),
+            }),
+            _ => panic!("unexpected query"),
+        };
+        SystemResult::Ok(ContractResult::Ok(res.unwrap()))
+    });
+}
+
+/// Sets up a module with a 10 block voting period that anyone with
+/// voting power may propose to.
+fn setup(veto: Option<VetoConfig>) -> Deps {
+    let mut deps = mock_dependencies();
+    mock_dao(&mut deps);
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("dao", &[]),
+        InstantiateMsg {
+            quorum: PercentageThreshold::Percent(Decimal::percent(15)),
+            voting_period: Duration::Height(10),
+            min_voting_period: None,
+            close_proposals_on_execution_failure: false,
+            veto,
+            cycle_resolution: CycleResolution::None,
+            pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
+            dao_code_hash: "dao_hash".to_string(),
+        },
+    )
+    .unwrap();
+    deps
+}
+
+fn env_at(height: u64) -> Env {
+    let mut env = mock_env();
+    env.block.height = height;
+    env
+}
+
+fn propose(deps: &mut Deps, proposer: &str) -> Result<Response, ContractError> {
+    execute(
+        deps.as_mut(),
+        env_at(START),
+        mock_info(proposer, &[]),
+        ExecuteMsg::Propose {
+            choices: vec![Choice { msgs: vec![] }],
+            key: "key".to_string(),
+            proposer: None,
+        },
+    )
+}
+
+fn vote(
+    deps: &mut Deps,
+    height: u64,
+    voter: &str,
+    vote: Vec<u32>,
+) -> Result<Response, ContractError> {
+    execute(
+        deps.as_mut(),
+        env_at(height),
+        mock_info(voter, &[]),
+        ExecuteMsg::Vote {
+            proposal_id: 1,
+            vote,
+            key: "key".to_string(),
+            rationale: Some("because".to_string()),
+        },
+    )
+}
+
+fn execute_proposal(deps: &mut Deps, height: u64, sender: &str) -> Result<Response, ContractError> {
+    execute(
+        deps.as_mut(),
+        env_at(height),
+        mock_info(sender, &[]),
+        ExecuteMsg::Execute {
+            proposal_id: 1,
+            key: "key".to_string(),
+        },
+    )
+}
+
+fn veto(deps: &mut Deps, height: u64, sender: &str) -> Result<Response, ContractError> {
+    execute(
+        deps.as_mut(),
+        env_at(height),
+        mock_info(sender, &[]),
+        ExecuteMsg::Veto { proposal_id: 1 },
+    )
+}
+
+fn status(deps: &Deps, height: u64) -> Status {
+    let ProposalResponse {
+        proposal, tally, ..
+    } = from_binary(&query(deps.as_ref(), env_at(height), QueryMsg::Proposal { id: 1 }).unwrap())
+        .unwrap();
+    proposal.status(&env_at(height).block, &tally).unwrap()
+}
+
+fn proposal_ids(deps: &Deps, msg: QueryMsg) -> Vec<u32> {
+    let ProposalListResponse { proposals } =
+        from_binary(&query(deps.as_ref(), env_at(START), msg).unwrap()).unwrap();
+    proposals.into_iter().map(|p| p.proposal.id).collect()
+}
+
+/// Returns the hook messages sent to HOOK in RES.
+fn hook_msgs<T: serde::de::DeserializeOwned>(res: &Response, hook: &str) -> Vec<T> {
+    res.messages
+        .iter()
+        .filter_map(|sub| match &sub.msg {
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr, msg, ..
+            }) if contract_addr == hook => Some(from_binary(msg).unwrap()),
+            _ => None,
+        })
+        .collect()
+}
+
+fn timelock() -> VetoConfig {
+    VetoConfig {
+        timelock_duration: Duration::Height(5),
+        vetoer: "vetoer".to_string(),
+        council: vec![],
+        veto_threshold: 1,
+        early_execute: false,
+        veto_before_passed: false,
+    }
+}
+
+#[test]
+fn test_list_proposals() {
+    let mut deps = setup(None);
+    for _ in 0..5 {
+        propose(&mut deps, "alice").unwrap();
+    }
+
+    let list = |start_after, limit| QueryMsg::ListProposals { start_after, limit };
+    assert_eq!(proposal_ids(&deps, list(None, None)), vec![1, 2, 3, 4, 5]);
+    assert_eq!(proposal_ids(&deps, list(Some(2), Some(2))), vec![3, 4]);
+    assert_eq!(proposal_ids(&deps, list(Some(4), None)), vec![5]);
+    assert_eq!(proposal_ids(&deps, list(Some(5), None)), Vec::<u32>::new());
+    assert_eq!(
+        proposal_ids(&deps, list(Some(u32::MAX), None)),
+        Vec::<u32>::new()
+    );
+
+    let reverse = |start_before, limit| QueryMsg::ReverseProposals {
+        start_before,
+        limit,
+    };
+    assert_eq!(
+        proposal_ids(&deps, reverse(None, None)),
+        vec![5, 4, 3, 2, 1]
+    );
+    assert_eq!(proposal_ids(&deps, reverse(Some(4), Some(2))), vec![3, 2]);
+    assert_eq!(proposal_ids(&deps, reverse(Some(100), Some(1))), vec![5]);
+    assert_eq!(
+        proposal_ids(&deps, reverse(Some(1), None)),
+        Vec::<u32>::new()
+    );
+
+    let count: u32 =
+        from_binary(&query(deps.as_ref(), env_at(START), QueryMsg::ProposalCount {}).unwrap())
+            .unwrap();
+    assert_eq!(count, 5);
+}
+
+#[test]
+fn test_ballots_are_private() {
+    let mut deps = setup(None);
+    propose(&mut deps, "alice").unwrap();
+    vote(&mut deps, START + 1, "alice", vec![0]).unwrap();
+    for (member, key) in [("alice", "alice_key"), ("bob", "bob_key")] {
+        execute(
+            deps.as_mut(),
+            env_at(START),
+            mock_info(member, &[]),
+            ExecuteMsg::SetViewingKey {
+                key: key.to_string(),
+                padding: None,
+            },
+        )
+        .unwrap();
+    }
+    let get_vote = |voter: &str, key: &str| {
+        query(
+            deps.as_ref(),
+            env_at(START + 1),
+            QueryMsg::GetVote {
+                proposal_id: 1,
+                voter: voter.to_string(),
+                key: key.to_string(),
+            },
+        )
+        .unwrap()
+    };
+
+    // Bob's key doesn't reveal Alice's ballot.
+    for key in ["wrong", "bob_key"] {
+        let err: ViewingKeyError = from_binary(&get_vote("alice", key)).unwrap();
+        assert_eq!(
+            err.msg,
+            "Wrong viewing key for this address or viewing key not set"
+        );
+    }
+
+    let VoteResponse { vote } = from_binary(&get_vote("alice", "alice_key")).unwrap();
+    assert_eq!(
+        vote,
+        Some(VoteInfo {
+            voter: Addr::unchecked("alice"),
+            vote: Vote::new(vec![0], 2).unwrap(),
+            power: Uint128::new(600),
+            rationale: Some("because".to_string()),
+        })
+    );
+    let VoteResponse { vote } = from_binary(&get_vote("bob", "bob_key")).unwrap();
+    assert_eq!(vote, None);
+}
+
+#[test]
+fn test_hooks() {
+    let mut deps = setup(None);
+    let add_hook = |address: &str| ExecuteMsg::AddProposalHook {
+        address: address.to_string(),
+        code_hash: "hook_hash".to_string(),
+    };
+
+    // Only the DAO may add hooks.
+    let err = execute(
+        deps.as_mut(),
+        env_at(START),
+        mock_info("alice", &[]),
+        add_hook("proposal_hook"),
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::NotDao {}));
+
+    execute(
+        deps.as_mut(),
+        env_at(START),
+        mock_info("dao", &[]),
+        add_hook("proposal_hook"),
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        env_at(START),
+        mock_info("dao", &[]),
+        ExecuteMsg::AddVoteHook {
+            address: "vote_hook".to_string(),
+            code_hash: "hook_hash".to_string(),
+        },
+    )
+    .unwrap();
+    let HooksResponse { hooks } =
+        from_binary(&query(deps.as_ref(), env_at(START), QueryMsg::ProposalHooks {}).unwrap())
+            .unwrap();
+    assert_eq!(
+        hooks,
+        vec![HookItem {
+            addr: Addr::unchecked("proposal_hook"),
+            code_hash: "hook_hash".to_string(),
+        }]
+    );
+
+    let res = propose(&mut deps, "alice").unwrap();
+    assert_eq!(
+        hook_msgs::<ProposalHookExecuteMsg>(&res, "proposal_hook"),
+        vec![ProposalHookExecuteMsg::ProposalHook(
+            ProposalHookMsg::NewProposal {
+                id: 1,
+                proposer: "alice".to_string(),
+                emergency: false,
+            }
+        )]
+    );
+
+    let res = vote(&mut deps, START + 1, "alice", vec![0]).unwrap();
+    assert_eq!(
+        hook_msgs::<VoteHookExecuteMsg>(&res, "vote_hook"),
+        vec![VoteHookExecuteMsg::VoteHook(VoteHookMsg::NewVote {
+            proposal_id: 1,
+            voter: "alice".to_string(),
+            vote: "0".to_string(),
+        })]
+    );
+
+    let res = execute_proposal(&mut deps, START + 1, "alice").unwrap();
+    assert_eq!(
+        hook_msgs::<ProposalHookExecuteMsg>(&res, "proposal_hook"),
+        vec![ProposalHookExecuteMsg::ProposalHook(
+            ProposalHookMsg::ProposalStatusChanged {
+                id: 1,
+                old_status: "passed".to_string(),
+                new_status: "executed".to_string(),
+            }
+        )]
+    );
+
+    // Removed hooks are no longer sent messages.
+    execute(
+        deps.as_mut(),
+        env_at(START),
+        mock_info("dao", &[]),
+        ExecuteMsg::RemoveProposalHook {
+            address: "proposal_hook".to_string(),
+            code_hash: "hook_hash".to_string(),
+        },
+    )
+    .unwrap();
+    let res = propose(&mut deps, "alice").unwrap();
+    assert!(res.messages.is_empty());
+}
+
+#[test]
+fn test_veto() {
+    let mut deps = setup(Some(timelock()));
+    propose(&mut deps, "alice").unwrap();
+
+    // Vetoing before a proposal passes is not enabled.
+    let err = veto(&mut deps, START + 1, "vetoer").unwrap_err();
+    assert!(matches!(
+        err,
+        ContractError::Veto(VetoError::NoVetoBeforePassed {})
+    ));
+
+    vote(&mut deps, START + 1, "alice", vec![0]).unwrap();
+    assert!(matches!(
+        status(&deps, START + 1),
+        Status::VetoTimelock { winner: 0, .. }
+    ));
+
+    // Members may not execute timelocked proposals.
+    let err = execute_proposal(&mut deps, START + 1, "alice").unwrap_err();
+    assert!(matches!(err, ContractError::Veto(VetoError::Timelocked {})));
+    // Nor veto them.
+    let err = veto(&mut deps, START + 1, "alice").unwrap_err();
+    assert!(matches!(
+        err,
+        ContractError::Veto(VetoError::Unauthorized {})
+    ));
+    // Early execution is not enabled.
+    let err = execute_proposal(&mut deps, START + 1, "vetoer").unwrap_err();
+    assert!(matches!(
+        err,
+        ContractError::Veto(VetoError::NoEarlyExecute {})
+    ));
+
+    veto(&mut deps, START + 2, "vetoer").unwrap();
+    assert_eq!(status(&deps, START + 2), Status::Vetoed);
+    // Vetoed proposals stay vetoed once the timelock expires.
+    assert_eq!(status(&deps, START + 100), Status::Vetoed);
+    let err = execute_proposal(&mut deps, START + 100, "alice").unwrap_err();
+    assert!(matches!(err, ContractError::Unexecutable {}));
+}
+
+#[test]
+fn test_veto_timelock_expires() {
+    let mut deps = setup(Some(timelock()));
+    propose(&mut deps, "alice").unwrap();
+    vote(&mut deps, START + 1, "alice", vec![0]).unwrap();
+
+    // The timelock runs for five blocks after voting closes.
+    assert!(matches!(
+        status(&deps, START + 14),
+        Status::VetoTimelock { winner: 0, .. }
+    ));
+    assert_eq!(status(&deps, START + 15), Status::Passed { winner: 0 });
+
+    let err = veto(&mut deps, START + 15, "vetoer").unwrap_err();
+    assert!(matches!(
+        err,
+        ContractError::Veto(VetoError::TimelockExpired {})
+    ));
+    execute_proposal(&mut deps, START + 15, "alice").unwrap();
+    assert_eq!(status(&deps, START + 15), Status::Executed);
+}
+
+#[test]
+fn test_dependency_query() {
+    #[derive(serde::Deserialize)]
+    struct DependencyResponse {
+        proposal: DependencyInfo,
+    }
+
+    // Other modules query proposals with `proposal_id` and read the
+    // status in their own form.
+    fn dependency(deps: &Deps, height: u64) -> DependencyInfo {
+        let msg =
+            from_binary(&Binary::from(br#"{"proposal":{"proposal_id":1}}"#.to_vec())).unwrap();
+        let res: DependencyResponse =
+            from_binary(&query(deps.as_ref(), env_at(height), msg).unwrap()).unwrap();
+        res.proposal
+    }
+
+    let mut deps = setup(None);
+    propose(&mut deps, "alice").unwrap();
+    assert_eq!(
+        dependency(&deps, START),
+        DependencyInfo {
+            status: VotingStatus::Open,
+            dependencies: vec![],
+        }
+    );
+
+    vote(&mut deps, START + 1, "alice", vec![0]).unwrap();
+    assert_eq!(dependency(&deps, START + 10).status, VotingStatus::Passed);
+}
@@ -1,14 +1,21 @@
-use std::ops::Index;
+use std::{fmt, ops::Index};
 
 use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Uint128};
 use thiserror::Error;
 
 #[cw_serde]
 pub struct Vote(Vec<u32>);
 
 impl Vote {
+    /// Creates a vote ranking `vote` in order of preference. A vote
+    /// may rank only some of the candidates, in which case the
+    /// unranked candidates are tied below every ranked one.
     pub(crate) fn new(vote: Vec<u32>, candidates: u32) -> Result<Self, VoteError> {
-        if vote.len() != candidates as usize {
+        if vote.is_empty() {
+            return Err(VoteError::EmptyBallot {});
+        }
+        if vote.len() > candidates as usize {
             return Err(VoteError::LenMissmatch {
                 got: vote.len() as u32,
                 expected: candidates,
@@ -32,6 +39,15 @@ impl Vote {
     }
 }
 
+/// Formats the vote as its candidates in order of preference
+/// separated by commas, for example "2,0,1".
+impl fmt::Display for Vote {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ranking: Vec<String> = self.0.iter().map(|c| c.to_string()).collect();
+        write!(f, "{}", ranking.join(","))
+    }
+}
+
 impl Index<usize> for Vote {
     type Output = u32;
 
@@ -40,6 +56,24 @@ impl Index<usize> for Vote {
     }
 }
 
+/// Information about a vote that was cast.
+#[cw_serde]
+pub struct VoteInfo {
+    /// The address that voted.
+    pub voter: Addr,
+    /// The voter's ranking of the proposal's candidates.
+    pub vote: Vote,
+    /// The voting power behind the vote.
+    pub power: Uint128,
+    /// The rationale behind the vote.
+    pub rationale: Option<String>,
+}
+
+#[cw_serde]
+pub struct VoteResponse {
+    pub vote: Option<VoteInfo>,
+}
+
 #[derive(Error, Debug, PartialEq)]
 pub enum VoteError {
     #[error("candidate ({candidate}) appears in ballot more than once")]
@@ -48,7 +82,10 @@ pub enum VoteError {
     #[error("no such candidate ({candidate})")]
     InvalidCandidate { candidate: u32 },
 
-    #[error("ballot has wrong number of candidates. got ({got}) expected ({expected})")]
+    #[error("ballot must rank at least one candidate")]
+    EmptyBallot {},
+
+    #[error("ballot has too many candidates. got ({got}) expected at most ({expected})")]
     LenMissmatch { got: u32, expected: u32 },
 }
 
@@ -72,12 +109,22 @@ mod tests {
         assert_eq!(
             Vote::new(vec![1, 1, 2, 2], 4).unwrap_err(),
             VoteError::DuplicateCandidate { candidate: 1 }
-        )
+        );
+        assert_eq!(Vote::new(vec![], 2).unwrap_err(), VoteError::EmptyBallot {});
     }
 
     #[test]
     fn test_vote_construction() {
         let vote = Vote::new(vec![0, 1, 2], 3).unwrap();
-        assert_eq!(vote.0, vec![0, 1, 2])
+        assert_eq!(vote.0, vec![0, 1, 2]);
+
+        let partial = Vote::new(vec![2], 3).unwrap();
+        assert_eq!(partial.0, vec![2])
+    }
+
+    #[test]
+    fn test_vote_display() {
+        let vote = Vote::new(vec![2, 0, 1], 3).unwrap();
+        assert_eq!(vote.to_string(), "2,0,1")
     }
 }
@@ -0,0 +1,854 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    to_binary, Addr, Binary, Decimal, Deps, DepsMut, Env, MessageInfo, Reply, Response, StdError,
+    StdResult, Storage, SubMsg, SubMsgResult, Uint128,
+};
+use cw_denom::{CheckedDenom, UncheckedDenom};
+use cw_hooks::{HookItem, Hooks};
+use dao_hooks::proposal::{
+    new_proposal_hooks, proposal_completed_hooks, proposal_status_changed_hooks,
+};
+use dao_hooks::vote::new_vote_hooks;
+use dao_interface::query::Snip20BalanceResponse;
+use dao_interface::state::AnyContractInfo;
+use dao_voting::pre_propose::{PreProposeInfo, ProposalCreationPolicy};
+use dao_voting::proposal::DEFAULT_LIMIT;
+use dao_voting::status::Status as CompletedStatus;
+use dao_voting::voting::{get_total_power, get_voting_power};
+use secret_cw2::set_contract_version;
+use secret_cw_controllers::ReplyEvent;
+use secret_toolkit::utils::HandleCallback;
+use secret_toolkit::viewing_key::{ViewingKey, ViewingKeyStore};
+use secret_utils::{parse_reply_event_for_contract_address, Duration};
+
+use crate::conviction::{half_life_units, now, threshold};
+use crate::msg::{
+    CreateViewingKey, ExecuteMsg, InstantiateMsg, ProposeMsg, QueryMsg, ViewingKeyError,
+};
+use crate::proposal::{advance_proposal_id, next_proposal_id, FundingRequest, Status};
+use crate::query::{ProposalListResponse, ProposalResponse, SupportResponse};
+use crate::state::{
+    Config, Support, CONFIG, CREATION_POLICY, DAO, MAX_SUPPORTED_PROPOSALS, PROPOSALS,
+    PROPOSAL_COUNT, PROPOSAL_HOOKS, REPLY_IDS, SUPPORT, SUPPORTERS, SUPPORT_KEYS, VOTE_HOOKS,
+};
+use crate::ContractError;
+
+pub(crate) const CONTRACT_NAME: &str = "crates.io:dao-proposal-conviction";
+pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    DAO.save(
+        deps.storage,
+        &AnyContractInfo {
+            code_hash: msg.dao_code_hash,
+            addr: info.sender.clone(),
+        },
+    )?;
+
+    let config = validate_config(
+        deps.as_ref(),
+        msg.denom,
+        msg.half_life,
+        msg.weight,
+        msg.max_ratio,
+        msg.min_threshold,
+        msg.close_proposal_on_execution_failure,
+    )?;
+
+    let (initial_policy, pre_propose_messages) = msg
+        .pre_propose_info
+        .into_initial_policy_and_messages(deps.storage, info.sender.clone(), REPLY_IDS)?;
+
+    // Initialize proposal count to zero so that queries return zero
+    // instead of None.
+    PROPOSAL_COUNT.save(deps.storage, &0)?;
+    CONFIG.save(deps.storage, &config)?;
+    CREATION_POLICY.save(deps.storage, &initial_policy)?;
+
+    Ok(Response::default()
+        .add_submessages(pre_propose_messages)
+        .add_attribute("action", "instantiate")
+        .add_attribute("dao", info.sender.to_string()))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Propose(ProposeMsg {
+            title,
+            description,
+            recipient,
+            amount,
+            proposer,
+        }) => execute_propose(
+            deps,
+            env,
+            info.sender,
+            title,
+            description,
+            recipient,
+            amount,
+            proposer,
+        ),
+        ExecuteMsg::Support {
+            key,
+            proposal_id,
+            power,
+        } => execute_support(deps, env, info, key, proposal_id, power),
+        ExecuteMsg::Execute { proposal_id } => execute_execute(deps, env, info, proposal_id),
+        ExecuteMsg::Cancel { proposal_id } => execute_cancel(deps, info, proposal_id),
+        ExecuteMsg::UpdateConfig {
+            denom,
+            half_life,
+            weight,
+            max_ratio,
+            min_threshold,
+            close_proposal_on_execution_failure,
+        } => {
+            if info.sender != DAO.load(deps.storage)?.addr {
+                return Err(ContractError::Unauthorized {});
+            }
+            let config = validate_config(
+                deps.as_ref(),
+                denom,
+                half_life,
+                weight,
+                max_ratio,
+                min_threshold,
+                close_proposal_on_execution_failure,
+            )?;
+            CONFIG.save(deps.storage, &config)?;
+            Ok(Response::default()
+                .add_attribute("action", "update_config")
+                .add_attribute("sender", info.sender))
+        }
+        ExecuteMsg::UpdatePreProposeInfo { info: new_info } => {
+            execute_update_proposal_creation_policy(deps, info, new_info)
+        }
+        ExecuteMsg::AddProposalHook { address, code_hash } => execute_update_hook(
+            deps,
+            info,
+            PROPOSAL_HOOKS,
+            "proposal",
+            address,
+            code_hash,
+            true,
+        ),
+        ExecuteMsg::RemoveProposalHook { address, code_hash } => execute_update_hook(
+            deps,
+            info,
+            PROPOSAL_HOOKS,
+            "proposal",
+            address,
+            code_hash,
+            false,
+        ),
+        ExecuteMsg::AddVoteHook { address, code_hash } => {
+            execute_update_hook(deps, info, VOTE_HOOKS, "vote", address, code_hash, true)
+        }
+        ExecuteMsg::RemoveVoteHook { address, code_hash } => {
+            execute_update_hook(deps, info, VOTE_HOOKS, "vote", address, code_hash, false)
+        }
+        ExecuteMsg::CreateViewingKey { entropy, .. } => {
+            let key = ViewingKey::create(
+                deps.storage,
+                &info,
+                &env,
+                info.sender.as_str(),
+                entropy.as_ref(),
+            );
+            Ok(Response::new().set_data(to_binary(&CreateViewingKey { key })?))
+        }
+        ExecuteMsg::SetViewingKey { key, .. } => {
+            ViewingKey::set(deps.storage, info.sender.as_str(), key.as_str());
+            Ok(Response::default())
+        }
+    }
+}
+
+fn validate_config(
+    deps: Deps,
+    denom: UncheckedDenom,
+    half_life: Duration,
+    weight: Decimal,
+    max_ratio: Decimal,
+    min_threshold: Decimal,
+    close_proposal_on_execution_failure: bool,
+) -> Result<Config, ContractError> {
+    if half_life_units(&half_life) == 0 {
+        return Err(ContractError::ZeroHalfLife {});
+    }
+    if max_ratio.is_zero() || max_ratio > Decimal::one() || min_threshold > Decimal::one() {
+        return Err(ContractError::InvalidThresholdParams {});
+    }
+    Ok(Config {
+        denom: denom.into_checked(deps)?,
+        half_life,
+        weight,
+        max_ratio,
+        min_threshold,
+        close_proposal_on_execution_failure,
+    })
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn execute_propose(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    title: String,
+    description: String,
+    recipient: String,
+    amount: Uint128,
+    proposer: Option<String>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let proposal_creation_policy = CREATION_POLICY.load(deps.storage)?;
+
+    // Check that the sender is permitted to create proposals.
+    if !proposal_creation_policy.is_permitted(&sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // Determine the appropriate proposer. If this is coming from our
+    // pre-propose module, it must be specified. Otherwise, the
+    // proposer should not be specified.
+    let proposer = match (proposer, &proposal_creation_policy) {
+        (None, ProposalCreationPolicy::Anyone {}) => sender.clone(),
+        (Some(proposer), ProposalCreationPolicy::Module { .. }) => {
+            deps.api.addr_validate(&proposer)?
+        }
+        _ => return Err(ContractError::InvalidProposer {}),
+    };
+
+    if amount.is_zero() {
+        return Err(ContractError::ZeroAmount {});
+    }
+
+    let proposal = FundingRequest {
+        title,
+        description,
+        proposer: proposer.clone(),
+        recipient: deps.api.addr_validate(&recipient)?,
+        amount,
+        start_height: env.block.height,
+        status: Status::Open,
+        support: Uint128::zero(),
+        conviction: Uint128::zero(),
+        last_update: now(&config.half_life, &env.block),
+    };
+    let id = advance_proposal_id(deps.storage)?;
+    PROPOSALS.insert(deps.storage, &id, &proposal)?;
+
+    let hooks = new_proposal_hooks(PROPOSAL_HOOKS, deps.storage, id, proposer.as_str())?;
+
+    Ok(Response::default()
+        .add_submessages(hooks)
+        .add_attribute("action", "propose")
+        .add_attribute("sender", sender)
+        .add_attribute("proposal_id", id.to_string())
+        .add_attribute("amount", amount))
+}
+
+pub fn execute_support(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    key: String,
+    proposal_id: u64,
+    power: Uint128,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let dao_info = DAO.load(deps.storage)?;
+    let mut prop = PROPOSALS
+        .get(deps.storage, &proposal_id)
+        .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+    if prop.status != Status::Open {
+        return Err(ContractError::NotOpen { id: proposal_id });
+    }
+
+    let voting_power = get_voting_power(
+        deps.as_ref(),
+        dao_info.code_hash.clone(),
+        info.sender.clone(),
+        key.clone(),
+        &dao_info.addr,
+        None,
+    )?;
+    if voting_power.is_zero() {
+        return Err(ContractError::NotRegistered {});
+    }
+    // Support for a proposal may not exceed the power the member held
+    // when it was created, so tokens moved to another address after
+    // that can't back it a second time.
+    let snapshot_power = get_voting_power(
+        deps.as_ref(),
+        dao_info.code_hash.clone(),
+        info.sender.clone(),
+        key.clone(),
+        &dao_info.addr,
+        Some(prop.start_height),
+    )?;
+    if power > snapshot_power {
+        return Err(ContractError::InsufficientPower {
+            support: power,
+            power: snapshot_power,
+        });
+    }
+
+    // Support for proposals which are no longer open is released.
+    let mut support = open_support(deps.storage, &info.sender);
+    let previous = support
+        .iter()
+        .position(|s| s.proposal_id == proposal_id)
+        .map(|idx| support.remove(idx).power)
+        .unwrap_or_default();
+    if !power.is_zero() {
+        support.push(Support { proposal_id, power });
+    }
+
+    let staked: Uint128 = support.iter().map(|s| s.power).sum();
+    if staked > voting_power {
+        return Err(ContractError::InsufficientPower {
+            support: staked,
+            power: voting_power,
+        });
+    }
+    if support.len() > MAX_SUPPORTED_PROPOSALS {
+        return Err(ContractError::TooManySupported {
+            max: MAX_SUPPORTED_PROPOSALS,
+        });
+    }
+
+    prop.update_support(
+        now(&config.half_life, &env.block),
+        half_life_units(&config.half_life),
+        previous,
+        power,
+    )?;
+    PROPOSALS.insert(deps.storage, &proposal_id, &prop)?;
+    save_support(deps.storage, &info.sender, &support)?;
+    let mut supporters = SUPPORTERS
+        .get(deps.storage, &proposal_id)
+        .unwrap_or_default();
+    supporters.retain(|member| *member != info.sender);
+    if !power.is_zero() {
+        supporters.push(info.sender.clone());
+    }
+    SUPPORTERS.insert(deps.storage, &proposal_id, &supporters)?;
+    SUPPORT_KEYS.insert(deps.storage, &info.sender, &key)?;
+
+    let hooks = new_vote_hooks(
+        VOTE_HOOKS,
+        deps.storage,
+        proposal_id,
+        info.sender.to_string(),
+        power.to_string(),
+    )?;
+
+    Ok(Response::default()
+        .add_submessages(hooks)
+        .add_attribute("action", "support")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("total_support", prop.support))
+}
+
+/// A member's support for proposals that are still open.
+fn open_support(storage: &dyn Storage, member: &Addr) -> Vec<Support> {
+    SUPPORT
+        .get(storage, member)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|s| {
+            PROPOSALS
+                .get(storage, &s.proposal_id)
+                .map_or(false, |p| p.status == Status::Open)
+        })
+        .collect()
+}
+
+/// Saves a member's support, removing them if they support nothing.
+fn save_support(storage: &mut dyn Storage, member: &Addr, support: &[Support]) -> StdResult<()> {
+    if support.is_empty() {
+        SUPPORT.remove(storage, member)
+    } else {
+        SUPPORT.insert(storage, member, &support.to_vec())
+    }
+}
+
+/// Re-checks the current voting power of everyone supporting a
+/// proposal and withdraws the support they no longer have the power
+/// to back, along with the conviction it built up. Members whose
+/// power can't be read, for example because their viewing key has
+/// changed, are treated as having none.
+fn release_stale_support(
+    deps: DepsMut,
+    dao_info: &AnyContractInfo,
+    proposal_id: u64,
+    prop: &mut FundingRequest,
+    now: u64,
+    half_life: u64,
+) -> StdResult<Uint128> {
+    let mut supporters = SUPPORTERS
+        .get(deps.storage, &proposal_id)
+        .unwrap_or_default();
+    let mut released = Uint128::zero();
+    for member in supporters.clone() {
+        let key = SUPPORT_KEYS.get(deps.storage, &member).unwrap_or_default();
+        let voting_power = get_voting_power(
+            deps.as_ref(),
+            dao_info.code_hash.clone(),
+            member.clone(),
+            key,
+            &dao_info.addr,
+            None,
+        )
+        .unwrap_or_default();
+
+        let mut support = open_support(deps.storage, &member);
+        let staked: Uint128 = support.iter().map(|s| s.power).sum();
+        let Some(idx) = support.iter().position(|s| s.proposal_id == proposal_id) else {
+            continue;
+        };
+        if staked <= voting_power {
+            continue;
+        }
+        let stale = (staked - voting_power).min(support[idx].power);
+        support[idx].power -= stale;
+        if support[idx].power.is_zero() {
+            support.remove(idx);
+            supporters.retain(|m| *m != member);
+        }
+        save_support(deps.storage, &member, &support)?;
+        released += stale;
+    }
+
+    if !released.is_zero() {
+        prop.release_stale_support(now, half_life, released)?;
+        SUPPORTERS.insert(deps.storage, &proposal_id, &supporters)?;
+    }
+    Ok(released)
+}
+
+pub fn execute_execute(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let dao_info = DAO.load(deps.storage)?;
+    let mut prop = PROPOSALS
+        .get(deps.storage, &proposal_id)
+        .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+    if prop.status != Status::Open {
+        return Err(ContractError::NotOpen { id: proposal_id });
+    }
+
+    let now = now(&config.half_life, &env.block);
+    let half_life = half_life_units(&config.half_life);
+    let released = release_stale_support(
+        deps.branch(),
+        &dao_info,
+        proposal_id,
+        &mut prop,
+        now,
+        half_life,
+    )?;
+    let conviction = prop.current_conviction(now, half_life);
+    let threshold = required_conviction(deps.as_ref(), &env, &config, &dao_info, &prop)?;
+    if threshold.map_or(true, |threshold| conviction < threshold) {
+        return Err(ContractError::InsufficientConviction {
+            conviction,
+            threshold,
+        });
+    }
+
+    let old_status = prop.status;
+    prop.status = Status::Executed;
+    prop.conviction = conviction;
+    prop.last_update = now;
+    PROPOSALS.insert(deps.storage, &proposal_id, &prop)?;
+    SUPPORTERS.remove(deps.storage, &proposal_id)?;
+
+    let execute_message = dao_interface::msg::ExecuteMsg::ExecuteProposalHook {
+        msgs: vec![config
+            .denom
+            .get_transfer_to_message(&prop.recipient, prop.amount)?],
+    }
+    .to_cosmos_msg(dao_info.code_hash, dao_info.addr.to_string(), None)?;
+    // If the proposal is closed should its execution fail, whether
+    // it completed as executed is only known once execution replies,
+    // so the deposit module is told then.
+    let (response, status_hooks) = if config.close_proposal_on_execution_failure {
+        let reply_id = REPLY_IDS.add_event(
+            deps.storage,
+            ReplyEvent::FailedProposalExecution { proposal_id },
+        )?;
+        (
+            Response::default().add_submessage(SubMsg::reply_always(execute_message, reply_id)),
+            proposal_status_changed_hooks(
+                PROPOSAL_HOOKS,
+                deps.storage,
+                proposal_id,
+                old_status.to_string(),
+                prop.status.to_string(),
+            )?,
+        )
+    } else {
+        (
+            Response::default().add_message(execute_message),
+            status_change_hooks(deps.storage, proposal_id, old_status, prop.status)?,
+        )
+    };
+
+    Ok(response
+        .add_submessages(status_hooks)
+        .add_attribute("action", "execute")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("conviction", conviction)
+        .add_attribute("released_support", released))
+}
+
+pub fn execute_cancel(
+    deps: DepsMut,
+    info: MessageInfo,
+    proposal_id: u64,
+) -> Result<Response, ContractError> {
+    let dao_info = DAO.load(deps.storage)?;
+    let mut prop = PROPOSALS
+        .get(deps.storage, &proposal_id)
+        .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+    if info.sender != prop.proposer && info.sender != dao_info.addr {
+        return Err(ContractError::Unauthorized {});
+    }
+    if prop.status != Status::Open {
+        return Err(ContractError::NotOpen { id: proposal_id });
+    }
+
+    let old_status = prop.status;
+    prop.status = Status::Cancelled;
+    PROPOSALS.insert(deps.storage, &proposal_id, &prop)?;
+    SUPPORTERS.remove(deps.storage, &proposal_id)?;
+
+    let status_hooks = status_change_hooks(deps.storage, proposal_id, old_status, prop.status)?;
+
+    Ok(Response::default()
+        .add_submessages(status_hooks)
+        .add_attribute("action", "cancel")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string()))
+}
+
+/// The conviction an open request needs to execute given the DAO's
+/// current voting power and treasury.
+fn required_conviction(
+    deps: Deps,
+    env: &Env,
+    config: &Config,
+    dao_info: &AnyContractInfo,
+    prop: &FundingRequest,
+) -> StdResult<Option<Uint128>> {
+    let total_power = get_total_power(
+        deps,
+        dao_info.code_hash.clone(),
+        &dao_info.addr,
+        Some(env.block.height),
+    )?;
+    let treasury = match &config.denom {
+        CheckedDenom::Native(denom) => deps.querier.query_balance(&dao_info.addr, denom)?.amount,
+        CheckedDenom::Cw20(token, _) => {
+            let res: Snip20BalanceResponse = deps.querier.query_wasm_smart(
+                dao_info.code_hash.clone(),
+                dao_info.addr.clone(),
+                &dao_interface::msg::QueryMsg::Snip20Balance {
+                    token: token.to_string(),
+                },
+            )?;
+            res.balance
+        }
+    };
+    Ok(threshold(
+        prop.amount,
+        treasury,
+        total_power,
+        config.weight,
+        config.max_ratio,
+        config.min_threshold,
+    ))
+}
+
+/// Prepares proposal status change hooks and, if the proposal has
+/// completed, the pre-propose module's completed hook which handles
+/// deposit refunds.
+fn status_change_hooks(
+    storage: &mut dyn Storage,
+    proposal_id: u64,
+    old_status: Status,
+    new_status: Status,
+) -> StdResult<Vec<SubMsg>> {
+    let mut hooks = proposal_status_changed_hooks(
+        PROPOSAL_HOOKS,
+        storage,
+        proposal_id,
+        old_status.to_string(),
+        new_status.to_string(),
+    )?;
+    if let Some(completed) = new_status.completed() {
+        let proposal_creation_policy = CREATION_POLICY.load(storage)?;
+        hooks.extend(proposal_completed_hooks(
+            proposal_creation_policy,
+            proposal_id,
+            completed,
+        )?);
+    }
+    Ok(hooks)
+}
+
+pub fn execute_update_proposal_creation_policy(
+    deps: DepsMut,
+    info: MessageInfo,
+    new_info: PreProposeInfo,
+) -> Result<Response, ContractError> {
+    let dao_info = DAO.load(deps.storage)?;
+
+    if dao_info.addr != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let (initial_policy, messages) =
+        new_info.into_initial_policy_and_messages(deps.storage, dao_info.addr, REPLY_IDS)?;
+    CREATION_POLICY.save(deps.storage, &initial_policy)?;
+
+    Ok(Response::default()
+        .add_submessages(messages)
+        .add_attribute("action", "update_proposal_creation_policy")
+        .add_attribute("sender", info.sender)
+        .add_attribute("new_policy", format!("{initial_policy:?}")))
+}
+
+/// Adds or removes a hook consumer. Only the DAO may call this
+/// method.
+pub fn execute_update_hook(
+    deps: DepsMut,
+    info: MessageInfo,
+    hooks: Hooks,
+    kind: &str,
+    address: String,
+    code_hash: String,
+    add: bool,
+) -> Result<Response, ContractError> {
+    let dao_info = DAO.load(deps.storage)?;
+    if dao_info.addr != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let hook = HookItem {
+        addr: deps.api.addr_validate(&address)?,
+        code_hash,
+    };
+    let action = if add {
+        hooks.add_hook(deps.storage, hook)?;
+        "add"
+    } else {
+        hooks.remove_hook(deps.storage, hook)?;
+        "remove"
+    };
+
+    Ok(Response::default()
+        .add_attribute("action", format!("{action}_{kind}_hook"))
+        .add_attribute("address", address))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&CONFIG.load(deps.storage)?),
+        QueryMsg::Dao {} => to_binary(&DAO.load(deps.storage)?),
+        QueryMsg::Proposal { proposal_id } => {
+            let proposal = PROPOSALS
+                .get(deps.storage, &proposal_id)
+                .ok_or_else(|| StdError::not_found("proposal"))?;
+            to_binary(&proposal_response(deps, &env, proposal_id, proposal)?)
+        }
+        QueryMsg::ListProposals { start_after, limit } => {
+            query_list_proposals(deps, env, start_after, limit)
+        }
+        QueryMsg::NextProposalId {} => to_binary(&next_proposal_id(deps.storage)?),
+        QueryMsg::ProposalCount {} => to_binary(&PROPOSAL_COUNT.load(deps.storage)?),
+        QueryMsg::ProposalCreationPolicy {} => to_binary(&CREATION_POLICY.load(deps.storage)?),
+        QueryMsg::ProposalHooks {} => to_binary(&PROPOSAL_HOOKS.query_hooks(deps)?),
+        QueryMsg::VoteHooks {} => to_binary(&VOTE_HOOKS.query_hooks(deps)?),
+        QueryMsg::Info {} => to_binary(&dao_interface::voting::InfoResponse {
+            info: secret_cw2::get_contract_version(deps.storage)?,
+        }),
+        _ => viewing_keys_queries(deps, msg),
+    }
+}
+
+pub fn viewing_keys_queries(deps: Deps, msg: QueryMsg) -> StdResult<Binary> {
+    let (addresses, key) = msg.get_validation_params(deps.api)?;
+
+    for address in addresses {
+        let result = ViewingKey::check(deps.storage, address.as_str(), key.as_str());
+        if result.is_ok() {
+            return match msg {
+                QueryMsg::Support { .. } => {
+                    let support = open_support(deps.storage, &address);
+                    let staked = support.iter().map(|s| s.power).sum();
+                    to_binary(&SupportResponse { support, staked })
+                }
+                _ => panic!("This query type does not require authentication"),
+            };
+        }
+    }
+
+    to_binary(&ViewingKeyError {
+        msg: "Wrong viewing key for this address or viewing key not set".to_string(),
+    })
+}
+
+/// Describes a proposal along with its current conviction and, if it
+/// is open, the conviction it needs to execute.
+fn proposal_response(
+    deps: Deps,
+    env: &Env,
+    id: u64,
+    proposal: FundingRequest,
+) -> StdResult<ProposalResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let (conviction, threshold) = if proposal.status == Status::Open {
+        let dao_info = DAO.load(deps.storage)?;
+        (
+            proposal.current_conviction(
+                now(&config.half_life, &env.block),
+                half_life_units(&config.half_life),
+            ),
+            required_conviction(deps, env, &config, &dao_info, &proposal)?,
+        )
+    } else {
+        (proposal.conviction, None)
+    };
+    Ok(ProposalResponse {
+        id,
+        proposal,
+        conviction,
+        threshold,
+    })
+}
+
+pub fn query_list_proposals(
+    deps: Deps,
+    env: Env,
+    start_after: Option<u64>,
+    limit: Option<u64>,
+) -> StdResult<Binary> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT) as usize;
+    let proposals = PROPOSALS
+        .iter(deps.storage)?
+        .filter(|item| match (item, start_after) {
+            (Ok((id, _)), Some(start_after)) => *id > start_after,
+            _ => true,
+        })
+        .take(limit)
+        .map(|item| item.and_then(|(id, proposal)| proposal_response(deps, &env, id, proposal)))
+        .collect::<StdResult<Vec<ProposalResponse>>>()?;
+
+    to_binary(&ProposalListResponse { proposals })
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    let repl = REPLY_IDS.get_event(deps.storage, msg.id)?;
+    match (repl, msg.result) {
+        // The proposal executed, so the deposit module may now be
+        // told it completed.
+        (ReplyEvent::FailedProposalExecution { proposal_id }, SubMsgResult::Ok(_)) => {
+            let proposal_creation_policy = CREATION_POLICY.load(deps.storage)?;
+            Ok(Response::new().add_submessages(proposal_completed_hooks(
+                proposal_creation_policy,
+                proposal_id,
+                CompletedStatus::Executed,
+            )?))
+        }
+        (ReplyEvent::FailedProposalExecution { proposal_id }, SubMsgResult::Err(err)) => {
+            let mut prop = PROPOSALS
+                .get(deps.storage, &proposal_id)
+                .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+            prop.status = Status::ExecutionFailed;
+            PROPOSALS.insert(deps.storage, &proposal_id, &prop)?;
+
+            let status_hooks =
+                status_change_hooks(deps.storage, proposal_id, Status::Executed, prop.status)?;
+
+            Ok(Response::new()
+                .add_submessages(status_hooks)
+                .add_attribute("proposal_execution_failed", proposal_id.to_string())
+                .add_attribute("error", err))
+        }
+        (ReplyEvent::FailedProposalHook { idx }, SubMsgResult::Err(_)) => {
+            let hook_item = PROPOSAL_HOOKS.remove_hook_by_index(deps.storage, idx)?;
+            Ok(Response::new().add_attribute(
+                "removed_proposal_hook",
+                format!("{0}:{idx}", hook_item.addr),
+            ))
+        }
+        (ReplyEvent::FailedVoteHook { idx }, SubMsgResult::Err(_)) => {
+            let hook_item = VOTE_HOOKS.remove_hook_by_index(deps.storage, idx)?;
+            Ok(Response::new()
+                .add_attribute("removed_vote_hook", format!("{0}:{idx}", hook_item.addr)))
+        }
+        (ReplyEvent::PreProposalModuleInstantiate { code_hash }, SubMsgResult::Ok(res)) => {
+            let contract_address = parse_reply_event_for_contract_address(res.events)?;
+            let module_addr = deps.api.addr_validate(&contract_address)?;
+            CREATION_POLICY.save(
+                deps.storage,
+                &ProposalCreationPolicy::Module {
+                    addr: module_addr.clone(),
+                    code_hash,
+                },
+            )?;
+
+            let response = Response::new().add_attribute("update_pre_propose_module", module_addr);
+            Ok(match res.data {
+                Some(data) => response.set_data(data),
+                None => response,
+            })
+        }
+        (ReplyEvent::FailedPreProposeModuleHook {}, _) => {
+            let addr = match CREATION_POLICY.load(deps.storage)? {
+                ProposalCreationPolicy::Anyone {} => {
+                    // Something is off if we're getting this reply
+                    // and we don't have a pre-propose module
+                    // installed. This should be unreachable.
+                    return Err(ContractError::InvalidReplyID { id: msg.id });
+                }
+                ProposalCreationPolicy::Module { addr, .. } => {
+                    // Our pre-propose module has errored while
+                    // receiving a proposal hook. Rest in peace
+                    // pre-propose module.
+                    CREATION_POLICY.save(deps.storage, &ProposalCreationPolicy::Anyone {})?;
+                    addr
+                }
+            };
+            Ok(Response::new().add_attribute("failed_prepropose_hook", addr))
+        }
+        _ => Err(ContractError::UnknownReplyID {}),
+    }
+}
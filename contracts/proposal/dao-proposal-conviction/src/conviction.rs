@@ -0,0 +1,178 @@
+use cosmwasm_std::{BlockInfo, Decimal, Uint128};
+use secret_utils::Duration;
+
+/// The number of half-lives after which conviction is considered to
+/// have fully converged on a request's support.
+const MAX_HALVINGS: u64 = 64;
+
+/// The current point in time in the units of `half_life`: the block
+/// height for height based half-lives, and seconds otherwise.
+pub fn now(half_life: &Duration, block: &BlockInfo) -> u64 {
+    match half_life {
+        Duration::Height(_) => block.height,
+        Duration::Time(_) => block.time.seconds(),
+    }
+}
+
+/// The length of a half-life in its own units.
+pub fn half_life_units(half_life: &Duration) -> u64 {
+    match half_life {
+        Duration::Height(units) | Duration::Time(units) => *units,
+    }
+}
+
+/// The fraction of the gap between conviction and support remaining
+/// after `elapsed` units, i.e. `0.5 ^ (elapsed / half_life)`. Within
+/// each half-life the curve is interpolated linearly, so the result
+/// is exact at whole half-lives and never off by more than a few
+/// percent in between.
+pub fn decay(elapsed: u64, half_life: u64) -> Decimal {
+    let halvings = elapsed / half_life;
+    if halvings >= MAX_HALVINGS {
+        return Decimal::zero();
+    }
+    let whole = Decimal::from_ratio(1u128, 1u128 << halvings);
+    let partial = Decimal::one() - Decimal::from_ratio(elapsed % half_life, 2 * half_life);
+    whole * partial
+}
+
+/// Moves `conviction` towards `support` over `elapsed` units. Rising
+/// support builds conviction, and withdrawn support lets it decay,
+/// along the same half-life curve.
+pub fn accrue(conviction: Uint128, support: Uint128, elapsed: u64, half_life: u64) -> Uint128 {
+    let remaining = decay(elapsed, half_life);
+    if conviction >= support {
+        support + (conviction - support) * remaining
+    } else {
+        support - (support - conviction) * remaining
+    }
+}
+
+/// The conviction a request for `amount` needs to execute, given the
+/// DAO's `total_power` and `treasury` balance:
+///
+/// ```text
+/// total_power * max(min_threshold, weight / (max_ratio - amount / treasury)^2)
+/// ```
+///
+/// Returns None if the request can never execute because it asks for
+/// `max_ratio` or more of the treasury.
+pub fn threshold(
+    amount: Uint128,
+    treasury: Uint128,
+    total_power: Uint128,
+    weight: Decimal,
+    max_ratio: Decimal,
+    min_threshold: Decimal,
+) -> Option<Uint128> {
+    if treasury.is_zero() {
+        return None;
+    }
+    let ratio = Decimal::from_ratio(amount, treasury);
+    if ratio >= max_ratio {
+        return None;
+    }
+    let gap = max_ratio - ratio;
+    let gap_squared = (gap * gap).atomics();
+    if gap_squared.is_zero() {
+        return None;
+    }
+    let required = total_power.multiply_ratio(weight.atomics(), gap_squared);
+    Some(required.max(total_power * min_threshold))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decay() {
+        assert_eq!(decay(0, 10), Decimal::one());
+        assert_eq!(decay(10, 10), Decimal::percent(50));
+        assert_eq!(decay(20, 10), Decimal::percent(25));
+        assert_eq!(decay(5, 10), Decimal::percent(75));
+        assert_eq!(decay(15, 10), Decimal::permille(375));
+        assert_eq!(decay(10 * MAX_HALVINGS, 10), Decimal::zero());
+    }
+
+    #[test]
+    fn test_accrue() {
+        // Conviction builds towards support.
+        let conviction = accrue(Uint128::zero(), Uint128::new(100), 10, 10);
+        assert_eq!(conviction, Uint128::new(50));
+        let conviction = accrue(conviction, Uint128::new(100), 10, 10);
+        assert_eq!(conviction, Uint128::new(75));
+
+        // Withdrawing support decays conviction gradually.
+        let conviction = accrue(conviction, Uint128::zero(), 10, 10);
+        assert_eq!(conviction, Uint128::new(37));
+        assert_eq!(accrue(conviction, Uint128::zero(), 0, 10), Uint128::new(37));
+    }
+
+    #[test]
+    fn test_threshold() {
+        let total_power = Uint128::new(1000);
+        let weight = Decimal::permille(25);
+        let max_ratio = Decimal::percent(50);
+
+        // 0.025 / (0.5 - 0.1)^2 = 0.15625
+        assert_eq!(
+            threshold(
+                Uint128::new(10),
+                Uint128::new(100),
+                total_power,
+                weight,
+                max_ratio,
+                Decimal::zero()
+            ),
+            Some(Uint128::new(156))
+        );
+        // Larger requests need more conviction.
+        assert_eq!(
+            threshold(
+                Uint128::new(30),
+                Uint128::new(100),
+                total_power,
+                weight,
+                max_ratio,
+                Decimal::zero()
+            ),
+            Some(Uint128::new(625))
+        );
+        // Small requests need at least the minimum threshold.
+        assert_eq!(
+            threshold(
+                Uint128::new(1),
+                Uint128::new(100),
+                total_power,
+                weight,
+                max_ratio,
+                Decimal::percent(20)
+            ),
+            Some(Uint128::new(200))
+        );
+        // Requests for max_ratio or more of the treasury never pass.
+        assert_eq!(
+            threshold(
+                Uint128::new(50),
+                Uint128::new(100),
+                total_power,
+                weight,
+                max_ratio,
+                Decimal::zero()
+            ),
+            None
+        );
+        assert_eq!(
+            threshold(
+                Uint128::new(1),
+                Uint128::zero(),
+                total_power,
+                weight,
+                max_ratio,
+                Decimal::zero()
+            ),
+            None
+        );
+    }
+}
@@ -0,0 +1,68 @@
+use cosmwasm_std::{StdError, Uint128};
+use cw_denom::DenomError;
+use cw_hooks::HookError;
+use secret_cw_controllers::ReplyError;
+use secret_utils::ParseReplyError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error(transparent)]
+    Std(#[from] StdError),
+
+    #[error(transparent)]
+    ParseReplyError(#[from] ParseReplyError),
+
+    #[error(transparent)]
+    HookError(#[from] HookError),
+
+    #[error(transparent)]
+    ReplyUdError(#[from] ReplyError),
+
+    #[error(transparent)]
+    DenomError(#[from] DenomError),
+
+    #[error("unauthorized")]
+    Unauthorized {},
+
+    #[error("no such proposal ({id})")]
+    NoSuchProposal { id: u64 },
+
+    #[error("An unknown reply ID was received.")]
+    UnknownReplyID {},
+
+    #[error("received a reply failure with an invalid ID: ({id})")]
+    InvalidReplyID { id: u64 },
+
+    #[error(
+        "pre-propose modules must specify a proposer. lacking one, no proposer should be specified"
+    )]
+    InvalidProposer {},
+
+    #[error("the half-life must be non-zero")]
+    ZeroHalfLife {},
+
+    #[error("max_ratio must be greater than zero and at most one, and min_threshold at most one")]
+    InvalidThresholdParams {},
+
+    #[error("funding requests must ask for a non-zero amount")]
+    ZeroAmount {},
+
+    #[error("proposal ({id}) is not open")]
+    NotOpen { id: u64 },
+
+    #[error("not registered to vote (no voting power)")]
+    NotRegistered {},
+
+    #[error("support of ({support}) exceeds available voting power of ({power})")]
+    InsufficientPower { support: Uint128, power: Uint128 },
+
+    #[error("a member may support at most ({max}) open proposals")]
+    TooManySupported { max: usize },
+
+    #[error("conviction of ({conviction}) has not reached the required ({threshold:?})")]
+    InsufficientConviction {
+        conviction: Uint128,
+        threshold: Option<Uint128>,
+    },
+}
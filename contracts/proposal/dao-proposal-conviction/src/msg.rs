@@ -0,0 +1,182 @@
+use cosmwasm_schema::QueryResponses;
+use cosmwasm_std::{Addr, Api, Decimal, StdResult, Uint128};
+use cw_denom::UncheckedDenom;
+use dao_dao_macros::proposal_module_query;
+use dao_voting::pre_propose::PreProposeInfo;
+use schemars::JsonSchema;
+use secret_utils::Duration;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub struct InstantiateMsg {
+    /// The token funding requests are paid in. SNIP-20 tokens must be
+    /// registered with the DAO so that its balance may be read.
+    pub denom: UncheckedDenom,
+    /// The time it takes conviction to close half of the gap to a
+    /// proposal's current support.
+    pub half_life: Duration,
+    /// Scales the conviction needed to execute a request.
+    pub weight: Decimal,
+    /// The largest share of the treasury a single request may ask
+    /// for.
+    pub max_ratio: Decimal,
+    /// The smallest share of the DAO's total voting power that must
+    /// have been built up as conviction for any request to execute.
+    pub min_threshold: Decimal,
+    /// Information about what addresses may create proposals.
+    pub pre_propose_info: PreProposeInfo,
+    /// If set to true proposals will be closed if their execution
+    /// fails. Otherwise, proposals will remain open after execution
+    /// failure.
+    pub close_proposal_on_execution_failure: bool,
+
+    pub dao_code_hash: String,
+}
+
+/// The message used to create a funding request.
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub struct ProposeMsg {
+    /// The title of the request.
+    pub title: String,
+    /// A description of the request.
+    pub description: String,
+    /// The address the requested funds are sent to.
+    pub recipient: String,
+    /// The amount of the module's token requested.
+    pub amount: Uint128,
+    /// The address creating the request. If no pre-propose module is
+    /// attached to this module this must always be None as the
+    /// proposer is the sender of the propose message. If a
+    /// pre-propose module is attached, this must be Some and will
+    /// set the proposer of the request it creates.
+    pub proposer: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Creates a funding request in the module.
+    Propose(ProposeMsg),
+    /// Sets the voting power the sender stakes behind a proposal.
+    /// This may not exceed the sender's voting power when the
+    /// proposal was created, and the sender's total stake across
+    /// open proposals may not exceed their current voting power.
+    /// Setting zero withdraws support.
+    Support {
+        /// The viewing key of the sender. Kept so that the sender's
+        /// power can be checked again before the proposal executes.
+        key: String,
+        proposal_id: u64,
+        power: Uint128,
+    },
+    /// Executes a request whose conviction has passed its
+    /// threshold, sending the requested funds from the DAO. Support
+    /// its members no longer have the voting power for is withdrawn
+    /// first.
+    Execute { proposal_id: u64 },
+    /// Cancels an open request. Only callable by the proposer or the
+    /// DAO.
+    Cancel { proposal_id: u64 },
+    /// Updates the module's configuration. Only the DAO may call
+    /// this method.
+    UpdateConfig {
+        denom: UncheckedDenom,
+        half_life: Duration,
+        weight: Decimal,
+        max_ratio: Decimal,
+        min_threshold: Decimal,
+        close_proposal_on_execution_failure: bool,
+    },
+    /// Update's the proposal creation policy used for this
+    /// module. Only the DAO may call this method.
+    UpdatePreProposeInfo { info: PreProposeInfo },
+    /// Adds an address as a consumer of proposal hooks. Consumers of
+    /// proposal hooks have hook messages executed on them whenever
+    /// the status of a proposal changes or a proposal is created. If
+    /// a consumer contract errors when handling a hook message it
+    /// will be removed from the list of consumers.
+    AddProposalHook { address: String, code_hash: String },
+    /// Removes a consumer of proposal hooks.
+    RemoveProposalHook { address: String, code_hash: String },
+    /// Adds an address as a consumer of vote hooks. Consumers of vote
+    /// hooks are sent the new amount of support as the vote whenever
+    /// a member changes their support. If a consumer contract errors
+    /// when handling a hook message it will be removed from the list
+    /// of consumers.
+    AddVoteHook { address: String, code_hash: String },
+    /// Removed a consumer of vote hooks.
+    RemoveVoteHook { address: String, code_hash: String },
+    CreateViewingKey {
+        entropy: String,
+        padding: Option<String>,
+    },
+    SetViewingKey {
+        key: String,
+        padding: Option<String>,
+    },
+}
+
+#[proposal_module_query]
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    /// Gets the proposal module's config.
+    #[returns(crate::state::Config)]
+    Config {},
+    /// Gets information about a proposal, including its current
+    /// conviction and threshold.
+    #[returns(crate::query::ProposalResponse)]
+    Proposal { proposal_id: u64 },
+    /// Lists all the proposals that have been created in this
+    /// module.
+    #[returns(crate::query::ProposalListResponse)]
+    ListProposals {
+        /// The proposal ID to start listing proposals after.
+        start_after: Option<u64>,
+        /// The maximum number of proposals to return as part of this
+        /// query. If no limit is set a max of 30 proposals will be
+        /// returned.
+        limit: Option<u64>,
+    },
+    /// Lists the open proposals a member supports. Requires the
+    /// member's viewing key.
+    #[returns(crate::query::SupportResponse)]
+    Support { address: String, key: String },
+    /// Returns the number of proposals that have been created in this module.
+    #[returns(::std::primitive::u64)]
+    ProposalCount {},
+    /// Gets the current proposal creation policy for this module.
+    #[returns(::dao_voting::pre_propose::ProposalCreationPolicy)]
+    ProposalCreationPolicy {},
+    /// Lists all of the consumers of proposal hooks for this module.
+    #[returns(::cw_hooks::HooksResponse)]
+    ProposalHooks {},
+    /// Lists all of the consumers of vote hooks for this module.
+    #[returns(::cw_hooks::HooksResponse)]
+    VoteHooks {},
+}
+
+impl QueryMsg {
+    pub fn get_validation_params(&self, api: &dyn Api) -> StdResult<(Vec<Addr>, String)> {
+        match self {
+            Self::Support { address, key } => {
+                let address = api.addr_validate(address.as_str())?;
+                Ok((vec![address], key.clone()))
+            }
+            _ => panic!("This query type does not require authentication"),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct CreateViewingKey {
+    pub key: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct ViewingKeyError {
+    pub msg: String,
+}
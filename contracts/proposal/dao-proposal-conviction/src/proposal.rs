@@ -0,0 +1,173 @@
+use cosmwasm_std::{Addr, StdResult, Storage, Uint128};
+use dao_voting::status::Status as CompletedStatus;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::conviction::accrue;
+use crate::state::PROPOSAL_COUNT;
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum Status {
+    /// The request is open for support.
+    Open,
+    /// The request's conviction passed its threshold and the funds
+    /// were sent.
+    Executed,
+    /// The request was cancelled by its proposer or the DAO.
+    Cancelled,
+    /// The request's execution failed.
+    ExecutionFailed,
+}
+
+impl std::fmt::Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Status::Open => write!(f, "open"),
+            Status::Executed => write!(f, "executed"),
+            Status::Cancelled => write!(f, "cancelled"),
+            Status::ExecutionFailed => write!(f, "execution_failed"),
+        }
+    }
+}
+
+impl Status {
+    /// The status reported to the pre-propose module when a request
+    /// completes.
+    pub fn completed(&self) -> Option<CompletedStatus> {
+        match self {
+            Status::Executed => Some(CompletedStatus::Executed),
+            Status::Cancelled => Some(CompletedStatus::Closed),
+            Status::ExecutionFailed => Some(CompletedStatus::ExecutionFailed),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub struct FundingRequest {
+    /// The title of the request
+    pub title: String,
+    /// The main body of the request text
+    pub description: String,
+    /// The address that created this request.
+    pub proposer: Addr,
+    /// The address the requested funds are sent to.
+    pub recipient: Addr,
+    /// The amount of the module's token requested.
+    pub amount: Uint128,
+    /// The block height at which this request was created.
+    pub start_height: u64,
+    /// The request status
+    pub status: Status,
+    /// The voting power currently staked behind the request.
+    pub support: Uint128,
+    /// The request's conviction as of `last_update`.
+    pub conviction: Uint128,
+    /// When conviction was last computed, in the units of the
+    /// module's half-life.
+    pub last_update: u64,
+}
+
+pub fn next_proposal_id(store: &dyn Storage) -> StdResult<u64> {
+    Ok(PROPOSAL_COUNT.may_load(store)?.unwrap_or_default() + 1)
+}
+
+pub fn advance_proposal_id(store: &mut dyn Storage) -> StdResult<u64> {
+    let id: u64 = next_proposal_id(store)?;
+    PROPOSAL_COUNT.save(store, &id)?;
+    Ok(id)
+}
+
+impl FundingRequest {
+    /// The request's conviction at `now`.
+    pub fn current_conviction(&self, now: u64, half_life: u64) -> Uint128 {
+        accrue(
+            self.conviction,
+            self.support,
+            now.saturating_sub(self.last_update),
+            half_life,
+        )
+    }
+
+    /// Brings the request's conviction up to date, then moves its
+    /// support by removing `removed` and adding `added`. Conviction
+    /// accrues towards the new support from `now` on.
+    pub fn update_support(
+        &mut self,
+        now: u64,
+        half_life: u64,
+        removed: Uint128,
+        added: Uint128,
+    ) -> StdResult<()> {
+        self.conviction = self.current_conviction(now, half_life);
+        self.last_update = now;
+        self.support = self.support.checked_sub(removed)?.checked_add(added)?;
+        Ok(())
+    }
+
+    /// Withdraws `stale` support whose members no longer hold the
+    /// voting power behind it. Unlike support a member moves away,
+    /// the conviction it built up is withdrawn with it rather than
+    /// left to decay.
+    pub fn release_stale_support(
+        &mut self,
+        now: u64,
+        half_life: u64,
+        stale: Uint128,
+    ) -> StdResult<()> {
+        self.update_support(now, half_life, stale, Uint128::zero())?;
+        self.conviction = self.conviction.saturating_sub(stale);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request() -> FundingRequest {
+        FundingRequest {
+            title: "title".to_string(),
+            description: "description".to_string(),
+            proposer: Addr::unchecked("proposer"),
+            recipient: Addr::unchecked("recipient"),
+            amount: Uint128::new(10),
+            start_height: 0,
+            status: Status::Open,
+            support: Uint128::zero(),
+            conviction: Uint128::zero(),
+            last_update: 0,
+        }
+    }
+
+    #[test]
+    fn test_update_support() {
+        let mut req = request();
+        req.update_support(0, 10, Uint128::zero(), Uint128::new(100))
+            .unwrap();
+        assert_eq!(req.current_conviction(10, 10), Uint128::new(50));
+
+        // Moving support away lets conviction decay from where it
+        // had built up to, rather than resetting it.
+        req.update_support(10, 10, Uint128::new(60), Uint128::zero())
+            .unwrap();
+        assert_eq!(req.conviction, Uint128::new(50));
+        assert_eq!(req.support, Uint128::new(40));
+        assert_eq!(req.current_conviction(20, 10), Uint128::new(45));
+        assert_eq!(req.current_conviction(1000, 10), Uint128::new(40));
+    }
+
+    #[test]
+    fn test_release_stale_support() {
+        let mut req = request();
+        req.update_support(0, 10, Uint128::zero(), Uint128::new(100))
+            .unwrap();
+        req.release_stale_support(1000, 10, Uint128::new(60))
+            .unwrap();
+        assert_eq!(req.support, Uint128::new(40));
+        assert_eq!(req.conviction, Uint128::new(40));
+        assert_eq!(req.last_update, 1000);
+    }
+}
@@ -0,0 +1,35 @@
+use crate::{proposal::FundingRequest, state::Support};
+use cosmwasm_std::Uint128;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Information about a proposal returned by proposal queries.
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub struct ProposalResponse {
+    /// The ID of the proposal being returned.
+    pub id: u64,
+    pub proposal: FundingRequest,
+    /// The proposal's conviction as of the current block.
+    pub conviction: Uint128,
+    /// The conviction the proposal needs to execute given the DAO's
+    /// current voting power and treasury. None if it can never
+    /// execute or is no longer open.
+    pub threshold: Option<Uint128>,
+}
+
+/// A list of proposals returned by `ListProposals`.
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub struct ProposalListResponse {
+    pub proposals: Vec<ProposalResponse>,
+}
+
+/// The open proposals a member supports.
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub struct SupportResponse {
+    pub support: Vec<Support>,
+    /// The total voting power the member has staked.
+    pub staked: Uint128,
+}
@@ -0,0 +1,72 @@
+use cosmwasm_std::{Addr, Decimal, Uint128};
+use cw_denom::CheckedDenom;
+use cw_hooks::Hooks;
+use dao_interface::state::AnyContractInfo;
+use dao_voting::pre_propose::ProposalCreationPolicy;
+use schemars::JsonSchema;
+use secret_cw_controllers::ReplyIds;
+use secret_storage_plus::Item;
+use secret_toolkit::{serialization::Json, storage::Keymap};
+use secret_utils::Duration;
+use serde::{Deserialize, Serialize};
+
+use crate::proposal::FundingRequest;
+
+/// The maximum number of open proposals a member may support at
+/// once. Bounds the cost of updating a member's support.
+pub const MAX_SUPPORTED_PROPOSALS: usize = 20;
+
+/// The conviction module's configuration.
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub struct Config {
+    /// The token funding requests are paid in.
+    pub denom: CheckedDenom,
+    /// The time it takes conviction to close half of the gap to a
+    /// proposal's current support.
+    pub half_life: Duration,
+    /// Scales the conviction needed to execute a request.
+    pub weight: Decimal,
+    /// The largest share of the treasury a single request may ask
+    /// for. Requests approaching this share need conviction
+    /// approaching infinity.
+    pub max_ratio: Decimal,
+    /// The smallest share of the DAO's total voting power that must
+    /// have been built up as conviction for any request to execute.
+    pub min_threshold: Decimal,
+    /// If set to true proposals will be closed if their execution
+    /// fails. Otherwise, proposals will remain open after execution
+    /// failure.
+    pub close_proposal_on_execution_failure: bool,
+}
+
+/// A member's support for a proposal.
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub struct Support {
+    pub proposal_id: u64,
+    /// The voting power staked behind the proposal.
+    pub power: Uint128,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+/// The number of proposals that have been created.
+pub const PROPOSAL_COUNT: Item<u64> = Item::new("proposal_count");
+pub static PROPOSALS: Keymap<u64, FundingRequest, Json> = Keymap::new(b"proposals");
+/// The open proposals each member supports.
+pub static SUPPORT: Keymap<Addr, Vec<Support>, Json> = Keymap::new(b"support");
+/// The members supporting each open proposal.
+pub static SUPPORTERS: Keymap<u64, Vec<Addr>, Json> = Keymap::new(b"supporters");
+/// The viewing key each member last gave for the DAO's voting power
+/// queries. Used to re-check a proposal's supporters before it
+/// executes.
+pub static SUPPORT_KEYS: Keymap<Addr, String, Json> = Keymap::new(b"support_keys");
+/// Consumers of proposal state change hooks.
+pub const PROPOSAL_HOOKS: Hooks = Hooks::new("proposal_hooks");
+/// Consumers of vote hooks. Support changes are sent as votes.
+pub const VOTE_HOOKS: Hooks = Hooks::new("vote_hooks");
+/// The address of the pre-propose module associated with this
+/// proposal module (if any).
+pub const CREATION_POLICY: Item<ProposalCreationPolicy> = Item::new("creation_policy");
+pub const DAO: Item<AnyContractInfo> = Item::new("dao");
+pub const REPLY_IDS: ReplyIds = ReplyIds::new(b"reply_ids", b"reply_ids_count");
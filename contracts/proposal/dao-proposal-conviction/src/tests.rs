@@ -0,0 +1,286 @@
+use cosmwasm_std::testing::{
+    mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+};
+use cosmwasm_std::{
+    coins, from_binary, to_binary, Addr, ContractResult, Decimal, Env, OwnedDeps, Response,
+    SystemResult, Uint128, WasmQuery,
+};
+use cw_denom::UncheckedDenom;
+use dao_interface::voting::{
+    Query as VotingQuery, TotalPowerAtHeightResponse, VotingPowerAtHeightResponse,
+};
+use dao_voting::pre_propose::PreProposeInfo;
+use secret_utils::Duration;
+
+use crate::contract::{execute, instantiate};
+use crate::msg::{ExecuteMsg, InstantiateMsg, ProposeMsg};
+use crate::proposal::Status;
+use crate::state::{PROPOSALS, SUPPORT, SUPPORTERS};
+use crate::ContractError;
+
+type Deps = OwnedDeps<MockStorage, MockApi, MockQuerier>;
+
+/// The height requests are created at.
+const START: u64 = 12_345;
+
+/// Answers the DAO's voting power queries with `power(address,
+/// height)` and reports a total power of 1000. Queries made with a
+/// key other than "key" fail.
+fn mock_dao(deps: &mut Deps, power: fn(&str, Option<u64>) -> u128) {
+    deps.querier.update_wasm(move |query| {
+        let WasmQuery::Smart { msg, .. } = query else {
+            panic!("unexpected query");
+        };
+        let res = match from_binary(msg).unwrap() {
+            VotingQuery::VotingPowerAtHeight {
+                address,
+                key,
+                height,
+            } => {
+                if key != "key" {
+                    return SystemResult::Ok(ContractResult::Err("wrong viewing key".to_string()));
+                }
+                to_binary(&VotingPowerAtHeightResponse {
+                    power: Uint128::new(power(&address, height)),
+                    height: height.unwrap_or(START),
+                })
+            }
+            VotingQuery::TotalPowerAtHeight { height } => to_binary(&TotalPowerAtHeightResponse {
+                power: Uint128::new(1000),
+                height: height.unwrap_or(START),
+            }),
+            _ => panic!("unexpected query"),
+        };
+        SystemResult::Ok(ContractResult::Ok(res.unwrap()))
+    });
+}
+
+/// Sets up a module with a 10 block half-life whose requests need
+/// conviction of at least 200, and creates one request.
+fn setup(power: fn(&str, Option<u64>) -> u128) -> Deps {
+    let mut deps = mock_dependencies();
+    mock_dao(&mut deps, power);
+    deps.querier.update_balance("dao", coins(1000, "ujuno"));
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("dao", &[]),
+        InstantiateMsg {
+            denom: UncheckedDenom::Native("ujuno".to_string()),
+            half_life: Duration::Height(10),
+            weight: Decimal::zero(),
+            max_ratio: Decimal::percent(50),
+            min_threshold: Decimal::percent(20),
+            pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
+            close_proposal_on_execution_failure: false,
+            dao_code_hash: "hash".to_string(),
+        },
+    )
+    .unwrap();
+    propose(&mut deps, START);
+    deps
+}
+
+fn propose(deps: &mut Deps, height: u64) {
+    execute(
+        deps.as_mut(),
+        env_at(height),
+        mock_info("proposer", &[]),
+        ExecuteMsg::Propose(ProposeMsg {
+            title: "title".to_string(),
+            description: "description".to_string(),
+            recipient: "recipient".to_string(),
+            amount: Uint128::new(10),
+            proposer: None,
+        }),
+    )
+    .unwrap();
+}
+
+fn env_at(height: u64) -> Env {
+    let mut env = mock_env();
+    env.block.height = height;
+    env
+}
+
+fn support(
+    deps: &mut Deps,
+    height: u64,
+    member: &str,
+    proposal_id: u64,
+    power: u128,
+) -> Result<(), ContractError> {
+    execute(
+        deps.as_mut(),
+        env_at(height),
+        mock_info(member, &[]),
+        ExecuteMsg::Support {
+            key: "key".to_string(),
+            proposal_id,
+            power: Uint128::new(power),
+        },
+    )
+    .map(|_| ())
+}
+
+fn execute_request(deps: &mut Deps, height: u64) -> Result<Response, ContractError> {
+    execute(
+        deps.as_mut(),
+        env_at(height),
+        mock_info("anyone", &[]),
+        ExecuteMsg::Execute { proposal_id: 1 },
+    )
+}
+
+#[test]
+fn test_support_checks_snapshot_power() {
+    // Alice moved her 100 tokens to Bob after the request was
+    // created.
+    let mut deps = setup(|address, height| match (address, height) {
+        ("alice", Some(START)) => 100,
+        ("bob", Some(START)) => 0,
+        ("alice", _) => 0,
+        ("bob", _) => 100,
+        _ => 0,
+    });
+
+    // Bob's tokens weren't his when the request was created.
+    let err = support(&mut deps, START + 1, "bob", 1, 50).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InsufficientPower {
+            support: Uint128::new(50),
+            power: Uint128::zero(),
+        }
+    );
+    // Alice no longer holds them.
+    let err = support(&mut deps, START + 1, "alice", 1, 50).unwrap_err();
+    assert_eq!(err, ContractError::NotRegistered {});
+
+    // Requests created after the move may be backed by Bob.
+    propose(&mut deps, START + 1);
+    let err = support(&mut deps, START + 1, "bob", 2, 101).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InsufficientPower {
+            support: Uint128::new(101),
+            power: Uint128::new(100),
+        }
+    );
+}
+
+#[test]
+fn test_support_is_split_across_requests() {
+    let mut deps = setup(|address, _| if address == "alice" { 100 } else { 0 });
+    propose(&mut deps, START);
+
+    support(&mut deps, START + 1, "alice", 1, 60).unwrap();
+    let err = support(&mut deps, START + 1, "alice", 2, 50).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InsufficientPower {
+            support: Uint128::new(110),
+            power: Uint128::new(100),
+        }
+    );
+    support(&mut deps, START + 1, "alice", 2, 40).unwrap();
+
+    // Withdrawing support removes the member from the request's
+    // supporters.
+    support(&mut deps, START + 2, "alice", 1, 0).unwrap();
+    assert_eq!(SUPPORTERS.get(&deps.storage, &1), Some(vec![]));
+    let prop = PROPOSALS.get(&deps.storage, &1).unwrap();
+    assert_eq!(prop.support, Uint128::zero());
+}
+
+#[test]
+fn test_execute() {
+    let mut deps = setup(|address, _| if address == "alice" { 300 } else { 0 });
+    support(&mut deps, START + 1, "alice", 1, 300).unwrap();
+
+    // Conviction has only had one half-life to build.
+    let err = execute_request(&mut deps, START + 11).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InsufficientConviction {
+            conviction: Uint128::new(150),
+            threshold: Some(Uint128::new(200)),
+        }
+    );
+
+    execute_request(&mut deps, START + 1000).unwrap();
+    let prop = PROPOSALS.get(&deps.storage, &1).unwrap();
+    assert_eq!(prop.status, Status::Executed);
+    assert_eq!(prop.support, Uint128::new(300));
+    assert_eq!(SUPPORTERS.get(&deps.storage, &1), None);
+}
+
+#[test]
+fn test_execute_releases_stale_support() {
+    let mut deps = setup(|address, _| if address == "alice" { 300 } else { 0 });
+    support(&mut deps, START + 1, "alice", 1, 300).unwrap();
+    // Alice has since unstaked 200 of her tokens.
+    mock_dao(&mut deps, |address, height| match (address, height) {
+        ("alice", Some(START)) => 300,
+        ("alice", _) => 100,
+        _ => 0,
+    });
+
+    // Conviction has converged on 300, but only the 100 Alice still
+    // holds counts.
+    let err = execute_request(&mut deps, START + 1000).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InsufficientConviction {
+            conviction: Uint128::new(100),
+            threshold: Some(Uint128::new(200)),
+        }
+    );
+}
+
+#[test]
+fn test_execute_releases_support_of_unreadable_members() {
+    let mut deps = setup(|address, _| match address {
+        "alice" => 300,
+        "bob" => 250,
+        _ => 0,
+    });
+    support(&mut deps, START + 1, "alice", 1, 300).unwrap();
+    support(&mut deps, START + 1, "bob", 1, 250).unwrap();
+
+    // Alice's power can no longer be read with the key she gave.
+    deps.querier.update_wasm(|query| {
+        let WasmQuery::Smart { msg, .. } = query else {
+            panic!("unexpected query");
+        };
+        let res = match from_binary(msg).unwrap() {
+            VotingQuery::VotingPowerAtHeight { address, .. } if address == "alice" => {
+                return SystemResult::Ok(ContractResult::Err("wrong viewing key".to_string()))
+            }
+            VotingQuery::VotingPowerAtHeight { height, .. } => {
+                to_binary(&VotingPowerAtHeightResponse {
+                    power: Uint128::new(250),
+                    height: height.unwrap_or(START),
+                })
+            }
+            VotingQuery::TotalPowerAtHeight { height } => to_binary(&TotalPowerAtHeightResponse {
+                power: Uint128::new(1000),
+                height: height.unwrap_or(START),
+            }),
+            _ => panic!("unexpected query"),
+        };
+        SystemResult::Ok(ContractResult::Ok(res.unwrap()))
+    });
+
+    // Bob's support alone carries the request.
+    let res = execute_request(&mut deps, START + 1000).unwrap();
+    assert!(res
+        .attributes
+        .iter()
+        .any(|attr| attr.key == "released_support" && attr.value == "300"));
+    let prop = PROPOSALS.get(&deps.storage, &1).unwrap();
+    assert_eq!(prop.status, Status::Executed);
+    assert_eq!(prop.support, Uint128::new(250));
+    assert_eq!(prop.conviction, Uint128::new(250));
+    assert_eq!(SUPPORT.get(&deps.storage, &Addr::unchecked("alice")), None);
+}
@@ -1,25 +1,33 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_binary, Addr, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Reply, Response, StdError,
-    StdResult, Storage, SubMsg, SubMsgResult,
+    to_binary, Addr, Binary, BlockInfo, Deps, DepsMut, Empty, Env, MessageInfo, Reply, Response,
+    StdError, StdResult, Storage, SubMsg, SubMsgResult,
 };
 
 use cw_hooks::{HookItem, Hooks};
 use dao_hooks::proposal::{
-    new_proposal_hooks, proposal_completed_hooks, proposal_status_changed_hooks,
+    new_proposal_hooks, proposal_closed_hooks, proposal_completed_hooks,
+    proposal_status_changed_hooks,
 };
-use dao_hooks::vote::new_vote_hooks;
+use dao_hooks::vote::{new_vote_hooks, voting_period_extended_hooks};
 use dao_interface::state::{AnyContractInfo, VotingModuleInfo};
 use dao_interface::voting::IsActiveResponse;
-use dao_voting::veto::{VetoConfig, VetoError};
+use dao_voting::veto::{withdraw_veto, VetoConfig, VetoError};
 use dao_voting::{
+    condition::{check_conditions, validate_conditions, ExecutionCondition},
+    dependency::{
+        check_dependencies, dependency_graph, validate_dependencies, DependencyError,
+        DependencyGraphResponse, DependencyInfo, ProposalDependency,
+    },
+    late_vote_extension::LateVoteExtensionConfig,
     multiple_choice::{
         MultipleChoiceOptions, MultipleChoiceVote, MultipleChoiceVotes, VotingStrategy,
     },
     pre_propose::{PreProposeInfo, ProposalCreationPolicy},
     proposal::{DEFAULT_LIMIT, MAX_PROPOSAL_SIZE},
     status::Status,
+    turnout::DynamicQuorumResponse,
     voting::{get_total_power, get_voting_power, validate_voting_period},
 };
 use secret_cw2::set_contract_version;
@@ -30,13 +38,15 @@ use secret_toolkit::viewing_key::{ViewingKey, ViewingKeyStore};
 use secret_utils::{parse_reply_event_for_contract_address, Duration};
 
 use crate::msg::{CreateViewingKey, QueryWithPermit, ViewingKeyError};
-use crate::state::{DAO, REPLY_IDS};
+use crate::state::{DAO, REPLY_IDS, TURNOUT};
 use crate::{msg::MigrateMsg, state::CREATION_POLICY};
 use crate::{
     msg::{ExecuteMsg, InstantiateMsg, QueryMsg},
     proposal::{MultipleChoiceProposal, VoteResult},
     query::{ProposalListResponse, ProposalResponse, VoteInfo, VoteListResponse, VoteResponse},
-    state::{Config, BALLOTS, CONFIG, PROPOSALS, PROPOSAL_COUNT, PROPOSAL_HOOKS, VOTE_HOOKS},
+    state::{
+        Ballot, Config, BALLOTS, CONFIG, PROPOSALS, PROPOSAL_COUNT, PROPOSAL_HOOKS, VOTE_HOOKS,
+    },
     ContractError,
 };
 
@@ -76,6 +86,11 @@ pub fn instantiate(
         veto_config.validate(&deps.as_ref(), &max_voting_period)?;
     };
 
+    // if late vote extension is configured, validate its fields
+    if let Some(extension_config) = &msg.late_vote_extension {
+        extension_config.validate(&max_voting_period)?;
+    };
+
     let config = Config {
         voting_strategy: msg.voting_strategy,
         min_voting_period,
@@ -84,6 +99,8 @@ pub fn instantiate(
         allow_revoting: msg.allow_revoting,
         close_proposal_on_execution_failure: msg.close_proposal_on_execution_failure,
         veto: msg.veto,
+        late_vote_extension: msg.late_vote_extension,
+        close_proposal_on_condition_failure: msg.close_proposal_on_condition_failure,
     };
 
     // Initialize proposal count to zero so that queries return zero
@@ -111,6 +128,8 @@ pub fn execute(
             description,
             choices,
             proposer,
+            conditions,
+            dependencies,
         } => execute_propose(
             deps,
             env,
@@ -119,6 +138,8 @@ pub fn execute(
             description,
             choices,
             proposer,
+            conditions,
+            dependencies,
         ),
         ExecuteMsg::Vote {
             key,
@@ -130,6 +151,9 @@ pub fn execute(
             execute_execute(deps, env, info, key, proposal_id)
         }
         ExecuteMsg::Veto { proposal_id } => execute_veto(deps, env, info, proposal_id),
+        ExecuteMsg::WithdrawVeto { proposal_id } => {
+            execute_withdraw_veto(deps, env, info, proposal_id)
+        }
         ExecuteMsg::Close { proposal_id } => execute_close(deps, env, info, proposal_id),
         ExecuteMsg::UpdateConfig {
             voting_strategy,
@@ -141,6 +165,8 @@ pub fn execute(
             code_hash,
             close_proposal_on_execution_failure,
             veto,
+            late_vote_extension,
+            close_proposal_on_condition_failure,
         } => execute_update_config(
             deps,
             info,
@@ -153,6 +179,8 @@ pub fn execute(
             code_hash,
             close_proposal_on_execution_failure,
             veto,
+            late_vote_extension,
+            close_proposal_on_condition_failure,
         ),
         ExecuteMsg::UpdatePreProposeInfo { info: new_info } => {
             execute_update_proposal_creation_policy(deps, info, new_info)
@@ -179,6 +207,7 @@ pub fn execute(
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn execute_propose(
     deps: DepsMut,
     env: Env,
@@ -187,6 +216,8 @@ pub fn execute_propose(
     description: String,
     options: MultipleChoiceOptions,
     proposer: Option<String>,
+    conditions: Vec<ExecutionCondition>,
+    dependencies: Vec<ProposalDependency>,
 ) -> Result<Response<Empty>, ContractError> {
     let config = CONFIG.load(deps.storage)?;
     let dao_info = DAO.load(deps.storage)?;
@@ -210,6 +241,17 @@ pub fn execute_propose(
         _ => return Err(ContractError::InvalidProposer {}),
     };
 
+    validate_conditions(deps.api, &conditions)?;
+    let local = |id: u64| local_dependency_info(deps.storage, &env.block, id);
+    validate_dependencies(
+        deps.as_ref(),
+        &env.contract.address,
+        &dao_info,
+        next_proposal_id(deps.storage)?,
+        &dependencies,
+        &local,
+    )?;
+
     let voting_module: VotingModuleInfo = deps.querier.query_wasm_smart(
         dao_info.code_hash.clone(),
         dao_info.addr.clone(),
@@ -246,13 +288,21 @@ pub fn execute_propose(
             start_height: env.block.height,
             min_voting_period: config.min_voting_period.map(|min| min.after(&env.block)),
             expiration,
-            voting_strategy: config.voting_strategy,
+            // Dynamic quorums are fixed when the proposal is created.
+            voting_strategy: config
+                .voting_strategy
+                .resolve(&TURNOUT.may_load(deps.storage)?.unwrap_or_default()),
             total_power,
             status: Status::Open,
             votes: MultipleChoiceVotes::zero(checked_multiple_choice_options.len()),
             allow_revoting: config.allow_revoting,
             choices: checked_multiple_choice_options,
             veto: config.veto,
+            late_vote_extension: config.late_vote_extension,
+            extensions: 0,
+            conditions,
+            dependencies,
+            vetoes: vec![],
         };
         // Update the proposal's status. Addresses case where proposal
         // expires on the same block as it is created.
@@ -343,6 +393,16 @@ pub fn execute_veto(
         }
     }
 
+    // Record the sender's veto. The proposal is vetoed once the veto
+    // threshold is reached.
+    if !veto_config.cast_veto(&mut prop.vetoes, &info.sender)? {
+        PROPOSALS.insert(deps.storage, &proposal_id, &prop)?;
+        return Ok(Response::new()
+            .add_attribute("action", "veto")
+            .add_attribute("proposal_id", proposal_id.to_string())
+            .add_attribute("vetoes", prop.vetoes.len().to_string()));
+    }
+
     // Update proposal status to vetoed
     prop.status = Status::Vetoed;
     PROPOSALS.insert(deps.storage, &proposal_id, &prop)?;
@@ -439,42 +499,43 @@ pub fn execute_vote(
     //     }),
     // })?;
 
-    let current_ballot = BALLOTS.get(deps.storage, &(proposal_id, info.sender.clone()));
-    if current_ballot.clone().is_some() {
-        if prop.allow_revoting {
-            if current_ballot.clone().unwrap().vote == vote {
-                //  Don't allow casting the same vote more than
-                // once. This seems liable to be confusing
-                // behavior.
-                return Err(ContractError::AlreadyCast {});
-            } else {
-                // Remove the old vote if this is a re-vote.
-                prop.votes.remove_vote(
-                    current_ballot.clone().unwrap().vote,
-                    current_ballot.clone().unwrap().power,
-                )?;
-                current_ballot.clone().unwrap().power = vote_power;
-                current_ballot.clone().unwrap().vote = vote;
-                current_ballot.clone().unwrap().rationale = rationale.clone();
-            }
-        } else {
+    // Snapshot the outcome before this vote is counted so that late
+    // swings can be detected.
+    let previous_outcome = prop.outcome(&env.block)?;
+
+    if let Some(current_ballot) = BALLOTS.get(deps.storage, &(proposal_id, info.sender.clone())) {
+        if !prop.allow_revoting {
             return Err(ContractError::AlreadyVoted {});
         }
-    } else {
-        current_ballot.clone().unwrap().power = vote_power;
-        current_ballot.clone().unwrap().vote = vote;
-        current_ballot.clone().unwrap().rationale = rationale.clone();
+        if current_ballot.vote == vote {
+            // Don't allow casting the same vote more than
+            // once. This seems liable to be confusing
+            // behavior.
+            return Err(ContractError::AlreadyCast {});
+        }
+        // Remove the old vote if this is a re-vote.
+        prop.votes
+            .remove_vote(current_ballot.vote, current_ballot.power)?;
     }
     BALLOTS.insert(
         deps.storage,
         &(proposal_id, info.sender.clone()),
-        &current_ballot.unwrap(),
+        &Ballot {
+            power: vote_power,
+            vote,
+            rationale,
+        },
     )?;
 
     let old_status = prop.status;
 
     prop.votes.add_vote(vote, vote_power)?;
     prop.update_status(&env.block)?;
+
+    // A proposal whose outcome is already decided can not be swung,
+    // so only open proposals are extended.
+    let extended = prop.status == Status::Open
+        && prop.apply_late_vote_extension(&env.block, previous_outcome)?;
     PROPOSALS.insert(deps.storage, &proposal_id, &prop)?;
     let new_status = prop.status;
     let change_hooks = proposal_status_changed_hooks(
@@ -491,14 +552,55 @@ pub fn execute_vote(
         info.sender.to_string(),
         vote.to_string(),
     )?;
+    let extension_hooks = if extended {
+        voting_period_extended_hooks(
+            VOTE_HOOKS,
+            deps.storage,
+            proposal_id,
+            prop.expiration.to_string(),
+            prop.extensions,
+        )?
+    } else {
+        vec![]
+    };
     Ok(Response::default()
         .add_submessages(change_hooks)
         .add_submessages(vote_hooks)
+        .add_submessages(extension_hooks)
         .add_attribute("action", "vote")
         .add_attribute("sender", info.sender)
         .add_attribute("proposal_id", proposal_id.to_string())
         .add_attribute("position", vote.to_string())
-        .add_attribute("status", prop.status.to_string()))
+        .add_attribute("status", prop.status.to_string())
+        .add_attribute("extended", extended.to_string()))
+}
+
+pub fn execute_withdraw_veto(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+) -> Result<Response, ContractError> {
+    let mut prop = PROPOSALS
+        .get(deps.storage, &proposal_id)
+        .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+
+    // Vetoes may not be withdrawn once the proposal has been vetoed.
+    prop.update_status(&env.block)?;
+    if prop.status == Status::Vetoed {
+        return Err(ContractError::VetoError(VetoError::InvalidProposalStatus {
+            status: prop.status.to_string(),
+        }));
+    }
+
+    withdraw_veto(&mut prop.vetoes, &info.sender)?;
+    PROPOSALS.insert(deps.storage, &proposal_id, &prop)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "withdraw_veto")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("vetoes", prop.vetoes.len().to_string()))
 }
 
 pub fn execute_execute(
@@ -551,7 +653,7 @@ pub fn execute_execute(
                 .ok_or(VetoError::NoVetoConfiguration {})?;
 
             // check that the sender is the vetoer
-            if veto_config.vetoer != info.sender {
+            if !veto_config.is_vetoer(&info.sender) {
                 // if the sender can normally execute, but is not the vetoer,
                 // return timelocked error. otherwise return unauthorized.
                 if sender_can_execute {
@@ -563,20 +665,77 @@ pub fn execute_execute(
 
             // if veto timelocked, only allow execution if early_execute enabled
             veto_config.check_early_execute_enabled()?;
+            veto_config.check_no_vetoes_cast(&prop.vetoes)?;
         }
         _ => {
             return Err(ContractError::NotPassed {});
         }
     }
 
+    // Check that the proposal's dependencies have been executed.
+    let local = |id: u64| local_dependency_info(deps.storage, &env.block, id);
+    if let Err(err) = check_dependencies(
+        deps.as_ref(),
+        &env.contract.address,
+        &prop.dependencies,
+        &local,
+    ) {
+        if !(config.close_proposal_on_condition_failure
+            && matches!(err, DependencyError::DependencyFailed { .. }))
+        {
+            return Err(err.into());
+        }
+        return close_on_condition_failure(
+            deps,
+            info,
+            proposal_id,
+            prop,
+            old_status,
+            err.to_string(),
+        );
+    }
+
+    // Check that the proposal's execution conditions hold.
+    let is_executed = |id: u64| {
+        PROPOSALS
+            .get(deps.storage, &id)
+            .map_or(false, |p| p.status == Status::Executed)
+    };
+    if let Err(err) = check_conditions(
+        deps.as_ref(),
+        &env.block,
+        &dao_info,
+        &prop.conditions,
+        &is_executed,
+    ) {
+        if !config.close_proposal_on_condition_failure {
+            return Err(err.into());
+        }
+        return close_on_condition_failure(
+            deps,
+            info,
+            proposal_id,
+            prop,
+            old_status,
+            err.to_string(),
+        );
+    }
+
     prop.status = Status::Executed;
 
     PROPOSALS.insert(deps.storage, &proposal_id, &prop)?;
+    record_turnout(deps.storage, &prop)?;
 
     let vote_result = prop.calculate_vote_result()?;
     match vote_result {
         VoteResult::Tie => Err(ContractError::Tie {}), // We don't anticipate this case as the proposal would not be in passed state, checked above.
         VoteResult::SingleWinner(winning_choice) => {
+            // If the proposal is closed should its execution fail,
+            // whether it completed as executed is only known once
+            // execution replies, so the deposit module is told then.
+            let completion_deferred =
+                !winning_choice.msgs.is_empty() && config.close_proposal_on_execution_failure;
+
             let response = if !winning_choice.msgs.is_empty() {
                 let execute_message = dao_interface::msg::ExecuteMsg::ExecuteProposalHook {
                     msgs: winning_choice.msgs,
@@ -587,7 +746,7 @@ pub fn execute_execute(
                             deps.storage,
                             ReplyEvent::FailedProposalExecution { proposal_id },
                         )?;
-                        Response::default().add_submessage(SubMsg::reply_on_error(
+                        Response::default().add_submessage(SubMsg::reply_always(
                             execute_message.to_cosmos_msg(
                                 dao_info.code_hash.clone(),
                                 dao_info.addr.clone().to_string(),
@@ -615,9 +774,12 @@ pub fn execute_execute(
             )?;
 
             // Add prepropose / deposit module hook which will handle deposit refunds.
-            let proposal_creation_policy = CREATION_POLICY.load(deps.storage)?;
-            let proposal_completed_hooks =
-                proposal_completed_hooks(proposal_creation_policy, proposal_id, prop.status)?;
+            let proposal_completed_hooks = if completion_deferred {
+                vec![]
+            } else {
+                let proposal_creation_policy = CREATION_POLICY.load(deps.storage)?;
+                proposal_completed_hooks(proposal_creation_policy, proposal_id, prop.status)?
+            };
 
             Ok(response
                 .add_submessages(proposal_status_changed_hooks)
@@ -630,6 +792,44 @@ pub fn execute_execute(
     }
 }
 
+/// Closes a proposal whose execution conditions do not hold, or
+/// whose dependencies will never be executed. Used when the module is
+/// configured to close such proposals rather than refusing to execute
+/// them.
+fn close_on_condition_failure(
+    deps: DepsMut,
+    info: MessageInfo,
+    proposal_id: u64,
+    mut prop: MultipleChoiceProposal,
+    old_status: Status,
+    reason: String,
+) -> Result<Response<Empty>, ContractError> {
+    prop.status = Status::Closed;
+    PROPOSALS.insert(deps.storage, &proposal_id, &prop)?;
+    record_turnout(deps.storage, &prop)?;
+
+    let proposal_status_changed_hooks = proposal_status_changed_hooks(
+        PROPOSAL_HOOKS,
+        deps.storage,
+        proposal_id,
+        old_status.to_string(),
+        prop.status.to_string(),
+    )?;
+
+    // Add prepropose / deposit module hook which will handle deposit refunds.
+    let proposal_creation_policy = CREATION_POLICY.load(deps.storage)?;
+    let proposal_completed_hooks =
+        proposal_closed_hooks(proposal_creation_policy, proposal_id, prop.quorum_met())?;
+
+    Ok(Response::default()
+        .add_submessages(proposal_status_changed_hooks)
+        .add_submessages(proposal_completed_hooks)
+        .add_attribute("action", "execute")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("condition_failed", reason))
+}
+
 pub fn execute_close(
     deps: DepsMut,
     env: Env,
@@ -648,6 +848,7 @@ pub fn execute_close(
     prop.status = Status::Closed;
 
     PROPOSALS.insert(deps.storage, &proposal_id, &prop)?;
+    record_turnout(deps.storage, &prop)?;
 
     let proposal_status_changed_hooks = proposal_status_changed_hooks(
         PROPOSAL_HOOKS,
@@ -660,7 +861,7 @@ pub fn execute_close(
     // Add prepropose / deposit module hook which will handle deposit refunds.
     let proposal_creation_policy = CREATION_POLICY.load(deps.storage)?;
     let proposal_completed_hooks =
-        proposal_completed_hooks(proposal_creation_policy, proposal_id, prop.status)?;
+        proposal_closed_hooks(proposal_creation_policy, proposal_id, prop.quorum_met())?;
 
     Ok(Response::default()
         .add_submessages(proposal_status_changed_hooks)
@@ -683,6 +884,8 @@ pub fn execute_update_config(
     code_hash: String,
     close_proposal_on_execution_failure: bool,
     veto: Option<VetoConfig>,
+    late_vote_extension: Option<LateVoteExtensionConfig>,
+    close_proposal_on_condition_failure: bool,
 ) -> Result<Response, ContractError> {
     let dao_info = DAO.load(deps.storage)?;
 
@@ -703,6 +906,11 @@ pub fn execute_update_config(
         veto_config.validate(&deps.as_ref(), &max_voting_period)?;
     };
 
+    // if late vote extension is configured, validate its fields
+    if let Some(extension_config) = &late_vote_extension {
+        extension_config.validate(&max_voting_period)?;
+    };
+
     CONFIG.save(
         deps.storage,
         &Config {
@@ -713,6 +921,8 @@ pub fn execute_update_config(
             allow_revoting,
             close_proposal_on_execution_failure,
             veto,
+            late_vote_extension,
+            close_proposal_on_condition_failure,
         },
     )?;
 
@@ -975,6 +1185,7 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::Config {} => query_config(deps),
         QueryMsg::Proposal { proposal_id } => query_proposal(deps, env, proposal_id),
+        QueryMsg::DependencyGraph { proposal_id } => query_dependency_graph(deps, env, proposal_id),
         QueryMsg::ListProposals { start_after, limit } => {
             query_list_proposals(deps, env, start_after, limit)
         }
@@ -991,6 +1202,7 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
             limit,
         } => query_reverse_proposals(deps, env, start_before, limit),
         QueryMsg::ProposalCreationPolicy {} => query_creation_policy(deps),
+        QueryMsg::DynamicQuorum {} => query_dynamic_quorum(deps),
         QueryMsg::ProposalHooks {} => to_binary(&PROPOSAL_HOOKS.query_hooks(deps)?),
         QueryMsg::VoteHooks {} => to_binary(&VOTE_HOOKS.query_hooks(deps)?),
         QueryMsg::Dao {} => query_dao(deps),
@@ -1066,6 +1278,57 @@ pub fn query_proposal(deps: Deps, env: Env, id: u64) -> StdResult<Binary> {
     to_binary(&proposal.unwrap().into_response(&env.block, id)?)
 }
 
+pub fn query_dependency_graph(deps: Deps, env: Env, id: u64) -> StdResult<Binary> {
+    let proposal = PROPOSALS
+        .get(deps.storage, &id)
+        .ok_or_else(|| StdError::not_found("proposal"))?;
+    let local = |id: u64| local_dependency_info(deps.storage, &env.block, id);
+    to_binary(&DependencyGraphResponse {
+        nodes: dependency_graph(deps, &env.contract.address, &proposal.dependencies, &local),
+    })
+}
+
+/// Looks up a proposal in this module for dependency resolution.
+fn local_dependency_info(
+    storage: &dyn Storage,
+    block: &BlockInfo,
+    id: u64,
+) -> Option<DependencyInfo> {
+    PROPOSALS.get(storage, &id).map(|prop| DependencyInfo {
+        status: prop.current_status(block).unwrap_or(prop.status),
+        dependencies: prop.dependencies,
+    })
+}
+
+/// Records the turnout of a completed proposal for use in dynamic
+/// quorums. Called for every proposal that is executed, rejected, or
+/// expires, including those that receive no votes, so that the quorum
+/// follows falling turnout as well as rising turnout. Vetoed proposals
+/// are not counted.
+fn record_turnout(storage: &mut dyn Storage, prop: &MultipleChoiceProposal) -> StdResult<()> {
+    let mut turnout = TURNOUT.may_load(storage)?.unwrap_or_default();
+    turnout.record(prop.votes.total(), prop.total_power);
+    TURNOUT.save(storage, &turnout)
+}
+
+pub fn query_dynamic_quorum(deps: Deps) -> StdResult<Binary> {
+    let config = CONFIG.load(deps.storage)?;
+    let turnout = TURNOUT.may_load(deps.storage)?.unwrap_or_default();
+    let response = match config.voting_strategy {
+        VotingStrategy::SingleChoiceDynamicQuorum {
+            min_quorum,
+            max_quorum,
+            adjustment,
+        } => turnout.query_quorum(min_quorum, max_quorum, &adjustment),
+        VotingStrategy::SingleChoice { .. } => DynamicQuorumResponse {
+            quorum: None,
+            average_turnout: None,
+            samples: turnout.recent.len() as u64,
+        },
+    };
+    to_binary(&response)
+}
+
 pub fn query_creation_policy(deps: Deps) -> StdResult<Binary> {
     let policy = CREATION_POLICY.load(deps.storage)?;
     to_binary(&policy)
@@ -1225,30 +1488,46 @@ pub fn query_info(deps: Deps) -> StdResult<Binary> {
 pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
     let repl = REPLY_IDS.get_event(deps.storage, msg.id)?;
     match repl {
-        ReplyEvent::FailedProposalExecution { proposal_id } => match msg.clone().result {
-            SubMsgResult::Err(err) => Err(ContractError::Std(StdError::GenericErr { msg: err })),
-            SubMsgResult::Ok(_) => {
-                // PROPOSALS.update(deps.storage, proposal_id, |prop| match prop {
-                //     Some(mut prop) => {
-                //         prop.status = Status::ExecutionFailed;
-
-                //         Ok(prop)
-                //     }
-                //     None => Err(ContractError::NoSuchProposal { id: proposal_id }),
-                // })?;
-                let proposals = PROPOSALS.get(deps.storage, &proposal_id);
-                if proposals.clone().is_some() {
-                    proposals.clone().unwrap().status = Status::ExecutionFailed;
-                } else {
-                    return Err(ContractError::NoSuchProposal { id: proposal_id });
+        ReplyEvent::FailedProposalExecution { proposal_id } => {
+            let proposal_creation_policy = CREATION_POLICY.load(deps.storage)?;
+            match msg.result {
+                // The proposal executed, so the deposit module may
+                // now be told it completed.
+                SubMsgResult::Ok(_) => {
+                    Ok(Response::new().add_submessages(proposal_completed_hooks(
+                        proposal_creation_policy,
+                        proposal_id,
+                        Status::Executed,
+                    )?))
+                }
+                SubMsgResult::Err(err) => {
+                    let mut prop = PROPOSALS
+                        .get(deps.storage, &proposal_id)
+                        .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+                    prop.status = Status::ExecutionFailed;
+                    PROPOSALS.insert(deps.storage, &proposal_id, &prop)?;
+
+                    let proposal_status_changed_hooks = proposal_status_changed_hooks(
+                        PROPOSAL_HOOKS,
+                        deps.storage,
+                        proposal_id,
+                        Status::Executed.to_string(),
+                        prop.status.to_string(),
+                    )?;
+                    let proposal_completed_hooks = proposal_completed_hooks(
+                        proposal_creation_policy,
+                        proposal_id,
+                        prop.status,
+                    )?;
+
+                    Ok(Response::new()
+                        .add_submessages(proposal_status_changed_hooks)
+                        .add_submessages(proposal_completed_hooks)
+                        .add_attribute("proposal_execution_failed", proposal_id.to_string())
+                        .add_attribute("error", err))
                 }
-                PROPOSALS.insert(deps.storage, &proposal_id, &proposals.unwrap())?;
-
-                Ok(Response::new()
-                    .add_attribute("proposal_execution_failed", proposal_id.to_string())
-                    .add_attribute("error", msg.result.into_result().err().unwrap_or_default()))
             }
-        },
+        }
         ReplyEvent::FailedProposalHook { idx } => match msg.result {
             SubMsgResult::Err(err) => Err(ContractError::Std(StdError::GenericErr { msg: err })),
             SubMsgResult::Ok(_) => {
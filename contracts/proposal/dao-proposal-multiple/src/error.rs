@@ -2,7 +2,11 @@ use std::u64;
 
 use cosmwasm_std::StdError;
 use cw_hooks::HookError;
-use dao_voting::{reply::error::TagError, threshold::ThresholdError, veto::VetoError};
+use dao_voting::{
+    condition::ConditionError, dependency::DependencyError,
+    late_vote_extension::LateVoteExtensionError, reply::error::TagError, threshold::ThresholdError,
+    veto::VetoError,
+};
 use secret_cw_controllers::ReplyError;
 use secret_utils::ParseReplyError;
 use thiserror::Error;
@@ -24,6 +28,15 @@ pub enum ContractError {
     #[error(transparent)]
     VetoError(#[from] VetoError),
 
+    #[error(transparent)]
+    LateVoteExtensionError(#[from] LateVoteExtensionError),
+
+    #[error(transparent)]
+    ConditionError(#[from] ConditionError),
+
+    #[error(transparent)]
+    DependencyError(#[from] DependencyError),
+
     #[error("Unauthorized")]
     Unauthorized {},
 
@@ -2,6 +2,9 @@ use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::{Addr, Api, StdResult};
 use dao_dao_macros::proposal_module_query;
 use dao_voting::{
+    condition::ExecutionCondition,
+    dependency::ProposalDependency,
+    late_vote_extension::LateVoteExtensionConfig,
     multiple_choice::{MultipleChoiceOptions, MultipleChoiceVote, VotingStrategy},
     pre_propose::PreProposeInfo,
     veto::VetoConfig,
@@ -46,6 +49,17 @@ pub struct InstantiateMsg {
     /// During this period an oversight account (`veto.vetoer`) can
     /// veto the proposal.
     pub veto: Option<VetoConfig>,
+    /// Optional configuration for extending a proposal's voting
+    /// period when a late vote changes its leading choice or newly
+    /// meets quorum.
+    pub late_vote_extension: Option<LateVoteExtensionConfig>,
+    /// If set to true proposals will be closed if one of their
+    /// execution conditions does not hold, or one of their
+    /// dependencies will never be executed, when they are executed.
+    /// Otherwise, execution is refused and proposals remain
+    /// executable until their conditions hold.
+    #[serde(default)]
+    pub close_proposal_on_condition_failure: bool,
 
     // dao code hash
     pub dao_code_hash: String,
@@ -67,6 +81,13 @@ pub enum ExecuteMsg {
         /// pre-propose module is attached, this must be Some and will
         /// set the proposer of the proposal it creates.
         proposer: Option<String>,
+        /// Conditions that must hold when the proposal is executed.
+        #[serde(default)]
+        conditions: Vec<ExecutionCondition>,
+        /// Proposals, in this or other proposal modules, that must be
+        /// executed before the proposal may be executed.
+        #[serde(default)]
+        dependencies: Vec<ProposalDependency>,
     },
     /// Votes on a proposal. Voting power is determined by the DAO's
     /// voting power module.
@@ -95,6 +116,12 @@ pub enum ExecuteMsg {
         /// The ID of the proposal to veto.
         proposal_id: u64,
     },
+    /// Withdraws the sender's veto of a proposal that has not yet
+    /// been vetoed by the veto council.
+    WithdrawVeto {
+        /// The ID of the proposal to withdraw the veto from.
+        proposal_id: u64,
+    },
     /// Closes a proposal that has failed (either not passed or timed
     /// out). If applicable this will cause the proposal deposit
     /// associated wth said proposal to be returned.
@@ -143,6 +170,17 @@ pub enum ExecuteMsg {
         /// Optional time delay on proposal execution, during which the
         /// proposal may be vetoed.
         veto: Option<VetoConfig>,
+        /// Optional configuration for extending a proposal's voting
+        /// period when a late vote swings its outcome. This will only
+        /// apply to proposals created after the config update.
+        late_vote_extension: Option<LateVoteExtensionConfig>,
+        /// If set to true proposals will be closed if one of their
+        /// execution conditions does not hold, or one of their
+        /// dependencies will never be executed, when they are executed.
+        /// Otherwise, execution is refused and proposals remain
+        /// executable until their conditions hold.
+        #[serde(default)]
+        close_proposal_on_condition_failure: bool,
     },
     /// Updates the sender's rationale for their vote on the specified
     /// proposal. Errors if no vote vote has been cast.
@@ -196,6 +234,10 @@ pub enum QueryMsg {
     /// Gets information about a proposal.
     #[returns(crate::query::ProposalResponse)]
     Proposal { proposal_id: u64 },
+    /// Lists the proposals a proposal depends on, directly or
+    /// transitively, along with their current statuses.
+    #[returns(dao_voting::dependency::DependencyGraphResponse)]
+    DependencyGraph { proposal_id: u64 },
     /// Lists all the proposals that have been cast in this module.
     #[returns(crate::query::ProposalListResponse)]
     ListProposals {
@@ -229,6 +271,10 @@ pub enum QueryMsg {
     /// Gets the current proposal creation policy for this module.
     #[returns(::dao_voting::pre_propose::ProposalCreationPolicy)]
     ProposalCreationPolicy {},
+    /// Gets the quorum a proposal created now would have, computed
+    /// from the turnout of recently completed proposals.
+    #[returns(::dao_voting::turnout::DynamicQuorumResponse)]
+    DynamicQuorum {},
     /// Lists all of the consumers of proposal hooks for this module.
     #[returns(::cw_hooks::HooksResponse)]
     ProposalHooks {},
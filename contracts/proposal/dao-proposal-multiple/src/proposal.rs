@@ -2,6 +2,9 @@ use std::ops::Add;
 
 use cosmwasm_std::{Addr, BlockInfo, StdError, StdResult, Uint128};
 use dao_voting::{
+    condition::ExecutionCondition,
+    dependency::ProposalDependency,
+    late_vote_extension::LateVoteExtensionConfig,
     multiple_choice::{
         CheckedMultipleChoiceOption, MultipleChoiceOptionType, MultipleChoiceVotes, VotingStrategy,
     },
@@ -53,6 +56,25 @@ pub struct MultipleChoiceProposal {
     /// Optional veto configuration. If set to `None`, veto option
     /// is disabled. Otherwise contains the configuration for veto flow.
     pub veto: Option<VetoConfig>,
+    /// Optional configuration for extending this proposal's voting
+    /// period when a late vote swings its outcome.
+    #[serde(default)]
+    pub late_vote_extension: Option<LateVoteExtensionConfig>,
+    /// The number of times this proposal's expiration has been moved
+    /// forward by late vote extensions.
+    #[serde(default)]
+    pub extensions: u64,
+    /// Conditions that must hold when this proposal is executed.
+    #[serde(default)]
+    pub conditions: Vec<ExecutionCondition>,
+    /// Proposals that must be executed before this proposal may be
+    /// executed.
+    #[serde(default)]
+    pub dependencies: Vec<ProposalDependency>,
+    /// The members of the veto council that have vetoed this
+    /// proposal.
+    #[serde(default)]
+    pub vetoes: Vec<Addr>,
 }
 
 pub enum VoteResult {
@@ -60,6 +82,16 @@ pub enum VoteResult {
     Tie,
 }
 
+/// How a proposal would be decided if voting closed with the votes
+/// cast so far. Used to detect late swings in a proposal's outcome.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Outcome {
+    /// The index of the leading choice, or None if there is a tie.
+    pub leading: Option<u32>,
+    /// Whether the proposal would pass.
+    pub passing: bool,
+}
+
 impl MultipleChoiceProposal {
     /// Consumes the proposal and returns a version which may be used
     /// in a query response. The difference being that proposal
@@ -107,6 +139,52 @@ impl MultipleChoiceProposal {
         }
     }
 
+    /// Returns how this proposal would be decided if voting closed
+    /// now with the votes cast so far.
+    pub fn outcome(&self, block: &BlockInfo) -> StdResult<Outcome> {
+        // Evaluate the proposal as of its expiration so that the
+        // passing check considers only the votes that have been cast.
+        let mut at_expiration = block.clone();
+        match self.expiration {
+            Expiration::AtHeight(height) => at_expiration.height = height.max(block.height),
+            Expiration::AtTime(time) => at_expiration.time = time.max(block.time),
+            Expiration::Never {} => (),
+        }
+        let leading = match self.calculate_vote_result()? {
+            VoteResult::SingleWinner(choice) => Some(choice.index),
+            VoteResult::Tie => None,
+        };
+        Ok(Outcome {
+            leading,
+            passing: self.is_passed(&at_expiration)?,
+        })
+    }
+
+    /// Moves this proposal's expiration forward if late vote
+    /// extension is configured, `block` is within the final window
+    /// before expiration, the proposal has not been extended the
+    /// maximum number of times, and its outcome differs from
+    /// `previous`. Returns true if the proposal was extended.
+    pub fn apply_late_vote_extension(
+        &mut self,
+        block: &BlockInfo,
+        previous: Outcome,
+    ) -> StdResult<bool> {
+        let config = match &self.late_vote_extension {
+            Some(config) => config,
+            None => return Ok(false),
+        };
+        if !config.can_extend(self.extensions)
+            || !config.is_in_window(&self.expiration, block)
+            || self.outcome(block)? == previous
+        {
+            return Ok(false);
+        }
+        self.expiration = self.expiration.add(config.extension)?;
+        self.extensions += 1;
+        Ok(true)
+    }
+
     /// Sets a proposals status to its current status.
     pub fn update_status(&mut self, block: &BlockInfo) -> StdResult<()> {
         let new_status = self.current_status(block)?;
@@ -165,6 +243,16 @@ impl MultipleChoiceProposal {
         Ok(false)
     }
 
+    /// Returns whether the votes cast on this proposal meet its
+    /// quorum.
+    pub fn quorum_met(&self) -> bool {
+        does_vote_count_pass(
+            self.votes.total(),
+            self.total_power,
+            self.voting_strategy.get_quorum(),
+        )
+    }
+
     pub fn is_rejected(&self, block: &BlockInfo) -> StdResult<bool> {
         // If re-voting is allowed and the proposal is not expired no
         // information is known.
@@ -217,7 +305,8 @@ impl MultipleChoiceProposal {
     /// Find the option with the highest vote weight, and note if there is a tie.
     pub fn calculate_vote_result(&self) -> StdResult<VoteResult> {
         match self.voting_strategy {
-            VotingStrategy::SingleChoice { quorum: _ } => {
+            VotingStrategy::SingleChoice { .. }
+            | VotingStrategy::SingleChoiceDynamicQuorum { .. } => {
                 // We expect to have at least 3 vote weights
                 if let Some(max_weight) = self.votes.vote_weights.iter().max_by(|&a, &b| a.cmp(b)) {
                     let top_choices: Vec<(usize, &Uint128)> = self
@@ -292,7 +381,9 @@ mod tests {
     use super::*;
 
     use cosmwasm_std::testing::mock_env;
-    use dao_voting::multiple_choice::{MultipleChoiceOption, MultipleChoiceOptions};
+    use dao_voting::multiple_choice::{
+        MultipleChoiceOption, MultipleChoiceOptions, MultipleChoiceVote,
+    };
 
     fn create_proposal(
         block: &BlockInfo,
@@ -338,6 +429,11 @@ mod tests {
             allow_revoting,
             min_voting_period: None,
             veto: None,
+            late_vote_extension: None,
+            extensions: 0,
+            conditions: vec![],
+            dependencies: vec![],
+            vetoes: vec![],
         }
     }
 
@@ -877,4 +973,53 @@ mod tests {
         // No quorum reached & proposal has expired => rejection
         assert!(prop.is_rejected(&env.block).unwrap());
     }
+
+    #[test]
+    fn test_late_vote_extension() {
+        let env = mock_env();
+        let voting_strategy = VotingStrategy::SingleChoice {
+            quorum: dao_voting::threshold::PercentageThreshold::Majority {},
+        };
+        let votes = MultipleChoiceVotes {
+            vote_weights: vec![Uint128::new(2), Uint128::new(1), Uint128::new(0)],
+        };
+        let mut prop = create_proposal(
+            &env.block,
+            voting_strategy,
+            votes,
+            Uint128::new(10),
+            false,
+            false,
+        );
+        prop.late_vote_extension = Some(LateVoteExtensionConfig {
+            window: secret_utils::Duration::Height(5),
+            extension: secret_utils::Duration::Height(3),
+            max_extensions: 2,
+        });
+        let expiration = prop.expiration;
+
+        // The leading choice changes inside the window.
+        let previous = prop.outcome(&env.block).unwrap();
+        prop.votes
+            .add_vote(MultipleChoiceVote { option_id: 1 }, Uint128::new(2))
+            .unwrap();
+        assert!(prop
+            .apply_late_vote_extension(&env.block, previous)
+            .unwrap());
+        assert_eq!(
+            prop.expiration,
+            expiration.add(secret_utils::Duration::Height(3)).unwrap()
+        );
+
+        // Quorum is newly met but the expiration is now outside the
+        // window.
+        let previous = prop.outcome(&env.block).unwrap();
+        prop.votes
+            .add_vote(MultipleChoiceVote { option_id: 1 }, Uint128::new(2))
+            .unwrap();
+        assert!(!prop
+            .apply_late_vote_extension(&env.block, previous)
+            .unwrap());
+        assert_eq!(prop.extensions, 1);
+    }
 }
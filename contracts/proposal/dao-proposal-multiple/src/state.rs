@@ -3,8 +3,10 @@ use cosmwasm_std::{Addr, Uint128};
 use cw_hooks::Hooks;
 use dao_interface::state::AnyContractInfo;
 use dao_voting::{
+    late_vote_extension::LateVoteExtensionConfig,
     multiple_choice::{MultipleChoiceVote, VotingStrategy},
     pre_propose::ProposalCreationPolicy,
+    turnout::TurnoutHistory,
     veto::VetoConfig,
 };
 use schemars::JsonSchema;
@@ -49,6 +51,18 @@ pub struct Config {
     /// Optional veto configuration. If set to `None`, veto option
     /// is disabled. Otherwise contains the configuration for veto flow.
     pub veto: Option<VetoConfig>,
+    /// Optional configuration for extending a proposal's voting
+    /// period when a late vote swings its outcome. If set to `None`,
+    /// proposals are never extended.
+    #[serde(default)]
+    pub late_vote_extension: Option<LateVoteExtensionConfig>,
+    /// If set to true proposals will be closed if one of their
+    /// execution conditions does not hold, or one of their
+    /// dependencies will never be executed, when they are executed.
+    /// Otherwise, execution is refused and proposals remain
+    /// executable until their conditions hold.
+    #[serde(default)]
+    pub close_proposal_on_condition_failure: bool,
 }
 
 // Each ballot stores a chosen vote and corresponding voting power and rationale.
@@ -75,5 +89,8 @@ pub const VOTE_HOOKS: Hooks = Hooks::new("vote_hooks");
 /// The address of the pre-propose module associated with this
 /// proposal module (if any).
 pub const CREATION_POLICY: Item<ProposalCreationPolicy> = Item::new("creation_policy");
+/// Turnout of recently completed proposals, from which dynamic
+/// quorums are computed.
+pub const TURNOUT: Item<TurnoutHistory> = Item::new("turnout");
 pub const DAO: Item<AnyContractInfo> = Item::new("dao");
 pub const REPLY_IDS: ReplyIds = ReplyIds::new(b"reply_ids", b"reply_ids_count");
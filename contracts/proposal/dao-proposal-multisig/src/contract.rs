@@ -0,0 +1,792 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    to_binary, Addr, Api, Binary, CosmosMsg, Deps, DepsMut, Empty, Env, MessageInfo, Reply,
+    Response, StdError, StdResult, Storage, SubMsg, SubMsgResult,
+};
+use cw_hooks::{HookItem, Hooks};
+use dao_hooks::proposal::{
+    new_proposal_hooks, proposal_completed_hooks, proposal_status_changed_hooks,
+};
+use dao_hooks::vote::new_vote_hooks;
+use dao_interface::state::AnyContractInfo;
+use dao_voting::pre_propose::{PreProposeInfo, ProposalCreationPolicy};
+use dao_voting::proposal::DEFAULT_LIMIT;
+use dao_voting::status::Status as CompletedStatus;
+use secret_cw2::set_contract_version;
+use secret_cw_controllers::ReplyEvent;
+use secret_toolkit::utils::HandleCallback;
+use secret_utils::{parse_reply_event_for_contract_address, Duration};
+
+use crate::msg::{ExecuteMsg, InstantiateMsg, ProposeMsg, QueryMsg, SignerInfo};
+use crate::proposal::{advance_proposal_id, next_proposal_id, MultisigProposal, Status};
+use crate::query::{
+    BallotResponse, ProposalListResponse, ProposalResponse, SignerListResponse, SignerResponse,
+};
+use crate::state::{
+    Ballot, Config, Signer, BALLOTS, CONFIG, CREATION_POLICY, DAO, PROPOSALS, PROPOSAL_COUNT,
+    PROPOSAL_HOOKS, REPLY_IDS, SIGNERS, VOTE_HOOKS,
+};
+use crate::ContractError;
+
+pub(crate) const CONTRACT_NAME: &str = "crates.io:dao-proposal-multisig";
+pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    DAO.save(
+        deps.storage,
+        &AnyContractInfo {
+            code_hash: msg.dao_code_hash,
+            addr: info.sender.clone(),
+        },
+    )?;
+
+    let signers = validate_signers(deps.api, msg.signers)?;
+    let total_weight = signers.iter().map(|s| s.weight).sum();
+    for signer in signers {
+        SIGNERS.insert(deps.storage, &signer.addr, &signer.weight)?;
+    }
+
+    let config = validate_config(
+        msg.threshold,
+        total_weight,
+        msg.max_voting_period,
+        msg.close_proposal_on_execution_failure,
+    )?;
+
+    let (initial_policy, pre_propose_messages) = msg
+        .pre_propose_info
+        .into_initial_policy_and_messages(deps.storage, info.sender.clone(), REPLY_IDS)?;
+
+    // Initialize proposal count to zero so that queries return zero
+    // instead of None.
+    PROPOSAL_COUNT.save(deps.storage, &0)?;
+    CONFIG.save(deps.storage, &config)?;
+    CREATION_POLICY.save(deps.storage, &initial_policy)?;
+
+    Ok(Response::default()
+        .add_submessages(pre_propose_messages)
+        .add_attribute("action", "instantiate")
+        .add_attribute("dao", info.sender.to_string())
+        .add_attribute("total_weight", total_weight.to_string()))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Propose(ProposeMsg {
+            title,
+            description,
+            msgs,
+            proposer,
+        }) => execute_propose(deps, env, info.sender, title, description, msgs, proposer),
+        ExecuteMsg::Approve { proposal_id } => execute_vote(deps, env, info, proposal_id, true),
+        ExecuteMsg::Reject { proposal_id } => execute_vote(deps, env, info, proposal_id, false),
+        ExecuteMsg::Execute { proposal_id } => execute_execute(deps, env, info, proposal_id),
+        ExecuteMsg::Close { proposal_id } => execute_close(deps, env, info, proposal_id),
+        ExecuteMsg::UpdateSigners { add, remove } => {
+            execute_update_signers(deps, info, add, remove)
+        }
+        ExecuteMsg::UpdateConfig {
+            threshold,
+            max_voting_period,
+            close_proposal_on_execution_failure,
+        } => execute_update_config(
+            deps,
+            info,
+            threshold,
+            max_voting_period,
+            close_proposal_on_execution_failure,
+        ),
+        ExecuteMsg::UpdatePreProposeInfo { info: new_info } => {
+            execute_update_proposal_creation_policy(deps, info, new_info)
+        }
+        ExecuteMsg::AddProposalHook { address, code_hash } => execute_update_hook(
+            deps,
+            info,
+            PROPOSAL_HOOKS,
+            "proposal",
+            address,
+            code_hash,
+            true,
+        ),
+        ExecuteMsg::RemoveProposalHook { address, code_hash } => execute_update_hook(
+            deps,
+            info,
+            PROPOSAL_HOOKS,
+            "proposal",
+            address,
+            code_hash,
+            false,
+        ),
+        ExecuteMsg::AddVoteHook { address, code_hash } => {
+            execute_update_hook(deps, info, VOTE_HOOKS, "vote", address, code_hash, true)
+        }
+        ExecuteMsg::RemoveVoteHook { address, code_hash } => {
+            execute_update_hook(deps, info, VOTE_HOOKS, "vote", address, code_hash, false)
+        }
+    }
+}
+
+/// Validates a list of signers, checking that none are listed twice
+/// and all have a non-zero weight.
+fn validate_signers(api: &dyn Api, signers: Vec<SignerInfo>) -> Result<Vec<Signer>, ContractError> {
+    let mut validated: Vec<Signer> = Vec::with_capacity(signers.len());
+    for SignerInfo { addr, weight } in signers {
+        let addr = api.addr_validate(&addr)?;
+        if weight == 0 {
+            return Err(ContractError::ZeroWeight {
+                signer: addr.into_string(),
+            });
+        }
+        if validated.iter().any(|s| s.addr == addr) {
+            return Err(ContractError::DuplicateSigner {
+                signer: addr.into_string(),
+            });
+        }
+        validated.push(Signer { addr, weight });
+    }
+    Ok(validated)
+}
+
+fn validate_config(
+    threshold: u64,
+    total_weight: u64,
+    max_voting_period: Duration,
+    close_proposal_on_execution_failure: bool,
+) -> Result<Config, ContractError> {
+    if threshold == 0 || threshold > total_weight {
+        return Err(ContractError::InvalidThreshold {
+            threshold,
+            total_weight,
+        });
+    }
+    if matches!(max_voting_period, Duration::Height(0) | Duration::Time(0)) {
+        return Err(ContractError::ZeroVotingPeriod {});
+    }
+    Ok(Config {
+        threshold,
+        max_voting_period,
+        close_proposal_on_execution_failure,
+    })
+}
+
+/// The module's current signers.
+fn current_signers(storage: &dyn Storage) -> StdResult<Vec<Signer>> {
+    SIGNERS
+        .iter(storage)?
+        .map(|item| item.map(|(addr, weight)| Signer { addr, weight }))
+        .collect()
+}
+
+pub fn execute_propose(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    title: String,
+    description: String,
+    msgs: Vec<CosmosMsg<Empty>>,
+    proposer: Option<String>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let proposal_creation_policy = CREATION_POLICY.load(deps.storage)?;
+
+    // Check that the sender is permitted to create proposals.
+    if !proposal_creation_policy.is_permitted(&sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // Determine the appropriate proposer. If this is coming from our
+    // pre-propose module, it must be specified. Otherwise, the
+    // proposer should not be specified and must be a signer.
+    let proposer = match (proposer, &proposal_creation_policy) {
+        (None, ProposalCreationPolicy::Anyone {}) => {
+            if !SIGNERS.contains(deps.storage, &sender) {
+                return Err(ContractError::Unauthorized {});
+            }
+            sender.clone()
+        }
+        (Some(proposer), ProposalCreationPolicy::Module { .. }) => {
+            deps.api.addr_validate(&proposer)?
+        }
+        _ => return Err(ContractError::InvalidProposer {}),
+    };
+
+    let mut proposal = MultisigProposal {
+        title,
+        description,
+        proposer: proposer.clone(),
+        start_height: env.block.height,
+        expiration: config.max_voting_period.after(&env.block),
+        threshold: config.threshold,
+        signers: current_signers(deps.storage)?,
+        approvals: 0,
+        rejections: 0,
+        msgs,
+        status: Status::Open,
+    };
+    let id = advance_proposal_id(deps.storage)?;
+
+    let mut hooks = new_proposal_hooks(PROPOSAL_HOOKS, deps.storage, id, proposer.as_str())?;
+
+    // A proposer who is a signer approves their own proposal.
+    if let Some(weight) = proposal.signer_weight(&proposer) {
+        BALLOTS.insert(
+            deps.storage,
+            &(id, proposer.clone()),
+            &Ballot {
+                weight,
+                approve: true,
+            },
+        )?;
+        proposal.vote(&env.block, weight, true);
+        hooks.extend(new_vote_hooks(
+            VOTE_HOOKS,
+            deps.storage,
+            id,
+            proposer.to_string(),
+            "approve".to_string(),
+        )?);
+        if proposal.status != Status::Open {
+            hooks.extend(status_change_hooks(
+                deps.storage,
+                id,
+                Status::Open,
+                proposal.status,
+            )?);
+        }
+    }
+    PROPOSALS.insert(deps.storage, &id, &proposal)?;
+
+    Ok(Response::default()
+        .add_submessages(hooks)
+        .add_attribute("action", "propose")
+        .add_attribute("sender", sender)
+        .add_attribute("proposal_id", id.to_string())
+        .add_attribute("status", proposal.status.to_string()))
+}
+
+pub fn execute_vote(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+    approve: bool,
+) -> Result<Response, ContractError> {
+    let mut prop = PROPOSALS
+        .get(deps.storage, &proposal_id)
+        .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+
+    prop.update_status(&env.block);
+    if prop.status != Status::Open {
+        return Err(ContractError::NotOpen { id: proposal_id });
+    }
+    let weight = prop
+        .signer_weight(&info.sender)
+        .ok_or(ContractError::NotSigner {})?;
+
+    let key = (proposal_id, info.sender.clone());
+    if BALLOTS.contains(deps.storage, &key) {
+        return Err(ContractError::AlreadyVoted {});
+    }
+    BALLOTS.insert(deps.storage, &key, &Ballot { weight, approve })?;
+
+    let old_status = prop.status;
+    prop.vote(&env.block, weight, approve);
+    PROPOSALS.insert(deps.storage, &proposal_id, &prop)?;
+
+    let vote = if approve { "approve" } else { "reject" };
+    let mut hooks = new_vote_hooks(
+        VOTE_HOOKS,
+        deps.storage,
+        proposal_id,
+        info.sender.to_string(),
+        vote.to_string(),
+    )?;
+    if prop.status != old_status {
+        hooks.extend(status_change_hooks(
+            deps.storage,
+            proposal_id,
+            old_status,
+            prop.status,
+        )?);
+    }
+
+    Ok(Response::default()
+        .add_submessages(hooks)
+        .add_attribute("action", "vote")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("vote", vote)
+        .add_attribute("status", prop.status.to_string()))
+}
+
+pub fn execute_execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+) -> Result<Response, ContractError> {
+    let dao_info = DAO.load(deps.storage)?;
+    let config = CONFIG.load(deps.storage)?;
+    let mut prop = PROPOSALS
+        .get(deps.storage, &proposal_id)
+        .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+
+    prop.update_status(&env.block);
+    if prop.status != Status::Passed {
+        return Err(ContractError::NotPassed {});
+    }
+
+    let old_status = prop.status;
+    prop.status = Status::Executed;
+    PROPOSALS.insert(deps.storage, &proposal_id, &prop)?;
+
+    // If the proposal is closed should its execution fail, whether
+    // it completed as executed is only known once execution replies,
+    // so the deposit module is told then.
+    let completion_deferred = !prop.msgs.is_empty() && config.close_proposal_on_execution_failure;
+
+    let response =
+        if prop.msgs.is_empty() {
+            Response::default()
+        } else {
+            let execute_message =
+                dao_interface::msg::ExecuteMsg::ExecuteProposalHook { msgs: prop.msgs }
+                    .to_cosmos_msg(dao_info.code_hash, dao_info.addr.to_string(), None)?;
+            if config.close_proposal_on_execution_failure {
+                let reply_id = REPLY_IDS.add_event(
+                    deps.storage,
+                    ReplyEvent::FailedProposalExecution { proposal_id },
+                )?;
+                Response::default().add_submessage(SubMsg::reply_always(execute_message, reply_id))
+            } else {
+                Response::default().add_message(execute_message)
+            }
+        };
+
+    let status_hooks = if completion_deferred {
+        proposal_status_changed_hooks(
+            PROPOSAL_HOOKS,
+            deps.storage,
+            proposal_id,
+            old_status.to_string(),
+            prop.status.to_string(),
+        )?
+    } else {
+        status_change_hooks(deps.storage, proposal_id, old_status, prop.status)?
+    };
+
+    Ok(response
+        .add_submessages(status_hooks)
+        .add_attribute("action", "execute")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("dao", dao_info.addr))
+}
+
+pub fn execute_close(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+) -> Result<Response, ContractError> {
+    let mut prop = PROPOSALS
+        .get(deps.storage, &proposal_id)
+        .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+
+    prop.update_status(&env.block);
+    if prop.status != Status::Rejected {
+        return Err(ContractError::NotRejected {});
+    }
+
+    let old_status = prop.status;
+    prop.status = Status::Closed;
+    PROPOSALS.insert(deps.storage, &proposal_id, &prop)?;
+
+    let status_hooks = status_change_hooks(deps.storage, proposal_id, old_status, prop.status)?;
+
+    Ok(Response::default()
+        .add_submessages(status_hooks)
+        .add_attribute("action", "close")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string()))
+}
+
+/// Prepares proposal status change hooks and, if the proposal has
+/// completed, the pre-propose module's completed hook which handles
+/// deposit refunds.
+fn status_change_hooks(
+    storage: &mut dyn Storage,
+    proposal_id: u64,
+    old_status: Status,
+    new_status: Status,
+) -> StdResult<Vec<SubMsg>> {
+    let mut hooks = proposal_status_changed_hooks(
+        PROPOSAL_HOOKS,
+        storage,
+        proposal_id,
+        old_status.to_string(),
+        new_status.to_string(),
+    )?;
+    if let Some(completed) = new_status.completed() {
+        let proposal_creation_policy = CREATION_POLICY.load(storage)?;
+        hooks.extend(proposal_completed_hooks(
+            proposal_creation_policy,
+            proposal_id,
+            completed,
+        )?);
+    }
+    Ok(hooks)
+}
+
+pub fn execute_update_signers(
+    deps: DepsMut,
+    info: MessageInfo,
+    add: Vec<SignerInfo>,
+    remove: Vec<String>,
+) -> Result<Response, ContractError> {
+    let dao_info = DAO.load(deps.storage)?;
+
+    // Only the DAO may call this method.
+    if info.sender != dao_info.addr {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    for addr in remove {
+        let addr = deps.api.addr_validate(&addr)?;
+        SIGNERS.remove(deps.storage, &addr)?;
+    }
+    for signer in validate_signers(deps.api, add)? {
+        SIGNERS.insert(deps.storage, &signer.addr, &signer.weight)?;
+    }
+
+    // The threshold must remain reachable by the new signer set.
+    let config = CONFIG.load(deps.storage)?;
+    let total_weight = current_signers(deps.storage)?
+        .iter()
+        .map(|s| s.weight)
+        .sum();
+    validate_config(
+        config.threshold,
+        total_weight,
+        config.max_voting_period,
+        config.close_proposal_on_execution_failure,
+    )?;
+
+    Ok(Response::default()
+        .add_attribute("action", "update_signers")
+        .add_attribute("sender", info.sender)
+        .add_attribute("total_weight", total_weight.to_string()))
+}
+
+pub fn execute_update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    threshold: u64,
+    max_voting_period: Duration,
+    close_proposal_on_execution_failure: bool,
+) -> Result<Response, ContractError> {
+    let dao_info = DAO.load(deps.storage)?;
+
+    // Only the DAO may call this method.
+    if info.sender != dao_info.addr {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let total_weight = current_signers(deps.storage)?
+        .iter()
+        .map(|s| s.weight)
+        .sum();
+    let config = validate_config(
+        threshold,
+        total_weight,
+        max_voting_period,
+        close_proposal_on_execution_failure,
+    )?;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::default()
+        .add_attribute("action", "update_config")
+        .add_attribute("sender", info.sender))
+}
+
+pub fn execute_update_proposal_creation_policy(
+    deps: DepsMut,
+    info: MessageInfo,
+    new_info: PreProposeInfo,
+) -> Result<Response, ContractError> {
+    let dao_info = DAO.load(deps.storage)?;
+
+    if dao_info.addr != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let (initial_policy, messages) =
+        new_info.into_initial_policy_and_messages(deps.storage, dao_info.addr, REPLY_IDS)?;
+    CREATION_POLICY.save(deps.storage, &initial_policy)?;
+
+    Ok(Response::default()
+        .add_submessages(messages)
+        .add_attribute("action", "update_proposal_creation_policy")
+        .add_attribute("sender", info.sender)
+        .add_attribute("new_policy", format!("{initial_policy:?}")))
+}
+
+/// Adds or removes a hook consumer. Only the DAO may call this
+/// method.
+pub fn execute_update_hook(
+    deps: DepsMut,
+    info: MessageInfo,
+    hooks: Hooks,
+    kind: &str,
+    address: String,
+    code_hash: String,
+    add: bool,
+) -> Result<Response, ContractError> {
+    let dao_info = DAO.load(deps.storage)?;
+    if dao_info.addr != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let hook = HookItem {
+        addr: deps.api.addr_validate(&address)?,
+        code_hash,
+    };
+    let action = if add {
+        hooks.add_hook(deps.storage, hook)?;
+        "add"
+    } else {
+        hooks.remove_hook(deps.storage, hook)?;
+        "remove"
+    };
+
+    Ok(Response::default()
+        .add_attribute("action", format!("{action}_{kind}_hook"))
+        .add_attribute("address", address))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&CONFIG.load(deps.storage)?),
+        QueryMsg::Dao {} => to_binary(&DAO.load(deps.storage)?),
+        QueryMsg::Proposal { proposal_id } => query_proposal(deps, env, proposal_id),
+        QueryMsg::ListProposals { start_after, limit } => {
+            query_list_proposals(deps, env, start_after, limit)
+        }
+        QueryMsg::Signer { address } => {
+            let addr = deps.api.addr_validate(&address)?;
+            to_binary(&SignerResponse {
+                weight: SIGNERS.get(deps.storage, &addr),
+            })
+        }
+        QueryMsg::ListSigners {} => {
+            let signers = current_signers(deps.storage)?;
+            let total_weight = signers.iter().map(|s| s.weight).sum();
+            to_binary(&SignerListResponse {
+                signers,
+                total_weight,
+            })
+        }
+        QueryMsg::Ballot {
+            proposal_id,
+            address,
+        } => {
+            let addr = deps.api.addr_validate(&address)?;
+            to_binary(&BallotResponse {
+                ballot: BALLOTS.get(deps.storage, &(proposal_id, addr)),
+            })
+        }
+        QueryMsg::NextProposalId {} => to_binary(&next_proposal_id(deps.storage)?),
+        QueryMsg::ProposalCount {} => to_binary(&PROPOSAL_COUNT.load(deps.storage)?),
+        QueryMsg::ProposalCreationPolicy {} => to_binary(&CREATION_POLICY.load(deps.storage)?),
+        QueryMsg::ProposalHooks {} => to_binary(&PROPOSAL_HOOKS.query_hooks(deps)?),
+        QueryMsg::VoteHooks {} => to_binary(&VOTE_HOOKS.query_hooks(deps)?),
+        QueryMsg::Info {} => to_binary(&dao_interface::voting::InfoResponse {
+            info: secret_cw2::get_contract_version(deps.storage)?,
+        }),
+    }
+}
+
+pub fn query_proposal(deps: Deps, env: Env, id: u64) -> StdResult<Binary> {
+    let proposal = PROPOSALS
+        .get(deps.storage, &id)
+        .ok_or_else(|| StdError::not_found("proposal"))?;
+    to_binary(&proposal.into_response(&env.block, id))
+}
+
+pub fn query_list_proposals(
+    deps: Deps,
+    env: Env,
+    start_after: Option<u64>,
+    limit: Option<u64>,
+) -> StdResult<Binary> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT) as usize;
+    let proposals = PROPOSALS
+        .iter(deps.storage)?
+        .filter(|item| match (item, start_after) {
+            (Ok((id, _)), Some(start_after)) => *id > start_after,
+            _ => true,
+        })
+        .take(limit)
+        .map(|item| item.map(|(id, proposal)| proposal.into_response(&env.block, id)))
+        .collect::<StdResult<Vec<ProposalResponse>>>()?;
+
+    to_binary(&ProposalListResponse { proposals })
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    let repl = REPLY_IDS.get_event(deps.storage, msg.id)?;
+    match (repl, msg.result) {
+        // The proposal executed, so the deposit module may now be
+        // told it completed.
+        (ReplyEvent::FailedProposalExecution { proposal_id }, SubMsgResult::Ok(_)) => {
+            let proposal_creation_policy = CREATION_POLICY.load(deps.storage)?;
+            Ok(Response::new().add_submessages(proposal_completed_hooks(
+                proposal_creation_policy,
+                proposal_id,
+                CompletedStatus::Executed,
+            )?))
+        }
+        (ReplyEvent::FailedProposalExecution { proposal_id }, SubMsgResult::Err(err)) => {
+            let mut prop = PROPOSALS
+                .get(deps.storage, &proposal_id)
+                .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+            prop.status = Status::ExecutionFailed;
+            PROPOSALS.insert(deps.storage, &proposal_id, &prop)?;
+
+            let status_hooks =
+                status_change_hooks(deps.storage, proposal_id, Status::Executed, prop.status)?;
+
+            Ok(Response::new()
+                .add_submessages(status_hooks)
+                .add_attribute("proposal_execution_failed", proposal_id.to_string())
+                .add_attribute("error", err))
+        }
+        (ReplyEvent::FailedProposalHook { idx }, SubMsgResult::Err(_)) => {
+            let hook_item = PROPOSAL_HOOKS.remove_hook_by_index(deps.storage, idx)?;
+            Ok(Response::new().add_attribute(
+                "removed_proposal_hook",
+                format!("{0}:{idx}", hook_item.addr),
+            ))
+        }
+        (ReplyEvent::FailedVoteHook { idx }, SubMsgResult::Err(_)) => {
+            let hook_item = VOTE_HOOKS.remove_hook_by_index(deps.storage, idx)?;
+            Ok(Response::new()
+                .add_attribute("removed_vote_hook", format!("{0}:{idx}", hook_item.addr)))
+        }
+        (ReplyEvent::PreProposalModuleInstantiate { code_hash }, SubMsgResult::Ok(res)) => {
+            let contract_address = parse_reply_event_for_contract_address(res.events)?;
+            let module_addr = deps.api.addr_validate(&contract_address)?;
+            CREATION_POLICY.save(
+                deps.storage,
+                &ProposalCreationPolicy::Module {
+                    addr: module_addr.clone(),
+                    code_hash,
+                },
+            )?;
+
+            let response = Response::new().add_attribute("update_pre_propose_module", module_addr);
+            Ok(match res.data {
+                Some(data) => response.set_data(data),
+                None => response,
+            })
+        }
+        (ReplyEvent::FailedPreProposeModuleHook {}, _) => {
+            let addr = match CREATION_POLICY.load(deps.storage)? {
+                ProposalCreationPolicy::Anyone {} => {
+                    // Something is off if we're getting this reply
+                    // and we don't have a pre-propose module
+                    // installed. This should be unreachable.
+                    return Err(ContractError::InvalidReplyID { id: msg.id });
+                }
+                ProposalCreationPolicy::Module { addr, .. } => {
+                    // Our pre-propose module has errored while
+                    // receiving a proposal hook. Rest in peace
+                    // pre-propose module.
+                    CREATION_POLICY.save(deps.storage, &ProposalCreationPolicy::Anyone {})?;
+                    addr
+                }
+            };
+            Ok(Response::new().add_attribute("failed_prepropose_hook", addr))
+        }
+        _ => Err(ContractError::UnknownReplyID {}),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::mock_dependencies;
+
+    fn signer(addr: &str, weight: u64) -> SignerInfo {
+        SignerInfo {
+            addr: addr.to_string(),
+            weight,
+        }
+    }
+
+    #[test]
+    fn test_validate_signers() {
+        let deps = mock_dependencies();
+        let signers =
+            validate_signers(&deps.api, vec![signer("alice", 2), signer("bob", 1)]).unwrap();
+        assert_eq!(signers.iter().map(|s| s.weight).sum::<u64>(), 3);
+
+        let err =
+            validate_signers(&deps.api, vec![signer("alice", 2), signer("alice", 1)]).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::DuplicateSigner {
+                signer: "alice".to_string()
+            }
+        );
+
+        let err = validate_signers(&deps.api, vec![signer("bob", 0)]).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::ZeroWeight {
+                signer: "bob".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_validate_threshold() {
+        let period = Duration::Height(10);
+        assert!(validate_config(3, 3, period, false).is_ok());
+        assert_eq!(
+            validate_config(4, 3, period, false).unwrap_err(),
+            ContractError::InvalidThreshold {
+                threshold: 4,
+                total_weight: 3
+            }
+        );
+        assert_eq!(
+            validate_config(0, 3, period, false).unwrap_err(),
+            ContractError::InvalidThreshold {
+                threshold: 0,
+                total_weight: 3
+            }
+        );
+        assert_eq!(
+            validate_config(1, 3, Duration::Time(0), false).unwrap_err(),
+            ContractError::ZeroVotingPeriod {}
+        );
+    }
+}
@@ -0,0 +1,64 @@
+use cosmwasm_std::StdError;
+use cw_hooks::HookError;
+use secret_cw_controllers::ReplyError;
+use secret_utils::ParseReplyError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error(transparent)]
+    Std(#[from] StdError),
+
+    #[error(transparent)]
+    ParseReplyError(#[from] ParseReplyError),
+
+    #[error(transparent)]
+    HookError(#[from] HookError),
+
+    #[error(transparent)]
+    ReplyUdError(#[from] ReplyError),
+
+    #[error("unauthorized")]
+    Unauthorized {},
+
+    #[error("no such proposal ({id})")]
+    NoSuchProposal { id: u64 },
+
+    #[error("An unknown reply ID was received.")]
+    UnknownReplyID {},
+
+    #[error("received a reply failure with an invalid ID: ({id})")]
+    InvalidReplyID { id: u64 },
+
+    #[error(
+        "pre-propose modules must specify a proposer. lacking one, no proposer should be specified"
+    )]
+    InvalidProposer {},
+
+    #[error("the max voting period must be non-zero")]
+    ZeroVotingPeriod {},
+
+    #[error("signer ({signer}) must have a non-zero weight")]
+    ZeroWeight { signer: String },
+
+    #[error("signer ({signer}) is listed more than once")]
+    DuplicateSigner { signer: String },
+
+    #[error("threshold ({threshold}) must be non-zero and at most the total signer weight ({total_weight})")]
+    InvalidThreshold { threshold: u64, total_weight: u64 },
+
+    #[error("not a signer of this proposal")]
+    NotSigner {},
+
+    #[error("already voted on this proposal")]
+    AlreadyVoted {},
+
+    #[error("proposal ({id}) is not open for approval")]
+    NotOpen { id: u64 },
+
+    #[error("proposal is not in 'passed' state")]
+    NotPassed {},
+
+    #[error("proposal is not in 'rejected' state")]
+    NotRejected {},
+}
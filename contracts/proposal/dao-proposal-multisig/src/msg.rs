@@ -0,0 +1,155 @@
+use cosmwasm_schema::QueryResponses;
+use cosmwasm_std::{CosmosMsg, Empty};
+use dao_dao_macros::proposal_module_query;
+use dao_voting::pre_propose::PreProposeInfo;
+use schemars::JsonSchema;
+use secret_utils::Duration;
+use serde::{Deserialize, Serialize};
+
+/// A signer and their weight.
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub struct SignerInfo {
+    pub addr: String,
+    pub weight: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub struct InstantiateMsg {
+    /// The module's initial signers.
+    pub signers: Vec<SignerInfo>,
+    /// The signer weight that must approve a proposal for it to
+    /// pass.
+    pub threshold: u64,
+    /// The amount of time signers have to approve a proposal after
+    /// it is created.
+    pub max_voting_period: Duration,
+    /// Information about what addresses may create proposals.
+    pub pre_propose_info: PreProposeInfo,
+    /// If set to true proposals will be closed if their execution
+    /// fails. Otherwise, proposals will remain passed after execution
+    /// failure and may be executed again.
+    pub close_proposal_on_execution_failure: bool,
+
+    pub dao_code_hash: String,
+}
+
+/// The message used to create a proposal. This matches the fields
+/// used by dao-pre-propose-single, so that module may be used to
+/// take deposits for this one.
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub struct ProposeMsg {
+    /// The title of the proposal.
+    pub title: String,
+    /// A description of the proposal.
+    pub description: String,
+    /// The messages that should be executed in response to this
+    /// proposal passing.
+    pub msgs: Vec<CosmosMsg<Empty>>,
+    /// The address creating the proposal. If no pre-propose
+    /// module is attached to this module this must always be None
+    /// as the proposer is the sender of the propose message. If a
+    /// pre-propose module is attached, this must be Some and will
+    /// set the proposer of the proposal it creates.
+    pub proposer: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Creates a proposal in the module. Without a pre-propose
+    /// module, only signers may create proposals.
+    Propose(ProposeMsg),
+    /// Approves a proposal with the sender's signer weight.
+    Approve { proposal_id: u64 },
+    /// Rejects a proposal with the sender's signer weight.
+    Reject { proposal_id: u64 },
+    /// Causes the messages associated with a passed proposal to be
+    /// executed by the DAO.
+    Execute { proposal_id: u64 },
+    /// Closes a proposal that has been rejected.
+    Close { proposal_id: u64 },
+    /// Updates the module's signers. Signers in `remove` are removed
+    /// before those in `add` are added, and adding an existing signer
+    /// updates their weight. Only the DAO may call this method.
+    /// Changes apply to proposals created after the update.
+    UpdateSigners {
+        add: Vec<SignerInfo>,
+        remove: Vec<String>,
+    },
+    /// Updates the module's configuration. Only the DAO may call
+    /// this method. The threshold and voting period apply to
+    /// proposals created after the update.
+    UpdateConfig {
+        threshold: u64,
+        max_voting_period: Duration,
+        close_proposal_on_execution_failure: bool,
+    },
+    /// Update's the proposal creation policy used for this
+    /// module. Only the DAO may call this method.
+    UpdatePreProposeInfo { info: PreProposeInfo },
+    /// Adds an address as a consumer of proposal hooks. Consumers of
+    /// proposal hooks have hook messages executed on them whenever
+    /// the status of a proposal changes or a proposal is created. If
+    /// a consumer contract errors when handling a hook message it
+    /// will be removed from the list of consumers.
+    AddProposalHook { address: String, code_hash: String },
+    /// Removes a consumer of proposal hooks.
+    RemoveProposalHook { address: String, code_hash: String },
+    /// Adds an address as a consumer of vote hooks. Consumers of vote
+    /// hooks are sent a vote of "approve" or "reject" whenever a
+    /// signer votes. If a consumer contract errors when handling a
+    /// hook message it will be removed from the list of consumers.
+    AddVoteHook { address: String, code_hash: String },
+    /// Removed a consumer of vote hooks.
+    RemoveVoteHook { address: String, code_hash: String },
+}
+
+#[proposal_module_query]
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    /// Gets the proposal module's config.
+    #[returns(crate::state::Config)]
+    Config {},
+    /// Gets information about a proposal.
+    #[returns(crate::query::ProposalResponse)]
+    Proposal { proposal_id: u64 },
+    /// Lists all the proposals that have been created in this
+    /// module.
+    #[returns(crate::query::ProposalListResponse)]
+    ListProposals {
+        /// The proposal ID to start listing proposals after. For
+        /// example, if this is set to 2 proposals with IDs 3 and
+        /// higher will be returned.
+        start_after: Option<u64>,
+        /// The maximum number of proposals to return as part of this
+        /// query. If no limit is set a max of 30 proposals will be
+        /// returned.
+        limit: Option<u64>,
+    },
+    /// Gets an address's current signer weight.
+    #[returns(crate::query::SignerResponse)]
+    Signer { address: String },
+    /// Lists the module's current signers and their total weight.
+    #[returns(crate::query::SignerListResponse)]
+    ListSigners {},
+    /// Gets a signer's vote on a proposal.
+    #[returns(crate::query::BallotResponse)]
+    Ballot { proposal_id: u64, address: String },
+    /// Returns the number of proposals that have been created in this module.
+    #[returns(::std::primitive::u64)]
+    ProposalCount {},
+    /// Gets the current proposal creation policy for this module.
+    #[returns(::dao_voting::pre_propose::ProposalCreationPolicy)]
+    ProposalCreationPolicy {},
+    /// Lists all of the consumers of proposal hooks for this module.
+    #[returns(::cw_hooks::HooksResponse)]
+    ProposalHooks {},
+    /// Lists all of the consumers of vote hooks for this module.
+    #[returns(::cw_hooks::HooksResponse)]
+    VoteHooks {},
+}
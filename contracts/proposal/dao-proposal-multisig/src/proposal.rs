@@ -0,0 +1,230 @@
+use cosmwasm_std::{Addr, BlockInfo, CosmosMsg, Empty, StdResult, Storage};
+use dao_voting::status::Status as CompletedStatus;
+use schemars::JsonSchema;
+use secret_utils::Expiration;
+use serde::{Deserialize, Serialize};
+
+use crate::query::ProposalResponse;
+use crate::state::{Signer, PROPOSAL_COUNT};
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum Status {
+    /// The proposal is open for approval.
+    Open,
+    /// The proposal has been rejected, either because enough weight
+    /// rejected it or because it expired before reaching the
+    /// threshold.
+    Rejected,
+    /// The proposal has been approved but has not been executed.
+    Passed,
+    /// The proposal has been passed and executed.
+    Executed,
+    /// The proposal has been rejected and closed.
+    Closed,
+    /// The proposal's execution failed.
+    ExecutionFailed,
+}
+
+impl std::fmt::Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Status::Open => write!(f, "open"),
+            Status::Rejected => write!(f, "rejected"),
+            Status::Passed => write!(f, "passed"),
+            Status::Executed => write!(f, "executed"),
+            Status::Closed => write!(f, "closed"),
+            Status::ExecutionFailed => write!(f, "execution_failed"),
+        }
+    }
+}
+
+impl Status {
+    /// The status reported to the pre-propose module when a proposal
+    /// completes.
+    pub fn completed(&self) -> Option<CompletedStatus> {
+        match self {
+            Status::Executed => Some(CompletedStatus::Executed),
+            Status::Closed => Some(CompletedStatus::Closed),
+            Status::ExecutionFailed => Some(CompletedStatus::ExecutionFailed),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub struct MultisigProposal {
+    /// The title of the proposal
+    pub title: String,
+    /// The main body of the proposal text
+    pub description: String,
+    /// The address that created this proposal.
+    pub proposer: Addr,
+    /// The block height at which this proposal was created.
+    pub start_height: u64,
+    /// The time at which this proposal will expire and no longer be
+    /// open for approval.
+    pub expiration: Expiration,
+    /// The signer weight that must approve the proposal for it to
+    /// pass.
+    pub threshold: u64,
+    /// The signers of this proposal, as of its creation.
+    pub signers: Vec<Signer>,
+    /// The signer weight that has approved the proposal.
+    pub approvals: u64,
+    /// The signer weight that has rejected the proposal.
+    pub rejections: u64,
+    /// The messages that will be executed should this proposal pass.
+    pub msgs: Vec<CosmosMsg<Empty>>,
+    /// The proposal status
+    pub status: Status,
+}
+
+pub fn next_proposal_id(store: &dyn Storage) -> StdResult<u64> {
+    Ok(PROPOSAL_COUNT.may_load(store)?.unwrap_or_default() + 1)
+}
+
+pub fn advance_proposal_id(store: &mut dyn Storage) -> StdResult<u64> {
+    let id: u64 = next_proposal_id(store)?;
+    PROPOSAL_COUNT.save(store, &id)?;
+    Ok(id)
+}
+
+impl MultisigProposal {
+    /// Consumes the proposal and returns a version which may be used
+    /// in a query response. Statuses are only updated on vote,
+    /// execute, and close events, so the stored status of an expired
+    /// proposal may be out of date.
+    pub fn into_response(mut self, block: &BlockInfo, id: u64) -> ProposalResponse {
+        self.update_status(block);
+        ProposalResponse { id, proposal: self }
+    }
+
+    /// The total weight of the proposal's signers.
+    pub fn total_weight(&self) -> u64 {
+        self.signers.iter().map(|s| s.weight).sum()
+    }
+
+    /// The weight of `addr` on this proposal, if they are one of its
+    /// signers.
+    pub fn signer_weight(&self, addr: &Addr) -> Option<u64> {
+        self.signers
+            .iter()
+            .find(|s| &s.addr == addr)
+            .map(|s| s.weight)
+    }
+
+    /// Gets the current status of the proposal.
+    pub fn current_status(&self, block: &BlockInfo) -> Status {
+        if self.status != Status::Open {
+            self.status
+        } else if self.approvals >= self.threshold {
+            Status::Passed
+        } else if self.expiration.is_expired(block)
+            // Enough weight has rejected the proposal that it can
+            // never reach the threshold.
+            || self.rejections > self.total_weight().saturating_sub(self.threshold)
+        {
+            Status::Rejected
+        } else {
+            Status::Open
+        }
+    }
+
+    /// Sets a proposals status to its current status.
+    pub fn update_status(&mut self, block: &BlockInfo) {
+        self.status = self.current_status(block);
+    }
+
+    /// Records a vote of `weight` on the proposal and updates its
+    /// status.
+    pub fn vote(&mut self, block: &BlockInfo, weight: u64, approve: bool) {
+        if approve {
+            self.approvals += weight;
+        } else {
+            self.rejections += weight;
+        }
+        self.update_status(block);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::mock_env;
+
+    fn proposal(threshold: u64) -> MultisigProposal {
+        let env = mock_env();
+        MultisigProposal {
+            title: "title".to_string(),
+            description: "description".to_string(),
+            proposer: Addr::unchecked("proposer"),
+            start_height: env.block.height,
+            expiration: Expiration::AtHeight(env.block.height + 10),
+            threshold,
+            signers: vec![
+                Signer {
+                    addr: Addr::unchecked("alice"),
+                    weight: 2,
+                },
+                Signer {
+                    addr: Addr::unchecked("bob"),
+                    weight: 1,
+                },
+                Signer {
+                    addr: Addr::unchecked("carol"),
+                    weight: 1,
+                },
+            ],
+            approvals: 0,
+            rejections: 0,
+            msgs: vec![],
+            status: Status::Open,
+        }
+    }
+
+    #[test]
+    fn test_approve() {
+        let env = mock_env();
+        let mut prop = proposal(3);
+        assert_eq!(prop.total_weight(), 4);
+        assert_eq!(prop.signer_weight(&Addr::unchecked("alice")), Some(2));
+        assert_eq!(prop.signer_weight(&Addr::unchecked("dave")), None);
+
+        prop.vote(&env.block, 2, true);
+        assert_eq!(prop.status, Status::Open);
+        prop.vote(&env.block, 1, false);
+        assert_eq!(prop.status, Status::Open);
+        prop.vote(&env.block, 1, true);
+        assert_eq!(prop.status, Status::Passed);
+
+        // Passed proposals stay passed once they expire.
+        let mut env = env;
+        env.block.height += 10;
+        assert_eq!(prop.current_status(&env.block), Status::Passed);
+    }
+
+    #[test]
+    fn test_reject() {
+        let env = mock_env();
+        let mut prop = proposal(3);
+
+        // With a threshold of 3 out of 4, 2 rejecting weight makes
+        // the proposal impossible to pass.
+        prop.vote(&env.block, 1, false);
+        assert_eq!(prop.status, Status::Open);
+        prop.vote(&env.block, 1, false);
+        assert_eq!(prop.status, Status::Rejected);
+    }
+
+    #[test]
+    fn test_expire() {
+        let mut env = mock_env();
+        let mut prop = proposal(3);
+
+        prop.vote(&env.block, 2, true);
+        env.block.height += 10;
+        assert_eq!(prop.current_status(&env.block), Status::Rejected);
+    }
+}
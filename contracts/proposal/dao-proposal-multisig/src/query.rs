@@ -0,0 +1,43 @@
+use crate::proposal::MultisigProposal;
+use crate::state::{Ballot, Signer};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Information about a proposal returned by proposal queries.
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub struct ProposalResponse {
+    /// The ID of the proposal being returned.
+    pub id: u64,
+    pub proposal: MultisigProposal,
+}
+
+/// A list of proposals returned by `ListProposals`.
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub struct ProposalListResponse {
+    pub proposals: Vec<ProposalResponse>,
+}
+
+/// The current signer set returned by `ListSigners`.
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub struct SignerListResponse {
+    pub signers: Vec<Signer>,
+    pub total_weight: u64,
+}
+
+/// A signer's current weight, or None if the address is not a
+/// signer.
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub struct SignerResponse {
+    pub weight: Option<u64>,
+}
+
+/// A signer's vote on a proposal, if they have voted.
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub struct BallotResponse {
+    pub ballot: Option<Ballot>,
+}
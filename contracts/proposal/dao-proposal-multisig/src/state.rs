@@ -0,0 +1,65 @@
+use cosmwasm_std::Addr;
+use cw_hooks::Hooks;
+use dao_interface::state::AnyContractInfo;
+use dao_voting::pre_propose::ProposalCreationPolicy;
+use schemars::JsonSchema;
+use secret_cw_controllers::ReplyIds;
+use secret_storage_plus::Item;
+use secret_toolkit::{serialization::Json, storage::Keymap};
+use secret_utils::Duration;
+use serde::{Deserialize, Serialize};
+
+use crate::proposal::MultisigProposal;
+
+/// The multisig proposal module's configuration.
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub struct Config {
+    /// The signer weight that must approve a proposal for it to
+    /// pass.
+    pub threshold: u64,
+    /// The amount of time signers have to approve a proposal after
+    /// it is created.
+    pub max_voting_period: Duration,
+    /// If set to true proposals will be closed if their execution
+    /// fails. Otherwise, proposals will remain passed after execution
+    /// failure and may be executed again.
+    pub close_proposal_on_execution_failure: bool,
+}
+
+/// A signer and their weight.
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub struct Signer {
+    pub addr: Addr,
+    pub weight: u64,
+}
+
+/// A signer's vote on a proposal.
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub struct Ballot {
+    /// The signer's weight on the proposal.
+    pub weight: u64,
+    /// True if the signer approved the proposal, false if they
+    /// rejected it.
+    pub approve: bool,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+/// The current signers and their weights. Proposals snapshot the
+/// signer set when they are created.
+pub static SIGNERS: Keymap<Addr, u64, Json> = Keymap::new(b"signers");
+/// The number of proposals that have been created.
+pub const PROPOSAL_COUNT: Item<u64> = Item::new("proposal_count");
+pub static PROPOSALS: Keymap<u64, MultisigProposal, Json> = Keymap::new(b"proposals");
+pub static BALLOTS: Keymap<(u64, Addr), Ballot, Json> = Keymap::new(b"ballots");
+/// Consumers of proposal state change hooks.
+pub const PROPOSAL_HOOKS: Hooks = Hooks::new("proposal_hooks");
+/// Consumers of vote hooks.
+pub const VOTE_HOOKS: Hooks = Hooks::new("vote_hooks");
+/// The address of the pre-propose module associated with this
+/// proposal module (if any).
+pub const CREATION_POLICY: Item<ProposalCreationPolicy> = Item::new("creation_policy");
+pub const DAO: Item<AnyContractInfo> = Item::new("dao");
+pub const REPLY_IDS: ReplyIds = ReplyIds::new(b"reply_ids", b"reply_ids_count");
@@ -0,0 +1,427 @@
+use cosmwasm_std::testing::{
+    mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+};
+use cosmwasm_std::{
+    coins, from_binary, Addr, BankMsg, CosmosMsg, Env, OwnedDeps, Reply, ReplyOn, Response,
+    SubMsgResponse, SubMsgResult, WasmMsg,
+};
+use dao_hooks::proposal::{PreProposeHookMsg, ProposalHookExecuteMsg, ProposalHookMsg};
+use dao_hooks::vote::{VoteHookExecuteMsg, VoteHookMsg};
+use dao_voting::pre_propose::{PreProposeInfo, ProposalCreationPolicy};
+use dao_voting::status::Status as CompletedStatus;
+use secret_utils::Duration;
+
+use crate::contract::{execute, instantiate, query, reply};
+use crate::msg::{ExecuteMsg, InstantiateMsg, ProposeMsg, QueryMsg, SignerInfo};
+use crate::proposal::{MultisigProposal, Status};
+use crate::query::{BallotResponse, ProposalResponse};
+use crate::state::{Ballot, Signer, CREATION_POLICY};
+use crate::ContractError;
+
+type Deps = OwnedDeps<MockStorage, MockApi, MockQuerier>;
+
+/// The height proposals are created at.
+const START: u64 = 12_345;
+
+fn signer(addr: &str, weight: u64) -> SignerInfo {
+    SignerInfo {
+        addr: addr.to_string(),
+        weight,
+    }
+}
+
+/// Sets up a module whose signers are Alice with a weight of two and
+/// Bob and Carol with a weight of one, three of which must approve a
+/// proposal within 10 blocks. "proposal_hook" and "vote_hook" receive
+/// hooks.
+fn setup() -> Deps {
+    setup_with(|_| ())
+}
+
+/// As above, with the instantiate message modified by CONFIGURE.
+fn setup_with(configure: impl FnOnce(&mut InstantiateMsg)) -> Deps {
+    let mut deps = mock_dependencies();
+    let mut msg = InstantiateMsg {
+        signers: vec![signer("alice", 2), signer("bob", 1), signer("carol", 1)],
+        threshold: 3,
+        max_voting_period: Duration::Height(10),
+        pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
+        close_proposal_on_execution_failure: false,
+        dao_code_hash: "dao_hash".to_string(),
+    };
+    configure(&mut msg);
+    instantiate(deps.as_mut(), mock_env(), mock_info("dao", &[]), msg).unwrap();
+    for msg in [
+        ExecuteMsg::AddProposalHook {
+            address: "proposal_hook".to_string(),
+            code_hash: "hook_hash".to_string(),
+        },
+        ExecuteMsg::AddVoteHook {
+            address: "vote_hook".to_string(),
+            code_hash: "hook_hash".to_string(),
+        },
+    ] {
+        execute(deps.as_mut(), mock_env(), mock_info("dao", &[]), msg).unwrap();
+    }
+    deps
+}
+
+fn env_at(height: u64) -> Env {
+    let mut env = mock_env();
+    env.block.height = height;
+    env
+}
+
+fn proposal_msgs() -> Vec<CosmosMsg> {
+    vec![CosmosMsg::Bank(BankMsg::Send {
+        to_address: "alice".to_string(),
+        amount: coins(1, "ujuno"),
+    })]
+}
+
+fn propose_msg(proposer: Option<&str>) -> ExecuteMsg {
+    ExecuteMsg::Propose(ProposeMsg {
+        title: "title".to_string(),
+        description: "description".to_string(),
+        msgs: proposal_msgs(),
+        proposer: proposer.map(str::to_string),
+    })
+}
+
+fn propose(deps: &mut Deps, sender: &str) -> Result<Response, ContractError> {
+    execute(
+        deps.as_mut(),
+        env_at(START),
+        mock_info(sender, &[]),
+        propose_msg(None),
+    )
+}
+
+fn vote(
+    deps: &mut Deps,
+    signer: &str,
+    proposal_id: u64,
+    approve: bool,
+) -> Result<Response, ContractError> {
+    let msg = if approve {
+        ExecuteMsg::Approve { proposal_id }
+    } else {
+        ExecuteMsg::Reject { proposal_id }
+    };
+    execute(
+        deps.as_mut(),
+        env_at(START + 1),
+        mock_info(signer, &[]),
+        msg,
+    )
+}
+
+fn execute_proposal(deps: &mut Deps, height: u64) -> Result<Response, ContractError> {
+    execute(
+        deps.as_mut(),
+        env_at(height),
+        mock_info("anyone", &[]),
+        ExecuteMsg::Execute { proposal_id: 1 },
+    )
+}
+
+fn proposal(deps: &Deps, height: u64, proposal_id: u64) -> MultisigProposal {
+    let ProposalResponse { proposal, .. } = from_binary(
+        &query(
+            deps.as_ref(),
+            env_at(height),
+            QueryMsg::Proposal { proposal_id },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    proposal
+}
+
+fn ballot(deps: &Deps, address: &str) -> Option<Ballot> {
+    let BallotResponse { ballot } = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Ballot {
+                proposal_id: 1,
+                address: address.to_string(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    ballot
+}
+
+/// Returns the hook messages sent to HOOK in RES.
+fn hook_msgs<T: serde::de::DeserializeOwned>(res: &Response, hook: &str) -> Vec<T> {
+    res.messages
+        .iter()
+        .filter_map(|sub| match &sub.msg {
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr, msg, ..
+            }) if contract_addr == hook => Some(from_binary(msg).unwrap()),
+            _ => None,
+        })
+        .collect()
+}
+
+fn status_changed(old_status: Status, new_status: Status) -> ProposalHookExecuteMsg {
+    ProposalHookExecuteMsg::ProposalHook(ProposalHookMsg::ProposalStatusChanged {
+        id: 1,
+        old_status: old_status.to_string(),
+        new_status: new_status.to_string(),
+    })
+}
+
+fn new_vote(voter: &str, vote: &str) -> VoteHookExecuteMsg {
+    VoteHookExecuteMsg::VoteHook(VoteHookMsg::NewVote {
+        proposal_id: 1,
+        voter: voter.to_string(),
+        vote: vote.to_string(),
+    })
+}
+
+#[test]
+fn test_propose_and_approve() {
+    let mut deps = setup();
+
+    // Without a pre-propose module only signers may propose.
+    let err = propose(&mut deps, "dave").unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // Alice approves her own proposal.
+    let res = propose(&mut deps, "alice").unwrap();
+    assert_eq!(
+        hook_msgs::<VoteHookExecuteMsg>(&res, "vote_hook"),
+        vec![new_vote("alice", "approve")]
+    );
+    assert_eq!(
+        ballot(&deps, "alice"),
+        Some(Ballot {
+            weight: 2,
+            approve: true
+        })
+    );
+    let prop = proposal(&deps, START, 1);
+    assert_eq!(prop.approvals, 2);
+    assert_eq!(prop.status, Status::Open);
+
+    let err = vote(&mut deps, "alice", 1, true).unwrap_err();
+    assert_eq!(err, ContractError::AlreadyVoted {});
+    let err = vote(&mut deps, "dave", 1, true).unwrap_err();
+    assert_eq!(err, ContractError::NotSigner {});
+    let err = execute_proposal(&mut deps, START + 1).unwrap_err();
+    assert_eq!(err, ContractError::NotPassed {});
+
+    let res = vote(&mut deps, "bob", 1, true).unwrap();
+    assert_eq!(
+        hook_msgs::<VoteHookExecuteMsg>(&res, "vote_hook"),
+        vec![new_vote("bob", "approve")]
+    );
+    assert_eq!(
+        hook_msgs::<ProposalHookExecuteMsg>(&res, "proposal_hook"),
+        vec![status_changed(Status::Open, Status::Passed)]
+    );
+    assert_eq!(proposal(&deps, START + 1, 1).status, Status::Passed);
+
+    let err = vote(&mut deps, "carol", 1, true).unwrap_err();
+    assert_eq!(err, ContractError::NotOpen { id: 1 });
+}
+
+#[test]
+fn test_execute() {
+    let mut deps = setup();
+    propose(&mut deps, "alice").unwrap();
+    vote(&mut deps, "bob", 1, true).unwrap();
+
+    // Passed proposals are executed by the DAO, and may be executed
+    // after their voting period ends.
+    let res = execute_proposal(&mut deps, START + 20).unwrap();
+    assert_eq!(
+        hook_msgs::<dao_interface::msg::ExecuteMsg>(&res, "dao"),
+        vec![dao_interface::msg::ExecuteMsg::ExecuteProposalHook {
+            msgs: proposal_msgs()
+        }]
+    );
+    assert_eq!(
+        hook_msgs::<ProposalHookExecuteMsg>(&res, "proposal_hook"),
+        vec![status_changed(Status::Passed, Status::Executed)]
+    );
+    assert_eq!(proposal(&deps, START + 20, 1).status, Status::Executed);
+
+    let err = execute_proposal(&mut deps, START + 20).unwrap_err();
+    assert_eq!(err, ContractError::NotPassed {});
+}
+
+#[test]
+fn test_reject_and_close() {
+    let mut deps = setup();
+    propose(&mut deps, "alice").unwrap();
+
+    let close = |deps: &mut Deps| {
+        execute(
+            deps.as_mut(),
+            env_at(START + 1),
+            mock_info("anyone", &[]),
+            ExecuteMsg::Close { proposal_id: 1 },
+        )
+    };
+
+    // Alice's approval and one more may still reach the threshold.
+    vote(&mut deps, "bob", 1, false).unwrap();
+    assert_eq!(proposal(&deps, START + 1, 1).status, Status::Open);
+    let err = close(&mut deps).unwrap_err();
+    assert_eq!(err, ContractError::NotRejected {});
+
+    let res = vote(&mut deps, "carol", 1, false).unwrap();
+    assert_eq!(
+        hook_msgs::<VoteHookExecuteMsg>(&res, "vote_hook"),
+        vec![new_vote("carol", "reject")]
+    );
+    assert_eq!(
+        hook_msgs::<ProposalHookExecuteMsg>(&res, "proposal_hook"),
+        vec![status_changed(Status::Open, Status::Rejected)]
+    );
+    let err = execute_proposal(&mut deps, START + 1).unwrap_err();
+    assert_eq!(err, ContractError::NotPassed {});
+
+    let res = close(&mut deps).unwrap();
+    assert_eq!(
+        hook_msgs::<ProposalHookExecuteMsg>(&res, "proposal_hook"),
+        vec![status_changed(Status::Rejected, Status::Closed)]
+    );
+    assert_eq!(proposal(&deps, START + 1, 1).status, Status::Closed);
+}
+
+#[test]
+fn test_update_signers_applies_to_later_proposals() {
+    let mut deps = setup();
+    propose(&mut deps, "alice").unwrap();
+
+    let update_signers = |deps: &mut Deps, sender: &str, add, remove: &[&str]| {
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(sender, &[]),
+            ExecuteMsg::UpdateSigners {
+                add,
+                remove: remove.iter().map(|addr| addr.to_string()).collect(),
+            },
+        )
+    };
+    let err = update_signers(&mut deps, "alice", vec![], &["carol"]).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    // Without Alice the threshold could not be reached.
+    let err = update_signers(&mut deps, "dao", vec![], &["alice"]).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidThreshold {
+            threshold: 3,
+            total_weight: 2
+        }
+    );
+
+    // Dave replaces Carol.
+    update_signers(&mut deps, "dao", vec![signer("dave", 1)], &["carol"]).unwrap();
+    assert_eq!(
+        proposal(&deps, START, 1).signers,
+        vec![
+            Signer {
+                addr: Addr::unchecked("alice"),
+                weight: 2
+            },
+            Signer {
+                addr: Addr::unchecked("bob"),
+                weight: 1
+            },
+            Signer {
+                addr: Addr::unchecked("carol"),
+                weight: 1
+            },
+        ]
+    );
+
+    // The existing proposal is still signed by Carol.
+    let err = vote(&mut deps, "dave", 1, true).unwrap_err();
+    assert_eq!(err, ContractError::NotSigner {});
+    vote(&mut deps, "carol", 1, true).unwrap();
+    assert_eq!(proposal(&deps, START + 1, 1).status, Status::Passed);
+
+    // Later proposals are signed by Dave.
+    propose(&mut deps, "alice").unwrap();
+    let err = vote(&mut deps, "carol", 2, true).unwrap_err();
+    assert_eq!(err, ContractError::NotSigner {});
+    vote(&mut deps, "dave", 2, true).unwrap();
+    assert_eq!(proposal(&deps, START + 1, 2).status, Status::Passed);
+}
+
+#[test]
+fn test_pre_propose_hooks() {
+    let mut deps = setup_with(|msg| msg.close_proposal_on_execution_failure = true);
+    CREATION_POLICY
+        .save(
+            deps.as_mut().storage,
+            &ProposalCreationPolicy::Module {
+                addr: Addr::unchecked("pre_propose"),
+                code_hash: "pre_propose_hash".to_string(),
+            },
+        )
+        .unwrap();
+
+    // Only the pre-propose module may now propose, on behalf of
+    // anyone.
+    let err = propose(&mut deps, "alice").unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    let res = execute(
+        deps.as_mut(),
+        env_at(START),
+        mock_info("pre_propose", &[]),
+        propose_msg(Some("alice")),
+    )
+    .unwrap();
+    assert_eq!(
+        hook_msgs::<ProposalHookExecuteMsg>(&res, "proposal_hook"),
+        vec![ProposalHookExecuteMsg::ProposalHook(
+            ProposalHookMsg::NewProposal {
+                id: 1,
+                proposer: "alice".to_string(),
+                emergency: false,
+            }
+        )]
+    );
+    assert_eq!(proposal(&deps, START, 1).approvals, 2);
+    vote(&mut deps, "bob", 1, true).unwrap();
+
+    // The pre-propose module is told the proposal completed once its
+    // execution replies.
+    let res = execute_proposal(&mut deps, START + 1).unwrap();
+    assert!(hook_msgs::<PreProposeHookMsg>(&res, "pre_propose").is_empty());
+    let execution = res
+        .messages
+        .iter()
+        .find(|sub| sub.reply_on == ReplyOn::Always)
+        .unwrap();
+    let res = reply(
+        deps.as_mut(),
+        env_at(START + 1),
+        Reply {
+            id: execution.id,
+            result: SubMsgResult::Ok(SubMsgResponse {
+                events: vec![],
+                data: None,
+            }),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        hook_msgs::<PreProposeHookMsg>(&res, "pre_propose"),
+        vec![PreProposeHookMsg::ProposalCompletedHook {
+            proposal_id: 1,
+            new_status: CompletedStatus::Executed,
+            quorum_met: None,
+        }]
+    );
+    assert_eq!(proposal(&deps, START + 1, 1).status, Status::Executed);
+}
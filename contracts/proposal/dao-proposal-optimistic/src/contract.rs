@@ -0,0 +1,776 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    to_binary, Addr, Api, Binary, CosmosMsg, Deps, DepsMut, Empty, Env, MessageInfo, Reply,
+    Response, StdError, StdResult, Storage, SubMsg, SubMsgResult, Uint128, WasmMsg,
+};
+use cw_hooks::{HookItem, Hooks};
+use dao_hooks::proposal::{
+    new_proposal_hooks, proposal_completed_hooks, proposal_status_changed_hooks,
+};
+use dao_hooks::vote::new_vote_hooks;
+use dao_interface::state::AnyContractInfo;
+use dao_voting::dependency::{query_dependency, ProposalDependency};
+use dao_voting::pre_propose::{PreProposeInfo, ProposalCreationPolicy};
+use dao_voting::proposal::{DEFAULT_LIMIT, MAX_PROPOSAL_SIZE};
+use dao_voting::status::Status as CompletedStatus;
+use dao_voting::threshold::{validate_quorum, PercentageThreshold};
+use dao_voting::voting::{get_total_power, get_voting_power};
+use secret_cw2::set_contract_version;
+use secret_cw_controllers::ReplyEvent;
+use secret_toolkit::utils::HandleCallback;
+use secret_utils::{parse_reply_event_for_contract_address, Duration};
+
+use crate::msg::{
+    EscalationExecuteExt, EscalationProposeMsg, ExecuteMsg, InstantiateMsg, ProposeMsg, QueryMsg,
+};
+use crate::proposal::{
+    advance_proposal_id, is_final, next_proposal_id, EscalatedProposal, OptimisticProposal, Status,
+};
+use crate::query::{ProposalListResponse, ProposalResponse};
+use crate::state::{
+    Config, Objection, CONFIG, CREATION_POLICY, DAO, OBJECTIONS, PROPOSALS, PROPOSAL_COUNT,
+    PROPOSAL_HOOKS, REPLY_IDS, VOTE_HOOKS,
+};
+use crate::ContractError;
+
+pub(crate) const CONTRACT_NAME: &str = "crates.io:dao-proposal-optimistic";
+pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    DAO.save(
+        deps.storage,
+        &AnyContractInfo {
+            code_hash: msg.dao_code_hash,
+            addr: info.sender.clone(),
+        },
+    )?;
+
+    let config = validate_config(
+        deps.api,
+        msg.challenge_period,
+        msg.objection_threshold,
+        msg.escalation,
+        msg.close_proposal_on_execution_failure,
+    )?;
+
+    let (initial_policy, pre_propose_messages) = msg
+        .pre_propose_info
+        .into_initial_policy_and_messages(deps.storage, info.sender.clone(), REPLY_IDS)?;
+
+    // Initialize proposal count to zero so that queries return zero
+    // instead of None.
+    PROPOSAL_COUNT.save(deps.storage, &0)?;
+    CONFIG.save(deps.storage, &config)?;
+    CREATION_POLICY.save(deps.storage, &initial_policy)?;
+
+    Ok(Response::default()
+        .add_submessages(pre_propose_messages)
+        .add_attribute("action", "instantiate")
+        .add_attribute("dao", info.sender.to_string()))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Propose(ProposeMsg {
+            title,
+            description,
+            msgs,
+            proposer,
+        }) => execute_propose(deps, env, info.sender, title, description, msgs, proposer),
+        ExecuteMsg::Object {
+            key,
+            proposal_id,
+            rationale,
+        } => execute_object(deps, env, info, key, proposal_id, rationale),
+        ExecuteMsg::Execute { proposal_id } => execute_execute(deps, env, info, proposal_id),
+        ExecuteMsg::Escalate { proposal_id, key } => {
+            execute_escalate(deps, env, info, proposal_id, key)
+        }
+        ExecuteMsg::Settle { proposal_id } => execute_settle(deps, env, info, proposal_id),
+        ExecuteMsg::Close { proposal_id } => execute_close(deps, env, info, proposal_id),
+        ExecuteMsg::UpdateConfig {
+            challenge_period,
+            objection_threshold,
+            escalation,
+            close_proposal_on_execution_failure,
+        } => execute_update_config(
+            deps,
+            info,
+            challenge_period,
+            objection_threshold,
+            escalation,
+            close_proposal_on_execution_failure,
+        ),
+        ExecuteMsg::UpdatePreProposeInfo { info: new_info } => {
+            execute_update_proposal_creation_policy(deps, info, new_info)
+        }
+        ExecuteMsg::AddProposalHook { address, code_hash } => execute_update_hook(
+            deps,
+            info,
+            PROPOSAL_HOOKS,
+            "proposal",
+            address,
+            code_hash,
+            true,
+        ),
+        ExecuteMsg::RemoveProposalHook { address, code_hash } => execute_update_hook(
+            deps,
+            info,
+            PROPOSAL_HOOKS,
+            "proposal",
+            address,
+            code_hash,
+            false,
+        ),
+        ExecuteMsg::AddVoteHook { address, code_hash } => {
+            execute_update_hook(deps, info, VOTE_HOOKS, "vote", address, code_hash, true)
+        }
+        ExecuteMsg::RemoveVoteHook { address, code_hash } => {
+            execute_update_hook(deps, info, VOTE_HOOKS, "vote", address, code_hash, false)
+        }
+    }
+}
+
+fn validate_config(
+    api: &dyn Api,
+    challenge_period: Duration,
+    objection_threshold: PercentageThreshold,
+    escalation: Option<AnyContractInfo>,
+    close_proposal_on_execution_failure: bool,
+) -> Result<Config, ContractError> {
+    if matches!(challenge_period, Duration::Height(0) | Duration::Time(0)) {
+        return Err(ContractError::ZeroChallengePeriod {});
+    }
+    validate_quorum(&objection_threshold)?;
+    let escalation = escalation
+        .map(|escalation| -> StdResult<_> {
+            Ok(AnyContractInfo {
+                addr: api.addr_validate(escalation.addr.as_str())?,
+                code_hash: escalation.code_hash,
+            })
+        })
+        .transpose()?;
+    Ok(Config {
+        challenge_period,
+        objection_threshold,
+        escalation,
+        close_proposal_on_execution_failure,
+    })
+}
+
+pub fn execute_propose(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    title: String,
+    description: String,
+    msgs: Vec<CosmosMsg<Empty>>,
+    proposer: Option<String>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let dao_info = DAO.load(deps.storage)?;
+    let proposal_creation_policy = CREATION_POLICY.load(deps.storage)?;
+
+    // Check that the sender is permitted to create proposals.
+    if !proposal_creation_policy.is_permitted(&sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // Determine the appropriate proposer. If this is coming from our
+    // pre-propose module, it must be specified. Otherwise, the
+    // proposer should not be specified.
+    let proposer = match (proposer, &proposal_creation_policy) {
+        (None, ProposalCreationPolicy::Anyone {}) => sender.clone(),
+        (Some(proposer), ProposalCreationPolicy::Module { .. }) => {
+            deps.api.addr_validate(&proposer)?
+        }
+        _ => return Err(ContractError::InvalidProposer {}),
+    };
+
+    let total_power = get_total_power(
+        deps.as_ref(),
+        dao_info.code_hash.clone(),
+        &dao_info.addr,
+        Some(env.block.height),
+    )?;
+    // Lacking any voting power, no one could challenge the proposal.
+    if total_power.is_zero() {
+        return Err(ContractError::ZeroTotalPower {});
+    }
+
+    let proposal = OptimisticProposal {
+        title,
+        description,
+        proposer: proposer.clone(),
+        start_height: env.block.height,
+        expiration: config.challenge_period.after(&env.block),
+        objection_threshold: config.objection_threshold,
+        total_power,
+        objections: Uint128::zero(),
+        msgs,
+        status: Status::Open,
+        escalated_to: None,
+    };
+    check_proposal_size(&proposal)?;
+    let id = advance_proposal_id(deps.storage)?;
+    PROPOSALS.insert(deps.storage, &id, &proposal)?;
+
+    let hooks = new_proposal_hooks(PROPOSAL_HOOKS, deps.storage, id, proposer.as_str())?;
+
+    Ok(Response::default()
+        .add_submessages(hooks)
+        .add_attribute("action", "propose")
+        .add_attribute("sender", sender)
+        .add_attribute("proposal_id", id.to_string())
+        .add_attribute("expiration", proposal.expiration.to_string()))
+}
+
+/// Checks that a proposal is small enough to be returned by queries.
+/// Chains accept larger execute messages than query responses, so
+/// without this check it is possible to create a proposal that can
+/// not be queried. See `MAX_PROPOSAL_SIZE`.
+fn check_proposal_size(proposal: &OptimisticProposal) -> Result<(), ContractError> {
+    // `to_vec` is the method used by cosmwasm to convert a struct
+    // into it's byte representation in storage.
+    let proposal_size = cosmwasm_std::to_vec(proposal)?.len() as u64;
+    if proposal_size > MAX_PROPOSAL_SIZE {
+        return Err(ContractError::ProposalTooLarge {
+            size: proposal_size,
+            max: MAX_PROPOSAL_SIZE,
+        });
+    }
+    Ok(())
+}
+
+pub fn execute_object(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    key: String,
+    proposal_id: u64,
+    rationale: Option<String>,
+) -> Result<Response, ContractError> {
+    let dao_info = DAO.load(deps.storage)?;
+    let mut prop = PROPOSALS
+        .get(deps.storage, &proposal_id)
+        .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+
+    prop.update_status(&env.block);
+    if prop.status != Status::Open {
+        return Err(ContractError::NotOpen { id: proposal_id });
+    }
+
+    let objector = (proposal_id, info.sender.clone());
+    if OBJECTIONS.contains(deps.storage, &objector) {
+        return Err(ContractError::AlreadyObjected {});
+    }
+
+    let power = get_voting_power(
+        deps.as_ref(),
+        dao_info.code_hash.clone(),
+        info.sender.clone(),
+        key,
+        &dao_info.addr,
+        Some(prop.start_height),
+    )?;
+    if power.is_zero() {
+        return Err(ContractError::NotRegistered {});
+    }
+
+    OBJECTIONS.insert(deps.storage, &objector, &Objection { power, rationale })?;
+
+    let old_status = prop.status;
+    prop.add_objection(power);
+    PROPOSALS.insert(deps.storage, &proposal_id, &prop)?;
+
+    let vote_hooks = new_vote_hooks(
+        VOTE_HOOKS,
+        deps.storage,
+        proposal_id,
+        info.sender.to_string(),
+        "object".to_string(),
+    )?;
+    let status_hooks = status_change_hooks(deps.storage, proposal_id, old_status, prop.status)?;
+
+    Ok(Response::default()
+        .add_submessages(vote_hooks)
+        .add_submessages(status_hooks)
+        .add_attribute("action", "object")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("status", prop.status.to_string()))
+}
+
+pub fn execute_execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+) -> Result<Response, ContractError> {
+    let dao_info = DAO.load(deps.storage)?;
+    let config = CONFIG.load(deps.storage)?;
+    let mut prop = PROPOSALS
+        .get(deps.storage, &proposal_id)
+        .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+
+    prop.update_status(&env.block);
+    if prop.status != Status::Passed {
+        return Err(ContractError::NotPassed {});
+    }
+
+    let old_status = prop.status;
+    prop.status = Status::Executed;
+    PROPOSALS.insert(deps.storage, &proposal_id, &prop)?;
+
+    // If the proposal is closed should its execution fail, whether
+    // it completed as executed is only known once execution replies,
+    // so the deposit module is told then.
+    let completion_deferred = !prop.msgs.is_empty() && config.close_proposal_on_execution_failure;
+
+    let response =
+        if prop.msgs.is_empty() {
+            Response::default()
+        } else {
+            let execute_message =
+                dao_interface::msg::ExecuteMsg::ExecuteProposalHook { msgs: prop.msgs }
+                    .to_cosmos_msg(dao_info.code_hash, dao_info.addr.to_string(), None)?;
+            if config.close_proposal_on_execution_failure {
+                let reply_id = REPLY_IDS.add_event(
+                    deps.storage,
+                    ReplyEvent::FailedProposalExecution { proposal_id },
+                )?;
+                Response::default().add_submessage(SubMsg::reply_always(execute_message, reply_id))
+            } else {
+                Response::default().add_message(execute_message)
+            }
+        };
+
+    let status_hooks = if completion_deferred {
+        proposal_status_changed_hooks(
+            PROPOSAL_HOOKS,
+            deps.storage,
+            proposal_id,
+            old_status.to_string(),
+            prop.status.to_string(),
+        )?
+    } else {
+        status_change_hooks(deps.storage, proposal_id, old_status, prop.status)?
+    };
+
+    Ok(response
+        .add_submessages(status_hooks)
+        .add_attribute("action", "execute")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("dao", dao_info.addr))
+}
+
+pub fn execute_escalate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+    key: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let escalation = config
+        .escalation
+        .ok_or(ContractError::EscalationDisabled {})?;
+    let mut prop = PROPOSALS
+        .get(deps.storage, &proposal_id)
+        .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+
+    prop.update_status(&env.block);
+    if prop.status != Status::Challenged {
+        return Err(ContractError::NotChallenged {});
+    }
+
+    // Record the ID the escalated proposal will be given. The
+    // pre-propose module creates it before any other message in this
+    // transaction may create a proposal in the same module.
+    let proposal_module: AnyContractInfo = deps.querier.query_wasm_smart(
+        escalation.code_hash.clone(),
+        escalation.addr.to_string(),
+        &dao_pre_propose_base::msg::QueryMsg::<Empty>::ProposalModule {},
+    )?;
+    let escalated_id: u64 = deps.querier.query_wasm_smart(
+        proposal_module.code_hash.clone(),
+        proposal_module.addr.to_string(),
+        &dao_interface::proposal::Query::NextProposalId {},
+    )?;
+
+    let old_status = prop.status;
+    prop.status = Status::Escalated;
+    prop.escalated_to = Some(EscalatedProposal {
+        proposal_module: proposal_module.addr,
+        code_hash: proposal_module.code_hash,
+        proposal_id: escalated_id,
+        outcome: None,
+    });
+    PROPOSALS.insert(deps.storage, &proposal_id, &prop)?;
+
+    // The sender is the proposer of the full vote. Their viewing key
+    // and deposit are passed on so the escalation module may check
+    // them as it would for any other proposer.
+    let propose = WasmMsg::Execute {
+        contract_addr: escalation.addr.into_string(),
+        code_hash: escalation.code_hash,
+        msg: to_binary(&dao_pre_propose_base::msg::ExecuteMsg::<
+            EscalationProposeMsg,
+            EscalationExecuteExt,
+        >::Extension {
+            msg: EscalationExecuteExt::ProposeOnBehalf {
+                proposer: info.sender.to_string(),
+                key,
+                msg: EscalationProposeMsg::Propose {
+                    title: prop.title,
+                    description: prop.description,
+                    msgs: prop.msgs,
+                },
+            },
+        })?,
+        funds: info.funds,
+    };
+
+    let status_hooks = status_change_hooks(deps.storage, proposal_id, old_status, prop.status)?;
+
+    Ok(Response::default()
+        .add_message(propose)
+        .add_submessages(status_hooks)
+        .add_attribute("action", "escalate")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("escalated_proposal_id", escalated_id.to_string()))
+}
+
+pub fn execute_settle(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+) -> Result<Response, ContractError> {
+    let mut prop = PROPOSALS
+        .get(deps.storage, &proposal_id)
+        .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+    let mut escalated = match (prop.status, prop.escalated_to.take()) {
+        (Status::Escalated, Some(escalated)) => escalated,
+        _ => return Err(ContractError::NotEscalated {}),
+    };
+    if escalated.outcome.is_some() {
+        return Err(ContractError::AlreadySettled { id: proposal_id });
+    }
+
+    let dependency = ProposalDependency {
+        proposal_module: escalated.proposal_module.to_string(),
+        code_hash: escalated.code_hash.clone(),
+        proposal_id: escalated.proposal_id,
+    };
+    let outcome = query_dependency(deps.as_ref(), &env.contract.address, &dependency, &|_| None)?
+        .map(|info| info.status)
+        .filter(is_final)
+        .ok_or(ContractError::EscalationPending { id: proposal_id })?;
+
+    escalated.outcome = Some(outcome);
+    prop.escalated_to = Some(escalated);
+    PROPOSALS.insert(deps.storage, &proposal_id, &prop)?;
+
+    // The deposit is handled as though the proposal had completed
+    // the way its full vote did.
+    let proposal_creation_policy = CREATION_POLICY.load(deps.storage)?;
+    let hooks = proposal_completed_hooks(proposal_creation_policy, proposal_id, outcome)?;
+
+    Ok(Response::default()
+        .add_submessages(hooks)
+        .add_attribute("action", "settle")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("outcome", outcome.to_string()))
+}
+
+pub fn execute_close(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut prop = PROPOSALS
+        .get(deps.storage, &proposal_id)
+        .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+
+    prop.update_status(&env.block);
+    if prop.status != Status::Challenged {
+        return Err(ContractError::NotChallenged {});
+    }
+    if config.escalation.is_some() {
+        return Err(ContractError::EscalationRequired {});
+    }
+
+    let old_status = prop.status;
+    prop.status = Status::Closed;
+    PROPOSALS.insert(deps.storage, &proposal_id, &prop)?;
+
+    let status_hooks = status_change_hooks(deps.storage, proposal_id, old_status, prop.status)?;
+
+    Ok(Response::default()
+        .add_submessages(status_hooks)
+        .add_attribute("action", "close")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string()))
+}
+
+/// Prepares proposal status change hooks and, if the proposal has
+/// completed, the pre-propose module's completed hook which handles
+/// deposit refunds.
+fn status_change_hooks(
+    storage: &mut dyn Storage,
+    proposal_id: u64,
+    old_status: Status,
+    new_status: Status,
+) -> StdResult<Vec<SubMsg>> {
+    if old_status == new_status {
+        return Ok(vec![]);
+    }
+    let mut hooks = proposal_status_changed_hooks(
+        PROPOSAL_HOOKS,
+        storage,
+        proposal_id,
+        old_status.to_string(),
+        new_status.to_string(),
+    )?;
+    if let Some(completed) = new_status.completed() {
+        let proposal_creation_policy = CREATION_POLICY.load(storage)?;
+        hooks.extend(proposal_completed_hooks(
+            proposal_creation_policy,
+            proposal_id,
+            completed,
+        )?);
+    }
+    Ok(hooks)
+}
+
+pub fn execute_update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    challenge_period: Duration,
+    objection_threshold: PercentageThreshold,
+    escalation: Option<AnyContractInfo>,
+    close_proposal_on_execution_failure: bool,
+) -> Result<Response, ContractError> {
+    let dao_info = DAO.load(deps.storage)?;
+
+    // Only the DAO may call this method.
+    if info.sender != dao_info.addr {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let config = validate_config(
+        deps.api,
+        challenge_period,
+        objection_threshold,
+        escalation,
+        close_proposal_on_execution_failure,
+    )?;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::default()
+        .add_attribute("action", "update_config")
+        .add_attribute("sender", info.sender))
+}
+
+pub fn execute_update_proposal_creation_policy(
+    deps: DepsMut,
+    info: MessageInfo,
+    new_info: PreProposeInfo,
+) -> Result<Response, ContractError> {
+    let dao_info = DAO.load(deps.storage)?;
+
+    if dao_info.addr != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let (initial_policy, messages) =
+        new_info.into_initial_policy_and_messages(deps.storage, dao_info.addr, REPLY_IDS)?;
+    CREATION_POLICY.save(deps.storage, &initial_policy)?;
+
+    Ok(Response::default()
+        .add_submessages(messages)
+        .add_attribute("action", "update_proposal_creation_policy")
+        .add_attribute("sender", info.sender)
+        .add_attribute("new_policy", format!("{initial_policy:?}")))
+}
+
+/// Adds or removes a hook consumer. Only the DAO may call this
+/// method.
+pub fn execute_update_hook(
+    deps: DepsMut,
+    info: MessageInfo,
+    hooks: Hooks,
+    kind: &str,
+    address: String,
+    code_hash: String,
+    add: bool,
+) -> Result<Response, ContractError> {
+    let dao_info = DAO.load(deps.storage)?;
+    if dao_info.addr != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let hook = HookItem {
+        addr: deps.api.addr_validate(&address)?,
+        code_hash,
+    };
+    let action = if add {
+        hooks.add_hook(deps.storage, hook)?;
+        "add"
+    } else {
+        hooks.remove_hook(deps.storage, hook)?;
+        "remove"
+    };
+
+    Ok(Response::default()
+        .add_attribute("action", format!("{action}_{kind}_hook"))
+        .add_attribute("address", address))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&CONFIG.load(deps.storage)?),
+        QueryMsg::Dao {} => to_binary(&DAO.load(deps.storage)?),
+        QueryMsg::Proposal { proposal_id } => query_proposal(deps, env, proposal_id),
+        QueryMsg::ListProposals { start_after, limit } => {
+            query_list_proposals(deps, env, start_after, limit)
+        }
+        QueryMsg::NextProposalId {} => to_binary(&next_proposal_id(deps.storage)?),
+        QueryMsg::ProposalCount {} => to_binary(&PROPOSAL_COUNT.load(deps.storage)?),
+        QueryMsg::ProposalCreationPolicy {} => to_binary(&CREATION_POLICY.load(deps.storage)?),
+        QueryMsg::ProposalHooks {} => to_binary(&PROPOSAL_HOOKS.query_hooks(deps)?),
+        QueryMsg::VoteHooks {} => to_binary(&VOTE_HOOKS.query_hooks(deps)?),
+        QueryMsg::Info {} => to_binary(&dao_interface::voting::InfoResponse {
+            info: secret_cw2::get_contract_version(deps.storage)?,
+        }),
+    }
+}
+
+pub fn query_proposal(deps: Deps, env: Env, id: u64) -> StdResult<Binary> {
+    let proposal = PROPOSALS
+        .get(deps.storage, &id)
+        .ok_or_else(|| StdError::not_found("proposal"))?;
+    to_binary(&proposal.into_response(&env.block, id))
+}
+
+pub fn query_list_proposals(
+    deps: Deps,
+    env: Env,
+    start_after: Option<u64>,
+    limit: Option<u64>,
+) -> StdResult<Binary> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT) as usize;
+    let proposals = PROPOSALS
+        .iter(deps.storage)?
+        .filter(|item| match (item, start_after) {
+            (Ok((id, _)), Some(start_after)) => *id > start_after,
+            _ => true,
+        })
+        .take(limit)
+        .map(|item| item.map(|(id, proposal)| proposal.into_response(&env.block, id)))
+        .collect::<StdResult<Vec<ProposalResponse>>>()?;
+
+    to_binary(&ProposalListResponse { proposals })
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    let repl = REPLY_IDS.get_event(deps.storage, msg.id)?;
+    match (repl, msg.result) {
+        // The proposal executed, so the deposit module may now be
+        // told it completed.
+        (ReplyEvent::FailedProposalExecution { proposal_id }, SubMsgResult::Ok(_)) => {
+            let proposal_creation_policy = CREATION_POLICY.load(deps.storage)?;
+            Ok(Response::new().add_submessages(proposal_completed_hooks(
+                proposal_creation_policy,
+                proposal_id,
+                CompletedStatus::Executed,
+            )?))
+        }
+        (ReplyEvent::FailedProposalExecution { proposal_id }, SubMsgResult::Err(err)) => {
+            let mut prop = PROPOSALS
+                .get(deps.storage, &proposal_id)
+                .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+            prop.status = Status::ExecutionFailed;
+            PROPOSALS.insert(deps.storage, &proposal_id, &prop)?;
+
+            let status_hooks =
+                status_change_hooks(deps.storage, proposal_id, Status::Executed, prop.status)?;
+
+            Ok(Response::new()
+                .add_submessages(status_hooks)
+                .add_attribute("proposal_execution_failed", proposal_id.to_string())
+                .add_attribute("error", err))
+        }
+        (ReplyEvent::FailedProposalHook { idx }, SubMsgResult::Err(_)) => {
+            let hook_item = PROPOSAL_HOOKS.remove_hook_by_index(deps.storage, idx)?;
+            Ok(Response::new().add_attribute(
+                "removed_proposal_hook",
+                format!("{0}:{idx}", hook_item.addr),
+            ))
+        }
+        (ReplyEvent::FailedVoteHook { idx }, SubMsgResult::Err(_)) => {
+            let hook_item = VOTE_HOOKS.remove_hook_by_index(deps.storage, idx)?;
+            Ok(Response::new()
+                .add_attribute("removed_vote_hook", format!("{0}:{idx}", hook_item.addr)))
+        }
+        (ReplyEvent::PreProposalModuleInstantiate { code_hash }, SubMsgResult::Ok(res)) => {
+            let contract_address = parse_reply_event_for_contract_address(res.events)?;
+            let module_addr = deps.api.addr_validate(&contract_address)?;
+            CREATION_POLICY.save(
+                deps.storage,
+                &ProposalCreationPolicy::Module {
+                    addr: module_addr.clone(),
+                    code_hash,
+                },
+            )?;
+
+            let response = Response::new().add_attribute("update_pre_propose_module", module_addr);
+            Ok(match res.data {
+                Some(data) => response.set_data(data),
+                None => response,
+            })
+        }
+        (ReplyEvent::FailedPreProposeModuleHook {}, _) => {
+            let addr = match CREATION_POLICY.load(deps.storage)? {
+                ProposalCreationPolicy::Anyone {} => {
+                    // Something is off if we're getting this reply
+                    // and we don't have a pre-propose module
+                    // installed. This should be unreachable.
+                    return Err(ContractError::InvalidReplyID { id: msg.id });
+                }
+                ProposalCreationPolicy::Module { addr, .. } => {
+                    // Our pre-propose module has errored while
+                    // receiving a proposal hook. Rest in peace
+                    // pre-propose module.
+                    CREATION_POLICY.save(deps.storage, &ProposalCreationPolicy::Anyone {})?;
+                    addr
+                }
+            };
+            Ok(Response::new().add_attribute("failed_prepropose_hook", addr))
+        }
+        _ => Err(ContractError::UnknownReplyID {}),
+    }
+}
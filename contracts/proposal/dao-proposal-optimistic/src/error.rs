@@ -0,0 +1,80 @@
+use cosmwasm_std::StdError;
+use cw_hooks::HookError;
+use dao_voting::threshold::ThresholdError;
+use secret_cw_controllers::ReplyError;
+use secret_utils::ParseReplyError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error(transparent)]
+    Std(#[from] StdError),
+
+    #[error(transparent)]
+    ParseReplyError(#[from] ParseReplyError),
+
+    #[error(transparent)]
+    HookError(#[from] HookError),
+
+    #[error(transparent)]
+    ReplyUdError(#[from] ReplyError),
+
+    #[error(transparent)]
+    ThresholdError(#[from] ThresholdError),
+
+    #[error("unauthorized")]
+    Unauthorized {},
+
+    #[error("no such proposal ({id})")]
+    NoSuchProposal { id: u64 },
+
+    #[error("An unknown reply ID was received.")]
+    UnknownReplyID {},
+
+    #[error("received a reply failure with an invalid ID: ({id})")]
+    InvalidReplyID { id: u64 },
+
+    #[error(
+        "pre-propose modules must specify a proposer. lacking one, no proposer should be specified"
+    )]
+    InvalidProposer {},
+
+    #[error("the challenge period must be non-zero")]
+    ZeroChallengePeriod {},
+
+    #[error("proposals can not be created while the DAO has no voting power")]
+    ZeroTotalPower {},
+
+    #[error("not registered to vote (no voting power) at time of proposal creation")]
+    NotRegistered {},
+
+    #[error("proposal ({id}) is no longer open to objections")]
+    NotOpen { id: u64 },
+
+    #[error("already objected to this proposal")]
+    AlreadyObjected {},
+
+    #[error("proposal is not in 'passed' state")]
+    NotPassed {},
+
+    #[error("proposal is not in 'challenged' state")]
+    NotChallenged {},
+
+    #[error("this module has no escalation pre-propose module")]
+    EscalationDisabled {},
+
+    #[error("challenged proposals must be escalated while an escalation module is configured")]
+    EscalationRequired {},
+
+    #[error("proposal is not in 'escalated' state")]
+    NotEscalated {},
+
+    #[error("the full vote on proposal ({id}) has not completed")]
+    EscalationPending { id: u64 },
+
+    #[error("proposal ({id}) has already been settled")]
+    AlreadySettled { id: u64 },
+
+    #[error("proposal is ({size}) bytes, must be <= ({max}) bytes")]
+    ProposalTooLarge { size: u64, max: u64 },
+}
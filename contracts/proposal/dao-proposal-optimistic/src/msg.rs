@@ -0,0 +1,183 @@
+use cosmwasm_schema::QueryResponses;
+use cosmwasm_std::{CosmosMsg, Empty};
+use dao_dao_macros::proposal_module_query;
+use dao_interface::state::AnyContractInfo;
+use dao_voting::{pre_propose::PreProposeInfo, threshold::PercentageThreshold};
+use schemars::JsonSchema;
+use secret_utils::Duration;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub struct InstantiateMsg {
+    /// The amount of time after a proposal is created during which
+    /// members may object to it.
+    pub challenge_period: Duration,
+    /// The share of the DAO's total voting power that must object to
+    /// a proposal for it to be challenged.
+    pub objection_threshold: PercentageThreshold,
+    /// The pre-propose module challenged proposals are escalated to
+    /// for a full vote, normally one attached to a
+    /// dao-proposal-single module. If None, challenged proposals may
+    /// only be closed.
+    pub escalation: Option<AnyContractInfo>,
+    /// Information about what addresses may create proposals.
+    pub pre_propose_info: PreProposeInfo,
+    /// If set to true proposals will be closed if their execution
+    /// fails. Otherwise, proposals will remain passed after execution
+    /// failure and may be executed again.
+    pub close_proposal_on_execution_failure: bool,
+
+    pub dao_code_hash: String,
+}
+
+/// The message used to create a proposal. This matches the fields
+/// used by dao-pre-propose-single, so that module may be used to
+/// take deposits for this one.
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub struct ProposeMsg {
+    /// The title of the proposal.
+    pub title: String,
+    /// A description of the proposal.
+    pub description: String,
+    /// The messages that should be executed in response to this
+    /// proposal passing.
+    pub msgs: Vec<CosmosMsg<Empty>>,
+    /// The address creating the proposal. If no pre-propose
+    /// module is attached to this module this must always be None
+    /// as the proposer is the sender of the propose message. If a
+    /// pre-propose module is attached, this must be Some and will
+    /// set the proposer of the proposal it creates.
+    pub proposer: Option<String>,
+}
+
+/// The proposal sent to the escalation pre-propose module to create
+/// a full vote on a challenged proposal.
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum EscalationProposeMsg {
+    Propose {
+        title: String,
+        description: String,
+        msgs: Vec<CosmosMsg<Empty>>,
+    },
+}
+
+/// The extension of the escalation pre-propose module used to create
+/// the full vote on behalf of the member escalating the proposal.
+/// This matches dao-pre-propose-single's `ExecuteExt`.
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum EscalationExecuteExt {
+    ProposeOnBehalf {
+        proposer: String,
+        key: String,
+        msg: EscalationProposeMsg,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Creates a proposal in the module.
+    Propose(ProposeMsg),
+    /// Objects to a proposal during its challenge window. Objection
+    /// power is determined by the DAO's voting power module.
+    Object {
+        /// The viewing key of the sender
+        key: String,
+        /// The ID of the proposal to object to.
+        proposal_id: u64,
+        /// An optional rationale for the objection.
+        rationale: Option<String>,
+    },
+    /// Causes the messages associated with a passed proposal to be
+    /// executed by the DAO.
+    Execute { proposal_id: u64 },
+    /// Submits a challenged proposal to the escalation pre-propose
+    /// module for a full vote. The sender is the proposer of the full
+    /// vote and pays its deposit, if any: native deposits are sent
+    /// with this message and passed on, SNIP-20 deposits are taken
+    /// from the sender's allowance to the escalation module.
+    Escalate {
+        proposal_id: u64,
+        /// The sender's viewing key, used by the escalation module
+        /// to check that the sender may create proposals.
+        key: String,
+    },
+    /// Settles the deposit of an escalated proposal once the full
+    /// vote on it has completed. The proposal's pre-propose module
+    /// is told the full vote's outcome, so the deposit is handled as
+    /// though the proposal had completed that way. Anyone may call
+    /// this method.
+    Settle { proposal_id: u64 },
+    /// Closes a challenged proposal. Only possible if the module has
+    /// no escalation pre-propose module.
+    Close { proposal_id: u64 },
+    /// Updates the module's configuration. Only the DAO may call
+    /// this method. The objection threshold and challenge period
+    /// apply to proposals created after the update.
+    UpdateConfig {
+        challenge_period: Duration,
+        objection_threshold: PercentageThreshold,
+        escalation: Option<AnyContractInfo>,
+        close_proposal_on_execution_failure: bool,
+    },
+    /// Update's the proposal creation policy used for this
+    /// module. Only the DAO may call this method.
+    UpdatePreProposeInfo { info: PreProposeInfo },
+    /// Adds an address as a consumer of proposal hooks. Consumers of
+    /// proposal hooks have hook messages executed on them whenever
+    /// the status of a proposal changes or a proposal is created. If
+    /// a consumer contract errors when handling a hook message it
+    /// will be removed from the list of consumers.
+    AddProposalHook { address: String, code_hash: String },
+    /// Removes a consumer of proposal hooks.
+    RemoveProposalHook { address: String, code_hash: String },
+    /// Adds an address as a consumer of vote hooks. Consumers of vote
+    /// hooks are sent a vote of "object" whenever an objection is
+    /// made. If a consumer contract errors when handling a hook
+    /// message it will be removed from the list of consumers.
+    AddVoteHook { address: String, code_hash: String },
+    /// Removed a consumer of vote hooks.
+    RemoveVoteHook { address: String, code_hash: String },
+}
+
+#[proposal_module_query]
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    /// Gets the proposal module's config.
+    #[returns(crate::state::Config)]
+    Config {},
+    /// Gets information about a proposal.
+    #[returns(crate::query::ProposalResponse)]
+    Proposal { proposal_id: u64 },
+    /// Lists all the proposals that have been created in this
+    /// module.
+    #[returns(crate::query::ProposalListResponse)]
+    ListProposals {
+        /// The proposal ID to start listing proposals after. For
+        /// example, if this is set to 2 proposals with IDs 3 and
+        /// higher will be returned.
+        start_after: Option<u64>,
+        /// The maximum number of proposals to return as part of this
+        /// query. If no limit is set a max of 30 proposals will be
+        /// returned.
+        limit: Option<u64>,
+    },
+    /// Returns the number of proposals that have been created in this module.
+    #[returns(::std::primitive::u64)]
+    ProposalCount {},
+    /// Gets the current proposal creation policy for this module.
+    #[returns(::dao_voting::pre_propose::ProposalCreationPolicy)]
+    ProposalCreationPolicy {},
+    /// Lists all of the consumers of proposal hooks for this module.
+    #[returns(::cw_hooks::HooksResponse)]
+    ProposalHooks {},
+    /// Lists all of the consumers of vote hooks for this module.
+    #[returns(::cw_hooks::HooksResponse)]
+    VoteHooks {},
+}
@@ -0,0 +1,246 @@
+use cosmwasm_std::{Addr, BlockInfo, CosmosMsg, Empty, StdResult, Storage, Uint128};
+use dao_voting::{
+    status::Status as CompletedStatus, threshold::PercentageThreshold, voting::does_vote_count_pass,
+};
+use schemars::JsonSchema;
+use secret_utils::Expiration;
+use serde::{Deserialize, Serialize};
+
+use crate::query::ProposalResponse;
+use crate::state::PROPOSAL_COUNT;
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum Status {
+    /// The proposal is in its challenge window and open to
+    /// objections.
+    Open,
+    /// Objections to the proposal reached the objection threshold.
+    Challenged,
+    /// The challenged proposal was submitted for a full vote. Its
+    /// outcome is recorded in `escalated_to` once settled.
+    Escalated,
+    /// The challenge window ended without the proposal being
+    /// challenged. It has not been executed.
+    Passed,
+    /// The proposal has been passed and executed.
+    Executed,
+    /// The challenged proposal has been closed.
+    Closed,
+    /// The proposal's execution failed.
+    ExecutionFailed,
+}
+
+impl std::fmt::Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Status::Open => write!(f, "open"),
+            Status::Challenged => write!(f, "challenged"),
+            Status::Escalated => write!(f, "escalated"),
+            Status::Passed => write!(f, "passed"),
+            Status::Executed => write!(f, "executed"),
+            Status::Closed => write!(f, "closed"),
+            Status::ExecutionFailed => write!(f, "execution_failed"),
+        }
+    }
+}
+
+impl Status {
+    /// The status reported to the pre-propose module when a proposal
+    /// completes. Escalated proposals are reported once settled, with
+    /// the outcome of their full vote.
+    pub fn completed(&self) -> Option<CompletedStatus> {
+        match self {
+            Status::Executed => Some(CompletedStatus::Executed),
+            Status::Closed => Some(CompletedStatus::Closed),
+            Status::ExecutionFailed => Some(CompletedStatus::ExecutionFailed),
+            _ => None,
+        }
+    }
+}
+
+/// The proposal a challenged proposal was escalated to.
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub struct EscalatedProposal {
+    /// The proposal module the full vote takes place in.
+    pub proposal_module: Addr,
+    /// The code hash of that module.
+    #[serde(default)]
+    pub code_hash: String,
+    /// The ID of the proposal in that module.
+    pub proposal_id: u64,
+    /// The final status of the full vote, set once the escalated
+    /// proposal is settled.
+    #[serde(default)]
+    pub outcome: Option<CompletedStatus>,
+}
+
+/// Returns true if an escalated proposal with STATUS has completed,
+/// so that the original proposal may be settled.
+pub fn is_final(status: &CompletedStatus) -> bool {
+    matches!(
+        status,
+        CompletedStatus::Executed
+            | CompletedStatus::ExecutionFailed
+            | CompletedStatus::Closed
+            | CompletedStatus::Vetoed
+            | CompletedStatus::Withdrawn
+    )
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub struct OptimisticProposal {
+    /// The title of the proposal
+    pub title: String,
+    /// The main body of the proposal text
+    pub description: String,
+    /// The address that created this proposal.
+    pub proposer: Addr,
+    /// The block height at which this proposal was created. Voting
+    /// power queries should query for voting power at this block
+    /// height.
+    pub start_height: u64,
+    /// The end of the proposal's challenge window.
+    pub expiration: Expiration,
+    /// The share of total voting power that must object for the
+    /// proposal to be challenged.
+    pub objection_threshold: PercentageThreshold,
+    /// The total amount of voting power at the time of this
+    /// proposal's creation.
+    pub total_power: Uint128,
+    /// The voting power that has objected to the proposal.
+    pub objections: Uint128,
+    /// The messages that will be executed should this proposal pass.
+    pub msgs: Vec<CosmosMsg<Empty>>,
+    /// The proposal status
+    pub status: Status,
+    /// The proposal this proposal was escalated to, if any.
+    pub escalated_to: Option<EscalatedProposal>,
+}
+
+pub fn next_proposal_id(store: &dyn Storage) -> StdResult<u64> {
+    Ok(PROPOSAL_COUNT.may_load(store)?.unwrap_or_default() + 1)
+}
+
+pub fn advance_proposal_id(store: &mut dyn Storage) -> StdResult<u64> {
+    let id: u64 = next_proposal_id(store)?;
+    PROPOSAL_COUNT.save(store, &id)?;
+    Ok(id)
+}
+
+impl OptimisticProposal {
+    /// Consumes the proposal and returns a version which may be used
+    /// in a query response. Statuses are only updated on objection,
+    /// execute, escalate, and close events, so the stored status of a
+    /// proposal whose challenge window has ended may be out of date.
+    pub fn into_response(mut self, block: &BlockInfo, id: u64) -> ProposalResponse {
+        self.update_status(block);
+        ProposalResponse { id, proposal: self }
+    }
+
+    /// Gets the current status of the proposal.
+    pub fn current_status(&self, block: &BlockInfo) -> Status {
+        if self.status == Status::Open && self.expiration.is_expired(block) {
+            Status::Passed
+        } else {
+            self.status
+        }
+    }
+
+    /// Sets a proposals status to its current status.
+    pub fn update_status(&mut self, block: &BlockInfo) {
+        self.status = self.current_status(block);
+    }
+
+    /// Adds an objection to the proposal, challenging it if
+    /// objections reach the objection threshold.
+    pub fn add_objection(&mut self, power: Uint128) {
+        self.objections += power;
+        if does_vote_count_pass(self.objections, self.total_power, self.objection_threshold) {
+            self.status = Status::Challenged;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::{testing::mock_env, Decimal};
+
+    fn proposal(objection_threshold: PercentageThreshold) -> OptimisticProposal {
+        let env = mock_env();
+        OptimisticProposal {
+            title: "title".to_string(),
+            description: "description".to_string(),
+            proposer: Addr::unchecked("proposer"),
+            start_height: env.block.height,
+            expiration: Expiration::AtHeight(env.block.height + 10),
+            objection_threshold,
+            total_power: Uint128::new(100),
+            objections: Uint128::zero(),
+            msgs: vec![],
+            status: Status::Open,
+            escalated_to: None,
+        }
+    }
+
+    #[test]
+    fn test_pass_unchallenged() {
+        let mut env = mock_env();
+        let mut prop = proposal(PercentageThreshold::Percent(Decimal::percent(10)));
+
+        prop.add_objection(Uint128::new(9));
+        assert_eq!(prop.current_status(&env.block), Status::Open);
+
+        env.block.height += 10;
+        assert_eq!(prop.current_status(&env.block), Status::Passed);
+        prop.update_status(&env.block);
+        assert_eq!(prop.status, Status::Passed);
+    }
+
+    #[test]
+    fn test_challenge() {
+        let mut env = mock_env();
+        let mut prop = proposal(PercentageThreshold::Percent(Decimal::percent(10)));
+
+        prop.add_objection(Uint128::new(4));
+        assert_eq!(prop.status, Status::Open);
+        prop.add_objection(Uint128::new(6));
+        assert_eq!(prop.status, Status::Challenged);
+
+        // Challenged proposals do not pass once the challenge window
+        // ends.
+        env.block.height += 10;
+        assert_eq!(prop.current_status(&env.block), Status::Challenged);
+    }
+
+    #[test]
+    fn test_challenge_majority() {
+        let mut prop = proposal(PercentageThreshold::Majority {});
+
+        prop.add_objection(Uint128::new(50));
+        assert_eq!(prop.status, Status::Open);
+        prop.add_objection(Uint128::new(1));
+        assert_eq!(prop.status, Status::Challenged);
+    }
+
+    #[test]
+    fn test_completed_status() {
+        assert_eq!(Status::Open.completed(), None);
+        assert_eq!(Status::Passed.completed(), None);
+        assert_eq!(Status::Escalated.completed(), None);
+        assert_eq!(Status::Closed.completed(), Some(CompletedStatus::Closed));
+        assert!(!is_final(&CompletedStatus::Rejected));
+        assert!(is_final(&CompletedStatus::Vetoed));
+        assert_eq!(
+            Status::Executed.completed(),
+            Some(CompletedStatus::Executed)
+        );
+        assert_eq!(
+            Status::ExecutionFailed.completed(),
+            Some(CompletedStatus::ExecutionFailed)
+        );
+    }
+}
@@ -0,0 +1,19 @@
+use crate::proposal::OptimisticProposal;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Information about a proposal returned by proposal queries.
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub struct ProposalResponse {
+    /// The ID of the proposal being returned.
+    pub id: u64,
+    pub proposal: OptimisticProposal,
+}
+
+/// A list of proposals returned by `ListProposals`.
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub struct ProposalListResponse {
+    pub proposals: Vec<ProposalResponse>,
+}
@@ -0,0 +1,58 @@
+use cosmwasm_std::{Addr, Uint128};
+use cw_hooks::Hooks;
+use dao_interface::state::AnyContractInfo;
+use dao_voting::{pre_propose::ProposalCreationPolicy, threshold::PercentageThreshold};
+use schemars::JsonSchema;
+use secret_cw_controllers::ReplyIds;
+use secret_storage_plus::Item;
+use secret_toolkit::{serialization::Json, storage::Keymap};
+use secret_utils::Duration;
+use serde::{Deserialize, Serialize};
+
+use crate::proposal::OptimisticProposal;
+
+/// The optimistic proposal module's configuration.
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub struct Config {
+    /// The amount of time after a proposal is created during which
+    /// members may object to it. Proposals that are not challenged
+    /// within this period pass.
+    pub challenge_period: Duration,
+    /// The share of the DAO's total voting power that must object to
+    /// a proposal for it to be challenged.
+    pub objection_threshold: PercentageThreshold,
+    /// The pre-propose module challenged proposals are escalated to
+    /// for a full vote. If None, challenged proposals may only be
+    /// closed.
+    pub escalation: Option<AnyContractInfo>,
+    /// If set to true proposals will be closed if their execution
+    /// fails. Otherwise, proposals will remain passed after execution
+    /// failure and may be executed again.
+    pub close_proposal_on_execution_failure: bool,
+}
+
+/// An objection to a proposal.
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub struct Objection {
+    /// The amount of voting power behind the objection.
+    pub power: Uint128,
+    /// An optional rationale for why the objection was made.
+    pub rationale: Option<String>,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+/// The number of proposals that have been created.
+pub const PROPOSAL_COUNT: Item<u64> = Item::new("proposal_count");
+pub static PROPOSALS: Keymap<u64, OptimisticProposal, Json> = Keymap::new(b"proposals");
+pub static OBJECTIONS: Keymap<(u64, Addr), Objection, Json> = Keymap::new(b"objections");
+/// Consumers of proposal state change hooks.
+pub const PROPOSAL_HOOKS: Hooks = Hooks::new("proposal_hooks");
+/// Consumers of vote hooks. Objections are sent as votes.
+pub const VOTE_HOOKS: Hooks = Hooks::new("vote_hooks");
+/// The address of the pre-propose module associated with this
+/// proposal module (if any).
+pub const CREATION_POLICY: Item<ProposalCreationPolicy> = Item::new("creation_policy");
+pub const DAO: Item<AnyContractInfo> = Item::new("dao");
+pub const REPLY_IDS: ReplyIds = ReplyIds::new(b"reply_ids", b"reply_ids_count");
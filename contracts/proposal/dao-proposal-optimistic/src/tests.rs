@@ -0,0 +1,571 @@
+use cosmwasm_std::testing::{
+    mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+};
+use cosmwasm_std::{
+    coins, from_binary, to_binary, Addr, BankMsg, Binary, ContractResult, CosmosMsg, Env,
+    OwnedDeps, Reply, ReplyOn, Response, SubMsgResponse, SubMsgResult, SystemResult, Uint128,
+    WasmMsg, WasmQuery,
+};
+use dao_hooks::proposal::{PreProposeHookMsg, ProposalHookExecuteMsg, ProposalHookMsg};
+use dao_hooks::vote::{VoteHookExecuteMsg, VoteHookMsg};
+use dao_interface::state::AnyContractInfo;
+use dao_interface::voting::{
+    Query as VotingQuery, TotalPowerAtHeightResponse, VotingPowerAtHeightResponse,
+};
+use dao_voting::dependency::DependencyInfo;
+use dao_voting::pre_propose::{PreProposeInfo, ProposalCreationPolicy};
+use dao_voting::status::Status as CompletedStatus;
+use dao_voting::threshold::PercentageThreshold;
+use secret_utils::Duration;
+use serde::{Deserialize, Serialize};
+
+use crate::contract::{execute, instantiate, query, reply};
+use crate::msg::{
+    EscalationExecuteExt, EscalationProposeMsg, ExecuteMsg, InstantiateMsg, ProposeMsg, QueryMsg,
+};
+use crate::proposal::{EscalatedProposal, OptimisticProposal, Status};
+use crate::query::ProposalResponse;
+use crate::state::{CREATION_POLICY, OBJECTIONS};
+use crate::ContractError;
+
+type Deps = OwnedDeps<MockStorage, MockApi, MockQuerier>;
+
+/// The height proposals are created at.
+const START: u64 = 12_345;
+
+/// The ID the full vote on an escalated proposal is given.
+const FULL_VOTE_ID: u64 = 7;
+
+/// The `Proposal` query of the full vote's proposal module.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum FullVoteQuery {
+    Proposal { proposal_id: u64 },
+}
+
+#[derive(Serialize)]
+struct FullVoteResponse {
+    proposal: DependencyInfo,
+}
+
+/// Answers the DAO's voting power queries with `power(address,
+/// height)` and reports a total power of 1000. Queries made with a
+/// key other than "key" fail. The "escalation" pre-propose module
+/// creates full votes in "full_vote", where the full vote on an
+/// escalated proposal has FULL_VOTE_STATUS.
+fn mock_dao(
+    deps: &mut Deps,
+    power: fn(&str, Option<u64>) -> u128,
+    full_vote_status: CompletedStatus,
+) {
+    deps.querier.update_wasm(move |query| {
+        let WasmQuery::Smart {
+            contract_addr, msg, ..
+        } = query
+        else {
+            panic!("unexpected query");
+        };
+        let res = match contract_addr.as_str() {
+            "dao" => match from_binary(msg).unwrap() {
+                VotingQuery::VotingPowerAtHeight {
+                    address,
+                    key,
+                    height,
+                } => {
+                    if key != "key" {
+                        return SystemResult::Ok(ContractResult::Err(
+                            "wrong viewing key".to_string(),
+                        ));
+                    }
+                    to_binary(&VotingPowerAtHeightResponse {
+                        power: Uint128::new(power(&address, height)),
+                        height: height.unwrap_or(START),
+                    })
+                }
+                VotingQuery::TotalPowerAtHeight { height } => {
+                    to_binary(&TotalPowerAtHeightResponse {
+                        power: Uint128::new(1000),
+                        height: height.unwrap_or(START),
+                    })
+                }
+                _ => panic!("unexpected query"),
+            },
+            "escalation" => to_binary(&AnyContractInfo {
+                addr: Addr::unchecked("full_vote"),
+                code_hash: "full_vote_hash".to_string(),
+            }),
+            "full_vote" => {
+                if let Ok(dao_interface::proposal::Query::NextProposalId {}) = from_binary(msg) {
+                    to_binary(&FULL_VOTE_ID)
+                } else {
+                    let FullVoteQuery::Proposal { proposal_id } = from_binary(msg).unwrap();
+                    assert_eq!(proposal_id, FULL_VOTE_ID);
+                    to_binary(&FullVoteResponse {
+                        proposal: DependencyInfo {
+                            status: full_vote_status,
+                            dependencies: vec![],
+                        },
+                    })
+                }
+            }
+            _ => panic!("unexpected query"),
+        };
+        SystemResult::Ok(ContractResult::Ok(res.unwrap()))
+    });
+}
+
+/// Alice has 600 of the 1000 voting power and Bob 300.
+fn power(address: &str, _height: Option<u64>) -> u128 {
+    match address {
+        "alice" => 600,
+        "bob" => 300,
+        _ => 0,
+    }
+}
+
+/// Sets up a module with a 10 block challenge window, in which a
+/// majority of the voting power must object to challenge a proposal,
+/// that escalates challenged proposals to "escalation". Proposals are
+/// created by the "pre_propose" module, and "proposal_hook" and
+/// "vote_hook" receive hooks.
+fn setup() -> Deps {
+    setup_with(|_| ())
+}
+
+/// As above, with the instantiate message modified by CONFIGURE.
+fn setup_with(configure: impl FnOnce(&mut InstantiateMsg)) -> Deps {
+    let mut deps = mock_dependencies();
+    mock_dao(&mut deps, power, CompletedStatus::Open);
+    let mut msg = InstantiateMsg {
+        challenge_period: Duration::Height(10),
+        objection_threshold: PercentageThreshold::Majority {},
+        escalation: Some(AnyContractInfo {
+            addr: Addr::unchecked("escalation"),
+            code_hash: "escalation_hash".to_string(),
+        }),
+        pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
+        close_proposal_on_execution_failure: false,
+        dao_code_hash: "dao_hash".to_string(),
+    };
+    configure(&mut msg);
+    instantiate(deps.as_mut(), mock_env(), mock_info("dao", &[]), msg).unwrap();
+    CREATION_POLICY
+        .save(
+            deps.as_mut().storage,
+            &ProposalCreationPolicy::Module {
+                addr: Addr::unchecked("pre_propose"),
+                code_hash: "pre_propose_hash".to_string(),
+            },
+        )
+        .unwrap();
+    for msg in [
+        ExecuteMsg::AddProposalHook {
+            address: "proposal_hook".to_string(),
+            code_hash: "hook_hash".to_string(),
+        },
+        ExecuteMsg::AddVoteHook {
+            address: "vote_hook".to_string(),
+            code_hash: "hook_hash".to_string(),
+        },
+    ] {
+        execute(deps.as_mut(), mock_env(), mock_info("dao", &[]), msg).unwrap();
+    }
+    deps
+}
+
+fn env_at(height: u64) -> Env {
+    let mut env = mock_env();
+    env.block.height = height;
+    env
+}
+
+fn proposal_msgs() -> Vec<CosmosMsg> {
+    vec![CosmosMsg::Bank(BankMsg::Send {
+        to_address: "alice".to_string(),
+        amount: coins(1, "ujuno"),
+    })]
+}
+
+/// Has the pre-propose module create a proposal by Alice.
+fn propose(deps: &mut Deps) -> Response {
+    execute(
+        deps.as_mut(),
+        env_at(START),
+        mock_info("pre_propose", &[]),
+        ExecuteMsg::Propose(ProposeMsg {
+            title: "title".to_string(),
+            description: "description".to_string(),
+            msgs: proposal_msgs(),
+            proposer: Some("alice".to_string()),
+        }),
+    )
+    .unwrap()
+}
+
+fn object(deps: &mut Deps, height: u64, member: &str) -> Result<Response, ContractError> {
+    execute(
+        deps.as_mut(),
+        env_at(height),
+        mock_info(member, &[]),
+        ExecuteMsg::Object {
+            key: "key".to_string(),
+            proposal_id: 1,
+            rationale: Some("no".to_string()),
+        },
+    )
+}
+
+fn execute_proposal(deps: &mut Deps, height: u64) -> Result<Response, ContractError> {
+    execute(
+        deps.as_mut(),
+        env_at(height),
+        mock_info("anyone", &[]),
+        ExecuteMsg::Execute { proposal_id: 1 },
+    )
+}
+
+fn proposal(deps: &Deps, height: u64) -> OptimisticProposal {
+    let ProposalResponse { proposal, .. } = from_binary(
+        &query(
+            deps.as_ref(),
+            env_at(height),
+            QueryMsg::Proposal { proposal_id: 1 },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    proposal
+}
+
+/// Returns the messages sent to CONTRACT in RES.
+fn sent_to(res: &Response, contract: &str) -> Vec<Binary> {
+    res.messages
+        .iter()
+        .filter_map(|sub| match &sub.msg {
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr, msg, ..
+            }) if contract_addr == contract => Some(msg.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Returns the hook messages sent to HOOK in RES.
+fn hook_msgs<T: serde::de::DeserializeOwned>(res: &Response, hook: &str) -> Vec<T> {
+    sent_to(res, hook)
+        .iter()
+        .map(|msg| from_binary(msg).unwrap())
+        .collect()
+}
+
+fn status_changed(old_status: Status, new_status: Status) -> ProposalHookExecuteMsg {
+    ProposalHookExecuteMsg::ProposalHook(ProposalHookMsg::ProposalStatusChanged {
+        id: 1,
+        old_status: old_status.to_string(),
+        new_status: new_status.to_string(),
+    })
+}
+
+fn completed(new_status: CompletedStatus) -> Vec<PreProposeHookMsg> {
+    vec![PreProposeHookMsg::ProposalCompletedHook {
+        proposal_id: 1,
+        new_status,
+        quorum_met: None,
+    }]
+}
+
+#[test]
+fn test_unchallenged_proposal_executes() {
+    let mut deps = setup();
+    propose(&mut deps);
+
+    let err = execute_proposal(&mut deps, START + 9).unwrap_err();
+    assert_eq!(err, ContractError::NotPassed {});
+    assert_eq!(proposal(&deps, START + 10).status, Status::Passed);
+
+    // The DAO executes the proposal's messages, and the pre-propose
+    // module is told the proposal completed.
+    let res = execute_proposal(&mut deps, START + 10).unwrap();
+    assert_eq!(
+        hook_msgs::<dao_interface::msg::ExecuteMsg>(&res, "dao"),
+        vec![dao_interface::msg::ExecuteMsg::ExecuteProposalHook {
+            msgs: proposal_msgs()
+        }]
+    );
+    assert_eq!(
+        hook_msgs::<ProposalHookExecuteMsg>(&res, "proposal_hook"),
+        vec![status_changed(Status::Passed, Status::Executed)]
+    );
+    assert_eq!(
+        hook_msgs::<PreProposeHookMsg>(&res, "pre_propose"),
+        completed(CompletedStatus::Executed)
+    );
+    assert_eq!(proposal(&deps, START + 10).status, Status::Executed);
+
+    let err = execute_proposal(&mut deps, START + 11).unwrap_err();
+    assert_eq!(err, ContractError::NotPassed {});
+}
+
+#[test]
+fn test_execution_reply_completes_proposal() {
+    let mut deps = setup_with(|msg| msg.close_proposal_on_execution_failure = true);
+    propose(&mut deps);
+
+    // Whether the proposal completed as executed is only known once
+    // its execution replies.
+    let res = execute_proposal(&mut deps, START + 10).unwrap();
+    assert!(sent_to(&res, "pre_propose").is_empty());
+    let execution = res
+        .messages
+        .iter()
+        .find(|sub| sub.reply_on == ReplyOn::Always)
+        .unwrap();
+
+    let res = reply(
+        deps.as_mut(),
+        env_at(START + 10),
+        Reply {
+            id: execution.id,
+            result: SubMsgResult::Err("error".to_string()),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        hook_msgs::<ProposalHookExecuteMsg>(&res, "proposal_hook"),
+        vec![status_changed(Status::Executed, Status::ExecutionFailed)]
+    );
+    assert_eq!(
+        hook_msgs::<PreProposeHookMsg>(&res, "pre_propose"),
+        completed(CompletedStatus::ExecutionFailed)
+    );
+    assert_eq!(proposal(&deps, START + 10).status, Status::ExecutionFailed);
+
+    // A proposal whose execution succeeds completes as executed.
+    let mut deps = setup_with(|msg| msg.close_proposal_on_execution_failure = true);
+    propose(&mut deps);
+    let res = execute_proposal(&mut deps, START + 10).unwrap();
+    let execution = res
+        .messages
+        .iter()
+        .find(|sub| sub.reply_on == ReplyOn::Always)
+        .unwrap();
+    let res = reply(
+        deps.as_mut(),
+        env_at(START + 10),
+        Reply {
+            id: execution.id,
+            result: SubMsgResult::Ok(SubMsgResponse {
+                events: vec![],
+                data: None,
+            }),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        hook_msgs::<PreProposeHookMsg>(&res, "pre_propose"),
+        completed(CompletedStatus::Executed)
+    );
+    assert_eq!(proposal(&deps, START + 10).status, Status::Executed);
+}
+
+#[test]
+fn test_objection_power() {
+    let mut deps = setup();
+    propose(&mut deps);
+    // Since the proposal was created, Carol has bought Bob's tokens.
+    mock_dao(
+        &mut deps,
+        |address, height| match (address, height) {
+            ("bob", Some(START)) => 300,
+            ("carol", Some(START)) => 0,
+            ("bob", _) => 0,
+            ("carol", _) => 300,
+            (address, height) => power(address, height),
+        },
+        CompletedStatus::Open,
+    );
+
+    // Objections are weighed by power when the proposal was created.
+    let err = object(&mut deps, START + 1, "carol").unwrap_err();
+    assert_eq!(err, ContractError::NotRegistered {});
+
+    let res = object(&mut deps, START + 1, "bob").unwrap();
+    assert_eq!(
+        hook_msgs::<VoteHookExecuteMsg>(&res, "vote_hook"),
+        vec![VoteHookExecuteMsg::VoteHook(VoteHookMsg::NewVote {
+            proposal_id: 1,
+            voter: "bob".to_string(),
+            vote: "object".to_string(),
+        })]
+    );
+    let objection = OBJECTIONS
+        .get(&deps.storage, &(1, Addr::unchecked("bob")))
+        .unwrap();
+    assert_eq!(objection.power, Uint128::new(300));
+    assert_eq!(objection.rationale, Some("no".to_string()));
+    let prop = proposal(&deps, START + 1);
+    assert_eq!(prop.objections, Uint128::new(300));
+    assert_eq!(prop.status, Status::Open);
+
+    let err = object(&mut deps, START + 2, "bob").unwrap_err();
+    assert_eq!(err, ContractError::AlreadyObjected {});
+
+    // Bob's 300 falls short of a majority, so the proposal passes
+    // once its challenge window ends.
+    let err = object(&mut deps, START + 10, "alice").unwrap_err();
+    assert_eq!(err, ContractError::NotOpen { id: 1 });
+    assert_eq!(proposal(&deps, START + 10).status, Status::Passed);
+}
+
+#[test]
+fn test_escalate_and_settle() {
+    let mut deps = setup();
+    propose(&mut deps);
+
+    let err = execute(
+        deps.as_mut(),
+        env_at(START + 1),
+        mock_info("bob", &[]),
+        ExecuteMsg::Escalate {
+            proposal_id: 1,
+            key: "key".to_string(),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::NotChallenged {});
+
+    object(&mut deps, START + 1, "bob").unwrap();
+    let res = object(&mut deps, START + 2, "alice").unwrap();
+    assert_eq!(
+        hook_msgs::<ProposalHookExecuteMsg>(&res, "proposal_hook"),
+        vec![status_changed(Status::Open, Status::Challenged)]
+    );
+    assert_eq!(proposal(&deps, START + 2).status, Status::Challenged);
+
+    // Challenged proposals do not pass once the window ends, and must
+    // be escalated rather than closed.
+    let err = execute_proposal(&mut deps, START + 10).unwrap_err();
+    assert_eq!(err, ContractError::NotPassed {});
+    let err = execute(
+        deps.as_mut(),
+        env_at(START + 10),
+        mock_info("anyone", &[]),
+        ExecuteMsg::Close { proposal_id: 1 },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::EscalationRequired {});
+
+    // Bob escalates the proposal, paying the full vote's deposit.
+    let res = execute(
+        deps.as_mut(),
+        env_at(START + 10),
+        mock_info("bob", &coins(10, "ujuno")),
+        ExecuteMsg::Escalate {
+            proposal_id: 1,
+            key: "key".to_string(),
+        },
+    )
+    .unwrap();
+    let propose_on_behalf = WasmMsg::Execute {
+        contract_addr: "escalation".to_string(),
+        code_hash: "escalation_hash".to_string(),
+        msg: to_binary(&dao_pre_propose_base::msg::ExecuteMsg::<
+            EscalationProposeMsg,
+            EscalationExecuteExt,
+        >::Extension {
+            msg: EscalationExecuteExt::ProposeOnBehalf {
+                proposer: "bob".to_string(),
+                key: "key".to_string(),
+                msg: EscalationProposeMsg::Propose {
+                    title: "title".to_string(),
+                    description: "description".to_string(),
+                    msgs: proposal_msgs(),
+                },
+            },
+        })
+        .unwrap(),
+        funds: coins(10, "ujuno"),
+    };
+    assert_eq!(res.messages[0].msg, CosmosMsg::Wasm(propose_on_behalf));
+    assert_eq!(
+        hook_msgs::<ProposalHookExecuteMsg>(&res, "proposal_hook"),
+        vec![status_changed(Status::Challenged, Status::Escalated)]
+    );
+    // The deposit is held until the full vote completes.
+    assert!(sent_to(&res, "pre_propose").is_empty());
+    let escalated_to = EscalatedProposal {
+        proposal_module: Addr::unchecked("full_vote"),
+        code_hash: "full_vote_hash".to_string(),
+        proposal_id: FULL_VOTE_ID,
+        outcome: None,
+    };
+    let prop = proposal(&deps, START + 10);
+    assert_eq!(prop.status, Status::Escalated);
+    assert_eq!(prop.escalated_to, Some(escalated_to.clone()));
+
+    let settle = |deps: &mut Deps| {
+        execute(
+            deps.as_mut(),
+            env_at(START + 20),
+            mock_info("anyone", &[]),
+            ExecuteMsg::Settle { proposal_id: 1 },
+        )
+    };
+    let err = settle(&mut deps).unwrap_err();
+    assert_eq!(err, ContractError::EscalationPending { id: 1 });
+
+    // Once the full vote is rejected, the deposit is handled as
+    // though the proposal had been.
+    mock_dao(&mut deps, power, CompletedStatus::Closed);
+    let res = settle(&mut deps).unwrap();
+    assert_eq!(
+        hook_msgs::<PreProposeHookMsg>(&res, "pre_propose"),
+        completed(CompletedStatus::Closed)
+    );
+    assert_eq!(
+        proposal(&deps, START + 20).escalated_to,
+        Some(EscalatedProposal {
+            outcome: Some(CompletedStatus::Closed),
+            ..escalated_to
+        })
+    );
+
+    let err = settle(&mut deps).unwrap_err();
+    assert_eq!(err, ContractError::AlreadySettled { id: 1 });
+}
+
+#[test]
+fn test_close_without_escalation() {
+    let mut deps = setup_with(|msg| msg.escalation = None);
+    propose(&mut deps);
+    object(&mut deps, START + 1, "alice").unwrap();
+
+    let err = execute(
+        deps.as_mut(),
+        env_at(START + 1),
+        mock_info("alice", &[]),
+        ExecuteMsg::Escalate {
+            proposal_id: 1,
+            key: "key".to_string(),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::EscalationDisabled {});
+
+    let res = execute(
+        deps.as_mut(),
+        env_at(START + 1),
+        mock_info("anyone", &[]),
+        ExecuteMsg::Close { proposal_id: 1 },
+    )
+    .unwrap();
+    assert_eq!(
+        hook_msgs::<ProposalHookExecuteMsg>(&res, "proposal_hook"),
+        vec![status_changed(Status::Challenged, Status::Closed)]
+    );
+    assert_eq!(
+        hook_msgs::<PreProposeHookMsg>(&res, "pre_propose"),
+        completed(CompletedStatus::Closed)
+    );
+    assert_eq!(proposal(&deps, START + 1).status, Status::Closed);
+}
@@ -1,23 +1,30 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_binary, Addr, Binary, CosmosMsg, Deps, DepsMut, Empty, Env, MessageInfo, Reply, Response,
-    StdError, StdResult, Storage, SubMsg, SubMsgResult,
+    to_binary, Addr, Binary, BlockInfo, CosmosMsg, Deps, DepsMut, Empty, Env, MessageInfo, Reply,
+    Response, StdError, StdResult, Storage, SubMsg, SubMsgResult,
 };
 use cw_hooks::{HookItem, Hooks};
 use dao_hooks::proposal::{
-    new_proposal_hooks, proposal_completed_hooks, proposal_status_changed_hooks,
+    new_emergency_proposal_hooks, new_proposal_hooks, proposal_amended_hooks,
+    proposal_closed_hooks, proposal_completed_hooks, proposal_status_changed_hooks,
 };
-use dao_hooks::vote::new_vote_hooks;
+use dao_hooks::vote::{new_vote_hooks, voting_period_extended_hooks};
 use dao_interface::state::{AnyContractInfo, VotingModuleInfo};
 use dao_interface::voting::IsActiveResponse;
+use dao_voting::condition::{check_conditions, validate_conditions, ExecutionCondition};
+use dao_voting::dependency::{
+    check_dependencies, dependency_graph, validate_dependencies, DependencyError,
+    DependencyGraphResponse, DependencyInfo, ProposalDependency,
+};
+use dao_voting::late_vote_extension::LateVoteExtensionConfig;
 use dao_voting::pre_propose::{PreProposeInfo, ProposalCreationPolicy};
 use dao_voting::proposal::{
     SingleChoiceProposeMsg as ProposeMsg, DEFAULT_LIMIT, MAX_PROPOSAL_SIZE,
 };
 use dao_voting::status::Status;
 use dao_voting::threshold::Threshold;
-use dao_voting::veto::{VetoConfig, VetoError};
+use dao_voting::veto::{withdraw_veto, VetoConfig, VetoError};
 use dao_voting::voting::{get_total_power, get_voting_power, validate_voting_period, Vote, Votes};
 use secret_cw2::set_contract_version;
 use secret_cw_controllers::ReplyEvent;
@@ -25,11 +32,16 @@ use secret_toolkit::permit::{Permit, RevokedPermits};
 use secret_toolkit::utils::HandleCallback;
 use secret_toolkit::viewing_key::{ViewingKey, ViewingKeyStore};
 use secret_utils::{parse_reply_event_for_contract_address, Duration};
+use std::ops::Add;
 
+use crate::emergency::{EmergencyConfig, EmergencyConfigResponse};
 use crate::msg::{CreateViewingKey, QueryWithPermit, ViewingKeyError};
 // use crate::msg::MigrateMsg;
 use crate::proposal::{next_proposal_id, SingleChoiceProposal};
-use crate::state::{Config, CREATION_POLICY, DAO, REPLY_IDS};
+use crate::state::{
+    Ballot, Config, CREATION_POLICY, DAO, EMERGENCY_CONFIG, REPLY_IDS, THRESHOLD_RULES, TURNOUT,
+};
+use crate::threshold_rules::{strictest_matching_rule, validate_threshold_rules, ThresholdRule};
 // use crate::v1_state::{
 //     v1_duration_to_v2, v1_expiration_to_v2, v1_status_to_v2, v1_threshold_to_v2, v1_votes_to_v2,
 // };
@@ -78,6 +90,13 @@ pub fn instantiate(
         veto_config.validate(&deps.as_ref(), &max_voting_period)?;
     };
 
+    validate_review_period(msg.review_period, max_voting_period)?;
+
+    // if late vote extension is configured, validate its fields
+    if let Some(extension_config) = &msg.late_vote_extension {
+        extension_config.validate(&max_voting_period)?;
+    };
+
     let config = Config {
         threshold: msg.threshold,
         max_voting_period,
@@ -86,13 +105,23 @@ pub fn instantiate(
         allow_revoting: msg.allow_revoting,
         close_proposal_on_execution_failure: msg.close_proposal_on_execution_failure,
         veto: msg.veto,
+        review_period: msg.review_period,
+        late_vote_extension: msg.late_vote_extension,
+        close_proposal_on_condition_failure: msg.close_proposal_on_condition_failure,
     };
 
+    validate_threshold_rules(deps.as_ref(), &config, &msg.threshold_rules)?;
+
     // Initialize proposal count to zero so that queries return zero
     // instead of None.
     PROPOSAL_COUNT.save(deps.storage, &0)?;
     CONFIG.save(deps.storage, &config)?;
     CREATION_POLICY.save(deps.storage, &initial_policy)?;
+    THRESHOLD_RULES.save(deps.storage, &msg.threshold_rules)?;
+    if let Some(emergency) = msg.emergency {
+        let emergency = emergency.into_checked(deps.api)?;
+        EMERGENCY_CONFIG.save(deps.storage, &emergency)?;
+    }
 
     Ok(Response::default()
         .add_submessages(pre_propose_messages)
@@ -113,7 +142,21 @@ pub fn execute(
             description,
             msgs,
             proposer,
-        }) => execute_propose(deps, env, info.sender, title, description, msgs, proposer),
+            conditions,
+            dependencies,
+            emergency,
+        }) => execute_propose(
+            deps,
+            env,
+            info.sender,
+            title,
+            description,
+            msgs,
+            proposer,
+            conditions,
+            dependencies,
+            emergency,
+        ),
         ExecuteMsg::Vote {
             key,
             proposal_id,
@@ -128,6 +171,15 @@ pub fn execute(
             execute_execute(deps, env, info, key, proposal_id)
         }
         ExecuteMsg::Close { proposal_id } => execute_close(deps, env, info, proposal_id),
+        ExecuteMsg::AmendProposal {
+            proposal_id,
+            title,
+            description,
+            msgs,
+        } => execute_amend_proposal(deps, env, info, proposal_id, title, description, msgs),
+        ExecuteMsg::WithdrawProposal { proposal_id } => {
+            execute_withdraw_proposal(deps, env, info, proposal_id)
+        }
         ExecuteMsg::UpdateConfig {
             threshold,
             max_voting_period,
@@ -138,6 +190,9 @@ pub fn execute(
             code_hash,
             close_proposal_on_execution_failure,
             veto,
+            review_period,
+            late_vote_extension,
+            close_proposal_on_condition_failure,
         } => execute_update_config(
             deps,
             info,
@@ -150,7 +205,16 @@ pub fn execute(
             code_hash,
             close_proposal_on_execution_failure,
             veto,
+            review_period,
+            late_vote_extension,
+            close_proposal_on_condition_failure,
         ),
+        ExecuteMsg::UpdateThresholdRules { rules } => {
+            execute_update_threshold_rules(deps, info, rules)
+        }
+        ExecuteMsg::UpdateEmergencyConfig { config } => {
+            execute_update_emergency_config(deps, info, config)
+        }
         ExecuteMsg::UpdatePreProposeInfo { info: new_info } => {
             execute_update_proposal_creation_policy(deps, info, new_info)
         }
@@ -167,12 +231,16 @@ pub fn execute(
             execute_remove_vote_hook(deps, env, info, address, code_hash)
         }
         ExecuteMsg::Veto { proposal_id } => execute_veto(deps, env, info, proposal_id),
+        ExecuteMsg::WithdrawVeto { proposal_id } => {
+            execute_withdraw_veto(deps, env, info, proposal_id)
+        }
         ExecuteMsg::CreateViewingKey { entropy, .. } => try_create_key(deps, env, info, entropy),
         ExecuteMsg::SetViewingKey { key, .. } => try_set_key(deps, info, key),
         ExecuteMsg::RevokePermit { permit_name, .. } => revoke_permit(deps, info, permit_name),
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn execute_propose(
     deps: DepsMut,
     env: Env,
@@ -181,6 +249,9 @@ pub fn execute_propose(
     description: String,
     msgs: Vec<CosmosMsg<Empty>>,
     proposer: Option<String>,
+    conditions: Vec<ExecutionCondition>,
+    dependencies: Vec<ProposalDependency>,
+    emergency: bool,
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
     let dao_info = DAO.load(deps.storage)?;
@@ -204,6 +275,17 @@ pub fn execute_propose(
         _ => return Err(ContractError::InvalidProposer {}),
     };
 
+    validate_conditions(deps.api, &conditions)?;
+    let local = |id: u64| local_dependency_info(deps.storage, &env.block, id);
+    validate_dependencies(
+        deps.as_ref(),
+        &env.contract.address,
+        &dao_info,
+        next_proposal_id(deps.storage)?,
+        &dependencies,
+        &local,
+    )?;
+
     let voting_module: VotingModuleInfo = deps.querier.query_wasm_smart(
         dao_info.code_hash.clone(),
         dao_info.addr.clone(),
@@ -225,7 +307,55 @@ pub fn execute_propose(
         return Err(ContractError::InactiveDao {});
     }
 
-    let expiration = config.max_voting_period.after(&env.block);
+    // Apply the strictest threshold rule matching the proposal's
+    // messages. Emergency proposals use the emergency configuration
+    // unless the rule's threshold is stricter, and have neither a
+    // review period nor a min voting period.
+    let threshold_rule = strictest_matching_rule(
+        &THRESHOLD_RULES.may_load(deps.storage)?.unwrap_or_default(),
+        &config,
+        &dao_info.addr,
+        &msgs,
+    );
+    let (threshold_rule, (threshold, max_voting_period, veto), review_period, min_voting_period) =
+        if emergency {
+            let emergency_config = EMERGENCY_CONFIG
+                .may_load(deps.storage)?
+                .ok_or(ContractError::EmergencyProposalsDisabled {})?;
+            emergency_config.record_proposal(deps.storage, &env.block, &proposer)?;
+            let applied =
+                emergency_config.apply(deps.as_ref(), &config, threshold_rule.as_ref())?;
+            (threshold_rule, applied, None, None)
+        } else {
+            let applied = ThresholdRule::apply(threshold_rule.as_ref(), &config);
+            (
+                threshold_rule,
+                applied,
+                config.review_period,
+                config.min_voting_period,
+            )
+        };
+    // Dynamic quorums are fixed when the proposal is created.
+    let threshold = TURNOUT
+        .may_load(deps.storage)?
+        .unwrap_or_default()
+        .resolve(&threshold);
+
+    // If a review period is configured, the voting period begins once
+    // it has ended.
+    let review_period = review_period.map(|review| review.after(&env.block));
+    let (min_voting_period, expiration) = match review_period {
+        Some(voting_start) => (
+            min_voting_period
+                .map(|min| voting_start.add(min))
+                .transpose()?,
+            voting_start.add(max_voting_period)?,
+        ),
+        None => (
+            min_voting_period.map(|min| min.after(&env.block)),
+            max_voting_period.after(&env.block),
+        ),
+    };
 
     let total_power = get_total_power(
         deps.as_ref(),
@@ -241,15 +371,24 @@ pub fn execute_propose(
             description,
             proposer: proposer.clone(),
             start_height: env.block.height,
-            min_voting_period: config.min_voting_period.map(|min| min.after(&env.block)),
+            min_voting_period,
             expiration,
-            threshold: config.threshold,
+            threshold,
             total_power,
             msgs,
             status: Status::Open,
             votes: Votes::zero(),
             allow_revoting: config.allow_revoting,
-            veto: config.veto,
+            veto,
+            review_period,
+            revision: 0,
+            late_vote_extension: config.late_vote_extension,
+            extensions: 0,
+            conditions,
+            dependencies,
+            threshold_rule,
+            vetoes: vec![],
+            emergency,
         };
         // Update the proposal's status. Addresses case where proposal
         // expires on the same block as it is created.
@@ -258,38 +397,175 @@ pub fn execute_propose(
     };
     let id = advance_proposal_id(deps.storage)?;
 
-    // Limit the size of proposals.
-    //
-    // The Juno mainnet has a larger limit for data that can be
-    // uploaded as part of an execute message than it does for data
-    // that can be queried as part of a query. This means that without
-    // this check it is possible to create a proposal that can not be
-    // queried.
-    //
-    // The size selected was determined by uploading versions of this
-    // contract to the Juno mainnet until queries worked within a
-    // reasonable margin of error.
-    //
-    // `to_vec` is the method used by cosmwasm to convert a struct
-    // into it's byte representation in storage.
-    let proposal_size = cosmwasm_std::to_vec(&proposal)?.len() as u64;
-    if proposal_size > MAX_PROPOSAL_SIZE {
-        return Err(ContractError::ProposalTooLarge {
-            size: proposal_size,
-            max: MAX_PROPOSAL_SIZE,
-        });
-    }
+    check_proposal_size(&proposal)?;
 
     PROPOSALS.insert(deps.storage, &id, &proposal)?;
 
-    let hooks = new_proposal_hooks(PROPOSAL_HOOKS, deps.storage, id, proposer.as_str())?;
+    let hooks = if emergency {
+        new_emergency_proposal_hooks(PROPOSAL_HOOKS, deps.storage, id, proposer.as_str())?
+    } else {
+        new_proposal_hooks(PROPOSAL_HOOKS, deps.storage, id, proposer.as_str())?
+    };
 
     Ok(Response::default()
         .add_submessages(hooks)
         .add_attribute("action", "propose")
         .add_attribute("sender", sender)
         .add_attribute("proposal_id", id.to_string())
-        .add_attribute("status", proposal.status.to_string()))
+        .add_attribute("status", proposal.status.to_string())
+        .add_attribute("emergency", emergency.to_string()))
+}
+
+pub fn execute_amend_proposal(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+    title: String,
+    description: String,
+    msgs: Vec<CosmosMsg<Empty>>,
+) -> Result<Response, ContractError> {
+    let mut prop = PROPOSALS
+        .get(deps.storage, &proposal_id)
+        .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+
+    check_proposer_can_revise(&prop, &env, &info, proposal_id)?;
+
+    // The amended messages may match different threshold rules, so
+    // the rules are applied again. As voting has not started yet, the
+    // voting period is computed from the end of the review period.
+    let config = CONFIG.load(deps.storage)?;
+    let dao_info = DAO.load(deps.storage)?;
+    let threshold_rule = strictest_matching_rule(
+        &THRESHOLD_RULES.may_load(deps.storage)?.unwrap_or_default(),
+        &config,
+        &dao_info.addr,
+        &msgs,
+    );
+    let (threshold, max_voting_period, veto) =
+        ThresholdRule::apply(threshold_rule.as_ref(), &config);
+    if let Some(voting_start) = prop.review_period {
+        prop.expiration = voting_start.add(max_voting_period)?;
+    }
+    prop.threshold = TURNOUT
+        .may_load(deps.storage)?
+        .unwrap_or_default()
+        .resolve(&threshold);
+    prop.veto = veto;
+    prop.threshold_rule = threshold_rule;
+
+    prop.title = title;
+    prop.description = description;
+    prop.msgs = msgs;
+    prop.revision += 1;
+
+    check_proposal_size(&prop)?;
+
+    PROPOSALS.insert(deps.storage, &proposal_id, &prop)?;
+
+    let hooks = proposal_amended_hooks(PROPOSAL_HOOKS, deps.storage, proposal_id, prop.revision)?;
+
+    Ok(Response::default()
+        .add_submessages(hooks)
+        .add_attribute("action", "amend_proposal")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("revision", prop.revision.to_string()))
+}
+
+pub fn execute_withdraw_proposal(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+) -> Result<Response, ContractError> {
+    let mut prop = PROPOSALS
+        .get(deps.storage, &proposal_id)
+        .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+
+    check_proposer_can_revise(&prop, &env, &info, proposal_id)?;
+
+    let old_status = prop.status;
+
+    prop.status = Status::Withdrawn;
+    PROPOSALS.insert(deps.storage, &proposal_id, &prop)?;
+
+    // Add proposal status change hooks
+    let proposal_status_changed_hooks = proposal_status_changed_hooks(
+        PROPOSAL_HOOKS,
+        deps.storage,
+        proposal_id,
+        old_status.to_string(),
+        prop.status.to_string(),
+    )?;
+
+    // Add prepropose / deposit module hook which will handle deposit refunds.
+    let proposal_creation_policy = CREATION_POLICY.load(deps.storage)?;
+    let proposal_completed_hooks =
+        proposal_completed_hooks(proposal_creation_policy, proposal_id, prop.status)?;
+
+    Ok(Response::default()
+        .add_submessages(proposal_status_changed_hooks)
+        .add_submessages(proposal_completed_hooks)
+        .add_attribute("action", "withdraw_proposal")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string()))
+}
+
+/// Checks that the sender is the proposer of the proposal and that
+/// the proposal is open and still in its review period.
+fn check_proposer_can_revise(
+    prop: &SingleChoiceProposal,
+    env: &Env,
+    info: &MessageInfo,
+    proposal_id: u64,
+) -> Result<(), ContractError> {
+    if prop.proposer != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    if prop.status != Status::Open || !prop.is_in_review(&env.block) {
+        return Err(ContractError::NotInReviewPeriod { id: proposal_id });
+    }
+    Ok(())
+}
+
+/// Limits the size of proposals.
+///
+/// The Juno mainnet has a larger limit for data that can be uploaded
+/// as part of an execute message than it does for data that can be
+/// queried as part of a query. This means that without this check it
+/// is possible to create a proposal that can not be queried.
+///
+/// The size selected was determined by uploading versions of this
+/// contract to the Juno mainnet until queries worked within a
+/// reasonable margin of error.
+fn check_proposal_size(proposal: &SingleChoiceProposal) -> Result<(), ContractError> {
+    // `to_vec` is the method used by cosmwasm to convert a struct
+    // into it's byte representation in storage.
+    let proposal_size = cosmwasm_std::to_vec(proposal)?.len() as u64;
+    if proposal_size > MAX_PROPOSAL_SIZE {
+        return Err(ContractError::ProposalTooLarge {
+            size: proposal_size,
+            max: MAX_PROPOSAL_SIZE,
+        });
+    }
+    Ok(())
+}
+
+/// Checks that the review period, if set, is measured in the same
+/// units as the max voting period. Voting opens once the review
+/// period ends, so the two are added together.
+fn validate_review_period(
+    review_period: Option<Duration>,
+    max_voting_period: Duration,
+) -> Result<(), ContractError> {
+    match (review_period, max_voting_period) {
+        (Some(Duration::Height(_)), Duration::Time(_))
+        | (Some(Duration::Time(_)), Duration::Height(_)) => {
+            Err(ContractError::ReviewPeriodUnitsConflict {})
+        }
+        _ => Ok(()),
+    }
 }
 
 pub fn execute_veto(
@@ -340,6 +616,16 @@ pub fn execute_veto(
         }
     }
 
+    // Record the sender's veto. The proposal is vetoed once the veto
+    // threshold is reached.
+    if !veto_config.cast_veto(&mut prop.vetoes, &info.sender)? {
+        PROPOSALS.insert(deps.storage, &proposal_id, &prop)?;
+        return Ok(Response::new()
+            .add_attribute("action", "veto")
+            .add_attribute("proposal_id", proposal_id.to_string())
+            .add_attribute("vetoes", prop.vetoes.len().to_string()));
+    }
+
     // Update proposal status to vetoed
     prop.status = Status::Vetoed;
     PROPOSALS.insert(deps.storage, &proposal_id, &prop)?;
@@ -365,6 +651,34 @@ pub fn execute_veto(
         .add_submessages(proposal_completed_hooks))
 }
 
+pub fn execute_withdraw_veto(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+) -> Result<Response, ContractError> {
+    let mut prop = PROPOSALS
+        .get(deps.storage, &proposal_id)
+        .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+
+    // Vetoes may not be withdrawn once the proposal has been vetoed.
+    prop.update_status(&env.block)?;
+    if prop.status == Status::Vetoed {
+        return Err(ContractError::VetoError(VetoError::InvalidProposalStatus {
+            status: prop.status.to_string(),
+        }));
+    }
+
+    withdraw_veto(&mut prop.vetoes, &info.sender)?;
+    PROPOSALS.insert(deps.storage, &proposal_id, &prop)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "withdraw_veto")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("vetoes", prop.vetoes.len().to_string()))
+}
+
 pub fn execute_execute(
     deps: DepsMut,
     env: Env,
@@ -416,7 +730,7 @@ pub fn execute_execute(
                 .ok_or(VetoError::NoVetoConfiguration {})?;
 
             // check that the sender is the vetoer
-            if veto_config.vetoer != info.sender {
+            if !veto_config.is_vetoer(&info.sender) {
                 // if the sender can normally execute, but is not the vetoer,
                 // return timelocked error. otherwise return unauthorized.
                 if sender_can_execute {
@@ -428,15 +742,71 @@ pub fn execute_execute(
 
             // if veto timelocked, only allow execution if early_execute enabled
             veto_config.check_early_execute_enabled()?;
+            veto_config.check_no_vetoes_cast(&prop.vetoes)?;
         }
         _ => {
             return Err(ContractError::NotPassed {});
         }
     }
 
+    // Check that the proposal's dependencies have been executed.
+    let local = |id: u64| local_dependency_info(deps.storage, &env.block, id);
+    if let Err(err) = check_dependencies(
+        deps.as_ref(),
+        &env.contract.address,
+        &prop.dependencies,
+        &local,
+    ) {
+        if !(config.close_proposal_on_condition_failure
+            && matches!(err, DependencyError::DependencyFailed { .. }))
+        {
+            return Err(err.into());
+        }
+        return close_on_condition_failure(
+            deps,
+            info,
+            proposal_id,
+            prop,
+            old_status,
+            err.to_string(),
+        );
+    }
+
+    // Check that the proposal's execution conditions hold.
+    let is_executed = |id: u64| {
+        PROPOSALS
+            .get(deps.storage, &id)
+            .map_or(false, |p| p.status == Status::Executed)
+    };
+    if let Err(err) = check_conditions(
+        deps.as_ref(),
+        &env.block,
+        &dao_info,
+        &prop.conditions,
+        &is_executed,
+    ) {
+        if !config.close_proposal_on_condition_failure {
+            return Err(err.into());
+        }
+        return close_on_condition_failure(
+            deps,
+            info,
+            proposal_id,
+            prop,
+            old_status,
+            err.to_string(),
+        );
+    }
+
     prop.status = Status::Executed;
 
     PROPOSALS.insert(deps.storage, &proposal_id, &prop)?;
+    record_turnout(deps.storage, &prop)?;
+
+    // If the proposal is closed should its execution fail, whether
+    // it completed as executed is only known once execution replies,
+    // so the deposit module is told then.
+    let completion_deferred = !prop.msgs.is_empty() && config.close_proposal_on_execution_failure;
 
     let response = {
         if !prop.msgs.is_empty() {
@@ -448,7 +818,7 @@ pub fn execute_execute(
                         deps.storage,
                         ReplyEvent::FailedProposalExecution { proposal_id },
                     )?;
-                    Response::default().add_submessage(SubMsg::reply_on_error(
+                    Response::default().add_submessage(SubMsg::reply_always(
                         execute_message.to_cosmos_msg(
                             dao_info.code_hash.clone(),
                             dao_info.addr.clone().into_string(),
@@ -478,9 +848,12 @@ pub fn execute_execute(
     )?;
 
     // Add prepropose / deposit module hook which will handle deposit refunds.
-    let proposal_creation_policy = CREATION_POLICY.load(deps.storage)?;
-    let proposal_completed_hooks =
-        proposal_completed_hooks(proposal_creation_policy, proposal_id, prop.status)?;
+    let proposal_completed_hooks = if completion_deferred {
+        vec![]
+    } else {
+        let proposal_creation_policy = CREATION_POLICY.load(deps.storage)?;
+        proposal_completed_hooks(proposal_creation_policy, proposal_id, prop.status)?
+    };
 
     Ok(response
         .add_submessages(proposal_status_changed_hooks)
@@ -491,6 +864,49 @@ pub fn execute_execute(
         .add_attribute("dao", dao_info.addr.to_string()))
 }
 
+/// Closes a proposal whose execution conditions do not hold, or
+/// whose dependencies will never be executed. Used when the module is
+/// configured to close such proposals rather than refusing to execute
+/// them.
+fn close_on_condition_failure(
+    deps: DepsMut,
+    info: MessageInfo,
+    proposal_id: u64,
+    mut prop: SingleChoiceProposal,
+    old_status: Status,
+    reason: String,
+) -> Result<Response, ContractError> {
+    prop.status = Status::Closed;
+    PROPOSALS.insert(deps.storage, &proposal_id, &prop)?;
+    record_turnout(deps.storage, &prop)?;
+
+    // Add proposal status change hooks
+    let proposal_status_changed_hooks = proposal_status_changed_hooks(
+        PROPOSAL_HOOKS,
+        deps.storage,
+        proposal_id,
+        old_status.to_string(),
+        prop.status.to_string(),
+    )?;
+
+    // Add prepropose / deposit module hook which will handle deposit refunds.
+    let proposal_creation_policy = CREATION_POLICY.load(deps.storage)?;
+    let proposal_completed_hooks = match prop.quorum_met() {
+        Some(quorum_met) => {
+            proposal_closed_hooks(proposal_creation_policy, proposal_id, quorum_met)?
+        }
+        None => proposal_completed_hooks(proposal_creation_policy, proposal_id, prop.status)?,
+    };
+
+    Ok(Response::default()
+        .add_submessages(proposal_status_changed_hooks)
+        .add_submessages(proposal_completed_hooks)
+        .add_attribute("action", "execute")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("condition_failed", reason))
+}
+
 pub fn execute_vote(
     deps: DepsMut,
     env: Env,
@@ -517,6 +933,11 @@ pub fn execute_vote(
         return Err(ContractError::Expired { id: proposal_id });
     }
 
+    // Voting does not open until the review period has ended.
+    if prop.is_in_review(&env.block) {
+        return Err(ContractError::InReviewPeriod { id: proposal_id });
+    }
+
     let vote_power = get_voting_power(
         deps.as_ref(),
         dao_info.code_hash.clone(),
@@ -560,36 +981,32 @@ pub fn execute_vote(
     //         rationale: rationale.clone(),
     //     }),
     // })?;
-    let current_ballot = BALLOTS.get(deps.storage, &(proposal_id, info.sender.clone()));
-    if current_ballot.clone().is_some() {
-        if prop.allow_revoting {
-            if current_ballot.clone().unwrap().vote == vote {
-                //  Don't allow casting the same vote more than
-                // once. This seems liable to be confusing
-                // behavior.
-                return Err(ContractError::AlreadyCast {});
-            } else {
-                // Remove the old vote if this is a re-vote.
-                prop.votes.remove_vote(
-                    current_ballot.clone().unwrap().vote,
-                    current_ballot.clone().unwrap().power,
-                );
-                current_ballot.clone().unwrap().power = vote_power;
-                current_ballot.clone().unwrap().vote = vote;
-                current_ballot.clone().unwrap().rationale = rationale.clone();
-            }
-        } else {
+    // Snapshot the outcome before this vote is counted so that late
+    // swings can be detected.
+    let previous_outcome = prop.outcome(&env.block);
+
+    if let Some(current_ballot) = BALLOTS.get(deps.storage, &(proposal_id, info.sender.clone())) {
+        if !prop.allow_revoting {
             return Err(ContractError::AlreadyVoted {});
         }
-    } else {
-        current_ballot.clone().unwrap().power = vote_power;
-        current_ballot.clone().unwrap().vote = vote;
-        current_ballot.clone().unwrap().rationale = rationale.clone();
+        if current_ballot.vote == vote {
+            // Don't allow casting the same vote more than
+            // once. This seems liable to be confusing
+            // behavior.
+            return Err(ContractError::AlreadyCast {});
+        }
+        // Remove the old vote if this is a re-vote.
+        prop.votes
+            .remove_vote(current_ballot.vote, current_ballot.power);
     }
     BALLOTS.insert(
         deps.storage,
         &(proposal_id, info.sender.clone()),
-        &current_ballot.unwrap(),
+        &Ballot {
+            power: vote_power,
+            vote,
+            rationale: rationale.clone(),
+        },
     )?;
 
     let old_status = prop.status;
@@ -597,6 +1014,11 @@ pub fn execute_vote(
     prop.votes.add_vote(vote, vote_power);
     prop.update_status(&env.block)?;
 
+    // A proposal whose outcome is already decided can not be swung,
+    // so only open proposals are extended.
+    let extended = prop.status == Status::Open
+        && prop.apply_late_vote_extension(&env.block, previous_outcome)?;
+
     PROPOSALS.insert(deps.storage, &proposal_id, &prop)?;
 
     let new_status = prop.status;
@@ -616,15 +1038,29 @@ pub fn execute_vote(
         vote.to_string(),
     )?;
 
+    let extension_hooks = if extended {
+        voting_period_extended_hooks(
+            VOTE_HOOKS,
+            deps.storage,
+            proposal_id,
+            prop.expiration.to_string(),
+            prop.extensions,
+        )?
+    } else {
+        vec![]
+    };
+
     Ok(Response::default()
         .add_submessages(change_hooks)
         .add_submessages(vote_hooks)
+        .add_submessages(extension_hooks)
         .add_attribute("action", "vote")
         .add_attribute("sender", info.sender)
         .add_attribute("proposal_id", proposal_id.to_string())
         .add_attribute("position", vote.to_string())
         .add_attribute("rationale", rationale.as_deref().unwrap_or("_none"))
-        .add_attribute("status", prop.status.to_string()))
+        .add_attribute("status", prop.status.to_string())
+        .add_attribute("extended", extended.to_string()))
 }
 
 pub fn execute_update_rationale(
@@ -690,6 +1126,7 @@ pub fn execute_close(
 
     prop.status = Status::Closed;
     PROPOSALS.insert(deps.storage, &proposal_id, &prop)?;
+    record_turnout(deps.storage, &prop)?;
 
     // Add proposal status change hooks
     let proposal_status_changed_hooks = proposal_status_changed_hooks(
@@ -702,8 +1139,12 @@ pub fn execute_close(
 
     // Add prepropose / deposit module hook which will handle deposit refunds.
     let proposal_creation_policy = CREATION_POLICY.load(deps.storage)?;
-    let proposal_completed_hooks =
-        proposal_completed_hooks(proposal_creation_policy, proposal_id, prop.status)?;
+    let proposal_completed_hooks = match prop.quorum_met() {
+        Some(quorum_met) => {
+            proposal_closed_hooks(proposal_creation_policy, proposal_id, quorum_met)?
+        }
+        None => proposal_completed_hooks(proposal_creation_policy, proposal_id, prop.status)?,
+    };
 
     Ok(Response::default()
         .add_submessages(proposal_status_changed_hooks)
@@ -726,6 +1167,9 @@ pub fn execute_update_config(
     code_hash: String,
     close_proposal_on_execution_failure: bool,
     veto: Option<VetoConfig>,
+    review_period: Option<Duration>,
+    late_vote_extension: Option<LateVoteExtensionConfig>,
+    close_proposal_on_condition_failure: bool,
 ) -> Result<Response, ContractError> {
     let mut dao_info = DAO.load(deps.storage)?;
 
@@ -744,18 +1188,35 @@ pub fn execute_update_config(
         veto_config.validate(&deps.as_ref(), &max_voting_period)?;
     };
 
-    CONFIG.save(
-        deps.storage,
-        &Config {
-            threshold,
-            max_voting_period,
-            min_voting_period,
-            only_members_execute,
-            allow_revoting,
-            close_proposal_on_execution_failure,
-            veto,
-        },
+    validate_review_period(review_period, max_voting_period)?;
+
+    // if late vote extension is configured, validate its fields
+    if let Some(extension_config) = &late_vote_extension {
+        extension_config.validate(&max_voting_period)?;
+    };
+
+    let config = Config {
+        threshold,
+        max_voting_period,
+        min_voting_period,
+        only_members_execute,
+        allow_revoting,
+        close_proposal_on_execution_failure,
+        veto,
+        review_period,
+        late_vote_extension,
+        close_proposal_on_condition_failure,
+    };
+
+    // Existing threshold rules must remain stricter than the new
+    // config.
+    validate_threshold_rules(
+        deps.as_ref(),
+        &config,
+        &THRESHOLD_RULES.may_load(deps.storage)?.unwrap_or_default(),
     )?;
+
+    CONFIG.save(deps.storage, &config)?;
     dao_info.addr = dao;
     dao_info.code_hash = code_hash;
 
@@ -766,6 +1227,53 @@ pub fn execute_update_config(
         .add_attribute("sender", info.sender))
 }
 
+pub fn execute_update_threshold_rules(
+    deps: DepsMut,
+    info: MessageInfo,
+    rules: Vec<ThresholdRule>,
+) -> Result<Response, ContractError> {
+    let dao_info = DAO.load(deps.storage)?;
+
+    // Only the DAO may call this method.
+    if info.sender != dao_info.addr {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let config = CONFIG.load(deps.storage)?;
+    validate_threshold_rules(deps.as_ref(), &config, &rules)?;
+    THRESHOLD_RULES.save(deps.storage, &rules)?;
+
+    Ok(Response::default()
+        .add_attribute("action", "update_threshold_rules")
+        .add_attribute("sender", info.sender)
+        .add_attribute("rules", rules.len().to_string()))
+}
+
+pub fn execute_update_emergency_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    config: Option<EmergencyConfig>,
+) -> Result<Response, ContractError> {
+    let dao_info = DAO.load(deps.storage)?;
+
+    // Only the DAO may call this method.
+    if info.sender != dao_info.addr {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    match config {
+        Some(config) => {
+            let config = config.into_checked(deps.api)?;
+            EMERGENCY_CONFIG.save(deps.storage, &config)?;
+        }
+        None => EMERGENCY_CONFIG.remove(deps.storage),
+    }
+
+    Ok(Response::default()
+        .add_attribute("action", "update_emergency_config")
+        .add_attribute("sender", info.sender))
+}
+
 pub fn execute_update_proposal_creation_policy(
     deps: DepsMut,
     info: MessageInfo,
@@ -960,6 +1468,7 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::Config {} => query_config(deps),
         QueryMsg::Dao {} => query_dao(deps),
         QueryMsg::Proposal { proposal_id } => query_proposal(deps, env, proposal_id),
+        QueryMsg::DependencyGraph { proposal_id } => query_dependency_graph(deps, env, proposal_id),
         QueryMsg::ListProposals { start_after, limit } => {
             query_list_proposals(deps, env, start_after, limit)
         }
@@ -976,6 +1485,11 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
             limit,
         } => query_reverse_proposals(deps, env, start_before, limit),
         QueryMsg::ProposalCreationPolicy {} => query_creation_policy(deps),
+        QueryMsg::DynamicQuorum {} => query_dynamic_quorum(deps),
+        QueryMsg::EmergencyConfig {} => query_emergency_config(deps, env),
+        QueryMsg::ThresholdRules {} => {
+            to_binary(&THRESHOLD_RULES.may_load(deps.storage)?.unwrap_or_default())
+        }
         QueryMsg::ProposalHooks {} => to_binary(&PROPOSAL_HOOKS.query_hooks(deps)?),
         QueryMsg::VoteHooks {} => to_binary(&VOTE_HOOKS.query_hooks(deps)?),
         QueryMsg::WithPermit { permit, query } => permit_queries(deps, env, permit, query),
@@ -1050,6 +1564,54 @@ pub fn query_proposal(deps: Deps, env: Env, id: u64) -> StdResult<Binary> {
     to_binary(&proposal.unwrap().into_response(&env.block, id)?)
 }
 
+pub fn query_dependency_graph(deps: Deps, env: Env, id: u64) -> StdResult<Binary> {
+    let proposal = PROPOSALS
+        .get(deps.storage, &id)
+        .ok_or_else(|| StdError::not_found("proposal"))?;
+    let local = |id: u64| local_dependency_info(deps.storage, &env.block, id);
+    to_binary(&DependencyGraphResponse {
+        nodes: dependency_graph(deps, &env.contract.address, &proposal.dependencies, &local),
+    })
+}
+
+/// Looks up a proposal in this module for dependency resolution.
+fn local_dependency_info(
+    storage: &dyn Storage,
+    block: &BlockInfo,
+    id: u64,
+) -> Option<DependencyInfo> {
+    PROPOSALS.get(storage, &id).map(|prop| DependencyInfo {
+        status: prop.current_status(block).unwrap_or(prop.status),
+        dependencies: prop.dependencies,
+    })
+}
+
+/// Records the turnout of a completed proposal for use in dynamic
+/// quorums. Called for every proposal that is executed, rejected, or
+/// expires, including those that receive no votes, so that the quorum
+/// follows falling turnout as well as rising turnout. Vetoed proposals
+/// are not counted.
+fn record_turnout(storage: &mut dyn Storage, prop: &SingleChoiceProposal) -> StdResult<()> {
+    let mut turnout = TURNOUT.may_load(storage)?.unwrap_or_default();
+    turnout.record(prop.votes.total(), prop.total_power);
+    TURNOUT.save(storage, &turnout)
+}
+
+pub fn query_emergency_config(deps: Deps, env: Env) -> StdResult<Binary> {
+    let config = EMERGENCY_CONFIG.may_load(deps.storage)?;
+    let available = match &config {
+        Some(config) => config.available(deps.storage, &env.block)?,
+        None => 0,
+    };
+    to_binary(&EmergencyConfigResponse { config, available })
+}
+
+pub fn query_dynamic_quorum(deps: Deps) -> StdResult<Binary> {
+    let config = CONFIG.load(deps.storage)?;
+    let turnout = TURNOUT.may_load(deps.storage)?.unwrap_or_default();
+    to_binary(&turnout.query(&config.threshold))
+}
+
 pub fn query_creation_policy(deps: Deps) -> StdResult<Binary> {
     let policy = CREATION_POLICY.load(deps.storage)?;
     to_binary(&policy)
@@ -1312,30 +1874,46 @@ pub fn query_info(deps: Deps) -> StdResult<Binary> {
 pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
     let repl = REPLY_IDS.get_event(deps.storage, msg.id)?;
     match repl {
-        ReplyEvent::FailedProposalExecution { proposal_id } => match msg.clone().result {
-            SubMsgResult::Err(err) => Err(ContractError::Std(StdError::GenericErr { msg: err })),
-            SubMsgResult::Ok(_) => {
-                // PROPOSALS.update(deps.storage, proposal_id, |prop| match prop {
-                //     Some(mut prop) => {
-                //         prop.status = Status::ExecutionFailed;
-
-                //         Ok(prop)
-                //     }
-                //     None => Err(ContractError::NoSuchProposal { id: proposal_id }),
-                // })?;
-                let proposals = PROPOSALS.get(deps.storage, &proposal_id);
-                if proposals.clone().is_some() {
-                    proposals.clone().unwrap().status = Status::ExecutionFailed;
-                } else {
-                    return Err(ContractError::NoSuchProposal { id: proposal_id });
+        ReplyEvent::FailedProposalExecution { proposal_id } => {
+            let proposal_creation_policy = CREATION_POLICY.load(deps.storage)?;
+            match msg.result {
+                // The proposal executed, so the deposit module may
+                // now be told it completed.
+                SubMsgResult::Ok(_) => {
+                    Ok(Response::new().add_submessages(proposal_completed_hooks(
+                        proposal_creation_policy,
+                        proposal_id,
+                        Status::Executed,
+                    )?))
                 }
-                PROPOSALS.insert(deps.storage, &proposal_id, &proposals.unwrap())?;
+                SubMsgResult::Err(err) => {
+                    let mut prop = PROPOSALS
+                        .get(deps.storage, &proposal_id)
+                        .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+                    prop.status = Status::ExecutionFailed;
+                    PROPOSALS.insert(deps.storage, &proposal_id, &prop)?;
+
+                    let proposal_status_changed_hooks = proposal_status_changed_hooks(
+                        PROPOSAL_HOOKS,
+                        deps.storage,
+                        proposal_id,
+                        Status::Executed.to_string(),
+                        prop.status.to_string(),
+                    )?;
+                    let proposal_completed_hooks = proposal_completed_hooks(
+                        proposal_creation_policy,
+                        proposal_id,
+                        prop.status,
+                    )?;
 
-                Ok(Response::new()
-                    .add_attribute("proposal_execution_failed", proposal_id.to_string())
-                    .add_attribute("error", msg.result.into_result().err().unwrap_or_default()))
+                    Ok(Response::new()
+                        .add_submessages(proposal_status_changed_hooks)
+                        .add_submessages(proposal_completed_hooks)
+                        .add_attribute("proposal_execution_failed", proposal_id.to_string())
+                        .add_attribute("error", err))
+                }
             }
-        },
+        }
         ReplyEvent::FailedProposalHook { idx } => match msg.result {
             SubMsgResult::Err(err) => Err(ContractError::Std(StdError::GenericErr { msg: err })),
             SubMsgResult::Ok(_) => {
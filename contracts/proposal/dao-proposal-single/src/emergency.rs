@@ -0,0 +1,404 @@
+use std::cmp::Ordering;
+
+use cosmwasm_std::{Addr, Api, BlockInfo, Deps, StdResult, Storage};
+use dao_voting::{
+    threshold::Threshold,
+    veto::{VetoConfig, VetoError},
+};
+use schemars::JsonSchema;
+use secret_utils::{Duration, Expiration};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    state::{Config, EMERGENCY_PROPOSALS},
+    threshold_rules::ThresholdRule,
+    ContractError,
+};
+
+/// Configures fast-tracked proposals for responding to emergencies,
+/// such as security incidents. Emergency proposals use this
+/// configuration in place of the module's threshold, voting periods,
+/// and review period. Threshold rules matching an emergency
+/// proposal's messages still apply if their threshold is stricter.
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub struct EmergencyConfig {
+    /// The addresses allowed to create emergency proposals. When a
+    /// pre-propose module is attached, this is checked against the
+    /// proposer it specifies.
+    pub proposers: Vec<String>,
+    /// The threshold emergency proposals must reach, for example 67%
+    /// of all voting power.
+    pub threshold: Threshold,
+    /// The voting period of emergency proposals. Emergency proposals
+    /// have no minimum voting period and may pass as soon as the
+    /// threshold is reached.
+    pub max_voting_period: Duration,
+    /// The veto timelock of emergency proposals if the module has a
+    /// veto configured. None sets the timelock duration to zero;
+    /// otherwise the module's timelock duration is replaced by this
+    /// one. Either way the vetoers may still veto emergency
+    /// proposals as the module's veto configuration allows.
+    pub veto_timelock: Option<Duration>,
+    /// The number of emergency proposals that may be created within
+    /// `rate_limit_period`.
+    pub max_proposals: u64,
+    /// The period over which emergency proposals are rate limited.
+    pub rate_limit_period: Duration,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub struct EmergencyConfigResponse {
+    /// The module's emergency configuration. None if emergency
+    /// proposals are disabled.
+    pub config: Option<EmergencyConfig>,
+    /// The number of emergency proposals that may currently be
+    /// created before the rate limit is reached.
+    pub available: u64,
+}
+
+fn is_zero(duration: &Duration) -> bool {
+    matches!(duration, Duration::Height(0) | Duration::Time(0))
+}
+
+/// A zero duration in the same units as DURATION.
+fn zero_like(duration: &Duration) -> Duration {
+    match duration {
+        Duration::Height(_) => Duration::Height(0),
+        Duration::Time(_) => Duration::Time(0),
+    }
+}
+
+/// Returns the stricter of THRESHOLD and RULE. Thresholds that can
+/// not be compared are combined, so that both must be met.
+fn stricter(threshold: &Threshold, rule: &Threshold) -> Threshold {
+    match rule.strictness_cmp(threshold) {
+        Some(Ordering::Greater) => rule.clone(),
+        Some(_) => threshold.clone(),
+        None => {
+            let parts = |t: &Threshold| match t {
+                Threshold::Composite { thresholds } => thresholds.clone(),
+                t => vec![t.clone()],
+            };
+            Threshold::Composite {
+                thresholds: [parts(threshold), parts(rule)].concat(),
+            }
+        }
+    }
+}
+
+impl EmergencyConfig {
+    /// Validates the configuration, normalizing proposer addresses.
+    pub fn into_checked(self, api: &dyn Api) -> Result<Self, ContractError> {
+        if self.proposers.is_empty()
+            || self.max_proposals == 0
+            || is_zero(&self.max_voting_period)
+            || is_zero(&self.rate_limit_period)
+        {
+            return Err(ContractError::InvalidEmergencyConfig {});
+        }
+        self.threshold.validate()?;
+        if let Some(veto_timelock) = &self.veto_timelock {
+            if zero_like(veto_timelock) != zero_like(&self.max_voting_period) {
+                return Err(VetoError::TimelockDurationUnitMismatch {}.into());
+            }
+        }
+        let proposers = self
+            .proposers
+            .iter()
+            .map(|proposer| Ok(api.addr_validate(proposer)?.into_string()))
+            .collect::<StdResult<_>>()?;
+        Ok(Self { proposers, ..self })
+    }
+
+    /// Returns the threshold, max voting period, and veto
+    /// configuration of an emergency proposal. If RULE matches the
+    /// proposal's messages, the stricter of its threshold and the
+    /// emergency threshold applies. The result is validated, as
+    /// combining thresholds may exceed the composite threshold limit
+    /// and the module's veto configuration may have changed since
+    /// the emergency configuration was set.
+    pub fn apply(
+        &self,
+        deps: Deps,
+        config: &Config,
+        rule: Option<&ThresholdRule>,
+    ) -> Result<(Threshold, Duration, Option<VetoConfig>), ContractError> {
+        let threshold = match rule {
+            Some(rule) => stricter(&self.threshold, &ThresholdRule::apply(Some(rule), config).0),
+            None => self.threshold.clone(),
+        };
+        let veto = config.veto.clone().map(|veto| VetoConfig {
+            timelock_duration: self
+                .veto_timelock
+                .unwrap_or_else(|| zero_like(&self.max_voting_period)),
+            ..veto
+        });
+        threshold.validate()?;
+        if let Some(veto) = &veto {
+            veto.validate(&deps, &self.max_voting_period)?;
+        }
+        Ok((threshold, self.max_voting_period, veto))
+    }
+
+    /// Checks that `proposer` may create an emergency proposal and
+    /// that the rate limit has not been reached, then records the
+    /// proposal against the rate limit.
+    pub fn record_proposal(
+        &self,
+        storage: &mut dyn Storage,
+        block: &BlockInfo,
+        proposer: &Addr,
+    ) -> Result<(), ContractError> {
+        if !self.proposers.iter().any(|p| p == proposer.as_str()) {
+            return Err(ContractError::NotEmergencyProposer {});
+        }
+        let mut recent = active_emergency_proposals(storage, block)?;
+        if recent.len() as u64 >= self.max_proposals {
+            return Err(ContractError::EmergencyRateLimited {
+                max: self.max_proposals,
+            });
+        }
+        recent.push(self.rate_limit_period.after(block));
+        EMERGENCY_PROPOSALS.save(storage, &recent)?;
+        Ok(())
+    }
+
+    /// The number of emergency proposals that may currently be
+    /// created.
+    pub fn available(&self, storage: &dyn Storage, block: &BlockInfo) -> StdResult<u64> {
+        let recent = active_emergency_proposals(storage, block)?;
+        Ok(self.max_proposals.saturating_sub(recent.len() as u64))
+    }
+}
+
+/// Returns when each emergency proposal counting against the rate
+/// limit stops counting against it.
+fn active_emergency_proposals(
+    storage: &dyn Storage,
+    block: &BlockInfo,
+) -> StdResult<Vec<Expiration>> {
+    Ok(EMERGENCY_PROPOSALS
+        .may_load(storage)?
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|expiration| !expiration.is_expired(block))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::threshold_rules::MessageMatcher;
+    use cosmwasm_std::{
+        testing::{mock_dependencies, mock_env},
+        Decimal, Uint128,
+    };
+    use dao_voting::threshold::{PercentageThreshold, ThresholdError, MAX_COMPOSITE_THRESHOLDS};
+
+    fn emergency_config() -> EmergencyConfig {
+        EmergencyConfig {
+            proposers: vec!["security".to_string()],
+            threshold: Threshold::AbsolutePercentage {
+                percentage: PercentageThreshold::Percent(Decimal::percent(67)),
+            },
+            max_voting_period: Duration::Time(3600),
+            veto_timelock: None,
+            max_proposals: 2,
+            rate_limit_period: Duration::Height(10),
+        }
+    }
+
+    #[test]
+    fn test_validate_emergency_config() {
+        let deps = mock_dependencies();
+        emergency_config().into_checked(&deps.api).unwrap();
+
+        let invalid = [
+            EmergencyConfig {
+                proposers: vec![],
+                ..emergency_config()
+            },
+            EmergencyConfig {
+                max_proposals: 0,
+                ..emergency_config()
+            },
+            EmergencyConfig {
+                rate_limit_period: Duration::Height(0),
+                ..emergency_config()
+            },
+        ];
+        for config in invalid {
+            assert_eq!(
+                config.into_checked(&deps.api).unwrap_err(),
+                ContractError::InvalidEmergencyConfig {}
+            );
+        }
+
+        // The veto timelock must have the same units as the voting
+        // period.
+        assert_eq!(
+            EmergencyConfig {
+                veto_timelock: Some(Duration::Height(10)),
+                ..emergency_config()
+            }
+            .into_checked(&deps.api)
+            .unwrap_err(),
+            ContractError::VetoError(VetoError::TimelockDurationUnitMismatch {})
+        );
+    }
+
+    #[test]
+    fn test_apply_emergency_config() {
+        let deps = mock_dependencies();
+        let percent = |p| Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Percent(Decimal::percent(p)),
+        };
+        let veto = VetoConfig {
+            timelock_duration: Duration::Time(600),
+            vetoer: "vetoer".to_string(),
+            council: vec![],
+            veto_threshold: 1,
+            early_execute: true,
+            veto_before_passed: true,
+        };
+        let config = Config {
+            threshold: percent(51),
+            max_voting_period: Duration::Time(86400),
+            min_voting_period: None,
+            only_members_execute: false,
+            allow_revoting: false,
+            close_proposal_on_execution_failure: false,
+            veto: Some(veto.clone()),
+            review_period: None,
+            late_vote_extension: None,
+            close_proposal_on_condition_failure: false,
+        };
+        let rule = |threshold| ThresholdRule {
+            matches: MessageMatcher::Migrate {},
+            threshold: Some(threshold),
+            max_voting_period: None,
+            veto: None,
+        };
+
+        // Without a timelock the veto configuration is kept with a
+        // zero timelock duration.
+        let (threshold, max_voting_period, applied_veto) = emergency_config()
+            .apply(deps.as_ref(), &config, None)
+            .unwrap();
+        assert_eq!(threshold, percent(67));
+        assert_eq!(max_voting_period, Duration::Time(3600));
+        assert_eq!(
+            applied_veto,
+            Some(VetoConfig {
+                timelock_duration: Duration::Time(0),
+                ..veto.clone()
+            })
+        );
+
+        let config_with_timelock = EmergencyConfig {
+            veto_timelock: Some(Duration::Time(60)),
+            ..emergency_config()
+        };
+        assert_eq!(
+            config_with_timelock
+                .apply(deps.as_ref(), &config, None)
+                .unwrap()
+                .2,
+            Some(VetoConfig {
+                timelock_duration: Duration::Time(60),
+                ..veto
+            })
+        );
+
+        // A stricter matching rule applies in place of the emergency
+        // threshold. A looser one does not.
+        assert_eq!(
+            emergency_config()
+                .apply(deps.as_ref(), &config, Some(&rule(percent(80))))
+                .unwrap()
+                .0,
+            percent(80)
+        );
+        assert_eq!(
+            emergency_config()
+                .apply(deps.as_ref(), &config, Some(&rule(percent(60))))
+                .unwrap()
+                .0,
+            percent(67)
+        );
+
+        // Thresholds that can not be compared must both be met.
+        let count = Threshold::AbsoluteCount {
+            threshold: Uint128::new(3),
+        };
+        assert_eq!(
+            emergency_config()
+                .apply(deps.as_ref(), &config, Some(&rule(count.clone())))
+                .unwrap()
+                .0,
+            Threshold::Composite {
+                thresholds: vec![percent(67), count.clone()],
+            }
+        );
+
+        // Combining thresholds may not exceed the composite threshold
+        // limit.
+        let full = EmergencyConfig {
+            threshold: Threshold::Composite {
+                thresholds: vec![count; MAX_COMPOSITE_THRESHOLDS],
+            },
+            ..emergency_config()
+        };
+        assert_eq!(
+            full.apply(deps.as_ref(), &config, Some(&rule(percent(80))))
+                .unwrap_err(),
+            ContractError::ThresholdError(ThresholdError::InvalidCompositeThreshold {
+                max: MAX_COMPOSITE_THRESHOLDS
+            })
+        );
+    }
+
+    #[test]
+    fn test_emergency_rate_limit() {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+        let config = emergency_config();
+        let security = Addr::unchecked("security");
+
+        assert_eq!(
+            config
+                .record_proposal(
+                    deps.as_mut().storage,
+                    &env.block,
+                    &Addr::unchecked("someone")
+                )
+                .unwrap_err(),
+            ContractError::NotEmergencyProposer {}
+        );
+
+        config
+            .record_proposal(deps.as_mut().storage, &env.block, &security)
+            .unwrap();
+        env.block.height += 5;
+        config
+            .record_proposal(deps.as_mut().storage, &env.block, &security)
+            .unwrap();
+        assert_eq!(config.available(&deps.storage, &env.block).unwrap(), 0);
+        assert_eq!(
+            config
+                .record_proposal(deps.as_mut().storage, &env.block, &security)
+                .unwrap_err(),
+            ContractError::EmergencyRateLimited { max: 2 }
+        );
+
+        // The first proposal stops counting against the limit once
+        // the rate limit period has passed.
+        env.block.height += 5;
+        assert_eq!(config.available(&deps.storage, &env.block).unwrap(), 1);
+        config
+            .record_proposal(deps.as_mut().storage, &env.block, &security)
+            .unwrap();
+    }
+}
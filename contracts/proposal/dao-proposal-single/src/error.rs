@@ -2,7 +2,10 @@ use std::u64;
 
 use cosmwasm_std::StdError;
 use cw_hooks::HookError;
-use dao_voting::{reply::error::TagError, veto::VetoError};
+use dao_voting::{
+    condition::ConditionError, dependency::DependencyError,
+    late_vote_extension::LateVoteExtensionError, reply::error::TagError, veto::VetoError,
+};
 use secret_cw_controllers::ReplyError;
 use secret_utils::ParseReplyError;
 use thiserror::Error;
@@ -24,6 +27,15 @@ pub enum ContractError {
     #[error(transparent)]
     VetoError(#[from] VetoError),
 
+    #[error(transparent)]
+    LateVoteExtensionError(#[from] LateVoteExtensionError),
+
+    #[error(transparent)]
+    ConditionError(#[from] ConditionError),
+
+    #[error(transparent)]
+    DependencyError(#[from] DependencyError),
+
     #[error("unauthorized")]
     Unauthorized {},
 
@@ -78,6 +90,39 @@ pub enum ContractError {
     #[error("min voting period must be less than or equal to max voting period")]
     InvalidMinVotingPeriod {},
 
+    #[error("review_period and max_voting_period must have the same units (height or time)")]
+    ReviewPeriodUnitsConflict {},
+
+    #[error("proposal ({id}) is in its review period and not yet open for voting")]
+    InReviewPeriod { id: u64 },
+
+    #[error("proposal ({id}) is not in its review period")]
+    NotInReviewPeriod { id: u64 },
+
+    #[error("a module may have at most ({max}) threshold rules")]
+    TooManyThresholdRules { max: usize },
+
+    #[error("threshold rule ({index}) must set a threshold, max_voting_period, or veto")]
+    EmptyThresholdRule { index: usize },
+
+    #[error("threshold rule ({index}) must match at least one core message variant")]
+    InvalidThresholdRuleMatcher { index: usize },
+
+    #[error("threshold rule ({index}) must be at least as strict as the module's config")]
+    ThresholdRuleNotStricter { index: usize },
+
+    #[error("emergency proposals are not enabled for this module")]
+    EmergencyProposalsDisabled {},
+
+    #[error("emergency configs must have proposers, a non-zero rate limit, and non-zero periods")]
+    InvalidEmergencyConfig {},
+
+    #[error("only emergency proposers may create emergency proposals")]
+    NotEmergencyProposer {},
+
+    #[error("at most ({max}) emergency proposals may be created per rate limit period")]
+    EmergencyRateLimited { max: u64 },
+
     #[error(
         "pre-propose modules must specify a proposer. lacking one, no proposer should be specified"
     )]
@@ -1,6 +1,7 @@
 #![doc = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/README.md"))]
 
 pub mod contract;
+pub mod emergency;
 mod error;
 pub mod msg;
 pub mod proposal;
@@ -8,8 +9,11 @@ pub mod query;
 
 #[cfg(test)]
 mod testing;
+#[cfg(test)]
+mod tests;
 
 pub mod state;
+pub mod threshold_rules;
 pub mod v1_state;
 
 pub use crate::error::ContractError;
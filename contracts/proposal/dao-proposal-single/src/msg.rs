@@ -1,15 +1,18 @@
 use cosmwasm_schema::QueryResponses;
-use cosmwasm_std::{Addr, Api, StdResult};
+use cosmwasm_std::{Addr, Api, CosmosMsg, Empty, StdResult};
 use dao_dao_macros::proposal_module_query;
 use dao_voting::{
-    pre_propose::PreProposeInfo, proposal::SingleChoiceProposeMsg, threshold::Threshold,
-    veto::VetoConfig, voting::Vote,
+    late_vote_extension::LateVoteExtensionConfig, pre_propose::PreProposeInfo,
+    proposal::SingleChoiceProposeMsg, threshold::Threshold, veto::VetoConfig, voting::Vote,
 };
 use schemars::JsonSchema;
 use secret_toolkit::permit::Permit;
 use secret_utils::Duration;
 use serde::{Deserialize, Serialize};
 
+use crate::emergency::EmergencyConfig;
+use crate::threshold_rules::ThresholdRule;
+
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct InstantiateMsg {
@@ -49,6 +52,31 @@ pub struct InstantiateMsg {
     /// During this period an oversight account (`veto.vetoer`) can
     /// veto the proposal.
     pub veto: Option<VetoConfig>,
+    /// An optional period at the start of each proposal during which
+    /// the proposer may amend or withdraw it. Voting does not open
+    /// until this period has elapsed. Must have the same units as
+    /// `max_voting_period`.
+    pub review_period: Option<Duration>,
+    /// Optional configuration for extending a proposal's voting
+    /// period when a late vote changes its leading outcome or newly
+    /// crosses its passing threshold.
+    pub late_vote_extension: Option<LateVoteExtensionConfig>,
+    /// If set to true proposals will be closed if one of their
+    /// execution conditions does not hold, or one of their
+    /// dependencies will never be executed, when they are executed.
+    /// Otherwise, execution is refused and proposals remain
+    /// executable until their conditions hold.
+    #[serde(default)]
+    pub close_proposal_on_condition_failure: bool,
+    /// Rules applying stricter voting requirements to proposals
+    /// containing matching messages. The strictest matching rule is
+    /// applied to a proposal when it is created.
+    #[serde(default)]
+    pub threshold_rules: Vec<ThresholdRule>,
+    /// Configuration of emergency proposals. Emergency proposals are
+    /// disabled if not set.
+    #[serde(default)]
+    pub emergency: Option<EmergencyConfig>,
 
     pub dao_code_hash: String,
 }
@@ -86,11 +114,34 @@ pub enum ExecuteMsg {
         /// The ID of the proposal to execute.
         proposal_id: u64,
     },
+    /// Replaces the title, description, and messages of a proposal
+    /// that is still in its review period. Only callable by the
+    /// proposer. Each amendment increments the proposal's revision.
+    AmendProposal {
+        /// The ID of the proposal to amend.
+        proposal_id: u64,
+        title: String,
+        description: String,
+        msgs: Vec<CosmosMsg<Empty>>,
+    },
+    /// Withdraws a proposal that is still in its review period. Only
+    /// callable by the proposer. If applicable this will cause the
+    /// proposal deposit associated with said proposal to be returned.
+    WithdrawProposal {
+        /// The ID of the proposal to withdraw.
+        proposal_id: u64,
+    },
     /// Callable only if veto is configured
     Veto {
         /// The ID of the proposal to veto.
         proposal_id: u64,
     },
+    /// Withdraws the sender's veto of a proposal that has not yet
+    /// been vetoed by the veto council.
+    WithdrawVeto {
+        /// The ID of the proposal to withdraw the veto from.
+        proposal_id: u64,
+    },
     /// Closes a proposal that has failed (either not passed or timed
     /// out). If applicable this will cause the proposal deposit
     /// associated wth said proposal to be returned.
@@ -137,7 +188,30 @@ pub enum ExecuteMsg {
         /// Optional time delay on proposal execution, during which the
         /// proposal may be vetoed.
         veto: Option<VetoConfig>,
+        /// An optional period at the start of each proposal during
+        /// which the proposer may amend or withdraw it. This will only
+        /// apply to proposals created after the config update.
+        review_period: Option<Duration>,
+        /// Optional configuration for extending a proposal's voting
+        /// period when a late vote swings its outcome. This will only
+        /// apply to proposals created after the config update.
+        late_vote_extension: Option<LateVoteExtensionConfig>,
+        /// If set to true proposals will be closed if one of their
+        /// execution conditions does not hold, or one of their
+        /// dependencies will never be executed, when they are executed.
+        /// Otherwise, execution is refused and proposals remain
+        /// executable until their conditions hold.
+        #[serde(default)]
+        close_proposal_on_condition_failure: bool,
     },
+    /// Replaces the module's threshold rules. Only the DAO may call
+    /// this method. Rules apply to proposals created after the
+    /// update.
+    UpdateThresholdRules { rules: Vec<ThresholdRule> },
+    /// Sets the module's emergency proposal configuration, or
+    /// disables emergency proposals if None. Only the DAO may call
+    /// this method.
+    UpdateEmergencyConfig { config: Option<EmergencyConfig> },
     /// Update's the proposal creation policy used for this
     /// module. Only the DAO may call this method.
     UpdatePreProposeInfo { info: PreProposeInfo },
@@ -182,6 +256,10 @@ pub enum QueryMsg {
     /// Gets information about a proposal.
     #[returns(crate::query::ProposalResponse)]
     Proposal { proposal_id: u64 },
+    /// Lists the proposals a proposal depends on, directly or
+    /// transitively, along with their current statuses.
+    #[returns(dao_voting::dependency::DependencyGraphResponse)]
+    DependencyGraph { proposal_id: u64 },
     /// Lists all the proposals that have been cast in this
     /// module.
     #[returns(crate::query::ProposalListResponse)]
@@ -234,6 +312,17 @@ pub enum QueryMsg {
     /// Gets the current proposal creation policy for this module.
     #[returns(::dao_voting::pre_propose::ProposalCreationPolicy)]
     ProposalCreationPolicy {},
+    /// Lists the module's threshold rules.
+    #[returns(Vec<crate::threshold_rules::ThresholdRule>)]
+    ThresholdRules {},
+    /// Gets the module's emergency proposal configuration and the
+    /// number of emergency proposals that may currently be created.
+    #[returns(crate::emergency::EmergencyConfigResponse)]
+    EmergencyConfig {},
+    /// Gets the quorum a proposal created now would have, computed
+    /// from the turnout of recently completed proposals.
+    #[returns(dao_voting::turnout::DynamicQuorumResponse)]
+    DynamicQuorum {},
     /// Lists all of the consumers of proposal hooks for this module.
     #[returns(::cw_hooks::HooksResponse)]
     ProposalHooks {},
@@ -1,13 +1,18 @@
 use crate::query::ProposalResponse;
 use crate::state::PROPOSAL_COUNT;
-use cosmwasm_std::{Addr, BlockInfo, CosmosMsg, Decimal, Empty, StdResult, Storage, Uint128};
+use crate::threshold_rules::ThresholdRule;
+use cosmwasm_std::{Addr, BlockInfo, CosmosMsg, Empty, StdResult, Storage, Uint128};
+use dao_voting::condition::ExecutionCondition;
+use dao_voting::dependency::ProposalDependency;
+use dao_voting::late_vote_extension::LateVoteExtensionConfig;
 use dao_voting::status::Status;
-use dao_voting::threshold::{PercentageThreshold, Threshold};
+use dao_voting::threshold::Threshold;
 use dao_voting::veto::VetoConfig;
-use dao_voting::voting::{does_vote_count_fail, does_vote_count_pass, Votes};
+use dao_voting::voting::{does_vote_count_pass, is_threshold_passed, is_threshold_rejected, Votes};
 use schemars::JsonSchema;
 use secret_utils::Expiration;
 use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
 use std::ops::Add;
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
@@ -47,6 +52,54 @@ pub struct SingleChoiceProposal {
     /// Optional veto configuration. If set to `None`, veto option
     /// is disabled. Otherwise contains the configuration for veto flow.
     pub veto: Option<VetoConfig>,
+    /// The time at which this proposal's review period ends and
+    /// voting opens. Until then the proposer may amend or withdraw
+    /// the proposal. None if the module had no review period
+    /// configured when the proposal was created.
+    #[serde(default)]
+    pub review_period: Option<Expiration>,
+    /// The number of times this proposal has been amended by its
+    /// proposer. Starts at zero.
+    #[serde(default)]
+    pub revision: u64,
+    /// Optional configuration for extending this proposal's voting
+    /// period when a late vote swings its outcome.
+    #[serde(default)]
+    pub late_vote_extension: Option<LateVoteExtensionConfig>,
+    /// The number of times this proposal's expiration has been moved
+    /// forward by late vote extensions.
+    #[serde(default)]
+    pub extensions: u64,
+    /// Conditions that must hold when this proposal is executed.
+    #[serde(default)]
+    pub conditions: Vec<ExecutionCondition>,
+    /// Proposals that must be executed before this proposal may be
+    /// executed.
+    #[serde(default)]
+    pub dependencies: Vec<ProposalDependency>,
+    /// The threshold rule applied to this proposal, if any. The
+    /// proposal's threshold, expiration, and veto configuration
+    /// reflect this rule.
+    #[serde(default)]
+    pub threshold_rule: Option<ThresholdRule>,
+    /// The members of the veto council that have vetoed this
+    /// proposal.
+    #[serde(default)]
+    pub vetoes: Vec<Addr>,
+    /// Whether this is an emergency proposal, created with the
+    /// module's emergency configuration.
+    #[serde(default)]
+    pub emergency: bool,
+}
+
+/// How a proposal would be decided if voting closed with the votes
+/// cast so far. Used to detect late swings in a proposal's outcome.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Outcome {
+    /// Whether yes votes lead, trail, or are tied with no votes.
+    pub leading: Ordering,
+    /// Whether the proposal would pass.
+    pub passing: bool,
 }
 
 pub fn next_proposal_id(store: &dyn Storage) -> StdResult<u64> {
@@ -72,9 +125,20 @@ impl SingleChoiceProposal {
         Ok(ProposalResponse { id, proposal: self })
     }
 
+    /// Returns true if this proposal is still in its review period,
+    /// during which it may be amended or withdrawn and may not be
+    /// voted on.
+    pub fn is_in_review(&self, block: &BlockInfo) -> bool {
+        self.review_period
+            .map_or(false, |review| !review.is_expired(block))
+    }
+
     /// Gets the current status of the proposal.
     pub fn current_status(&self, block: &BlockInfo) -> StdResult<Status> {
         match self.status {
+            // No votes may be cast during the review period so the
+            // outcome of the proposal can not be known yet.
+            Status::Open if self.is_in_review(block) => Ok(Status::Open),
             Status::Open if self.is_passed(block) => match &self.veto {
                 // if prop is passed and veto is configured, calculate timelock
                 // expiration. if it's expired, this proposal has passed.
@@ -106,6 +170,48 @@ impl SingleChoiceProposal {
         }
     }
 
+    /// Returns how this proposal would be decided if voting closed
+    /// now with the votes cast so far.
+    pub fn outcome(&self, block: &BlockInfo) -> Outcome {
+        // Evaluate the proposal as of its expiration so that the
+        // passing check considers only the votes that have been cast.
+        let mut at_expiration = block.clone();
+        match self.expiration {
+            Expiration::AtHeight(height) => at_expiration.height = height.max(block.height),
+            Expiration::AtTime(time) => at_expiration.time = time.max(block.time),
+            Expiration::Never {} => (),
+        }
+        Outcome {
+            leading: self.votes.yes.cmp(&self.votes.no),
+            passing: self.is_passed(&at_expiration),
+        }
+    }
+
+    /// Moves this proposal's expiration forward if late vote
+    /// extension is configured, `block` is within the final window
+    /// before expiration, the proposal has not been extended the
+    /// maximum number of times, and its outcome differs from
+    /// `previous`. Returns true if the proposal was extended.
+    pub fn apply_late_vote_extension(
+        &mut self,
+        block: &BlockInfo,
+        previous: Outcome,
+    ) -> StdResult<bool> {
+        let config = match &self.late_vote_extension {
+            Some(config) => config,
+            None => return Ok(false),
+        };
+        if !config.can_extend(self.extensions)
+            || !config.is_in_window(&self.expiration, block)
+            || self.outcome(block) == previous
+        {
+            return Ok(false);
+        }
+        self.expiration = self.expiration.add(config.extension)?;
+        self.extensions += 1;
+        Ok(true)
+    }
+
     /// Sets a proposals status to its current status.
     pub fn update_status(&mut self, block: &BlockInfo) -> StdResult<()> {
         let new_status = self.current_status(block)?;
@@ -133,30 +239,12 @@ impl SingleChoiceProposal {
             }
         }
 
-        match self.threshold {
-            Threshold::AbsolutePercentage { percentage } => {
-                let options = self.total_power - self.votes.abstain;
-                does_vote_count_pass(self.votes.yes, options, percentage)
-            }
-            Threshold::ThresholdQuorum { threshold, quorum } => {
-                if !does_vote_count_pass(self.votes.total(), self.total_power, quorum) {
-                    return false;
-                }
-
-                if self.expiration.is_expired(block) {
-                    // If the quorum is met and the proposal is
-                    // expired the number of votes needed to pass a
-                    // proposal is compared to the number of votes on
-                    // the proposal.
-                    let options = self.votes.total() - self.votes.abstain;
-                    does_vote_count_pass(self.votes.yes, options, threshold)
-                } else {
-                    let options = self.total_power - self.votes.abstain;
-                    does_vote_count_pass(self.votes.yes, options, threshold)
-                }
-            }
-            Threshold::AbsoluteCount { threshold } => self.votes.yes >= threshold,
-        }
+        is_threshold_passed(
+            &self.threshold,
+            &self.votes,
+            self.total_power,
+            self.expiration.is_expired(block),
+        )
     }
 
     /// As above for the passed check, used to check if a proposal is
@@ -168,107 +256,36 @@ impl SingleChoiceProposal {
             return false;
         }
 
-        match self.threshold {
-            Threshold::AbsolutePercentage {
-                percentage: percentage_needed,
-            } => {
-                let options = self.total_power - self.votes.abstain;
-
-                // If there is a 100% passing threshold..
-                if percentage_needed == PercentageThreshold::Percent(Decimal::percent(100)) {
-                    if options == Uint128::zero() {
-                        // and there are no possible votes (zero
-                        // voting power or all abstain), then this
-                        // proposal has been rejected.
-                        return true;
-                    } else {
-                        // and there are possible votes, then this is
-                        // rejected if there is a single no vote.
-                        //
-                        // We need this check becuase otherwise when
-                        // we invert the threshold (`Decimal::one() -
-                        // threshold`) we get a 0% requirement for no
-                        // votes. Zero no votes do indeed meet a 0%
-                        // threshold.
-                        return self.votes.no >= Uint128::new(1);
-                    }
-                }
+        is_threshold_rejected(
+            &self.threshold,
+            &self.votes,
+            self.total_power,
+            self.expiration.is_expired(block),
+        )
+    }
 
-                does_vote_count_fail(self.votes.no, options, percentage_needed)
-            }
-            Threshold::ThresholdQuorum { threshold, quorum } => {
-                match (
-                    does_vote_count_pass(self.votes.total(), self.total_power, quorum),
-                    self.expiration.is_expired(block),
-                ) {
-                    // Has met quorum and is expired.
-                    (true, true) => {
-                        // => consider only votes cast and see if no
-                        //    votes meet threshold.
-                        let options = self.votes.total() - self.votes.abstain;
-
-                        // If there is a 100% passing threshold..
-                        if threshold == PercentageThreshold::Percent(Decimal::percent(100)) {
-                            if options == Uint128::zero() {
-                                // and there are no possible votes (zero
-                                // voting power or all abstain), then this
-                                // proposal has been rejected.
-                                return true;
-                            } else {
-                                // and there are possible votes, then this is
-                                // rejected if there is a single no vote.
-                                //
-                                // We need this check becuase
-                                // otherwise when we invert the
-                                // threshold (`Decimal::one() -
-                                // threshold`) we get a 0% requirement
-                                // for no votes. Zero no votes do
-                                // indeed meet a 0% threshold.
-                                return self.votes.no >= Uint128::new(1);
-                            }
-                        }
-                        does_vote_count_fail(self.votes.no, options, threshold)
-                    }
-                    // Has met quorum and is not expired.
-                    // | Hasn't met quorum and is not expired.
-                    (true, false) | (false, false) => {
-                        // => consider all possible votes and see if
-                        //    no votes meet threshold.
-                        let options = self.total_power - self.votes.abstain;
-
-                        // If there is a 100% passing threshold..
-                        if threshold == PercentageThreshold::Percent(Decimal::percent(100)) {
-                            if options == Uint128::zero() {
-                                // and there are no possible votes (zero
-                                // voting power or all abstain), then this
-                                // proposal has been rejected.
-                                return true;
-                            } else {
-                                // and there are possible votes, then this is
-                                // rejected if there is a single no vote.
-                                //
-                                // We need this check because otherwise
-                                // when we invert the threshold
-                                // (`Decimal::one() - threshold`) we
-                                // get a 0% requirement for no
-                                // votes. Zero no votes do indeed meet
-                                // a 0% threshold.
-                                return self.votes.no >= Uint128::new(1);
-                            }
-                        }
-
-                        does_vote_count_fail(self.votes.no, options, threshold)
-                    }
-                    // Hasn't met quorum requirement and voting has closed => rejected.
-                    (false, true) => true,
-                }
-            }
-            Threshold::AbsoluteCount { threshold } => {
-                // If all the outstanding votes voting yes would not
-                // cause this proposal to pass then it is rejected.
-                let outstanding_votes = self.total_power - self.votes.total();
-                self.votes.yes + outstanding_votes < threshold
-            }
+    /// Returns whether the votes cast on this proposal meet its
+    /// quorum, or None if its threshold has no quorum.
+    pub fn quorum_met(&self) -> Option<bool> {
+        self.threshold_quorum_met(&self.threshold)
+    }
+
+    fn threshold_quorum_met(&self, threshold: &Threshold) -> Option<bool> {
+        match *threshold {
+            Threshold::ThresholdQuorum { quorum, .. } => Some(does_vote_count_pass(
+                self.votes.total(),
+                self.total_power,
+                quorum,
+            )),
+            // A composite threshold's quorum is met once the quorums
+            // of all of its thresholds that have one are.
+            Threshold::Composite { ref thresholds } => thresholds
+                .iter()
+                .filter_map(|threshold| self.threshold_quorum_met(threshold))
+                .reduce(|a, b| a && b),
+            Threshold::AbsolutePercentage { .. }
+            | Threshold::AbsoluteCount { .. }
+            | Threshold::DynamicQuorum { .. } => None,
         }
     }
 }
@@ -280,6 +297,8 @@ mod test {
         testing::{mock_dependencies, mock_env},
         Decimal,
     };
+    use dao_voting::threshold::PercentageThreshold;
+    use dao_voting::voting::Vote;
 
     fn setup_prop(
         threshold: Threshold,
@@ -313,6 +332,15 @@ mod test {
             veto: None,
             total_power,
             votes,
+            review_period: None,
+            revision: 0,
+            late_vote_extension: None,
+            extensions: 0,
+            conditions: vec![],
+            dependencies: vec![],
+            threshold_rule: None,
+            vetoes: vec![],
+            emergency: false,
         };
         (prop, block)
     }
@@ -1127,6 +1155,210 @@ mod test {
         ));
     }
 
+    /// Majority of votes cast with a 20% quorum and at least three
+    /// yes votes.
+    fn composite_threshold() -> Threshold {
+        Threshold::Composite {
+            thresholds: vec![
+                Threshold::ThresholdQuorum {
+                    threshold: PercentageThreshold::Majority {},
+                    quorum: PercentageThreshold::Percent(Decimal::percent(20)),
+                },
+                Threshold::AbsoluteCount {
+                    threshold: Uint128::new(3),
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_composite_threshold_pass() {
+        // Three of five vote yes, all thresholds are met.
+        assert!(check_is_passed(
+            composite_threshold(),
+            Votes {
+                yes: Uint128::new(3),
+                no: Uint128::zero(),
+                abstain: Uint128::zero(),
+            },
+            Uint128::new(5),
+            false,
+            true,
+            false
+        ));
+
+        // A majority of all voting power but only two yes votes. The
+        // proposal may still pass if the last member votes yes.
+        let votes = Votes {
+            yes: Uint128::new(2),
+            no: Uint128::zero(),
+            abstain: Uint128::zero(),
+        };
+        assert!(!check_is_passed(
+            composite_threshold(),
+            votes.clone(),
+            Uint128::new(3),
+            false,
+            true,
+            false
+        ));
+        assert!(!check_is_rejected(
+            composite_threshold(),
+            votes.clone(),
+            Uint128::new(3),
+            false,
+            true,
+            false
+        ));
+
+        // Once expired, a majority of votes cast with quorum is not
+        // enough without the minimum number of yes votes.
+        assert!(!check_is_passed(
+            composite_threshold(),
+            votes,
+            Uint128::new(10),
+            true,
+            true,
+            false
+        ));
+        assert!(check_is_passed(
+            composite_threshold(),
+            Votes {
+                yes: Uint128::new(3),
+                no: Uint128::new(1),
+                abstain: Uint128::zero(),
+            },
+            Uint128::new(10),
+            true,
+            true,
+            false
+        ));
+
+        // Thresholds are not checked before the min voting period
+        // has elapsed.
+        assert!(!check_is_passed(
+            composite_threshold(),
+            Votes {
+                yes: Uint128::new(5),
+                no: Uint128::zero(),
+                abstain: Uint128::zero(),
+            },
+            Uint128::new(5),
+            false,
+            false,
+            false
+        ));
+    }
+
+    #[test]
+    fn test_composite_threshold_rejection() {
+        // A majority votes no, so the majority threshold can not be
+        // met though three yes votes are still possible.
+        assert!(check_is_rejected(
+            composite_threshold(),
+            Votes {
+                yes: Uint128::zero(),
+                no: Uint128::new(6),
+                abstain: Uint128::zero(),
+            },
+            Uint128::new(10),
+            false,
+            true,
+            false
+        ));
+
+        // Abstentions leave too little power for three yes votes.
+        assert!(check_is_rejected(
+            composite_threshold(),
+            Votes {
+                yes: Uint128::new(1),
+                no: Uint128::zero(),
+                abstain: Uint128::new(8),
+            },
+            Uint128::new(10),
+            false,
+            true,
+            false
+        ));
+
+        // Both thresholds may still be met.
+        assert!(!check_is_rejected(
+            composite_threshold(),
+            Votes {
+                yes: Uint128::new(3),
+                no: Uint128::new(2),
+                abstain: Uint128::zero(),
+            },
+            Uint128::new(10),
+            false,
+            true,
+            false
+        ));
+
+        // Expired without meeting quorum.
+        assert!(check_is_rejected(
+            composite_threshold(),
+            Votes {
+                yes: Uint128::new(1),
+                no: Uint128::zero(),
+                abstain: Uint128::zero(),
+            },
+            Uint128::new(10),
+            true,
+            true,
+            false
+        ));
+    }
+
+    #[test]
+    fn test_composite_threshold_revoting() {
+        let all_yes = Votes {
+            yes: Uint128::new(10),
+            no: Uint128::zero(),
+            abstain: Uint128::zero(),
+        };
+        let all_no = Votes {
+            yes: Uint128::zero(),
+            no: Uint128::new(10),
+            abstain: Uint128::zero(),
+        };
+
+        // With revoting, nothing is known until the proposal
+        // expires.
+        assert!(!check_is_passed(
+            composite_threshold(),
+            all_yes.clone(),
+            Uint128::new(10),
+            false,
+            true,
+            true
+        ));
+        assert!(check_is_passed(
+            composite_threshold(),
+            all_yes,
+            Uint128::new(10),
+            true,
+            true,
+            true
+        ));
+        assert!(!check_is_rejected(
+            composite_threshold(),
+            all_no.clone(),
+            Uint128::new(10),
+            false,
+            true,
+            true
+        ));
+        assert!(check_is_rejected(
+            composite_threshold(),
+            all_no,
+            Uint128::new(10),
+            true,
+            true,
+            true
+        ));
+    }
+
     #[test]
     fn test_proposal_ids_advance() {
         // do they advance, lets find out!
@@ -1143,4 +1375,131 @@ mod test {
         let now = advance_proposal_id(storage).unwrap();
         assert_eq!(now, next);
     }
+
+    #[test]
+    fn test_review_period_holds_status_open() {
+        let threshold = Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Majority {},
+        };
+        // Zero power means the proposal would otherwise be rejected
+        // right away.
+        let (mut prop, block) = setup_prop(
+            threshold,
+            Votes::zero(),
+            Uint128::zero(),
+            false,
+            true,
+            false,
+        );
+        prop.review_period = Some(Expiration::AtHeight(block.height + 1));
+        assert!(prop.is_in_review(&block));
+        assert_eq!(prop.current_status(&block).unwrap(), Status::Open);
+
+        // Once the review period ends the usual rules apply.
+        prop.review_period = Some(Expiration::AtHeight(block.height));
+        assert!(!prop.is_in_review(&block));
+        assert_eq!(prop.current_status(&block).unwrap(), Status::Rejected);
+
+        prop.review_period = None;
+        assert!(!prop.is_in_review(&block));
+    }
+
+    #[test]
+    fn test_late_vote_extension() {
+        let threshold = Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Majority {},
+        };
+        let (mut prop, block) = setup_prop(
+            threshold,
+            Votes {
+                yes: Uint128::new(3),
+                no: Uint128::new(4),
+                abstain: Uint128::zero(),
+            },
+            Uint128::new(15),
+            false,
+            true,
+            false,
+        );
+        prop.late_vote_extension = Some(LateVoteExtensionConfig {
+            window: secret_utils::Duration::Height(100),
+            extension: secret_utils::Duration::Height(10),
+            max_extensions: 1,
+        });
+        let expiration = prop.expiration;
+
+        // Outcome unchanged, no extension.
+        let previous = prop.outcome(&block);
+        assert!(!prop.apply_late_vote_extension(&block, previous).unwrap());
+
+        // Yes takes the lead inside the window, extension applied.
+        prop.votes.add_vote(Vote::Yes, Uint128::new(2));
+        assert!(prop.apply_late_vote_extension(&block, previous).unwrap());
+        assert_eq!(
+            prop.expiration,
+            expiration.add(secret_utils::Duration::Height(10)).unwrap()
+        );
+        assert_eq!(prop.extensions, 1);
+
+        // Maximum number of extensions reached.
+        let previous = prop.outcome(&block);
+        prop.votes.add_vote(Vote::No, Uint128::new(4));
+        assert!(!prop.apply_late_vote_extension(&block, previous).unwrap());
+        assert_eq!(prop.extensions, 1);
+    }
+
+    #[test]
+    fn test_quorum_met() {
+        let votes = Votes {
+            yes: Uint128::new(1),
+            no: Uint128::new(1),
+            abstain: Uint128::zero(),
+        };
+        let quorum = |percent| Threshold::ThresholdQuorum {
+            threshold: PercentageThreshold::Majority {},
+            quorum: PercentageThreshold::Percent(Decimal::percent(percent)),
+        };
+        let quorum_met = |threshold| {
+            let (prop, _) = setup_prop(
+                threshold,
+                votes.clone(),
+                Uint128::new(10),
+                true,
+                true,
+                false,
+            );
+            prop.quorum_met()
+        };
+
+        // 2 of 10 voted.
+        assert_eq!(quorum_met(quorum(20)), Some(true));
+        assert_eq!(quorum_met(quorum(30)), Some(false));
+
+        // Thresholds without a quorum report none.
+        let count = Threshold::AbsoluteCount {
+            threshold: Uint128::new(1),
+        };
+        assert_eq!(quorum_met(count.clone()), None);
+
+        // A composite threshold's quorum is met once all of its
+        // quorums are.
+        assert_eq!(
+            quorum_met(Threshold::Composite {
+                thresholds: vec![count.clone(), quorum(20)]
+            }),
+            Some(true)
+        );
+        assert_eq!(
+            quorum_met(Threshold::Composite {
+                thresholds: vec![quorum(20), quorum(30)]
+            }),
+            Some(false)
+        );
+        assert_eq!(
+            quorum_met(Threshold::Composite {
+                thresholds: vec![count]
+            }),
+            None
+        );
+    }
 }
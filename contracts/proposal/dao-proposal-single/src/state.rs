@@ -2,16 +2,19 @@ use cosmwasm_std::{Addr, Uint128};
 use cw_hooks::Hooks;
 use dao_interface::state::AnyContractInfo;
 use dao_voting::{
-    pre_propose::ProposalCreationPolicy, threshold::Threshold, veto::VetoConfig, voting::Vote,
+    late_vote_extension::LateVoteExtensionConfig, pre_propose::ProposalCreationPolicy,
+    threshold::Threshold, turnout::TurnoutHistory, veto::VetoConfig, voting::Vote,
 };
 use schemars::JsonSchema;
 use secret_cw_controllers::ReplyIds;
 use secret_storage_plus::Item;
 use secret_toolkit::{serialization::Json, storage::Keymap};
-use secret_utils::Duration;
+use secret_utils::{Duration, Expiration};
 use serde::{Deserialize, Serialize};
 
+use crate::emergency::EmergencyConfig;
 use crate::proposal::SingleChoiceProposal;
+use crate::threshold_rules::ThresholdRule;
 
 /// A vote cast for a proposal.
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
@@ -64,6 +67,24 @@ pub struct Config {
     /// Optional veto configuration. If set to `None`, veto option
     /// is disabled. Otherwise contains the configuration for veto flow.
     pub veto: Option<VetoConfig>,
+    /// An optional period at the start of each proposal during which
+    /// the proposer may amend or withdraw it. Voting does not open
+    /// until this period has elapsed. Must have the same units as
+    /// `max_voting_period`.
+    #[serde(default)]
+    pub review_period: Option<Duration>,
+    /// Optional configuration for extending a proposal's voting
+    /// period when a late vote swings its outcome. If set to `None`,
+    /// proposals are never extended.
+    #[serde(default)]
+    pub late_vote_extension: Option<LateVoteExtensionConfig>,
+    /// If set to true proposals will be closed if one of their
+    /// execution conditions does not hold, or one of their
+    /// dependencies will never be executed, when they are executed.
+    /// Otherwise, execution is refused and proposals remain
+    /// executable until their conditions hold.
+    #[serde(default)]
+    pub close_proposal_on_condition_failure: bool,
 }
 
 /// The current top level config for the module.  The "config" key was
@@ -80,5 +101,17 @@ pub const VOTE_HOOKS: Hooks = Hooks::new("vote_hooks");
 /// The address of the pre-propose module associated with this
 /// proposal module (if any).
 pub const CREATION_POLICY: Item<ProposalCreationPolicy> = Item::new("creation_policy");
+/// Rules applying stricter voting requirements to proposals
+/// containing sensitive messages.
+pub const THRESHOLD_RULES: Item<Vec<ThresholdRule>> = Item::new("threshold_rules");
+/// Configuration of emergency proposals. Emergency proposals are
+/// disabled if not set.
+pub const EMERGENCY_CONFIG: Item<EmergencyConfig> = Item::new("emergency_config");
+/// When each recent emergency proposal stops counting against the
+/// emergency proposal rate limit.
+pub const EMERGENCY_PROPOSALS: Item<Vec<Expiration>> = Item::new("emergency_proposals");
+/// Turnout of recently completed proposals, from which dynamic
+/// quorums are computed.
+pub const TURNOUT: Item<TurnoutHistory> = Item::new("turnout");
 pub const DAO: Item<AnyContractInfo> = Item::new("dao");
 pub const REPLY_IDS: ReplyIds = ReplyIds::new(b"reply_ids", b"reply_ids_count");
@@ -0,0 +1,548 @@
+use cosmwasm_std::testing::{
+    mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+};
+use cosmwasm_std::{
+    coins, from_binary, to_binary, Addr, BankMsg, ContractResult, CosmosMsg, Decimal, Env,
+    OwnedDeps, Reply, ReplyOn, Response, SubMsgResponse, SubMsgResult, SystemResult, Uint128,
+    WasmMsg, WasmQuery,
+};
+use dao_hooks::proposal::{PreProposeHookMsg, ProposalHookExecuteMsg, ProposalHookMsg};
+use dao_interface::state::VotingModuleInfo;
+use dao_interface::voting::{
+    Query as VotingQuery, TotalPowerAtHeightResponse, VotingPowerAtHeightResponse,
+};
+use dao_voting::pre_propose::{PreProposeInfo, ProposalCreationPolicy};
+use dao_voting::proposal::SingleChoiceProposeMsg;
+use dao_voting::status::Status;
+use dao_voting::threshold::{PercentageThreshold, QuorumAdjustment, Threshold};
+use dao_voting::turnout::DynamicQuorumResponse;
+use dao_voting::voting::Vote;
+use secret_utils::Duration;
+
+use crate::contract::{execute, instantiate, query, reply};
+use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+use crate::proposal::SingleChoiceProposal;
+use crate::query::{ProposalResponse, VoteInfo, VoteListResponse};
+use crate::state::CREATION_POLICY;
+use crate::ContractError;
+
+type Deps = OwnedDeps<MockStorage, MockApi, MockQuerier>;
+
+/// The height proposals are created at.
+const START: u64 = 12_345;
+
+/// Answers the DAO's queries. Alice has 600 of the 1000 voting power
+/// and Bob 300. Queries made with a key other than "key" fail, as
+/// does the voting module's `IsActive` query, so the DAO is active.
+fn mock_dao(deps: &mut Deps) {
+    deps.querier.update_wasm(|query| {
+        let WasmQuery::Smart { msg, .. } = query else {
+            panic!("unexpected query");
+        };
+        let res = match from_binary(msg) {
+            Ok(VotingQuery::VotingPowerAtHeight {
+                address,
+                key,
+                height,
+            }) => {
+                if key != "key" {
+                    return SystemResult::Ok(ContractResult::Err("wrong viewing key".to_string()));
+                }
+                let power = match address.as_str() {
+                    "alice" => 600,
+                    "bob" => 300,
+                    _ => 0,
+                };
+                to_binary(&VotingPowerAtHeightResponse {
+                    power: Uint128::new(power),
+                    height: height.unwrap_or(START),
+                })
+            }
+            Ok(VotingQuery::TotalPowerAtHeight { height }) => {
+                to_binary(&TotalPowerAtHeightResponse {
+                    power: Uint128::new(1000),
+                    height: height.unwrap_or(START),
+                })
+            }
+            Ok(VotingQuery::IsActive {}) => {
+                return SystemResult::Ok(ContractResult::Err("not implemented".to_string()))
+            }
+            Ok(_) => panic!("unexpected query"),
+            Err(_) => match from_binary(msg).unwrap() {
+                dao_interface::msg::QueryMsg::VotingModule {} => to_binary(&VotingModuleInfo {
+                    addr: Addr::unchecked("voting"),
+                    code_hash: "voting_hash".to_string(),
+                }),
+                _ => panic!("unexpected query"),
+            },
+        };
+        SystemResult::Ok(ContractResult::Ok(res.unwrap()))
+    });
+}
+
+/// Sets up a module with a 5 block review period and a 10 block
+/// voting period, that "pre_propose" creates proposals in. "hook"
+/// receives proposal hooks.
+fn setup() -> Deps {
+    setup_with(|_| ())
+}
+
+/// As above, with the instantiate message modified by CONFIGURE.
+fn setup_with(configure: impl FnOnce(&mut InstantiateMsg)) -> Deps {
+    let mut deps = mock_dependencies();
+    mock_dao(&mut deps);
+    let mut msg = InstantiateMsg {
+        threshold: Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Majority {},
+        },
+        max_voting_period: Duration::Height(10),
+        min_voting_period: None,
+        only_members_execute: false,
+        allow_revoting: false,
+        pre_propose_info: PreProposeInfo::AnyoneMayPropose {},
+        close_proposal_on_execution_failure: true,
+        veto: None,
+        review_period: Some(Duration::Height(5)),
+        late_vote_extension: None,
+        close_proposal_on_condition_failure: false,
+        threshold_rules: vec![],
+        emergency: None,
+        dao_code_hash: "dao_hash".to_string(),
+    };
+    configure(&mut msg);
+    instantiate(deps.as_mut(), mock_env(), mock_info("dao", &[]), msg).unwrap();
+    CREATION_POLICY
+        .save(
+            deps.as_mut().storage,
+            &ProposalCreationPolicy::Module {
+                addr: Addr::unchecked("pre_propose"),
+                code_hash: "pre_propose_hash".to_string(),
+            },
+        )
+        .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("dao", &[]),
+        ExecuteMsg::AddProposalHook {
+            address: "hook".to_string(),
+            code_hash: "hook_hash".to_string(),
+        },
+    )
+    .unwrap();
+    deps
+}
+
+fn env_at(height: u64) -> Env {
+    let mut env = mock_env();
+    env.block.height = height;
+    env
+}
+
+/// Has the pre-propose module create a proposal by PROPOSER.
+fn propose(deps: &mut Deps, proposer: &str) -> Result<Response, ContractError> {
+    propose_at(deps, START, proposer)
+}
+
+fn propose_at(deps: &mut Deps, height: u64, proposer: &str) -> Result<Response, ContractError> {
+    execute(
+        deps.as_mut(),
+        env_at(height),
+        mock_info("pre_propose", &[]),
+        ExecuteMsg::Propose(SingleChoiceProposeMsg {
+            title: "title".to_string(),
+            description: "description".to_string(),
+            msgs: vec![],
+            proposer: Some(proposer.to_string()),
+            conditions: vec![],
+            dependencies: vec![],
+            emergency: false,
+        }),
+    )
+}
+
+fn vote(
+    deps: &mut Deps,
+    height: u64,
+    voter: &str,
+    vote: Vote,
+    rationale: &str,
+) -> Result<Response, ContractError> {
+    execute(
+        deps.as_mut(),
+        env_at(height),
+        mock_info(voter, &[]),
+        ExecuteMsg::Vote {
+            key: "key".to_string(),
+            proposal_id: 1,
+            vote,
+            rationale: Some(rationale.to_string()),
+        },
+    )
+}
+
+fn amend(deps: &mut Deps, height: u64, sender: &str) -> Result<Response, ContractError> {
+    execute(
+        deps.as_mut(),
+        env_at(height),
+        mock_info(sender, &[]),
+        ExecuteMsg::AmendProposal {
+            proposal_id: 1,
+            title: "amended".to_string(),
+            description: "amended description".to_string(),
+            msgs: vec![],
+        },
+    )
+}
+
+fn withdraw(deps: &mut Deps, height: u64, sender: &str) -> Result<Response, ContractError> {
+    execute(
+        deps.as_mut(),
+        env_at(height),
+        mock_info(sender, &[]),
+        ExecuteMsg::WithdrawProposal { proposal_id: 1 },
+    )
+}
+
+fn proposal(deps: &Deps, height: u64) -> SingleChoiceProposal {
+    proposal_with_id(deps, height, 1)
+}
+
+fn proposal_with_id(deps: &Deps, height: u64, proposal_id: u64) -> SingleChoiceProposal {
+    let ProposalResponse { proposal, .. } = from_binary(
+        &query(
+            deps.as_ref(),
+            env_at(height),
+            QueryMsg::Proposal { proposal_id },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    proposal
+}
+
+fn votes(deps: &Deps) -> Vec<VoteInfo> {
+    let VoteListResponse { votes } = from_binary(
+        &query(
+            deps.as_ref(),
+            env_at(START),
+            QueryMsg::ListVotes {
+                proposal_id: 1,
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    votes
+}
+
+/// Returns the hook messages sent to HOOK in RES.
+fn hook_msgs<T: serde::de::DeserializeOwned>(res: &Response, hook: &str) -> Vec<T> {
+    res.messages
+        .iter()
+        .filter_map(|sub| match &sub.msg {
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr, msg, ..
+            }) if contract_addr == hook => Some(from_binary(msg).unwrap()),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn test_amend_proposal() {
+    let mut deps = setup();
+    propose(&mut deps, "alice").unwrap();
+
+    for (height, revision) in [(START + 1, 1), (START + 4, 2)] {
+        let res = amend(&mut deps, height, "alice").unwrap();
+        assert_eq!(
+            hook_msgs::<ProposalHookExecuteMsg>(&res, "hook"),
+            vec![ProposalHookExecuteMsg::ProposalHook(
+                ProposalHookMsg::ProposalAmended { id: 1, revision }
+            )]
+        );
+    }
+
+    let prop = proposal(&deps, START + 4);
+    assert_eq!(prop.revision, 2);
+    assert_eq!(prop.title, "amended");
+    assert_eq!(prop.description, "amended description");
+    assert_eq!(prop.status, Status::Open);
+}
+
+#[test]
+fn test_revise_after_review_period() {
+    let mut deps = setup();
+    propose(&mut deps, "alice").unwrap();
+
+    let err = amend(&mut deps, START + 5, "alice").unwrap_err();
+    assert_eq!(err, ContractError::NotInReviewPeriod { id: 1 });
+    let err = withdraw(&mut deps, START + 5, "alice").unwrap_err();
+    assert_eq!(err, ContractError::NotInReviewPeriod { id: 1 });
+
+    // A withdrawn proposal can not be revised either.
+    let mut deps = setup();
+    propose(&mut deps, "alice").unwrap();
+    withdraw(&mut deps, START + 1, "alice").unwrap();
+    let err = amend(&mut deps, START + 2, "alice").unwrap_err();
+    assert_eq!(err, ContractError::NotInReviewPeriod { id: 1 });
+    let err = withdraw(&mut deps, START + 2, "alice").unwrap_err();
+    assert_eq!(err, ContractError::NotInReviewPeriod { id: 1 });
+    assert_eq!(proposal(&deps, START + 2).revision, 0);
+}
+
+#[test]
+fn test_revise_unauthorized() {
+    let mut deps = setup();
+    propose(&mut deps, "alice").unwrap();
+
+    for sender in ["bob", "pre_propose", "dao"] {
+        let err = amend(&mut deps, START + 1, sender).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        let err = withdraw(&mut deps, START + 1, sender).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+    }
+
+    let prop = proposal(&deps, START + 1);
+    assert_eq!(prop.revision, 0);
+    assert_eq!(prop.status, Status::Open);
+}
+
+#[test]
+fn test_withdraw_refunds_deposit() {
+    let mut deps = setup();
+    propose(&mut deps, "alice").unwrap();
+
+    let res = withdraw(&mut deps, START + 1, "alice").unwrap();
+    assert_eq!(proposal(&deps, START + 1).status, Status::Withdrawn);
+    assert_eq!(
+        hook_msgs::<ProposalHookExecuteMsg>(&res, "hook"),
+        vec![ProposalHookExecuteMsg::ProposalHook(
+            ProposalHookMsg::ProposalStatusChanged {
+                id: 1,
+                old_status: Status::Open.to_string(),
+                new_status: Status::Withdrawn.to_string(),
+            }
+        )]
+    );
+    // The pre-propose module is told to settle the deposit.
+    assert_eq!(
+        hook_msgs::<PreProposeHookMsg>(&res, "pre_propose"),
+        vec![PreProposeHookMsg::ProposalCompletedHook {
+            proposal_id: 1,
+            new_status: Status::Withdrawn,
+            quorum_met: None,
+        }]
+    );
+}
+
+/// Tests that a voter's first ballot is stored, and that a revote
+/// replaces it and removes the old vote from the tally.
+#[test]
+fn test_revote_replaces_ballot() {
+    let mut deps = setup_with(|msg| msg.allow_revoting = true);
+    propose(&mut deps, "alice").unwrap();
+
+    vote(&mut deps, START + 5, "alice", Vote::Yes, "yes").unwrap();
+    assert_eq!(
+        votes(&deps),
+        vec![VoteInfo {
+            voter: Addr::unchecked("alice"),
+            vote: Vote::Yes,
+            power: Uint128::new(600),
+            rationale: Some("yes".to_string()),
+        }]
+    );
+
+    let err = vote(&mut deps, START + 6, "alice", Vote::Yes, "yes").unwrap_err();
+    assert_eq!(err, ContractError::AlreadyCast {});
+
+    vote(&mut deps, START + 6, "alice", Vote::No, "no").unwrap();
+    assert_eq!(
+        votes(&deps),
+        vec![VoteInfo {
+            voter: Addr::unchecked("alice"),
+            vote: Vote::No,
+            power: Uint128::new(600),
+            rationale: Some("no".to_string()),
+        }]
+    );
+    let prop = proposal(&deps, START + 6);
+    assert_eq!(prop.votes.yes, Uint128::zero());
+    assert_eq!(prop.votes.no, Uint128::new(600));
+}
+
+/// Tests that a proposal whose turnout falls short of its quorum
+/// lowers the quorum of later proposals.
+#[test]
+fn test_dynamic_quorum_falls_with_turnout() {
+    let mut deps = setup_with(|msg| {
+        msg.threshold = Threshold::DynamicQuorum {
+            threshold: PercentageThreshold::Majority {},
+            min_quorum: Decimal::percent(10),
+            max_quorum: Decimal::percent(50),
+            adjustment: QuorumAdjustment {
+                window: 2,
+                factor: Decimal::one(),
+                initial_quorum: Some(Decimal::percent(40)),
+            },
+        }
+    });
+    let dynamic_quorum = |deps: &Deps| -> DynamicQuorumResponse {
+        from_binary(&query(deps.as_ref(), env_at(START), QueryMsg::DynamicQuorum {}).unwrap())
+            .unwrap()
+    };
+    let quorum = |percent| Threshold::ThresholdQuorum {
+        threshold: PercentageThreshold::Majority {},
+        quorum: PercentageThreshold::Percent(Decimal::percent(percent)),
+    };
+    assert_eq!(dynamic_quorum(&deps).quorum, Some(Decimal::percent(40)));
+
+    propose(&mut deps, "alice").unwrap();
+    assert_eq!(proposal(&deps, START).threshold, quorum(40));
+
+    // Only Bob votes, so turnout is 30% and the proposal is rejected
+    // for falling short of its quorum.
+    vote(&mut deps, START + 5, "bob", Vote::Yes, "yes").unwrap();
+    execute(
+        deps.as_mut(),
+        env_at(START + 15),
+        mock_info("anyone", &[]),
+        ExecuteMsg::Close { proposal_id: 1 },
+    )
+    .unwrap();
+    assert_eq!(proposal(&deps, START + 15).status, Status::Rejected);
+
+    assert_eq!(
+        dynamic_quorum(&deps),
+        DynamicQuorumResponse {
+            quorum: Some(Decimal::percent(30)),
+            average_turnout: Some(Decimal::percent(30)),
+            samples: 1,
+        }
+    );
+    propose_at(&mut deps, START + 15, "alice").unwrap();
+    assert_eq!(proposal_with_id(&deps, START + 15, 2).threshold, quorum(30));
+}
+
+/// Tests that the deposit module is told a proposal completed once
+/// its execution replies, as executed or as failed.
+#[test]
+fn test_execution_reply_sends_completion_hook() {
+    let mut deps = setup();
+    let completed_hook = |proposal_id: u64, new_status: Status| {
+        vec![PreProposeHookMsg::ProposalCompletedHook {
+            proposal_id,
+            new_status,
+            quorum_met: None,
+        }]
+    };
+
+    // Returns the reply ID of the proposal's execution.
+    let pass_and_execute = |deps: &mut Deps, proposal_id: u64| -> u64 {
+        execute(
+            deps.as_mut(),
+            env_at(START),
+            mock_info("pre_propose", &[]),
+            ExecuteMsg::Propose(SingleChoiceProposeMsg {
+                title: "title".to_string(),
+                description: "description".to_string(),
+                msgs: vec![CosmosMsg::Bank(BankMsg::Send {
+                    to_address: "alice".to_string(),
+                    amount: coins(1, "ujuno"),
+                })],
+                proposer: Some("alice".to_string()),
+                conditions: vec![],
+                dependencies: vec![],
+                emergency: false,
+            }),
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            env_at(START + 5),
+            mock_info("alice", &[]),
+            ExecuteMsg::Vote {
+                key: "key".to_string(),
+                proposal_id,
+                vote: Vote::Yes,
+                rationale: None,
+            },
+        )
+        .unwrap();
+        let res = execute(
+            deps.as_mut(),
+            env_at(START + 6),
+            mock_info("alice", &[]),
+            ExecuteMsg::Execute {
+                key: "key".to_string(),
+                proposal_id,
+            },
+        )
+        .unwrap();
+        // Completion waits on the reply.
+        assert!(hook_msgs::<PreProposeHookMsg>(&res, "pre_propose").is_empty());
+        let execution = res
+            .messages
+            .iter()
+            .find(|sub| sub.reply_on == ReplyOn::Always)
+            .unwrap();
+        execution.id
+    };
+
+    // The deposit module hears of the proposal once it executes.
+    let reply_id = pass_and_execute(&mut deps, 1);
+    let res = reply(
+        deps.as_mut(),
+        env_at(START + 6),
+        Reply {
+            id: reply_id,
+            result: SubMsgResult::Ok(SubMsgResponse {
+                events: vec![],
+                data: None,
+            }),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        hook_msgs::<PreProposeHookMsg>(&res, "pre_propose"),
+        completed_hook(1, Status::Executed)
+    );
+    assert_eq!(
+        proposal_with_id(&deps, START + 6, 1).status,
+        Status::Executed
+    );
+
+    // Or once its execution fails.
+    let reply_id = pass_and_execute(&mut deps, 2);
+    let res = reply(
+        deps.as_mut(),
+        env_at(START + 6),
+        Reply {
+            id: reply_id,
+            result: SubMsgResult::Err("error".to_string()),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        hook_msgs::<PreProposeHookMsg>(&res, "pre_propose"),
+        completed_hook(2, Status::ExecutionFailed)
+    );
+    assert_eq!(
+        hook_msgs::<ProposalHookExecuteMsg>(&res, "hook"),
+        vec![ProposalHookExecuteMsg::ProposalHook(
+            ProposalHookMsg::ProposalStatusChanged {
+                id: 2,
+                old_status: Status::Executed.to_string(),
+                new_status: Status::ExecutionFailed.to_string(),
+            }
+        )]
+    );
+    assert_eq!(
+        proposal_with_id(&deps, START + 6, 2).status,
+        Status::ExecutionFailed
+    );
+}
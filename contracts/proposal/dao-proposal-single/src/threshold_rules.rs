@@ -0,0 +1,531 @@
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+
+use cosmwasm_std::{from_binary, Addr, BankMsg, Binary, CosmosMsg, Deps, Empty, Uint128, WasmMsg};
+use dao_voting::{threshold::Threshold, veto::VetoConfig, voting::validate_voting_period};
+use schemars::JsonSchema;
+use secret_utils::Duration;
+use serde::{de::IgnoredAny, Deserialize, Serialize};
+
+use crate::{state::Config, ContractError};
+
+/// The maximum number of threshold rules a module may have.
+pub const MAX_THRESHOLD_RULES: usize = 20;
+
+/// Describes the messages a threshold rule applies to.
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum MessageMatcher {
+    /// Matches messages executing one of `variants` on the DAO's
+    /// core contract. Variants are given as they are serialized, for
+    /// example `update_voting_module` or `update_proposal_modules`.
+    CoreExecute { variants: Vec<String> },
+    /// Matches any `WasmMsg::Migrate`.
+    Migrate {},
+    /// Matches proposals sending more than `amount` of `denom` in
+    /// total, through bank sends or funds attached to contract
+    /// messages.
+    BankSendOver { denom: String, amount: Uint128 },
+    /// Matches proposals whose SNIP-20 `transfer`, `send`,
+    /// `transfer_from`, and `send_from` messages, and their batch
+    /// versions, move more than `amount` of `token` in total.
+    Snip20TransferOver { token: String, amount: Uint128 },
+    /// Matches any execute or migrate message sent to `contract`.
+    Contract { contract: String },
+}
+
+/// A rule that applies stricter voting requirements to proposals
+/// containing matching messages.
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub struct ThresholdRule {
+    /// The messages this rule applies to.
+    pub matches: MessageMatcher,
+    /// The threshold matching proposals must reach. Must be of the
+    /// same kind as, and at least as strict as, the module's
+    /// threshold.
+    pub threshold: Option<Threshold>,
+    /// The maximum voting period of matching proposals. Must be at
+    /// least as long as the module's max voting period.
+    pub max_voting_period: Option<Duration>,
+    /// A veto timelock matching proposals must go through. Replaces
+    /// the module's veto configuration for matching proposals.
+    pub veto: Option<VetoConfig>,
+}
+
+/// The SNIP-20 messages that move tokens. Other fields of these
+/// messages are ignored.
+#[derive(Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum Snip20Transfer {
+    Transfer { amount: Uint128 },
+    Send { amount: Uint128 },
+    TransferFrom { amount: Uint128 },
+    SendFrom { amount: Uint128 },
+    BatchTransfer { actions: Vec<Snip20Action> },
+    BatchSend { actions: Vec<Snip20Action> },
+    BatchTransferFrom { actions: Vec<Snip20Action> },
+    BatchSendFrom { actions: Vec<Snip20Action> },
+}
+
+/// An action of a SNIP-20 batch message.
+#[derive(Deserialize)]
+struct Snip20Action {
+    amount: Uint128,
+}
+
+/// Returns the name of the variant an execute message invokes, that
+/// is the only key of its outermost JSON object. The message is
+/// decoded so that escaped keys are compared as the contract
+/// receiving the message reads them.
+fn execute_variant(msg: &Binary) -> Option<String> {
+    let object: BTreeMap<String, IgnoredAny> = from_binary(msg).ok()?;
+    let mut keys = object.into_keys();
+    match (keys.next(), keys.next()) {
+        (Some(variant), None) => Some(variant),
+        _ => None,
+    }
+}
+
+/// Compares two durations. Returns None if they have different units.
+fn compare_durations(a: &Duration, b: &Duration) -> Option<Ordering> {
+    match (a, b) {
+        (Duration::Height(a), Duration::Height(b)) | (Duration::Time(a), Duration::Time(b)) => {
+            Some(a.cmp(b))
+        }
+        _ => None,
+    }
+}
+
+fn at_least(ordering: Option<Ordering>) -> bool {
+    matches!(ordering, Some(Ordering::Greater | Ordering::Equal))
+}
+
+/// The amount of DENOM MSG sends, either by a bank send or as funds
+/// attached to a contract message.
+fn native_sent(msg: &CosmosMsg<Empty>, denom: &str) -> Uint128 {
+    let coins = match msg {
+        CosmosMsg::Bank(BankMsg::Send { amount, .. }) => amount,
+        CosmosMsg::Wasm(WasmMsg::Execute { funds, .. } | WasmMsg::Instantiate { funds, .. }) => {
+            funds
+        }
+        _ => return Uint128::zero(),
+    };
+    coins
+        .iter()
+        .filter(|coin| coin.denom == denom)
+        .fold(Uint128::zero(), |total, coin| {
+            total.saturating_add(coin.amount)
+        })
+}
+
+/// The amount of TOKEN MSG moves with a SNIP-20 transfer or send.
+fn snip20_sent(msg: &CosmosMsg<Empty>, token: &str) -> Uint128 {
+    match msg {
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr, msg, ..
+        }) if contract_addr == token => match from_binary::<Snip20Transfer>(msg) {
+            Ok(
+                Snip20Transfer::Transfer { amount }
+                | Snip20Transfer::Send { amount }
+                | Snip20Transfer::TransferFrom { amount }
+                | Snip20Transfer::SendFrom { amount },
+            ) => amount,
+            Ok(
+                Snip20Transfer::BatchTransfer { actions }
+                | Snip20Transfer::BatchSend { actions }
+                | Snip20Transfer::BatchTransferFrom { actions }
+                | Snip20Transfer::BatchSendFrom { actions },
+            ) => actions.iter().fold(Uint128::zero(), |total, action| {
+                total.saturating_add(action.amount)
+            }),
+            Err(_) => Uint128::zero(),
+        },
+        _ => Uint128::zero(),
+    }
+}
+
+impl MessageMatcher {
+    /// Returns true if a proposal with MSGS matches. Transfers are
+    /// totalled across all of the proposal's messages, so splitting a
+    /// large transfer into several smaller ones does not avoid a
+    /// rule.
+    pub fn matches(&self, dao: &Addr, msgs: &[CosmosMsg<Empty>]) -> bool {
+        match self {
+            MessageMatcher::BankSendOver { denom, amount } => {
+                msgs.iter().fold(Uint128::zero(), |total, msg| {
+                    total.saturating_add(native_sent(msg, denom))
+                }) > *amount
+            }
+            MessageMatcher::Snip20TransferOver { token, amount } => {
+                msgs.iter().fold(Uint128::zero(), |total, msg| {
+                    total.saturating_add(snip20_sent(msg, token))
+                }) > *amount
+            }
+            _ => msgs.iter().any(|msg| self.matches_msg(dao, msg)),
+        }
+    }
+
+    /// Returns true if MSG matches. Only used for matchers that look
+    /// at messages one at a time.
+    fn matches_msg(&self, dao: &Addr, msg: &CosmosMsg<Empty>) -> bool {
+        match (self, msg) {
+            (
+                MessageMatcher::CoreExecute { variants },
+                CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr, msg, ..
+                }),
+            ) => {
+                contract_addr == dao.as_str()
+                    && execute_variant(msg)
+                        .map_or(false, |variant| variants.iter().any(|v| *v == variant))
+            }
+            (MessageMatcher::Migrate {}, CosmosMsg::Wasm(WasmMsg::Migrate { .. })) => true,
+            (
+                MessageMatcher::Contract { contract },
+                CosmosMsg::Wasm(
+                    WasmMsg::Execute { contract_addr, .. } | WasmMsg::Migrate { contract_addr, .. },
+                ),
+            ) => contract_addr == contract,
+            _ => false,
+        }
+    }
+}
+
+impl ThresholdRule {
+    /// Validates the rule against the module's configuration. Rules
+    /// may only make voting requirements stricter.
+    pub fn validate(&self, deps: Deps, config: &Config, index: usize) -> Result<(), ContractError> {
+        match &self.matches {
+            MessageMatcher::CoreExecute { variants } if variants.is_empty() => {
+                return Err(ContractError::InvalidThresholdRuleMatcher { index })
+            }
+            MessageMatcher::Snip20TransferOver { token: addr, .. }
+            | MessageMatcher::Contract { contract: addr } => {
+                deps.api.addr_validate(addr)?;
+            }
+            _ => (),
+        }
+
+        if self.threshold.is_none() && self.max_voting_period.is_none() && self.veto.is_none() {
+            return Err(ContractError::EmptyThresholdRule { index });
+        }
+
+        if let Some(threshold) = &self.threshold {
+            threshold.validate()?;
+            if !at_least(threshold.strictness_cmp(&config.threshold)) {
+                return Err(ContractError::ThresholdRuleNotStricter { index });
+            }
+        }
+
+        if let Some(max_voting_period) = &self.max_voting_period {
+            validate_voting_period(config.min_voting_period, *max_voting_period)?;
+            if !at_least(compare_durations(
+                max_voting_period,
+                &config.max_voting_period,
+            )) {
+                return Err(ContractError::ThresholdRuleNotStricter { index });
+            }
+        }
+
+        if let Some(veto) = &self.veto {
+            let max_voting_period = self.max_voting_period.unwrap_or(config.max_voting_period);
+            veto.validate(&deps, &max_voting_period)?;
+            if let Some(module_veto) = &config.veto {
+                if !at_least(compare_durations(
+                    &veto.timelock_duration,
+                    &module_veto.timelock_duration,
+                )) {
+                    return Err(ContractError::ThresholdRuleNotStricter { index });
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the threshold, max voting period, and veto
+    /// configuration of proposals this rule applies to.
+    pub fn apply(
+        rule: Option<&ThresholdRule>,
+        config: &Config,
+    ) -> (Threshold, Duration, Option<VetoConfig>) {
+        match rule {
+            Some(rule) => (
+                rule.threshold
+                    .clone()
+                    .unwrap_or_else(|| config.threshold.clone()),
+                rule.max_voting_period.unwrap_or(config.max_voting_period),
+                rule.veto.clone().or_else(|| config.veto.clone()),
+            ),
+            None => (
+                config.threshold.clone(),
+                config.max_voting_period,
+                config.veto.clone(),
+            ),
+        }
+    }
+
+    /// Compares how strict two rules are. Rules are compared by
+    /// threshold, then by max voting period, and then by veto
+    /// timelock. Incomparable values are treated as equal.
+    fn strictness_cmp(&self, other: &Self, config: &Config) -> Ordering {
+        let (threshold_a, period_a, veto_a) = ThresholdRule::apply(Some(self), config);
+        let (threshold_b, period_b, veto_b) = ThresholdRule::apply(Some(other), config);
+        let timelocks = match (veto_a, veto_b) {
+            (Some(a), Some(b)) => compare_durations(&a.timelock_duration, &b.timelock_duration)
+                .unwrap_or(Ordering::Equal),
+            (a, b) => a.is_some().cmp(&b.is_some()),
+        };
+        threshold_a
+            .strictness_cmp(&threshold_b)
+            .unwrap_or(Ordering::Equal)
+            .then(compare_durations(&period_a, &period_b).unwrap_or(Ordering::Equal))
+            .then(timelocks)
+    }
+}
+
+/// Validates a module's threshold rules against its configuration.
+pub fn validate_threshold_rules(
+    deps: Deps,
+    config: &Config,
+    rules: &[ThresholdRule],
+) -> Result<(), ContractError> {
+    if rules.len() > MAX_THRESHOLD_RULES {
+        return Err(ContractError::TooManyThresholdRules {
+            max: MAX_THRESHOLD_RULES,
+        });
+    }
+    for (index, rule) in rules.iter().enumerate() {
+        rule.validate(deps, config, index)?;
+    }
+    Ok(())
+}
+
+/// Returns the strictest rule matching `msgs`. If several matching
+/// rules are equally strict, the first is returned.
+pub fn strictest_matching_rule(
+    rules: &[ThresholdRule],
+    config: &Config,
+    dao: &Addr,
+    msgs: &[CosmosMsg<Empty>],
+) -> Option<ThresholdRule> {
+    rules
+        .iter()
+        .filter(|rule| rule.matches.matches(dao, msgs))
+        .fold(
+            None,
+            |strictest: Option<&ThresholdRule>, rule| match strictest {
+                Some(strictest) if strictest.strictness_cmp(rule, config) != Ordering::Less => {
+                    Some(strictest)
+                }
+                _ => Some(rule),
+            },
+        )
+        .cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::{coins, to_binary, Decimal};
+    use dao_voting::threshold::PercentageThreshold;
+
+    fn config() -> Config {
+        Config {
+            threshold: Threshold::AbsolutePercentage {
+                percentage: PercentageThreshold::Majority {},
+            },
+            max_voting_period: Duration::Height(10),
+            min_voting_period: None,
+            only_members_execute: false,
+            allow_revoting: false,
+            close_proposal_on_execution_failure: false,
+            veto: None,
+            review_period: None,
+            late_vote_extension: None,
+            close_proposal_on_condition_failure: false,
+        }
+    }
+
+    fn rule(matches: MessageMatcher, percent: u64) -> ThresholdRule {
+        ThresholdRule {
+            matches,
+            threshold: Some(Threshold::AbsolutePercentage {
+                percentage: PercentageThreshold::Percent(Decimal::percent(percent)),
+            }),
+            max_voting_period: None,
+            veto: None,
+        }
+    }
+
+    #[test]
+    fn test_matches() {
+        let dao = Addr::unchecked("dao");
+        let core_msg = CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "dao".to_string(),
+            code_hash: "hash".to_string(),
+            msg: to_binary(&dao_interface::msg::ExecuteMsg::UpdateConfig {
+                config: dao_interface::state::Config {
+                    dao_uri: None,
+                    name: "name".to_string(),
+                    description: "description".to_string(),
+                    image_url: None,
+                    automatically_add_snip20s: false,
+                    automatically_add_snip721s: false,
+                },
+            })
+            .unwrap(),
+            funds: vec![],
+        });
+        let matcher = MessageMatcher::CoreExecute {
+            variants: vec!["update_config".to_string()],
+        };
+        assert!(matcher.matches(&dao, &[core_msg.clone()]));
+        assert!(!matcher.matches(&Addr::unchecked("other"), &[core_msg]));
+
+        let transfer = CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "token".to_string(),
+            code_hash: "hash".to_string(),
+            msg: Binary::from(br#"{"transfer":{"recipient":"ekez","amount":"11"}}"#.as_slice()),
+            funds: vec![],
+        });
+        let over = |amount: u128| MessageMatcher::Snip20TransferOver {
+            token: "token".to_string(),
+            amount: Uint128::new(amount),
+        };
+        assert!(over(10).matches(&dao, &[transfer.clone()]));
+        assert!(!over(11).matches(&dao, &[transfer.clone()]));
+        // Transfers are totalled across messages.
+        assert!(over(21).matches(&dao, &[transfer.clone(), transfer.clone()]));
+        assert!(!over(22).matches(&dao, &[transfer.clone(), transfer.clone()]));
+
+        let send = CosmosMsg::Bank(BankMsg::Send {
+            to_address: "ekez".to_string(),
+            amount: coins(11, "uscrt"),
+        });
+        let bank_over = MessageMatcher::BankSendOver {
+            denom: "uscrt".to_string(),
+            amount: Uint128::new(10),
+        };
+        assert!(bank_over.matches(&dao, &[send.clone()]));
+        assert!(!bank_over.matches(&dao, &[transfer]));
+
+        // Splitting a send into smaller ones, or attaching the funds to
+        // a contract message, does not avoid the rule.
+        let small_send = CosmosMsg::Bank(BankMsg::Send {
+            to_address: "ekez".to_string(),
+            amount: coins(6, "uscrt"),
+        });
+        assert!(!bank_over.matches(&dao, &[small_send.clone()]));
+        assert!(bank_over.matches(&dao, &[small_send.clone(), small_send.clone()]));
+        let funded = CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "contract".to_string(),
+            code_hash: "hash".to_string(),
+            msg: Binary::default(),
+            funds: coins(6, "uscrt"),
+        });
+        assert!(bank_over.matches(&dao, &[small_send, funded]));
+    }
+
+    #[test]
+    fn test_escaped_core_execute_matches() {
+        let dao = Addr::unchecked("dao");
+        let matcher = MessageMatcher::CoreExecute {
+            variants: vec!["update_voting_module".to_string()],
+        };
+        let core_msg = |msg: &[u8]| {
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "dao".to_string(),
+                code_hash: "hash".to_string(),
+                msg: Binary::from(msg),
+                funds: vec![],
+            })
+        };
+        assert!(matcher.matches(&dao, &[core_msg(br#"{"update_voting_module":{}}"#)]));
+        // Escaping the key does not avoid the rule, as the core
+        // contract decodes it to the same variant.
+        assert!(matcher.matches(
+            &dao,
+            &[core_msg(br#" { "\u0075pdate_voting_module" : {} }"#)]
+        ));
+        assert!(!matcher.matches(&dao, &[core_msg(br#"{"update_config":{}}"#)]));
+        assert!(!matcher.matches(&dao, &[core_msg(b"not json")]));
+    }
+
+    #[test]
+    fn test_snip20_batch_transfers_match() {
+        let dao = Addr::unchecked("dao");
+        let over = MessageMatcher::Snip20TransferOver {
+            token: "token".to_string(),
+            amount: Uint128::new(10),
+        };
+        // A batch message moving AMOUNTS, one per action.
+        let batch = |variant: &str, amounts: &[u128]| {
+            let actions: Vec<String> = amounts
+                .iter()
+                .map(|amount| {
+                    format!(r#"{{"owner":"dao","recipient":"ekez","amount":"{amount}"}}"#)
+                })
+                .collect();
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "token".to_string(),
+                code_hash: "hash".to_string(),
+                msg: Binary::from(
+                    format!(r#"{{"{variant}":{{"actions":[{}]}}}}"#, actions.join(",")).as_bytes(),
+                ),
+                funds: vec![],
+            })
+        };
+        for variant in [
+            "batch_transfer",
+            "batch_send",
+            "batch_transfer_from",
+            "batch_send_from",
+        ] {
+            // Each action stays under the limit, but together they
+            // move more than it.
+            assert!(over.matches(&dao, &[batch(variant, &[6, 5])]), "{variant}");
+            assert!(!over.matches(&dao, &[batch(variant, &[6, 4])]), "{variant}");
+        }
+    }
+
+    #[test]
+    fn test_strictest_matching_rule() {
+        let deps = cosmwasm_std::testing::mock_dependencies();
+        let config = config();
+        let dao = Addr::unchecked("dao");
+        let rules = vec![
+            rule(MessageMatcher::Migrate {}, 60),
+            rule(
+                MessageMatcher::Contract {
+                    contract: "target".to_string(),
+                },
+                75,
+            ),
+        ];
+        validate_threshold_rules(deps.as_ref(), &config, &rules).unwrap();
+
+        let migrate = CosmosMsg::Wasm(WasmMsg::Migrate {
+            contract_addr: "target".to_string(),
+            code_hash: "hash".to_string(),
+            code_id: 1,
+            msg: Binary::default(),
+        });
+        assert_eq!(
+            strictest_matching_rule(&rules, &config, &dao, &[migrate]),
+            Some(rules[1].clone())
+        );
+        assert_eq!(strictest_matching_rule(&rules, &config, &dao, &[]), None);
+
+        // Rules may not loosen the module's threshold.
+        assert_eq!(
+            validate_threshold_rules(
+                deps.as_ref(),
+                &config,
+                &[rule(MessageMatcher::Migrate {}, 50)]
+            ),
+            Err(ContractError::ThresholdRuleNotStricter { index: 0 })
+        );
+    }
+}
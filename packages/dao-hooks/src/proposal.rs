@@ -17,12 +17,26 @@ pub enum ProposalHookMsg {
     NewProposal {
         id: u64,
         proposer: String,
+        /// Set for emergency proposals. Omitted otherwise so that
+        /// consumers unaware of emergency proposals are unaffected.
+        #[serde(default, skip_serializing_if = "is_false")]
+        emergency: bool,
     },
     ProposalStatusChanged {
         id: u64,
         old_status: String,
         new_status: String,
     },
+    /// Fired when a proposer amends a proposal during its review
+    /// period. `revision` is the proposal's new revision.
+    ProposalAmended {
+        id: u64,
+        revision: u64,
+    },
+}
+
+fn is_false(value: &bool) -> bool {
+    !value
 }
 
 /// Prepares new proposal hook messages. These messages reply on error
@@ -33,11 +47,33 @@ pub fn new_proposal_hooks(
     storage: &dyn Storage,
     id: u64,
     proposer: &str,
+) -> StdResult<Vec<SubMsg>> {
+    prepare_new_proposal_hooks(hooks, storage, id, proposer, false)
+}
+
+/// Prepares new proposal hook messages for an emergency proposal.
+/// As above, but the hook message is marked as an emergency.
+pub fn new_emergency_proposal_hooks(
+    hooks: Hooks,
+    storage: &dyn Storage,
+    id: u64,
+    proposer: &str,
+) -> StdResult<Vec<SubMsg>> {
+    prepare_new_proposal_hooks(hooks, storage, id, proposer, true)
+}
+
+fn prepare_new_proposal_hooks(
+    hooks: Hooks,
+    storage: &dyn Storage,
+    id: u64,
+    proposer: &str,
+    emergency: bool,
 ) -> StdResult<Vec<SubMsg>> {
     let msg = to_binary(&ProposalHookExecuteMsg::ProposalHook(
         ProposalHookMsg::NewProposal {
             id,
             proposer: proposer.to_string(),
+            emergency,
         },
     ))?;
 
@@ -96,6 +132,34 @@ pub fn proposal_status_changed_hooks(
     Ok(messages)
 }
 
+/// Prepares proposal amended hook messages. These messages reply on
+/// error and have even reply IDs, as above.
+pub fn proposal_amended_hooks(
+    hooks: Hooks,
+    storage: &dyn Storage,
+    id: u64,
+    revision: u64,
+) -> StdResult<Vec<SubMsg>> {
+    let msg = to_binary(&ProposalHookExecuteMsg::ProposalHook(
+        ProposalHookMsg::ProposalAmended { id, revision },
+    ))?;
+    let mut index: u64 = 0;
+    let messages = hooks.prepare_hooks(storage, |hook_item| {
+        let execute = WasmMsg::Execute {
+            contract_addr: hook_item.addr.to_string(),
+            code_hash: hook_item.code_hash.clone(),
+            msg: msg.clone(),
+            funds: vec![],
+        };
+        let masked_index = mask_proposal_hook_index(index);
+        let tmp = SubMsg::reply_on_error(execute, masked_index);
+        index += 1;
+        Ok(tmp)
+    })?;
+
+    Ok(messages)
+}
+
 /// Message type used for firing hooks to a proposal module's pre-propose
 /// module, if one is installed.
 pub type PreProposeHookMsg = dao_pre_propose_base::msg::ExecuteMsg<Empty, Empty>;
@@ -105,6 +169,31 @@ pub fn proposal_completed_hooks(
     proposal_creation_policy: ProposalCreationPolicy,
     proposal_id: u64,
     new_status: Status,
+) -> StdResult<Vec<SubMsg>> {
+    prepare_proposal_completed_hooks(proposal_creation_policy, proposal_id, new_status, None)
+}
+
+/// Adds prepropose / deposit module hook for a closed proposal. As
+/// above, but reports whether the proposal reached quorum so that
+/// deposit refunds may be graded on it.
+pub fn proposal_closed_hooks(
+    proposal_creation_policy: ProposalCreationPolicy,
+    proposal_id: u64,
+    quorum_met: bool,
+) -> StdResult<Vec<SubMsg>> {
+    prepare_proposal_completed_hooks(
+        proposal_creation_policy,
+        proposal_id,
+        Status::Closed,
+        Some(quorum_met),
+    )
+}
+
+fn prepare_proposal_completed_hooks(
+    proposal_creation_policy: ProposalCreationPolicy,
+    proposal_id: u64,
+    new_status: Status,
+    quorum_met: Option<bool>,
 ) -> StdResult<Vec<SubMsg>> {
     let mut hooks: Vec<SubMsg> = vec![];
     match proposal_creation_policy {
@@ -113,6 +202,7 @@ pub fn proposal_completed_hooks(
             let msg = to_binary(&PreProposeHookMsg::ProposalCompletedHook {
                 proposal_id,
                 new_status,
+                quorum_met,
             })?;
             hooks.push(SubMsg::reply_on_error(
                 WasmMsg::Execute {
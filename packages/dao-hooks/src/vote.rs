@@ -4,7 +4,8 @@ use cw_hooks::Hooks;
 use dao_voting::reply::mask_vote_hook_index;
 use cosmwasm_std::{to_binary, StdResult, Storage, SubMsg, WasmMsg};
 
-/// An enum representing vote hooks, fired when new votes are cast.
+/// An enum representing vote hooks, fired when new votes are cast or
+/// when a late vote extends a proposal's voting period.
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
 #[serde(rename_all = "snake_case")]
 pub enum VoteHookMsg {
@@ -13,6 +14,14 @@ pub enum VoteHookMsg {
         voter: String,
         vote: String,
     },
+    VotingPeriodExtended {
+        proposal_id: u64,
+        /// The proposal's new expiration.
+        expiration: String,
+        /// The number of times the proposal has been extended,
+        /// including this extension.
+        extensions: u64,
+    },
 }
 
 /// Prepares new vote hook messages. These messages reply on error
@@ -45,6 +54,37 @@ pub fn new_vote_hooks(
     })
 }
 
+/// Prepares voting period extension hook messages. These messages
+/// reply on error and use the same reply IDs as new vote hooks.
+pub fn voting_period_extended_hooks(
+    hooks: Hooks,
+    storage: &dyn Storage,
+    proposal_id: u64,
+    expiration: String,
+    extensions: u64,
+) -> StdResult<Vec<SubMsg>> {
+    let msg = to_binary(&VoteHookExecuteMsg::VoteHook(
+        VoteHookMsg::VotingPeriodExtended {
+            proposal_id,
+            expiration,
+            extensions,
+        },
+    ))?;
+    let mut index: u64 = 0;
+    hooks.prepare_hooks(storage, |hook_item| {
+        let execute = WasmMsg::Execute {
+            contract_addr: hook_item.addr.to_string(),
+            code_hash: hook_item.code_hash.clone(),
+            msg: msg.clone(),
+            funds: vec![],
+        };
+        let masked_index = mask_vote_hook_index(index);
+        let tmp = SubMsg::reply_on_error(execute, masked_index);
+        index += 1;
+        Ok(tmp)
+    })
+}
+
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
 #[serde(rename_all = "snake_case")]
 pub enum VoteHookExecuteMsg {
@@ -205,6 +205,10 @@ pub enum QueryMsg {
         start_after: Option<String>,
         limit: Option<u32>,
     },
+    /// Gets the contract's balance of a single snip20 token using the
+    /// viewing key the contract holds for that token.
+    #[returns(crate::query::Snip20BalanceResponse)]
+    Snip20Balance { token: String },
     /// Lists the addresses of the cw20 tokens in this contract's
     /// treasury.
     #[returns(Vec<cosmwasm_std::Addr>)]
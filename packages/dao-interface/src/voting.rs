@@ -1,6 +1,7 @@
 use cosmwasm_schema:: QueryResponses;
 use cosmwasm_std::Uint128;
 use secret_cw2::ContractVersion;
+use secret_toolkit::permit::Permit;
 use serde::{Deserialize, Serialize};
 use schemars::JsonSchema;
 
@@ -37,6 +38,25 @@ pub enum Query {
     IsActive {},
 }
 
+/// A query made with a permit signed by the address being queried,
+/// for voting modules that support query permits.
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum PermitQuery {
+    WithPermit {
+        permit: Permit,
+        query: QueryWithPermit,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryWithPermit {
+    /// Returns the voting power of the permit's signer at a given
+    /// height.
+    VotingPowerAtHeight { address: String, height: u64 },
+}
+
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
 #[serde(rename_all = "snake_case")]
 pub enum ActiveThresholdQuery {
@@ -1,6 +1,6 @@
 use cw_denom::DenomError;
-use cosmwasm_std::StdError;
-use secret_utils::ParseReplyError;
+use cosmwasm_std::{StdError, Uint128};
+use secret_utils::{Expiration, ParseReplyError};
 use thiserror::Error;
 
 use cw_hooks::HookError;
@@ -32,12 +32,36 @@ pub enum PreProposeError {
     #[error("You must be a member of this DAO (have voting power) to create a proposal")]
     NotMember {},
 
+    #[error("Not enough voting power to submit a proposal ({power} including co-sponsors)")]
+    InsufficientPower { power: Uint128 },
+
+    #[error("Co-sponsor ({address}) is listed more than once or is the proposer")]
+    DuplicateCoSponsor { address: String },
+
+    #[error("Permit for co-sponsor ({address}) was not signed by them")]
+    InvalidPermitSigner { address: String },
+
+    #[error("Minimum voting power must be non-zero and at most 100% of total power")]
+    InvalidMinimumPower {},
+
+    #[error("Proposer already has the maximum number of open proposals ({max})")]
+    ProposerLimitReached { max: u32 },
+
+    #[error("The DAO already has the maximum number of open proposals ({max})")]
+    DaoLimitReached { max: u32 },
+
+    #[error("Proposer may not submit another proposal until {available}")]
+    SubmissionCooldown { available: Expiration },
+
     #[error("No denomination for withdrawal. specify a denomination to withdraw")]
     NoWithdrawalDenom {},
 
     #[error("Nothing to withdraw")]
     NothingToWithdraw {},
 
+    #[error("A proposal deposit must be sent with a message to propose")]
+    NoReceiveMsg {},
+
     #[error("Proposal status ({status}) is not completed")]
     NotCompleted { status: Status },
 
@@ -47,6 +71,24 @@ pub enum PreProposeError {
     #[error("Unauthorized")]
     Unauthorized {},
 
+    #[error("Approver has already voted on this proposal")]
+    AlreadyVoted {},
+
+    #[error("Approvers must be unique with non-zero weight and able to meet the threshold")]
+    InvalidCommittee {},
+
+    #[error("Pending proposal has not expired")]
+    PendingProposalNotExpired {},
+
+    #[error("Pending proposal is neither approved nor rejected by the committee")]
+    NotDecided {},
+
+    #[error("Only rejected proposals may be resubmitted")]
+    NotRejected {},
+
+    #[error("No deposit is held for this proposal")]
+    NoHeldDeposit {},
+
     #[error("An unknown reply ID was received.")]
     UnknownReplyID {},
 }
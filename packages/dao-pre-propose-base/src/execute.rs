@@ -1,39 +1,53 @@
 use cosmwasm_schema::schemars::JsonSchema;
 use cosmwasm_std::{
-    to_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult, SubMsg, WasmMsg,
+    from_binary, to_binary, Addr, Binary, BlockInfo, Deps, DepsMut, Env, MessageInfo, Response,
+    StdResult, Storage, SubMsg, Uint128, WasmMsg,
 };
 
 use cw_hooks::HookItem;
 use secret_cw2::set_contract_version;
 
-use cw_denom::UncheckedDenom;
-use dao_interface::voting::{Query as CwCoreQuery, VotingPowerAtHeightResponse};
+use cw_denom::{CheckedDenom, UncheckedDenom};
+use dao_interface::msg::Snip20ReceiveMsg;
+use dao_interface::voting::{
+    PermitQuery, Query as CwCoreQuery, QueryWithPermit, VotingPowerAtHeightResponse,
+};
 use dao_interface::state::AnyContractInfo;
 use dao_voting::{
-    deposit::{DepositRefundPolicy, UncheckedDepositInfo},
+    deposit::{CheckedDepositInfo, DepositError, UncheckedDepositInfo},
     status::Status,
+    voting::{get_total_power, get_voting_power},
 };
-use serde::Serialize;
+use serde::{de::DeserializeOwned, Serialize};
 
 use crate::{
     error::PreProposeError,
-    msg::{DepositInfoResponse, ExecuteMsg, InstantiateMsg, QueryMsg},
-    state::{Config, PreProposeContract},
+    msg::{
+        CoSponsor, DepositInfoResponse, ExecuteMsg, InstantiateMsg, PowerAuth, QueryMsg,
+        ReceiveMsg, SubmissionQuotaResponse,
+    },
+    state::{Config, PreProposeContract, PreProposeSubmissionPolicy, SubmissionLimits},
 };
 
 const CONTRACT_NAME: &str = "crates.io::dao-pre-propose-base";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+/// Storage prefix `secret_toolkit::permit::validate` reads revoked
+/// permits from. Nothing in this module writes it, as permits are
+/// revoked with the voting module they are signed for, so the lookup
+/// always finds no revocations.
+const PREFIX_REVOKED_PERMITS: &str = "revoked_permits";
+
 impl<InstantiateExt, ExecuteExt, QueryExt, ProposalMessage>
     PreProposeContract<InstantiateExt, ExecuteExt, QueryExt, ProposalMessage>
 where
-    ProposalMessage: Serialize,
+    ProposalMessage: Serialize + DeserializeOwned,
     QueryExt: JsonSchema,
 {
     pub fn instantiate(
         &self,
         deps: DepsMut,
-        _env: Env,
+        env: Env,
         info: MessageInfo,
         msg: InstantiateMsg<InstantiateExt>,
     ) -> Result<Response, PreProposeError> {
@@ -68,7 +82,10 @@ where
 
         self.config.save(deps.storage, &config)?;
 
+        let register_receive = register_receive_msg(&env, config.deposit_info.as_ref())?;
+
         Ok(Response::default()
+            .add_messages(register_receive)
             .add_attribute("method", "instantiate")
             .add_attribute("proposal_module", info.sender.into_string())
             .add_attribute("deposit_info", format!("{:?}", config.deposit_info))
@@ -87,13 +104,24 @@ where
         msg: ExecuteMsg<ProposalMessage, ExecuteExt>,
     ) -> Result<Response, PreProposeError> {
         match msg {
-            ExecuteMsg::Propose {key, msg } => self.execute_propose(deps, env, info,key, msg),
+            ExecuteMsg::Propose {
+                key,
+                msg,
+                co_sponsors,
+            } => self.execute_propose(deps, env, info, key, msg, co_sponsors),
+            ExecuteMsg::Receive(wrapper) => self.execute_receive(deps, env, info, wrapper),
             ExecuteMsg::UpdateConfig {
                 deposit_info,
                 open_proposal_submission,
             } => {
                 self.execute_update_config(deps, info, env, deposit_info, open_proposal_submission)
             }
+            ExecuteMsg::UpdateSubmissionLimits { limits } => {
+                self.execute_update_submission_limits(deps, info, limits)
+            }
+            ExecuteMsg::UpdateSubmissionPolicy { policy } => {
+                self.execute_update_submission_policy(deps, info, policy)
+            }
             ExecuteMsg::Withdraw { denom, key } => {
                 self.execute_withdraw(deps.as_ref(), env, info, denom, key)
             }
@@ -106,12 +134,14 @@ where
             ExecuteMsg::ProposalCompletedHook {
                 proposal_id,
                 new_status,
+                quorum_met,
             } => self.execute_proposal_completed_hook(
-                deps.as_ref(),
+                deps,
                 info,
                 env,
                 proposal_id,
                 new_status,
+                quorum_met,
             ),
 
             ExecuteMsg::Extension { .. } => Ok(Response::default()),
@@ -125,13 +155,8 @@ where
         info: MessageInfo,
         key: String,
         msg: ProposalMessage,
+        co_sponsors: Vec<CoSponsor>,
     ) -> Result<Response, PreProposeError> {
-        self.check_can_submit(
-            deps.as_ref(),
-            info.sender.clone(),
-            key,
-        )?;
-
         let config = self.config.load(deps.storage)?;
 
         let deposit_messages = if let Some(ref deposit_info) = config.deposit_info {
@@ -144,6 +169,68 @@ where
             vec![]
         };
 
+        Ok(self
+            .propose(deps, env, info.sender, key, msg, co_sponsors)?
+            .add_messages(deposit_messages))
+    }
+
+    pub fn execute_receive(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        wrapper: Snip20ReceiveMsg,
+    ) -> Result<Response, PreProposeError> {
+        let ReceiveMsg::Propose {
+            key,
+            msg,
+            co_sponsors,
+        } = self.check_receive(deps.as_ref(), &info, &wrapper)?;
+        self.propose(deps, env, wrapper.from, key, msg, co_sponsors)
+    }
+
+    /// Checks that a SNIP-20 `Send` to this module pays the
+    /// configured deposit, and returns the message sent with it.
+    pub fn check_receive<T: DeserializeOwned>(
+        &self,
+        deps: Deps,
+        info: &MessageInfo,
+        wrapper: &Snip20ReceiveMsg,
+    ) -> Result<ReceiveMsg<T>, PreProposeError> {
+        let config = self.config.load(deps.storage)?;
+        config
+            .deposit_info
+            .ok_or(DepositError::InvalidDepositToken {
+                token: info.sender.clone(),
+            })?
+            .check_snip20_deposit_paid(&info.sender, wrapper.amount)?;
+
+        let msg = wrapper.msg.as_ref().ok_or(PreProposeError::NoReceiveMsg {})?;
+        Ok(from_binary(msg)?)
+    }
+
+    /// Creates a proposal on behalf of PROPOSER. The caller is
+    /// responsible for taking the proposal's deposit, if any.
+    pub fn propose(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        proposer: Addr,
+        key: String,
+        msg: ProposalMessage,
+        co_sponsors: Vec<CoSponsor>,
+    ) -> Result<Response, PreProposeError> {
+        self.check_can_submit(
+            deps.as_ref(),
+            &env,
+            proposer.clone(),
+            key,
+            &co_sponsors,
+        )?;
+        self.record_submission(deps.storage, &env.block, &proposer)?;
+
+        let config = self.config.load(deps.storage)?;
+
         let proposal_module_info = self.proposal_module.load(deps.storage)?;
 
         // Snapshot the deposit using the ID of the proposal that we
@@ -156,8 +243,9 @@ where
         self.deposits.insert(
             deps.storage,
             &next_id,
-            &(config.deposit_info, info.sender.clone()),
+            &(config.deposit_info, proposer.clone()),
         )?;
+        self.open_proposals.insert(deps.storage, &next_id, &proposer)?;
 
         let propose_messsage = WasmMsg::Execute {
             contract_addr: proposal_module_info.addr.into_string(),
@@ -180,21 +268,20 @@ where
 
         Ok(Response::default()
             .add_attribute("method", "execute_propose")
-            .add_attribute("sender", info.sender)
+            .add_attribute("sender", proposer)
             // It's important that the propose message is
             // first. Otherwise, a hook receiver could create a
             // proposal before us and invalidate our `NextProposalId
             // {}` query.
             .add_message(propose_messsage)
-            .add_submessages(hooks_msgs)
-            .add_messages(deposit_messages))
+            .add_submessages(hooks_msgs))
     }
 
     pub fn execute_update_config(
         &self,
         deps: DepsMut,
         info: MessageInfo,
-        _env: Env,
+        env: Env,
         deposit_info: Option<UncheckedDepositInfo>,
         open_proposal_submission: bool,
     ) -> Result<Response, PreProposeError> {
@@ -205,6 +292,20 @@ where
             let deposit_info = deposit_info
                 .map(|d| d.into_checked(deps.as_ref(), dao.addr.clone(), dao.code_hash.clone()))
                 .transpose()?;
+
+            // Register with the new deposit token if it changed.
+            let old_denom = self
+                .config
+                .load(deps.storage)?
+                .deposit_info
+                .map(|info| info.denom);
+            let register_receive = match deposit_info {
+                Some(ref info) if Some(&info.denom) != old_denom.as_ref() => {
+                    register_receive_msg(&env, Some(info))?
+                }
+                _ => None,
+            };
+
             self.config.save(
                 deps.storage,
                 &Config {
@@ -214,11 +315,56 @@ where
             )?;
 
             Ok(Response::default()
+                .add_messages(register_receive)
                 .add_attribute("method", "update_config")
                 .add_attribute("sender", info.sender))
         }
     }
 
+    pub fn execute_update_submission_limits(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        limits: SubmissionLimits,
+    ) -> Result<Response, PreProposeError> {
+        let dao = self.dao.load(deps.storage)?;
+        if info.sender != dao.addr {
+            return Err(PreProposeError::NotDao {});
+        }
+        self.submission_limits.save(deps.storage, &limits)?;
+
+        Ok(Response::default()
+            .add_attribute("method", "update_submission_limits")
+            .add_attribute("sender", info.sender))
+    }
+
+    pub fn execute_update_submission_policy(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        policy: Option<PreProposeSubmissionPolicy>,
+    ) -> Result<Response, PreProposeError> {
+        let dao = self.dao.load(deps.storage)?;
+        if info.sender != dao.addr {
+            return Err(PreProposeError::NotDao {});
+        }
+        match policy {
+            Some(policy) => {
+                if let PreProposeSubmissionPolicy::MinimumPower { ref minimum } = policy {
+                    if !minimum.is_valid() {
+                        return Err(PreProposeError::InvalidMinimumPower {});
+                    }
+                }
+                self.submission_policy.save(deps.storage, &policy)?;
+            }
+            None => self.submission_policy.remove(deps.storage),
+        }
+
+        Ok(Response::default()
+            .add_attribute("method", "update_submission_policy")
+            .add_attribute("sender", info.sender))
+    }
+
     pub fn execute_withdraw(
         &self,
         deps: Deps,
@@ -312,11 +458,12 @@ where
 
     pub fn execute_proposal_completed_hook(
         &self,
-        deps: Deps,
+        deps: DepsMut,
         info: MessageInfo,
         _env: Env,
         id: u64,
         new_status: Status,
+        quorum_met: Option<bool>,
     ) -> Result<Response, PreProposeError> {
         let proposal_module = self.proposal_module.load(deps.storage)?;
         if info.sender != proposal_module.addr.clone() {
@@ -330,35 +477,42 @@ where
         // module and open proposal submission to anyone.
         if new_status != Status::Closed
             && new_status != Status::Executed
+            && new_status != Status::ExecutionFailed
             && new_status != Status::Vetoed
+            && new_status != Status::Withdrawn
         {
             return Err(PreProposeError::NotCompleted { status: new_status });
         }
 
+        // The proposal no longer counts towards its proposer's
+        // submission limits.
+        if let Some(proposer) = self.open_proposals.get(deps.storage, &id) {
+            self.open_proposals.remove(deps.storage, &id)?;
+            self.record_completion(deps.storage, &proposer)?;
+        }
+
         match self.deposits.get(deps.storage, &id) {
             Some((deposit_info, proposer)) => {
                 let messages = if let Some(ref deposit_info) = deposit_info {
-                    // Determine if refund can be issued
-                    let should_refund_to_proposer =
-                        match (new_status, deposit_info.clone().refund_policy) {
-                            // If policy is refund only passed props, refund for executed status
-                            (Status::Executed, DepositRefundPolicy::OnlyPassed) => true,
-                            // Don't refund other statuses for OnlyPassed policy
-                            (_, DepositRefundPolicy::OnlyPassed) => false,
-                            // Refund if the refund policy is always refund
-                            (_, DepositRefundPolicy::Always) => true,
-                            // Don't refund if the refund is never refund
-                            (_, DepositRefundPolicy::Never) => false,
-                        };
-
-                    if should_refund_to_proposer {
-                        deposit_info
-                            .get_return_deposit_message(&proposer)?
-                    } else {
-                        // If the proposer doesn't get the deposit, the DAO does.
-                        let dao = self.dao.load(deps.storage)?;
-                        deposit_info
-                            .get_return_deposit_message(&dao.addr.clone())?
+                    // Refund the proposer's share of the deposit, or
+                    // each contributor's if it was split, according
+                    // to the refund policy. Whatever is not refunded
+                    // is slashed and, unless the policy says
+                    // otherwise, goes to the DAO.
+                    let dao = self.dao.load(deps.storage)?;
+                    match self.deposit_contributions.get(deps.storage, &id) {
+                        Some(contributors) => deposit_info.get_split_completion_messages(
+                            new_status,
+                            quorum_met,
+                            &contributors,
+                            &dao.addr,
+                        )?,
+                        None => deposit_info.get_completion_messages(
+                            new_status,
+                            quorum_met,
+                            &proposer,
+                            &dao.addr,
+                        )?,
                     }
                 } else {
                     // No deposit info for this proposal. Nothing to do.
@@ -382,33 +536,237 @@ where
         }
     }
 
+    /// Returns who may submit proposals. If no policy has been set,
+    /// `open_proposal_submission` in the config decides.
+    pub fn submission_policy(
+        &self,
+        storage: &dyn Storage,
+    ) -> StdResult<PreProposeSubmissionPolicy> {
+        match self.submission_policy.may_load(storage)? {
+            Some(policy) => Ok(policy),
+            None => {
+                let config = self.config.load(storage)?;
+                Ok(if config.open_proposal_submission {
+                    PreProposeSubmissionPolicy::Anyone {}
+                } else {
+                    PreProposeSubmissionPolicy::Members {}
+                })
+            }
+        }
+    }
+
+    /// Checks that WHO may submit a proposal under the submission
+    /// policy. Under a minimum power policy, the voting power of
+    /// CO_SPONSORS is added to WHO's.
     pub fn check_can_submit(
         &self,
         deps: Deps,
+        env: &Env,
         who: Addr,
-        key: String
+        key: String,
+        co_sponsors: &[CoSponsor],
     ) -> Result<(), PreProposeError> {
-        let config = self.config.load(deps.storage)?;
+        match self.submission_policy(deps.storage)? {
+            PreProposeSubmissionPolicy::Anyone {} => Ok(()),
+            PreProposeSubmissionPolicy::Members {} => {
+                let dao = self.dao.load(deps.storage)?;
+                let voting_power: VotingPowerAtHeightResponse = deps.querier.query_wasm_smart(
+                    dao.code_hash.clone(),
+                    dao.addr.clone().into_string(),
+                    &CwCoreQuery::VotingPowerAtHeight {
+                        address: who.into_string(),
+                        key,
+                        height: None,
+                    },
+                )?;
+                if voting_power.power.is_zero() {
+                    return Err(PreProposeError::NotMember {});
+                }
+                Ok(())
+            }
+            PreProposeSubmissionPolicy::MinimumPower { minimum } => {
+                let dao = self.dao.load(deps.storage)?;
+                let height = Some(env.block.height);
+                let mut power = get_voting_power(
+                    deps,
+                    dao.code_hash.clone(),
+                    who.clone(),
+                    key,
+                    &dao.addr,
+                    height,
+                )?;
+
+                let mut seen = vec![who];
+                for co_sponsor in co_sponsors {
+                    let address = deps.api.addr_validate(&co_sponsor.address)?;
+                    if seen.contains(&address) {
+                        return Err(PreProposeError::DuplicateCoSponsor {
+                            address: address.into_string(),
+                        });
+                    }
+                    power += self.co_sponsor_power(deps, env, &dao, &address, &co_sponsor.auth)?;
+                    seen.push(address);
+                }
+
+                let total = get_total_power(deps, dao.code_hash, &dao.addr, height)?;
+                if !minimum.is_met(power, total) {
+                    return Err(PreProposeError::InsufficientPower { power });
+                }
+                Ok(())
+            }
+        }
+    }
 
-        if !config.open_proposal_submission {
-            let dao = self.dao.load(deps.storage)?;
-            let voting_power: VotingPowerAtHeightResponse = deps.querier.query_wasm_smart(
+    /// Queries the voting power of a co-sponsor at the current height.
+    fn co_sponsor_power(
+        &self,
+        deps: Deps,
+        env: &Env,
+        dao: &AnyContractInfo,
+        address: &Addr,
+        auth: &PowerAuth,
+    ) -> Result<Uint128, PreProposeError> {
+        match auth {
+            PowerAuth::ViewingKey { key } => Ok(get_voting_power(
+                deps,
                 dao.code_hash.clone(),
-                dao.addr.clone().into_string(),
-                &CwCoreQuery::VotingPowerAtHeight {
-                    address: who.into_string(),
-                    key,
-                    height: None,
-                },
-            )?;
-            if voting_power.power.is_zero() {
-                return Err(PreProposeError::NotMember {});
+                address.clone(),
+                key.clone(),
+                &dao.addr,
+                Some(env.block.height),
+            )?),
+            PowerAuth::Permit { permit } => {
+                // Permits are signed for the voting module, so they
+                // are sent to it directly rather than through the DAO.
+                let voting_module: AnyContractInfo = deps.querier.query_wasm_smart(
+                    dao.code_hash.clone(),
+                    dao.addr.clone(),
+                    &dao_interface::msg::QueryMsg::VotingModule {},
+                )?;
+                // This checks the signature and that the permit is for
+                // the voting module, so that we may check its signer is
+                // the co-sponsor. Revocations are not checked here but
+                // by the voting module when it answers the query below.
+                let signer = secret_toolkit::permit::validate(
+                    deps,
+                    PREFIX_REVOKED_PERMITS,
+                    permit,
+                    voting_module.addr.to_string(),
+                    None,
+                )?;
+                if signer != address.as_str() {
+                    return Err(PreProposeError::InvalidPermitSigner {
+                        address: address.to_string(),
+                    });
+                }
+                let response: VotingPowerAtHeightResponse = deps.querier.query_wasm_smart(
+                    voting_module.code_hash,
+                    voting_module.addr,
+                    &PermitQuery::WithPermit {
+                        permit: permit.clone(),
+                        query: QueryWithPermit::VotingPowerAtHeight {
+                            address: address.to_string(),
+                            height: env.block.height,
+                        },
+                    },
+                )?;
+                Ok(response.power)
+            }
+        }
+    }
+
+    /// Checks that PROPOSER may submit a proposal under the
+    /// configured submission limits, and if so records the
+    /// submission. The proposal counts towards PROPOSER's limits until
+    /// `record_completion` is called for it.
+    pub fn record_submission(
+        &self,
+        storage: &mut dyn Storage,
+        block: &BlockInfo,
+        proposer: &Addr,
+    ) -> Result<(), PreProposeError> {
+        let limits = self.submission_limits.may_load(storage)?.unwrap_or_default();
+
+        if let Some(available) = self.cooldowns.get(storage, proposer) {
+            if !available.is_expired(block) {
+                return Err(PreProposeError::SubmissionCooldown { available });
+            }
+        }
+        let open = self.open_proposal_counts.get(storage, proposer).unwrap_or_default();
+        if let Some(max) = limits.max_open_per_proposer {
+            if open >= max {
+                return Err(PreProposeError::ProposerLimitReached { max });
+            }
+        }
+        let total = self.open_proposal_total.may_load(storage)?.unwrap_or_default();
+        if let Some(max) = limits.max_open {
+            if total >= max {
+                return Err(PreProposeError::DaoLimitReached { max });
             }
         }
+
+        // Counts are kept even if no limits are configured so that
+        // limits added later account for proposals already open.
+        self.open_proposal_counts.insert(storage, proposer, &(open + 1))?;
+        self.open_proposal_total.save(storage, &(total + 1))?;
+        if let Some(cooldown) = limits.cooldown {
+            self.cooldowns.insert(storage, proposer, &cooldown.after(block))?;
+        }
         Ok(())
     }
 
-    pub fn query(&self, deps: Deps, _env: Env, msg: QueryMsg<QueryExt>) -> StdResult<Binary> {
+    /// Records that one of PROPOSER's proposals has completed, so it
+    /// no longer counts towards their submission limits.
+    pub fn record_completion(
+        &self,
+        storage: &mut dyn Storage,
+        proposer: &Addr,
+    ) -> Result<(), PreProposeError> {
+        // Proposals submitted before counts were kept are not
+        // counted, so counts saturate at zero.
+        let open = self.open_proposal_counts.get(storage, proposer).unwrap_or_default();
+        self.open_proposal_counts.insert(storage, proposer, &open.saturating_sub(1))?;
+        let total = self.open_proposal_total.may_load(storage)?.unwrap_or_default();
+        self.open_proposal_total.save(storage, &total.saturating_sub(1))?;
+        Ok(())
+    }
+
+    pub fn query_submission_quota(
+        &self,
+        deps: Deps,
+        env: &Env,
+        proposer: String,
+    ) -> StdResult<SubmissionQuotaResponse> {
+        let proposer = deps.api.addr_validate(&proposer)?;
+        let limits = self.submission_limits.may_load(deps.storage)?.unwrap_or_default();
+
+        let open_proposals = self
+            .open_proposal_counts
+            .get(deps.storage, &proposer)
+            .unwrap_or_default();
+        let total = self.open_proposal_total.may_load(deps.storage)?.unwrap_or_default();
+        let remaining = [
+            limits
+                .max_open_per_proposer
+                .map(|max| max.saturating_sub(open_proposals)),
+            limits.max_open.map(|max| max.saturating_sub(total)),
+        ]
+        .into_iter()
+        .flatten()
+        .min();
+        let cooldown_expires = self
+            .cooldowns
+            .get(deps.storage, &proposer)
+            .filter(|available| !available.is_expired(&env.block));
+
+        Ok(SubmissionQuotaResponse {
+            open_proposals,
+            remaining,
+            cooldown_expires,
+        })
+    }
+
+    pub fn query(&self, deps: Deps, env: Env, msg: QueryMsg<QueryExt>) -> StdResult<Binary> {
         match msg {
             QueryMsg::ProposalModule {} => to_binary(&self.proposal_module.load(deps.storage)?),
             QueryMsg::Dao {} => to_binary(&self.dao.load(deps.storage)?),
@@ -417,6 +775,9 @@ where
                 let (deposit_info, proposer) =
                     self.deposits.get(deps.storage, &proposal_id).unwrap();
                 to_binary(&DepositInfoResponse {
+                    expected_refunds: deposit_info
+                        .as_ref()
+                        .map(CheckedDepositInfo::expected_refunds),
                     deposit_info,
                     proposer,
                 })
@@ -424,7 +785,38 @@ where
             QueryMsg::ProposalSubmittedHooks {} => {
                 to_binary(&self.proposal_submitted_hooks.query_hooks(deps)?)
             }
+            QueryMsg::SubmissionPolicy {} => to_binary(&self.submission_policy(deps.storage)?),
+            QueryMsg::SubmissionLimits {} => to_binary(
+                &self
+                    .submission_limits
+                    .may_load(deps.storage)?
+                    .unwrap_or_default(),
+            ),
+            QueryMsg::SubmissionQuota { proposer } => {
+                to_binary(&self.query_submission_quota(deps, &env, proposer)?)
+            }
             QueryMsg::QueryExtension { .. } => Ok(Binary::default()),
         }
     }
 }
+
+/// Registers this module's code hash with the deposit token if it is
+/// a SNIP-20, so that deposits paid with `Send` are passed on to this
+/// module's `Receive` handler.
+fn register_receive_msg(
+    env: &Env,
+    deposit_info: Option<&CheckedDepositInfo>,
+) -> StdResult<Option<WasmMsg>> {
+    match deposit_info.map(|info| &info.denom) {
+        Some(CheckedDenom::Cw20(token, code_hash)) => Ok(Some(WasmMsg::Execute {
+            contract_addr: token.to_string(),
+            code_hash: code_hash.clone(),
+            msg: to_binary(&secret_toolkit::snip20::HandleMsg::RegisterReceive {
+                code_hash: env.contract.code_hash.clone(),
+                padding: None,
+            })?,
+            funds: vec![],
+        })),
+        _ => Ok(None),
+    }
+}
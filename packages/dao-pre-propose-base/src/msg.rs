@@ -1,10 +1,16 @@
 use cosmwasm_schema::{schemars::JsonSchema, QueryResponses};
 use cw_denom::UncheckedDenom;
+use dao_interface::msg::Snip20ReceiveMsg;
 use dao_voting::{
-    deposit::{CheckedDepositInfo, UncheckedDepositInfo},
+    deposit::{CheckedDepositInfo, ExpectedRefunds, UncheckedDepositInfo},
     status::Status,
 };
+use secret_toolkit::permit::Permit;
+use secret_utils::Expiration;
 use serde::{Deserialize, Serialize};
+
+use crate::state::{PreProposeSubmissionPolicy, SubmissionLimits};
+
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct InstantiateMsg<InstantiateExt> {
@@ -27,9 +33,20 @@ pub enum ExecuteMsg<ProposalMessage, ExecuteExt> {
     /// Creates a new proposal in the pre-propose module. MSG will be
     /// serialized and used as the proposal creation message.
     Propose { key: String, 
-        msg: ProposalMessage 
+        msg: ProposalMessage,
+        /// Members co-sponsoring the proposal, whose voting power is
+        /// combined with the proposer's under a minimum power
+        /// submission policy. Ignored under other policies.
+        #[serde(default)]
+        co_sponsors: Vec<CoSponsor>,
     },
 
+    /// Receives a proposal deposit sent with a SNIP-20 `Send`. The
+    /// `msg` of the send must be a `ReceiveMsg`, and the amount sent
+    /// must be exactly the configured deposit. This lets SNIP-20
+    /// deposits be paid without first increasing an allowance.
+    Receive(Snip20ReceiveMsg),
+
     /// Updates the configuration of this module. This will completely
     /// override the existing configuration. This new configuration
     /// will only apply to proposals created after the config is
@@ -39,6 +56,18 @@ pub enum ExecuteMsg<ProposalMessage, ExecuteExt> {
         open_proposal_submission: bool,
     },
 
+    /// Updates the limits on how often proposals may be
+    /// submitted. Proposals that are already pending or open count
+    /// towards the new limits. Only the DAO may execute this message.
+    UpdateSubmissionLimits { limits: SubmissionLimits },
+
+    /// Updates who may submit proposals. If `None`, the
+    /// `open_proposal_submission` config decides. Only the DAO may
+    /// execute this message.
+    UpdateSubmissionPolicy {
+        policy: Option<PreProposeSubmissionPolicy>,
+    },
+
     /// Withdraws funds inside of this contract to the message
     /// sender. The contracts entire balance for the specifed DENOM is
     /// withdrawn to the message sender. Only the DAO may call this
@@ -91,9 +120,51 @@ pub enum ExecuteMsg<ProposalMessage, ExecuteExt> {
     ProposalCompletedHook {
         proposal_id: u64,
         new_status: Status,
+        /// Whether a closed proposal reached quorum, if its proposal
+        /// module reports it. Used to grade deposit refunds. Omitted
+        /// otherwise so that modules unaware of quorum are unaffected.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        quorum_met: Option<bool>,
+    },
+}
+
+/// The message embedded in the `msg` field of a SNIP-20 `Send` to
+/// this module.
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum ReceiveMsg<ProposalMessage> {
+    /// Creates a new proposal in the pre-propose module, paying its
+    /// deposit with the tokens sent. The owner of the tokens (`from`,
+    /// which differs from `sender` for a `SendFrom`) is the proposer
+    /// and receives any refund of the deposit.
+    Propose {
+        key: String,
+        msg: ProposalMessage,
+        #[serde(default)]
+        co_sponsors: Vec<CoSponsor>,
     },
 }
 
+/// A member co-sponsoring a proposal.
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub struct CoSponsor {
+    pub address: String,
+    /// Proves the co-sponsor's voting power.
+    pub auth: PowerAuth,
+}
+
+/// How this module queries a co-sponsor's voting power.
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum PowerAuth {
+    /// The co-sponsor's viewing key for the DAO's voting module.
+    ViewingKey { key: String },
+    /// A query permit signed by the co-sponsor for the DAO's voting
+    /// module. The voting module must support query permits.
+    Permit { permit: Permit },
+}
+
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
 #[serde(rename_all = "snake_case")]
 #[derive(QueryResponses)]
@@ -119,6 +190,15 @@ where
     /// Returns list of proposal submitted hooks.
     #[returns(cw_hooks::HooksResponse)]
     ProposalSubmittedHooks {},
+    /// Gets who may submit proposals.
+    #[returns(crate::state::PreProposeSubmissionPolicy)]
+    SubmissionPolicy {},
+    /// Gets the limits on how often proposals may be submitted.
+    #[returns(crate::state::SubmissionLimits)]
+    SubmissionLimits {},
+    /// Gets how many more proposals PROPOSER may submit, and when.
+    #[returns(SubmissionQuotaResponse)]
+    SubmissionQuota { proposer: String },
     /// Extension for queries. The default implementation will do
     /// nothing if queried for will return `Binary::default()`.
     #[returns(cosmwasm_std::Binary)]
@@ -132,4 +212,21 @@ pub struct DepositInfoResponse {
     pub deposit_info: Option<CheckedDepositInfo>,
     /// The address that created the proposal.
     pub proposer: cosmwasm_std::Addr,
+    /// The amount of the deposit the proposer can expect back for
+    /// each way the proposal may complete, if a deposit was paid.
+    pub expected_refunds: Option<ExpectedRefunds>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub struct SubmissionQuotaResponse {
+    /// The number of pending or open proposals the proposer has.
+    pub open_proposals: u32,
+    /// The number of additional proposals the proposer may have open
+    /// at once, accounting for both the per-proposer and DAO-wide
+    /// limits. `None` if neither limit is configured.
+    pub remaining: Option<u32>,
+    /// The time at which the proposer's submission cooldown expires,
+    /// if one is in effect.
+    pub cooldown_expires: Option<Expiration>,
 }
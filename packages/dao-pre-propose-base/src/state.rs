@@ -1,11 +1,12 @@
 use std::marker::PhantomData;
 
-use cosmwasm_std::Addr;
+use cosmwasm_std::{Addr, Decimal, Uint128};
 use cw_hooks::Hooks;
 use dao_interface::state::AnyContractInfo;
 use schemars::JsonSchema;
 use secret_storage_plus::Item;
 use secret_toolkit::{serialization::Json, storage::Keymap};
+use secret_utils::{Duration, Expiration};
 
 use dao_voting::deposit::CheckedDepositInfo;
 use serde::{Deserialize, Serialize};
@@ -22,6 +23,74 @@ pub struct Config {
     pub open_proposal_submission: bool,
 }
 
+/// Who may submit proposals.
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum PreProposeSubmissionPolicy {
+    /// Any address may submit proposals so long as they pay the
+    /// deposit.
+    Anyone {},
+    /// Only members (addresses with voting power) may submit
+    /// proposals.
+    Members {},
+    /// Only proposers whose voting power, combined with that of their
+    /// co-sponsors, meets the minimum may submit proposals. Voting
+    /// power is checked at the height the proposal is submitted.
+    MinimumPower { minimum: MinimumPower },
+}
+
+/// The minimum voting power needed to submit a proposal.
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum MinimumPower {
+    /// An absolute amount of voting power.
+    Absolute { power: Uint128 },
+    /// A percentage of the DAO's total voting power.
+    PercentOfTotal { percent: Decimal },
+}
+
+impl MinimumPower {
+    /// Returns true if POWER meets the minimum, given the DAO's TOTAL
+    /// voting power.
+    pub fn is_met(&self, power: Uint128, total: Uint128) -> bool {
+        match self {
+            MinimumPower::Absolute { power: minimum } => power >= *minimum,
+            MinimumPower::PercentOfTotal { percent } => {
+                !total.is_zero() && Decimal::from_ratio(power, total) >= *percent
+            }
+        }
+    }
+
+    /// Returns true if the minimum is non-zero and, for percentages,
+    /// at most 100%.
+    pub fn is_valid(&self) -> bool {
+        match self {
+            MinimumPower::Absolute { power } => !power.is_zero(),
+            MinimumPower::PercentOfTotal { percent } => {
+                !percent.is_zero() && *percent <= Decimal::one()
+            }
+        }
+    }
+}
+
+/// Limits on how often proposals may be submitted. Proposals pending
+/// or open in the proposal module count towards these limits until
+/// they complete.
+#[derive(Serialize, Deserialize, Clone, Default, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub struct SubmissionLimits {
+    /// The maximum number of open proposals each proposer may
+    /// have. If `None`, there is no limit.
+    pub max_open_per_proposer: Option<u32>,
+    /// How long a proposer must wait after submitting a proposal
+    /// before they may submit another. If `None`, there is no
+    /// cooldown.
+    pub cooldown: Option<Duration>,
+    /// The maximum number of proposals that may be open at once
+    /// across all proposers. If `None`, there is no limit.
+    pub max_open: Option<u32>,
+}
+
 pub struct PreProposeContract<InstantiateExt, ExecuteExt, QueryExt, ProposalMessage> {
     /// The proposal module that this module is associated with.
     pub proposal_module: Item<'static, AnyContractInfo>,
@@ -34,6 +103,25 @@ pub struct PreProposeContract<InstantiateExt, ExecuteExt, QueryExt, ProposalMess
     pub deposits: Keymap<'static, u64, (Option<CheckedDepositInfo>, Addr), Json>,
     /// Consumers of proposal submitted hooks.
     pub proposal_submitted_hooks: Hooks<'static>,
+    /// Map between proposal IDs and who paid their deposits, for
+    /// deposits paid by more than the proposer. Refunds of these
+    /// deposits are split between the contributors.
+    pub deposit_contributions: Keymap<'static, u64, Vec<(Addr, Uint128)>, Json>,
+    /// Who may submit proposals. If unset, `open_proposal_submission`
+    /// in the config decides.
+    pub submission_policy: Item<'static, PreProposeSubmissionPolicy>,
+    /// Limits on how often proposals may be submitted. Unset if no
+    /// limits have been configured.
+    pub submission_limits: Item<'static, SubmissionLimits>,
+    /// Map between the IDs of proposals open in the proposal module
+    /// and their proposers. Proposals are removed once they complete.
+    pub open_proposals: Keymap<'static, u64, Addr, Json>,
+    /// The number of pending or open proposals each proposer has.
+    pub open_proposal_counts: Keymap<'static, Addr, u32, Json>,
+    /// The number of pending or open proposals across all proposers.
+    pub open_proposal_total: Item<'static, u32>,
+    /// The time at which each proposer's submission cooldown expires.
+    pub cooldowns: Keymap<'static, Addr, Expiration, Json>,
 
     // These types are used in associated functions, but not
     // assocaited data. To stop the compiler complaining about unused
@@ -53,6 +141,13 @@ impl<InstantiateExt, ExecuteExt, QueryExt, ProposalMessage>
         config_key: &'static str,
         deposits_key: &'static str,
         proposal_submitted_hooks_key: &'static str,
+        deposit_contributions_key: &'static str,
+        submission_policy_key: &'static str,
+        submission_limits_key: &'static str,
+        open_proposals_key: &'static str,
+        open_proposal_counts_key: &'static str,
+        open_proposal_total_key: &'static str,
+        cooldowns_key: &'static str,
     ) -> Self {
         Self {
             proposal_module: Item::new(proposal_key),
@@ -60,6 +155,13 @@ impl<InstantiateExt, ExecuteExt, QueryExt, ProposalMessage>
             config: Item::new(config_key),
             deposits: Keymap::new(deposits_key.as_bytes()),
             proposal_submitted_hooks: Hooks::new(proposal_submitted_hooks_key),
+            deposit_contributions: Keymap::new(deposit_contributions_key.as_bytes()),
+            submission_policy: Item::new(submission_policy_key),
+            submission_limits: Item::new(submission_limits_key),
+            open_proposals: Keymap::new(open_proposals_key.as_bytes()),
+            open_proposal_counts: Keymap::new(open_proposal_counts_key.as_bytes()),
+            open_proposal_total: Item::new(open_proposal_total_key),
+            cooldowns: Keymap::new(cooldowns_key.as_bytes()),
             execute_type: PhantomData,
             instantiate_type: PhantomData,
             query_type: PhantomData,
@@ -81,6 +183,13 @@ impl<InstantiateExt, ExecuteExt, QueryExt, ProposalMessage> Default
             "config",
             "deposits",
             "proposal_submitted_hooks",
+            "deposit_contributions",
+            "submission_policy",
+            "submission_limits",
+            "open_proposals",
+            "open_proposal_counts",
+            "open_proposal_total",
+            "cooldowns",
         )
     }
 }
@@ -1,15 +1,23 @@
 use cw_hooks::HooksResponse;
 use dao_voting::status::Status;
+use secret_utils::{Duration, Expiration};
 use cosmwasm_std::{
     from_binary,
     testing::{mock_dependencies, mock_env, mock_info},
-    to_binary, Addr, Binary, ContractResult, Empty, Response, SubMsg, WasmMsg,
+    to_binary, Addr, Binary, ContractResult, Decimal, Empty, Response, SubMsg, Uint128,
+    WasmMsg,
 };
+use dao_interface::msg::Snip20ReceiveMsg;
+use dao_interface::state::AnyContractInfo;
+use dao_voting::deposit::{CheckedDepositInfo, DepositRefundPolicy};
+use cw_denom::CheckedDenom;
 
 use crate::{
     error::PreProposeError,
-    msg::{ExecuteMsg, QueryMsg},
-    state::{Config, PreProposeContract},
+    msg::{DepositInfoResponse, ExecuteMsg, QueryMsg, ReceiveMsg},
+    state::{
+        Config, MinimumPower, PreProposeContract, PreProposeSubmissionPolicy, SubmissionLimits,
+    },
 };
 
 type Contract = PreProposeContract<Empty, Empty, Empty, Empty>;
@@ -33,6 +41,7 @@ fn test_completed_hook_status_invariant() {
         ExecuteMsg::ProposalCompletedHook {
             proposal_id: 1,
             new_status: Status::Passed,
+            quorum_met: None,
         },
     );
 
@@ -62,6 +71,7 @@ fn test_completed_hook_auth() {
         ExecuteMsg::ProposalCompletedHook {
             proposal_id: 1,
             new_status: Status::Passed,
+            quorum_met: None,
         },
     );
 
@@ -201,3 +211,266 @@ fn test_execute_ext_does_nothing() {
         .unwrap();
     assert_eq!(res, Response::default())
 }
+
+#[test]
+fn test_submission_limits() {
+    let mut deps = mock_dependencies();
+    let mut env = mock_env();
+    let module = Contract::default();
+    let a = Addr::unchecked("a");
+    let b = Addr::unchecked("b");
+
+    module
+        .submission_limits
+        .save(
+            &mut deps.storage,
+            &SubmissionLimits {
+                max_open_per_proposer: Some(2),
+                cooldown: Some(Duration::Height(5)),
+                max_open: Some(3),
+            },
+        )
+        .unwrap();
+
+    module
+        .record_submission(&mut deps.storage, &env.block, &a)
+        .unwrap();
+
+    // Proposers must wait for their cooldown to expire.
+    let err = module
+        .record_submission(&mut deps.storage, &env.block, &a)
+        .unwrap_err();
+    assert_eq!(
+        err,
+        PreProposeError::SubmissionCooldown {
+            available: Expiration::AtHeight(env.block.height + 5)
+        }
+    );
+
+    env.block.height += 5;
+    module
+        .record_submission(&mut deps.storage, &env.block, &a)
+        .unwrap();
+
+    // Proposers may have at most two open proposals.
+    env.block.height += 5;
+    let err = module
+        .record_submission(&mut deps.storage, &env.block, &a)
+        .unwrap_err();
+    assert_eq!(err, PreProposeError::ProposerLimitReached { max: 2 });
+
+    // And the DAO at most three.
+    module
+        .record_submission(&mut deps.storage, &env.block, &b)
+        .unwrap();
+    env.block.height += 5;
+    let err = module
+        .record_submission(&mut deps.storage, &env.block, &b)
+        .unwrap_err();
+    assert_eq!(err, PreProposeError::DaoLimitReached { max: 3 });
+
+    let quota = module
+        .query_submission_quota(deps.as_ref(), &env, "a".to_string())
+        .unwrap();
+    assert_eq!(quota.open_proposals, 2);
+    assert_eq!(quota.remaining, Some(0));
+    assert_eq!(quota.cooldown_expires, None);
+
+    // Completed proposals no longer count.
+    module.record_completion(&mut deps.storage, &a).unwrap();
+    let quota = module
+        .query_submission_quota(deps.as_ref(), &env, "b".to_string())
+        .unwrap();
+    assert_eq!(quota.open_proposals, 1);
+    assert_eq!(quota.remaining, Some(1));
+    module
+        .record_submission(&mut deps.storage, &env.block, &b)
+        .unwrap();
+}
+
+#[test]
+fn test_minimum_power() {
+    let absolute = MinimumPower::Absolute {
+        power: Uint128::new(10),
+    };
+    assert!(absolute.is_valid());
+    assert!(absolute.is_met(Uint128::new(10), Uint128::zero()));
+    assert!(!absolute.is_met(Uint128::new(9), Uint128::new(100)));
+
+    let percent = MinimumPower::PercentOfTotal {
+        percent: Decimal::percent(10),
+    };
+    assert!(percent.is_valid());
+    assert!(percent.is_met(Uint128::new(10), Uint128::new(100)));
+    assert!(!percent.is_met(Uint128::new(9), Uint128::new(100)));
+    // Nobody meets a percentage of no power.
+    assert!(!percent.is_met(Uint128::zero(), Uint128::zero()));
+
+    assert!(!MinimumPower::Absolute {
+        power: Uint128::zero()
+    }
+    .is_valid());
+    assert!(!MinimumPower::PercentOfTotal {
+        percent: Decimal::zero()
+    }
+    .is_valid());
+    assert!(!MinimumPower::PercentOfTotal {
+        percent: Decimal::percent(101)
+    }
+    .is_valid());
+}
+
+#[test]
+fn test_update_submission_policy() {
+    let mut deps = mock_dependencies();
+    let module = Contract::default();
+
+    module
+        .dao
+        .save(
+            &mut deps.storage,
+            &AnyContractInfo {
+                addr: Addr::unchecked("dao"),
+                code_hash: "dao_hash".to_string(),
+            },
+        )
+        .unwrap();
+    module
+        .config
+        .save(
+            &mut deps.storage,
+            &Config {
+                deposit_info: None,
+                open_proposal_submission: true,
+            },
+        )
+        .unwrap();
+
+    // Without a policy, the config decides.
+    assert_eq!(
+        module.submission_policy(&deps.storage).unwrap(),
+        PreProposeSubmissionPolicy::Anyone {}
+    );
+
+    let policy = PreProposeSubmissionPolicy::MinimumPower {
+        minimum: MinimumPower::PercentOfTotal {
+            percent: Decimal::percent(5),
+        },
+    };
+    let err = module
+        .execute_update_submission_policy(
+            deps.as_mut(),
+            mock_info("ekez", &[]),
+            Some(policy.clone()),
+        )
+        .unwrap_err();
+    assert_eq!(err, PreProposeError::NotDao {});
+
+    let err = module
+        .execute_update_submission_policy(
+            deps.as_mut(),
+            mock_info("dao", &[]),
+            Some(PreProposeSubmissionPolicy::MinimumPower {
+                minimum: MinimumPower::Absolute {
+                    power: Uint128::zero(),
+                },
+            }),
+        )
+        .unwrap_err();
+    assert_eq!(err, PreProposeError::InvalidMinimumPower {});
+
+    module
+        .execute_update_submission_policy(
+            deps.as_mut(),
+            mock_info("dao", &[]),
+            Some(policy.clone()),
+        )
+        .unwrap();
+    let res = module
+        .query(deps.as_ref(), mock_env(), QueryMsg::SubmissionPolicy {})
+        .unwrap();
+    let queried: PreProposeSubmissionPolicy = from_binary(&res).unwrap();
+    assert_eq!(queried, policy);
+
+    // Clearing the policy defers to the config again.
+    module
+        .execute_update_submission_policy(deps.as_mut(), mock_info("dao", &[]), None)
+        .unwrap();
+    assert_eq!(
+        module.submission_policy(&deps.storage).unwrap(),
+        PreProposeSubmissionPolicy::Anyone {}
+    );
+}
+
+#[test]
+fn test_receive_send_from() {
+    let mut deps = mock_dependencies();
+    let module = Contract::default();
+    let deposit_info = CheckedDepositInfo {
+        denom: CheckedDenom::Cw20(Addr::unchecked("token"), "token_hash".to_string()),
+        amount: Uint128::new(10),
+        refund_policy: DepositRefundPolicy::Always,
+    };
+
+    module
+        .proposal_module
+        .save(
+            &mut deps.storage,
+            &AnyContractInfo {
+                addr: Addr::unchecked("pm"),
+                code_hash: "pm_hash".to_string(),
+            },
+        )
+        .unwrap();
+    module
+        .config
+        .save(
+            &mut deps.storage,
+            &Config {
+                deposit_info: Some(deposit_info.clone()),
+                open_proposal_submission: true,
+            },
+        )
+        .unwrap();
+    deps.querier.update_wasm(|_| {
+        // for responding to the next proposal ID query that gets fired by propose.
+        cosmwasm_std::SystemResult::Ok(ContractResult::Ok(to_binary(&1u64).unwrap()))
+    });
+
+    // With a `SendFrom` the tokens are paid by `from`, who is the
+    // proposer rather than the spender who sent them.
+    module
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("token", &[]),
+            ExecuteMsg::Receive(Snip20ReceiveMsg {
+                sender: Addr::unchecked("spender"),
+                from: Addr::unchecked("owner"),
+                amount: Uint128::new(10),
+                memo: None,
+                msg: Some(
+                    to_binary(&ReceiveMsg::Propose {
+                        key: "key".to_string(),
+                        msg: Empty::default(),
+                        co_sponsors: vec![],
+                    })
+                    .unwrap(),
+                ),
+            }),
+        )
+        .unwrap();
+
+    let deposit: DepositInfoResponse = from_binary(
+        &module
+            .query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::DepositInfo { proposal_id: 1 },
+            )
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(deposit.proposer, Addr::unchecked("owner"));
+    assert_eq!(deposit.deposit_info, Some(deposit_info));
+}
@@ -0,0 +1,211 @@
+use cosmwasm_std::{Api, BlockInfo, Deps, StdError, StdResult, Uint128};
+use dao_interface::{
+    query::{GetItemResponse, Snip20BalanceResponse},
+    state::AnyContractInfo,
+};
+use schemars::JsonSchema;
+use secret_utils::Expiration;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// The maximum number of conditions that may be attached to a single
+/// proposal. Each condition may require a query during execution.
+pub const MAX_CONDITIONS: usize = 10;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ConditionError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("A proposal may have at most {max} execution conditions.")]
+    TooManyConditions { max: usize },
+
+    #[error(
+        "The DAO holds {balance} of token {token} but the proposal requires at least {amount}."
+    )]
+    InsufficientBalance {
+        token: String,
+        balance: Uint128,
+        amount: Uint128,
+    },
+
+    #[error("The DAO's item ({key}) does not have the value required by the proposal.")]
+    ItemMismatch { key: String },
+
+    #[error("The proposal's execution deadline has passed.")]
+    DeadlinePassed {},
+
+    #[error("Proposal ({id}) has not been executed.")]
+    ProposalNotExecuted { id: u64 },
+}
+
+/// A condition that must hold at the time a proposal is executed. If
+/// any of a proposal's conditions does not hold, the proposal's
+/// messages are not executed.
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecutionCondition {
+    /// The DAO's treasury holds at least `amount` of the SNIP-20
+    /// `token`. The balance is read using the viewing key the DAO
+    /// holds for the token, so the token must be registered with the
+    /// DAO.
+    Snip20BalanceAtLeast { token: String, amount: Uint128 },
+    /// The DAO's item stored under `key` is equal to `value`. A value
+    /// of None requires that no item is stored under `key`.
+    ItemEquals { key: String, value: Option<String> },
+    /// The proposal is executed before `deadline`.
+    BeforeDeadline { deadline: Expiration },
+    /// The proposal with ID `proposal_id` in the same proposal module
+    /// has been executed.
+    ProposalExecuted { proposal_id: u64 },
+}
+
+impl ExecutionCondition {
+    pub fn validate(&self, api: &dyn Api) -> StdResult<()> {
+        if let ExecutionCondition::Snip20BalanceAtLeast { token, .. } = self {
+            api.addr_validate(token)?;
+        }
+        Ok(())
+    }
+
+    /// Checks that the condition holds. `is_executed` is used to look
+    /// up whether another proposal in the calling module has been
+    /// executed.
+    pub fn check(
+        &self,
+        deps: Deps,
+        block: &BlockInfo,
+        dao: &AnyContractInfo,
+        is_executed: &dyn Fn(u64) -> bool,
+    ) -> Result<(), ConditionError> {
+        match self {
+            ExecutionCondition::Snip20BalanceAtLeast { token, amount } => {
+                let res: Snip20BalanceResponse = deps.querier.query_wasm_smart(
+                    dao.code_hash.clone(),
+                    dao.addr.clone(),
+                    &dao_interface::msg::QueryMsg::Snip20Balance {
+                        token: token.clone(),
+                    },
+                )?;
+                if res.balance < *amount {
+                    return Err(ConditionError::InsufficientBalance {
+                        token: token.clone(),
+                        balance: res.balance,
+                        amount: *amount,
+                    });
+                }
+            }
+            ExecutionCondition::ItemEquals { key, value } => {
+                let res: GetItemResponse = deps.querier.query_wasm_smart(
+                    dao.code_hash.clone(),
+                    dao.addr.clone(),
+                    &dao_interface::msg::QueryMsg::GetItem { key: key.clone() },
+                )?;
+                if res.item != *value {
+                    return Err(ConditionError::ItemMismatch { key: key.clone() });
+                }
+            }
+            ExecutionCondition::BeforeDeadline { deadline } => {
+                if deadline.is_expired(block) {
+                    return Err(ConditionError::DeadlinePassed {});
+                }
+            }
+            ExecutionCondition::ProposalExecuted { proposal_id } => {
+                if !is_executed(*proposal_id) {
+                    return Err(ConditionError::ProposalNotExecuted { id: *proposal_id });
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Validates a proposal's list of execution conditions.
+pub fn validate_conditions(
+    api: &dyn Api,
+    conditions: &[ExecutionCondition],
+) -> Result<(), ConditionError> {
+    if conditions.len() > MAX_CONDITIONS {
+        return Err(ConditionError::TooManyConditions {
+            max: MAX_CONDITIONS,
+        });
+    }
+    for condition in conditions {
+        condition.validate(api)?;
+    }
+    Ok(())
+}
+
+/// Checks a proposal's execution conditions in order, returning the
+/// first that does not hold.
+pub fn check_conditions(
+    deps: Deps,
+    block: &BlockInfo,
+    dao: &AnyContractInfo,
+    conditions: &[ExecutionCondition],
+    is_executed: &dyn Fn(u64) -> bool,
+) -> Result<(), ConditionError> {
+    for condition in conditions {
+        condition.check(deps, block, dao, is_executed)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::{
+        testing::{mock_dependencies, mock_env},
+        Addr,
+    };
+
+    #[test]
+    fn test_deadline_and_executed_conditions() {
+        let deps = mock_dependencies();
+        let block = mock_env().block;
+        let dao = AnyContractInfo {
+            addr: Addr::unchecked("dao"),
+            code_hash: "hash".to_string(),
+        };
+        let is_executed = |id: u64| id == 1;
+
+        let conditions = vec![
+            ExecutionCondition::BeforeDeadline {
+                deadline: Expiration::AtHeight(block.height + 1),
+            },
+            ExecutionCondition::ProposalExecuted { proposal_id: 1 },
+        ];
+        assert_eq!(
+            check_conditions(deps.as_ref(), &block, &dao, &conditions, &is_executed),
+            Ok(())
+        );
+
+        let expired = ExecutionCondition::BeforeDeadline {
+            deadline: Expiration::AtHeight(block.height),
+        };
+        assert_eq!(
+            expired.check(deps.as_ref(), &block, &dao, &is_executed),
+            Err(ConditionError::DeadlinePassed {})
+        );
+
+        let not_executed = ExecutionCondition::ProposalExecuted { proposal_id: 2 };
+        assert_eq!(
+            not_executed.check(deps.as_ref(), &block, &dao, &is_executed),
+            Err(ConditionError::ProposalNotExecuted { id: 2 })
+        );
+    }
+
+    #[test]
+    fn test_validate_conditions() {
+        let deps = mock_dependencies();
+        let conditions =
+            vec![ExecutionCondition::ProposalExecuted { proposal_id: 1 }; MAX_CONDITIONS + 1];
+        assert_eq!(
+            validate_conditions(&deps.api, &conditions),
+            Err(ConditionError::TooManyConditions {
+                max: MAX_CONDITIONS
+            })
+        );
+        assert_eq!(validate_conditions(&deps.api, &conditions[1..]), Ok(()));
+    }
+}
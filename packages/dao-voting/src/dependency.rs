@@ -0,0 +1,501 @@
+use std::collections::{BTreeSet, VecDeque};
+
+use cosmwasm_std::{Addr, Deps, StdError, StdResult};
+use dao_interface::state::{AnyContractInfo, ProposalModule};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::status::Status;
+
+/// The maximum number of dependencies that may be declared by a
+/// single proposal.
+pub const MAX_DEPENDENCIES: usize = 10;
+/// The maximum number of proposals visited when building a
+/// proposal's dependency graph.
+pub const MAX_DEPENDENCY_GRAPH_NODES: usize = 50;
+/// The number of proposal modules requested per page when checking
+/// that a module is registered with a DAO.
+const PROPOSAL_MODULES_PAGE_SIZE: u32 = 30;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum DependencyError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("A proposal may have at most {max} dependencies.")]
+    TooManyDependencies { max: usize },
+
+    #[error("Proposal ({id}) in this module can not be a dependency as it does not exist yet.")]
+    InvalidDependency { id: u64 },
+
+    #[error("Proposal module ({proposal_module}) is not registered with this module's DAO.")]
+    UnknownProposalModule { proposal_module: String },
+
+    #[error("Dependency proposal ({id}) in module ({proposal_module}) does not exist.")]
+    DependencyNotFound { proposal_module: String, id: u64 },
+
+    #[error("Dependency proposal ({id}) in module ({proposal_module}) depends on this proposal.")]
+    DependencyCycle { proposal_module: String, id: u64 },
+
+    #[error("Dependency proposal ({id}) in module ({proposal_module}) has not been executed.")]
+    DependencyNotExecuted { proposal_module: String, id: u64 },
+
+    #[error("Dependency proposal ({id}) in module ({proposal_module}) will never be executed.")]
+    DependencyFailed { proposal_module: String, id: u64 },
+}
+
+/// A proposal that must be executed before the proposal declaring
+/// the dependency may be executed. The dependency may live in any
+/// proposal module that implements the `Proposal` query, including
+/// the declaring module.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, PartialOrd, Ord, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub struct ProposalDependency {
+    /// The address of the proposal module the dependency lives in.
+    pub proposal_module: String,
+    /// The code hash of that proposal module.
+    pub code_hash: String,
+    /// The ID of the dependency in that proposal module.
+    pub proposal_id: u64,
+}
+
+/// The parts of a proposal that are relevant to dependency
+/// resolution. Proposal modules' `Proposal` query responses are
+/// deserialized into this, ignoring all other fields.
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub struct DependencyInfo {
+    pub status: Status,
+    #[serde(default)]
+    pub dependencies: Vec<ProposalDependency>,
+}
+
+/// A proposal in a dependency graph.
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub struct DependencyNode {
+    pub proposal_module: String,
+    pub proposal_id: u64,
+    /// The proposal's current status. None if the proposal could not
+    /// be queried.
+    pub status: Option<Status>,
+    /// The proposal's own dependencies.
+    pub dependencies: Vec<ProposalDependency>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub struct DependencyGraphResponse {
+    /// The proposals the queried proposal depends on, directly or
+    /// transitively, in breadth-first order.
+    pub nodes: Vec<DependencyNode>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "snake_case")]
+enum ProposalModuleQuery {
+    Proposal { proposal_id: u64 },
+}
+
+#[derive(Deserialize)]
+struct ProposalModuleResponse {
+    proposal: DependencyInfo,
+}
+
+/// Validates a new proposal's dependencies. Dependencies must
+/// already exist, and dependencies in other modules must live in a
+/// proposal module registered with DAO, so every dependency points
+/// at an older proposal of the same DAO. As modules that do not check
+/// this may still declare dependencies on proposals that do not exist
+/// yet, the dependency graph is also walked, up to
+/// `MAX_DEPENDENCY_GRAPH_NODES` proposals, to check that it does not
+/// lead back to the new proposal.
+pub fn validate_dependencies(
+    deps: Deps,
+    this_module: &Addr,
+    dao: &AnyContractInfo,
+    next_proposal_id: u64,
+    dependencies: &[ProposalDependency],
+    local: &dyn Fn(u64) -> Option<DependencyInfo>,
+) -> Result<(), DependencyError> {
+    if dependencies.len() > MAX_DEPENDENCIES {
+        return Err(DependencyError::TooManyDependencies {
+            max: MAX_DEPENDENCIES,
+        });
+    }
+    for dependency in dependencies {
+        let proposal_module = deps.api.addr_validate(&dependency.proposal_module)?;
+        if proposal_module == *this_module {
+            if dependency.proposal_id >= next_proposal_id {
+                return Err(DependencyError::InvalidDependency {
+                    id: dependency.proposal_id,
+                });
+            }
+            continue;
+        }
+        if !is_dao_proposal_module(deps, dao, &proposal_module)? {
+            return Err(DependencyError::UnknownProposalModule {
+                proposal_module: dependency.proposal_module.clone(),
+            });
+        }
+        if query_dependency(deps, this_module, dependency, local).is_err() {
+            return Err(DependencyError::DependencyNotFound {
+                proposal_module: dependency.proposal_module.clone(),
+                id: dependency.proposal_id,
+            });
+        }
+    }
+
+    // The new proposal, and any later one in this module, does not
+    // exist yet, so any path to it is a cycle.
+    let cycle = dependency_graph(deps, this_module, dependencies, local)
+        .into_iter()
+        .find(|node| {
+            node.proposal_module == this_module.as_str() && node.proposal_id >= next_proposal_id
+        });
+    if let Some(node) = cycle {
+        return Err(DependencyError::DependencyCycle {
+            proposal_module: node.proposal_module,
+            id: node.proposal_id,
+        });
+    }
+    Ok(())
+}
+
+/// Returns true if PROPOSAL_MODULE is registered with DAO, whether
+/// or not it is enabled.
+fn is_dao_proposal_module(
+    deps: Deps,
+    dao: &AnyContractInfo,
+    proposal_module: &Addr,
+) -> StdResult<bool> {
+    Ok(query_dao_proposal_module(deps, dao, proposal_module)?.is_some())
+}
+
+/// Looks up PROPOSAL_MODULE in DAO's proposal modules. Returns None
+/// if it is not registered with DAO.
+pub fn query_dao_proposal_module(
+    deps: Deps,
+    dao: &AnyContractInfo,
+    proposal_module: &Addr,
+) -> StdResult<Option<ProposalModule>> {
+    let mut start_after: Option<String> = None;
+    loop {
+        let page: Vec<ProposalModule> = deps.querier.query_wasm_smart(
+            dao.code_hash.clone(),
+            dao.addr.clone(),
+            &dao_interface::msg::QueryMsg::ProposalModules {
+                start_after: start_after.clone(),
+                limit: Some(PROPOSAL_MODULES_PAGE_SIZE),
+            },
+        )?;
+        // Pages may start with `start_after` itself, so stop once a
+        // page brings nothing new.
+        let last = page.last().map(|m| m.address.to_string());
+        if let Some(module) = page.into_iter().find(|m| m.address == *proposal_module) {
+            return Ok(Some(module));
+        }
+        if last.is_none() || last == start_after {
+            return Ok(None);
+        }
+        start_after = last;
+    }
+}
+
+/// Looks up a dependency. Dependencies in the calling module are
+/// read with `local`, others through their module's `Proposal`
+/// query.
+pub fn query_dependency(
+    deps: Deps,
+    this_module: &Addr,
+    dependency: &ProposalDependency,
+    local: &dyn Fn(u64) -> Option<DependencyInfo>,
+) -> StdResult<Option<DependencyInfo>> {
+    if dependency.proposal_module == this_module.as_str() {
+        return Ok(local(dependency.proposal_id));
+    }
+    let res: ProposalModuleResponse = deps.querier.query_wasm_smart(
+        dependency.code_hash.clone(),
+        dependency.proposal_module.clone(),
+        &ProposalModuleQuery::Proposal {
+            proposal_id: dependency.proposal_id,
+        },
+    )?;
+    Ok(Some(res.proposal))
+}
+
+/// Checks that all of a proposal's dependencies have been executed.
+pub fn check_dependencies(
+    deps: Deps,
+    this_module: &Addr,
+    dependencies: &[ProposalDependency],
+    local: &dyn Fn(u64) -> Option<DependencyInfo>,
+) -> Result<(), DependencyError> {
+    for dependency in dependencies {
+        let status = query_dependency(deps, this_module, dependency, local)?.map(|d| d.status);
+        match status {
+            Some(Status::Executed) => (),
+            Some(
+                Status::Rejected
+                | Status::Closed
+                | Status::ExecutionFailed
+                | Status::Vetoed
+                | Status::Withdrawn,
+            ) => {
+                return Err(DependencyError::DependencyFailed {
+                    proposal_module: dependency.proposal_module.clone(),
+                    id: dependency.proposal_id,
+                })
+            }
+            _ => {
+                return Err(DependencyError::DependencyNotExecuted {
+                    proposal_module: dependency.proposal_module.clone(),
+                    id: dependency.proposal_id,
+                })
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Walks a proposal's dependencies breadth-first, visiting at most
+/// `MAX_DEPENDENCY_GRAPH_NODES` proposals. Proposals that can not be
+/// queried are included without a status and are not expanded.
+pub fn dependency_graph(
+    deps: Deps,
+    this_module: &Addr,
+    dependencies: &[ProposalDependency],
+    local: &dyn Fn(u64) -> Option<DependencyInfo>,
+) -> Vec<DependencyNode> {
+    let mut nodes = vec![];
+    let mut visited = BTreeSet::new();
+    let mut queue: VecDeque<ProposalDependency> = dependencies.iter().cloned().collect();
+
+    while let Some(dependency) = queue.pop_front() {
+        if nodes.len() >= MAX_DEPENDENCY_GRAPH_NODES {
+            break;
+        }
+        if !visited.insert((dependency.proposal_module.clone(), dependency.proposal_id)) {
+            continue;
+        }
+        let info = query_dependency(deps, this_module, &dependency, local)
+            .ok()
+            .flatten();
+        let (status, dependencies) = match info {
+            Some(info) => (Some(info.status), info.dependencies),
+            None => (None, vec![]),
+        };
+        queue.extend(dependencies.iter().cloned());
+        nodes.push(DependencyNode {
+            proposal_module: dependency.proposal_module,
+            proposal_id: dependency.proposal_id,
+            status,
+            dependencies,
+        });
+    }
+
+    nodes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::mock_dependencies;
+    use cosmwasm_std::{from_binary, to_binary, ContractResult, SystemResult, WasmQuery};
+    use dao_interface::state::ProposalModuleStatus;
+
+    fn dependency(id: u64) -> ProposalDependency {
+        ProposalDependency {
+            proposal_module: "module".to_string(),
+            code_hash: "hash".to_string(),
+            proposal_id: id,
+        }
+    }
+
+    fn dao() -> AnyContractInfo {
+        AnyContractInfo {
+            addr: Addr::unchecked("dao"),
+            code_hash: "dao_hash".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_validate_dependencies() {
+        let deps = mock_dependencies();
+        let module = Addr::unchecked("module");
+        let local = |id: u64| {
+            (id < 3).then_some(DependencyInfo {
+                status: Status::Open,
+                dependencies: vec![],
+            })
+        };
+        let validate = |dependencies: &[ProposalDependency]| {
+            validate_dependencies(deps.as_ref(), &module, &dao(), 3, dependencies, &local)
+        };
+        assert_eq!(validate(&[dependency(1), dependency(2)]), Ok(()));
+        assert_eq!(
+            validate(&[dependency(3)]),
+            Err(DependencyError::InvalidDependency { id: 3 })
+        );
+        assert_eq!(
+            validate(&vec![dependency(1); 11]),
+            Err(DependencyError::TooManyDependencies {
+                max: MAX_DEPENDENCIES
+            })
+        );
+    }
+
+    #[derive(Deserialize)]
+    #[serde(rename_all = "snake_case")]
+    enum MockProposalQuery {
+        Proposal { proposal_id: u64 },
+    }
+
+    #[derive(Serialize)]
+    struct MockProposalResponse {
+        proposal: DependencyInfo,
+    }
+
+    #[test]
+    fn test_validate_foreign_dependencies() {
+        let mut deps = mock_dependencies();
+        let module = Addr::unchecked("module");
+        // The DAO has "module" and "other" registered. Proposal 1 in
+        // "other" depends on proposal 5 in "module", which does not
+        // exist yet, and proposal 2 has no dependencies.
+        deps.querier.update_wasm(|query| {
+            let WasmQuery::Smart {
+                contract_addr, msg, ..
+            } = query
+            else {
+                panic!("unexpected query");
+            };
+            let res = match contract_addr.as_str() {
+                "dao" => to_binary(
+                    &["module", "other"]
+                        .iter()
+                        .map(|address| ProposalModule {
+                            address: Addr::unchecked(*address),
+                            code_hash: "hash".to_string(),
+                            prefix: "A".to_string(),
+                            status: ProposalModuleStatus::Enabled,
+                        })
+                        .collect::<Vec<_>>(),
+                ),
+                "other" => {
+                    let MockProposalQuery::Proposal { proposal_id } = from_binary(msg).unwrap();
+                    let dependencies = match proposal_id {
+                        1 => vec![dependency(5)],
+                        2 => vec![],
+                        _ => return SystemResult::Ok(ContractResult::Err("not found".to_string())),
+                    };
+                    to_binary(&MockProposalResponse {
+                        proposal: DependencyInfo {
+                            status: Status::Open,
+                            dependencies,
+                        },
+                    })
+                }
+                _ => return SystemResult::Ok(ContractResult::Err("no such contract".to_string())),
+            };
+            SystemResult::Ok(ContractResult::Ok(res.unwrap()))
+        });
+        let local = |_: u64| None;
+        let foreign = |module: &str, id: u64| ProposalDependency {
+            proposal_module: module.to_string(),
+            code_hash: "hash".to_string(),
+            proposal_id: id,
+        };
+        let validate = |next_proposal_id: u64, dependency: ProposalDependency| {
+            validate_dependencies(
+                deps.as_ref(),
+                &module,
+                &dao(),
+                next_proposal_id,
+                &[dependency],
+                &local,
+            )
+        };
+
+        assert_eq!(validate(5, foreign("other", 2)), Ok(()));
+        assert_eq!(
+            validate(5, foreign("stranger", 1)),
+            Err(DependencyError::UnknownProposalModule {
+                proposal_module: "stranger".to_string()
+            })
+        );
+        assert_eq!(
+            validate(5, foreign("other", 3)),
+            Err(DependencyError::DependencyNotFound {
+                proposal_module: "other".to_string(),
+                id: 3
+            })
+        );
+        // Proposal 5 would depend on itself through "other".
+        assert_eq!(
+            validate(5, foreign("other", 1)),
+            Err(DependencyError::DependencyCycle {
+                proposal_module: "module".to_string(),
+                id: 5
+            })
+        );
+        assert_eq!(validate(6, foreign("other", 1)), Ok(()));
+    }
+
+    #[test]
+    fn test_local_dependencies() {
+        let deps = mock_dependencies();
+        let module = Addr::unchecked("module");
+        // Proposal 1 executed, 2 depends on 1 and is passed, 3 was
+        // rejected.
+        let local = |id: u64| match id {
+            1 => Some(DependencyInfo {
+                status: Status::Executed,
+                dependencies: vec![],
+            }),
+            2 => Some(DependencyInfo {
+                status: Status::Passed,
+                dependencies: vec![dependency(1)],
+            }),
+            3 => Some(DependencyInfo {
+                status: Status::Rejected,
+                dependencies: vec![],
+            }),
+            _ => None,
+        };
+
+        assert_eq!(
+            check_dependencies(deps.as_ref(), &module, &[dependency(1)], &local),
+            Ok(())
+        );
+        assert_eq!(
+            check_dependencies(deps.as_ref(), &module, &[dependency(2)], &local),
+            Err(DependencyError::DependencyNotExecuted {
+                proposal_module: "module".to_string(),
+                id: 2
+            })
+        );
+        assert_eq!(
+            check_dependencies(deps.as_ref(), &module, &[dependency(3)], &local),
+            Err(DependencyError::DependencyFailed {
+                proposal_module: "module".to_string(),
+                id: 3
+            })
+        );
+
+        let graph = dependency_graph(
+            deps.as_ref(),
+            &module,
+            &[dependency(2), dependency(1)],
+            &local,
+        );
+        assert_eq!(
+            graph
+                .iter()
+                .map(|n| (n.proposal_id, n.status))
+                .collect::<Vec<_>>(),
+            vec![(2, Some(Status::Passed)), (1, Some(Status::Executed))]
+        );
+    }
+}
@@ -1,5 +1,5 @@
 use cosmwasm_std::{
-    to_binary, Addr, CosmosMsg, Deps, MessageInfo, StdError, StdResult, Uint128, WasmMsg,
+    to_binary, Addr, CosmosMsg, Decimal, Deps, MessageInfo, StdError, StdResult, Uint128, WasmMsg,
 };
 use schemars::JsonSchema;
 use secret_utils::{must_pay, PaymentError};
@@ -10,6 +10,8 @@ use thiserror::Error;
 
 use cw_denom::{CheckedDenom, DenomError, UncheckedDenom};
 
+use crate::status::Status;
+
 /// Error type for deposit methods.
 #[derive(Error, Debug, PartialEq)]
 pub enum DepositError {
@@ -27,6 +29,12 @@ pub enum DepositError {
 
     #[error("invalid deposit amount. got ({actual}), expected ({expected})")]
     InvalidDeposit { actual: Uint128, expected: Uint128 },
+
+    #[error("deposits are not paid in token ({token})")]
+    InvalidDepositToken { token: Addr },
+
+    #[error("refund shares must be between zero and one")]
+    InvalidRefundShare,
 }
 
 // The voting module token type to expect.
@@ -35,6 +43,9 @@ pub enum DepositError {
 #[serde(rename_all = "snake_case")]
 pub enum VotingModuleTokenType {
     Native,
+    /// A SNIP-20 token, such as the token staked with
+    /// `dao-voting-snip20-staked`.
+    #[serde(alias = "snip20")]
     Cw20,
 }
 
@@ -45,14 +56,15 @@ pub enum VotingModuleTokenType {
 pub enum DepositToken {
     /// Use a specific token address as the deposit token.
     Token { denom: UncheckedDenom },
-    /// Use the token native denom or cw20 contract address of the associated
+    /// Use the token native denom or SNIP-20 contract of the associated
     /// DAO's voting module. NOTE: in order to retrieve the token automatically
     /// via this variant, the voting module must either (1) use a native token
     /// and implement the `Denom {}` query type defined by
-    /// `dao_dao_macros::native_token_query` OR (2) use a cw20 token and
+    /// `dao_dao_macros::native_token_query` OR (2) use a SNIP-20 token and
     /// implement the `TokenContract {}` query type defined by
-    /// `dao_dao_macros::cw20_token_query`. Failing to implement correctly will
-    /// cause this option to fail to instantiate.
+    /// `dao_dao_macros::cw20_token_query`, returning the token's address
+    /// and code hash as `dao-voting-snip20-staked` does. Failing to
+    /// implement correctly will cause this option to fail to instantiate.
     VotingModuleToken { token_type: VotingModuleTokenType },
 }
 
@@ -76,10 +88,112 @@ pub struct UncheckedDepositInfo {
 pub enum DepositRefundPolicy {
     /// Deposits should always be refunded.
     Always,
-    /// Deposits should only be refunded for passed proposals.
+    /// Deposits should only be refunded for passed proposals and for
+    /// proposals withdrawn by their proposer before voting opened.
     OnlyPassed,
     /// Deposits should never be refunded.
     Never,
+    /// A share of the deposit is refunded depending on how the
+    /// proposal completed, and the rest is slashed.
+    Graded(GradedRefundPolicy),
+}
+
+/// The share of a deposit refunded for each way a proposal may
+/// complete. Each share must be between zero and one.
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub struct GradedRefundPolicy {
+    /// Refunded when the proposal passes and is executed.
+    pub passed: Decimal,
+    /// Refunded when the proposal passes but its execution fails.
+    pub execution_failed: Decimal,
+    /// Refunded when the proposal is rejected after reaching
+    /// quorum, or when the proposal module does not report quorum.
+    pub rejected: Decimal,
+    /// Refunded when the proposal is rejected without reaching
+    /// quorum.
+    pub rejected_without_quorum: Decimal,
+    /// Refunded when the proposal is vetoed.
+    pub vetoed: Decimal,
+    /// Refunded when the proposal is withdrawn by its proposer
+    /// before voting opened.
+    pub withdrawn: Decimal,
+    /// The address slashed deposits are sent to. If unset, they are
+    /// sent to the DAO's treasury.
+    pub slash_recipient: Option<String>,
+}
+
+/// The amount of a deposit that will be refunded for each way its
+/// proposal may complete.
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub struct ExpectedRefunds {
+    pub passed: Uint128,
+    pub execution_failed: Uint128,
+    pub rejected: Uint128,
+    pub rejected_without_quorum: Uint128,
+    pub vetoed: Uint128,
+    pub withdrawn: Uint128,
+}
+
+impl DepositRefundPolicy {
+    /// Checks that refund shares are at most one and that the slash
+    /// recipient, if any, is a valid address.
+    pub fn validate(&self, deps: Deps) -> Result<(), DepositError> {
+        if let Self::Graded(graded) = self {
+            let shares = [
+                graded.passed,
+                graded.execution_failed,
+                graded.rejected,
+                graded.rejected_without_quorum,
+                graded.vetoed,
+                graded.withdrawn,
+            ];
+            if shares.iter().any(|share| *share > Decimal::one()) {
+                return Err(DepositError::InvalidRefundShare);
+            }
+            if let Some(ref recipient) = graded.slash_recipient {
+                deps.api.addr_validate(recipient)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// The share of a deposit refunded for a proposal that completed
+    /// with STATUS. QUORUM_MET is whether a closed proposal reached
+    /// quorum, if its proposal module reports it.
+    pub fn refund_share(&self, status: Status, quorum_met: Option<bool>) -> Decimal {
+        match self {
+            Self::Always => Decimal::one(),
+            // Proposals withdrawn before voting opened were never
+            // decided on, so they are refunded.
+            Self::OnlyPassed => match status {
+                Status::Executed | Status::ExecutionFailed | Status::Withdrawn => Decimal::one(),
+                _ => Decimal::zero(),
+            },
+            Self::Never => Decimal::zero(),
+            Self::Graded(graded) => match status {
+                Status::Executed => graded.passed,
+                Status::ExecutionFailed => graded.execution_failed,
+                Status::Vetoed => graded.vetoed,
+                Status::Withdrawn => graded.withdrawn,
+                Status::Closed if quorum_met == Some(false) => graded.rejected_without_quorum,
+                _ => graded.rejected,
+            },
+        }
+    }
+
+    /// The address slashed deposits are sent to, if not the DAO.
+    pub fn slash_recipient(&self) -> Option<Addr> {
+        match self {
+            // Validated in `validate`.
+            Self::Graded(GradedRefundPolicy {
+                slash_recipient: Some(recipient),
+                ..
+            }) => Some(Addr::unchecked(recipient)),
+            _ => None,
+        }
+    }
 }
 
 /// Counterpart to the `DepositInfo` struct which has been
@@ -121,6 +235,7 @@ impl UncheckedDepositInfo {
         if amount.is_zero() {
             return Err(DepositError::ZeroDeposit);
         }
+        refund_policy.validate(deps)?;
 
         let denom = match denom {
             DepositToken::Token { denom } => denom.into_checked(deps),
@@ -198,6 +313,27 @@ impl CheckedDepositInfo {
         }
     }
 
+    /// Checks that AMOUNT of TOKEN, received with a SNIP-20 `Send`,
+    /// pays this deposit.
+    pub fn check_snip20_deposit_paid(
+        &self,
+        token: &Addr,
+        amount: Uint128,
+    ) -> Result<(), DepositError> {
+        if !self.denom.is_cw20(token) {
+            Err(DepositError::InvalidDepositToken {
+                token: token.clone(),
+            })
+        } else if amount != self.amount {
+            Err(DepositError::InvalidDeposit {
+                actual: amount,
+                expected: self.amount,
+            })
+        } else {
+            Ok(())
+        }
+    }
+
     pub fn get_take_deposit_messages(
         &self,
         depositor: &Addr,
@@ -236,6 +372,77 @@ impl CheckedDepositInfo {
         Ok(take_deposit_msg)
     }
 
+    /// Gets messages that refund PROPOSER's share of this deposit
+    /// for a proposal that completed with STATUS, and send the
+    /// remainder to the policy's slash recipient, or DAO if it has
+    /// none.
+    pub fn get_completion_messages(
+        &self,
+        status: Status,
+        quorum_met: Option<bool>,
+        proposer: &Addr,
+        dao: &Addr,
+    ) -> StdResult<Vec<CosmosMsg>> {
+        self.get_split_completion_messages(
+            status,
+            quorum_met,
+            &[(proposer.clone(), self.amount)],
+            dao,
+        )
+    }
+
+    /// Like `get_completion_messages`, but for a deposit paid by
+    /// several CONTRIBUTORS. Each is refunded their share of the
+    /// refund in proportion to what they paid. Rounding leftovers are
+    /// slashed.
+    pub fn get_split_completion_messages(
+        &self,
+        status: Status,
+        quorum_met: Option<bool>,
+        contributors: &[(Addr, Uint128)],
+        dao: &Addr,
+    ) -> StdResult<Vec<CosmosMsg>> {
+        let refund = self.refund(status, quorum_met);
+        let slash_recipient = self
+            .refund_policy
+            .slash_recipient()
+            .unwrap_or_else(|| dao.clone());
+
+        let mut messages = vec![];
+        let mut refunded = Uint128::zero();
+        for (contributor, paid) in contributors {
+            let share = refund.multiply_ratio(*paid, self.amount);
+            if !share.is_zero() {
+                messages.push(self.denom.get_transfer_to_message(contributor, share)?);
+                refunded += share;
+            }
+        }
+        let slashed = self.amount - refunded;
+        if !slashed.is_zero() {
+            messages.push(self.denom.get_transfer_to_message(&slash_recipient, slashed)?);
+        }
+        Ok(messages)
+    }
+
+    /// The amount of this deposit refunded for a proposal that
+    /// completed with STATUS.
+    pub fn refund(&self, status: Status, quorum_met: Option<bool>) -> Uint128 {
+        self.amount * self.refund_policy.refund_share(status, quorum_met)
+    }
+
+    /// The amount of this deposit refunded for each way its proposal
+    /// may complete.
+    pub fn expected_refunds(&self) -> ExpectedRefunds {
+        ExpectedRefunds {
+            passed: self.refund(Status::Executed, None),
+            execution_failed: self.refund(Status::ExecutionFailed, None),
+            rejected: self.refund(Status::Closed, Some(true)),
+            rejected_without_quorum: self.refund(Status::Closed, Some(false)),
+            vetoed: self.refund(Status::Vetoed, None),
+            withdrawn: self.refund(Status::Withdrawn, None),
+        }
+    }
+
     pub fn get_return_deposit_message(
         &self,
         depositor: &Addr,
@@ -349,6 +556,51 @@ pub mod tests {
         assert_eq!(err, DepositError::Payment(PaymentError::NoFunds {}));
     }
 
+    #[test]
+    fn test_check_snip20_deposit_paid() {
+        let mut deposit_info = CheckedDepositInfo {
+            denom: CheckedDenom::Cw20(Addr::unchecked(CW20), "code_hash".to_string()),
+            amount: Uint128::new(10),
+            refund_policy: DepositRefundPolicy::Always,
+        };
+        deposit_info
+            .check_snip20_deposit_paid(&Addr::unchecked(CW20), Uint128::new(10))
+            .unwrap();
+
+        let err = deposit_info
+            .check_snip20_deposit_paid(&Addr::unchecked(CW20), Uint128::new(9))
+            .unwrap_err();
+        assert_eq!(
+            err,
+            DepositError::InvalidDeposit {
+                actual: Uint128::new(9),
+                expected: Uint128::new(10)
+            }
+        );
+
+        let err = deposit_info
+            .check_snip20_deposit_paid(&Addr::unchecked("other"), Uint128::new(10))
+            .unwrap_err();
+        assert_eq!(
+            err,
+            DepositError::InvalidDepositToken {
+                token: Addr::unchecked("other")
+            }
+        );
+
+        // Native deposits may not be paid with a SNIP-20.
+        deposit_info.denom = CheckedDenom::Native(NATIVE_DENOM.to_string());
+        let err = deposit_info
+            .check_snip20_deposit_paid(&Addr::unchecked(CW20), Uint128::new(10))
+            .unwrap_err();
+        assert_eq!(
+            err,
+            DepositError::InvalidDepositToken {
+                token: Addr::unchecked(CW20)
+            }
+        );
+    }
+
     #[test]
     fn test_get_take_deposit_messages() {
         // Does nothing if a native token is being used.
@@ -400,6 +652,160 @@ pub mod tests {
         assert_eq!(messages, vec![]);
     }
 
+    #[test]
+    fn test_graded_refund() {
+        let deposit_info = CheckedDepositInfo {
+            denom: CheckedDenom::Native(NATIVE_DENOM.to_string()),
+            amount: Uint128::new(10),
+            refund_policy: DepositRefundPolicy::Graded(GradedRefundPolicy {
+                passed: Decimal::one(),
+                execution_failed: Decimal::one(),
+                rejected: Decimal::percent(50),
+                rejected_without_quorum: Decimal::zero(),
+                vetoed: Decimal::zero(),
+                withdrawn: Decimal::one(),
+                slash_recipient: Some("treasury".to_string()),
+            }),
+        };
+        assert_eq!(
+            deposit_info.expected_refunds(),
+            ExpectedRefunds {
+                passed: Uint128::new(10),
+                execution_failed: Uint128::new(10),
+                rejected: Uint128::new(5),
+                rejected_without_quorum: Uint128::zero(),
+                vetoed: Uint128::zero(),
+                withdrawn: Uint128::new(10),
+            }
+        );
+
+        let proposer = Addr::unchecked("ekez");
+        let dao = Addr::unchecked("dao");
+        let messages = deposit_info
+            .get_completion_messages(Status::Closed, Some(true), &proposer, &dao)
+            .unwrap();
+        assert_eq!(
+            messages,
+            vec![
+                CosmosMsg::Bank(BankMsg::Send {
+                    to_address: "ekez".to_string(),
+                    amount: coins(5, NATIVE_DENOM)
+                }),
+                CosmosMsg::Bank(BankMsg::Send {
+                    to_address: "treasury".to_string(),
+                    amount: coins(5, NATIVE_DENOM)
+                }),
+            ]
+        );
+
+        // Proposal modules that do not report quorum are graded as
+        // rejected with quorum.
+        assert_eq!(deposit_info.refund(Status::Closed, None), Uint128::new(5));
+
+        let messages = deposit_info
+            .get_completion_messages(Status::Vetoed, None, &proposer, &dao)
+            .unwrap();
+        assert_eq!(
+            messages,
+            vec![CosmosMsg::Bank(BankMsg::Send {
+                to_address: "treasury".to_string(),
+                amount: coins(10, NATIVE_DENOM)
+            })]
+        );
+    }
+
+    #[test]
+    fn test_split_refund() {
+        let deposit_info = CheckedDepositInfo {
+            denom: CheckedDenom::Native(NATIVE_DENOM.to_string()),
+            amount: Uint128::new(10),
+            refund_policy: DepositRefundPolicy::Graded(GradedRefundPolicy {
+                passed: Decimal::one(),
+                execution_failed: Decimal::one(),
+                rejected: Decimal::percent(50),
+                rejected_without_quorum: Decimal::zero(),
+                vetoed: Decimal::zero(),
+                withdrawn: Decimal::one(),
+                slash_recipient: None,
+            }),
+        };
+        let contributors = [
+            (Addr::unchecked("ekez"), Uint128::new(7)),
+            (Addr::unchecked("zeke"), Uint128::new(3)),
+        ];
+        let dao = Addr::unchecked("dao");
+
+        let messages = deposit_info
+            .get_split_completion_messages(Status::Executed, None, &contributors, &dao)
+            .unwrap();
+        assert_eq!(
+            messages,
+            vec![
+                CosmosMsg::Bank(BankMsg::Send {
+                    to_address: "ekez".to_string(),
+                    amount: coins(7, NATIVE_DENOM)
+                }),
+                CosmosMsg::Bank(BankMsg::Send {
+                    to_address: "zeke".to_string(),
+                    amount: coins(3, NATIVE_DENOM)
+                }),
+            ]
+        );
+
+        // Half of each contribution is refunded, rounding down, and
+        // the rest goes to the DAO.
+        let messages = deposit_info
+            .get_split_completion_messages(Status::Closed, Some(true), &contributors, &dao)
+            .unwrap();
+        assert_eq!(
+            messages,
+            vec![
+                CosmosMsg::Bank(BankMsg::Send {
+                    to_address: "ekez".to_string(),
+                    amount: coins(3, NATIVE_DENOM)
+                }),
+                CosmosMsg::Bank(BankMsg::Send {
+                    to_address: "zeke".to_string(),
+                    amount: coins(1, NATIVE_DENOM)
+                }),
+                CosmosMsg::Bank(BankMsg::Send {
+                    to_address: "dao".to_string(),
+                    amount: coins(6, NATIVE_DENOM)
+                }),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_ungraded_refund() {
+        let mut deposit_info = CheckedDepositInfo {
+            denom: CheckedDenom::Native(NATIVE_DENOM.to_string()),
+            amount: Uint128::new(10),
+            refund_policy: DepositRefundPolicy::OnlyPassed,
+        };
+        assert_eq!(deposit_info.refund(Status::Executed, None), Uint128::new(10));
+        assert_eq!(deposit_info.refund(Status::Withdrawn, None), Uint128::new(10));
+        assert_eq!(deposit_info.refund(Status::Closed, Some(true)), Uint128::zero());
+
+        // Slashed deposits go to the DAO by default.
+        deposit_info.refund_policy = DepositRefundPolicy::Never;
+        let messages = deposit_info
+            .get_completion_messages(
+                Status::Executed,
+                None,
+                &Addr::unchecked("ekez"),
+                &Addr::unchecked("dao"),
+            )
+            .unwrap();
+        assert_eq!(
+            messages,
+            vec![CosmosMsg::Bank(BankMsg::Send {
+                to_address: "dao".to_string(),
+                amount: coins(10, NATIVE_DENOM)
+            })]
+        );
+    }
+
     #[test]
     fn test_get_return_deposit_message_native() {
         let mut deposit_info = CheckedDepositInfo {
@@ -0,0 +1,126 @@
+use cosmwasm_std::{BlockInfo, StdError};
+use schemars::JsonSchema;
+use secret_utils::{Duration, Expiration};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum LateVoteExtensionError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("The late vote extension window and extension must have the same units as the max_voting_period of the proposal (height or time).")]
+    DurationUnitMismatch {},
+
+    #[error("The late vote extension window must not be longer than the max_voting_period.")]
+    WindowTooLong {},
+
+    #[error("The late vote extension duration and max_extensions must be non-zero.")]
+    ZeroExtension {},
+}
+
+/// Configuration for extending a proposal's voting period when its
+/// outcome swings late. This prevents large holders from waiting
+/// until the final block to flip a proposal's outcome without giving
+/// other voters a chance to respond.
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub struct LateVoteExtensionConfig {
+    /// The window before a proposal's expiration in which a vote that
+    /// changes the proposal's leading outcome, or newly crosses its
+    /// passing threshold, triggers an extension.
+    pub window: Duration,
+    /// The amount of time the proposal's expiration is moved forward
+    /// by each extension.
+    pub extension: Duration,
+    /// The maximum number of times a single proposal may be extended.
+    pub max_extensions: u64,
+}
+
+impl LateVoteExtensionConfig {
+    pub fn validate(&self, max_voting_period: &Duration) -> Result<(), LateVoteExtensionError> {
+        // Validate duration units match voting period.
+        match (self.window, self.extension, max_voting_period) {
+            (Duration::Time(window), Duration::Time(extension), Duration::Time(max))
+            | (Duration::Height(window), Duration::Height(extension), Duration::Height(max)) => {
+                if extension == 0 || self.max_extensions == 0 {
+                    return Err(LateVoteExtensionError::ZeroExtension {});
+                }
+                if window > *max {
+                    return Err(LateVoteExtensionError::WindowTooLong {});
+                }
+            }
+            _ => return Err(LateVoteExtensionError::DurationUnitMismatch {}),
+        };
+
+        Ok(())
+    }
+
+    /// Returns true if `block` falls within the final window before
+    /// `expiration`.
+    pub fn is_in_window(&self, expiration: &Expiration, block: &BlockInfo) -> bool {
+        match (expiration, self.window) {
+            (Expiration::AtHeight(height), Duration::Height(window)) => {
+                block.height.saturating_add(window) >= *height
+            }
+            (Expiration::AtTime(time), Duration::Time(window)) => {
+                block.time.plus_seconds(window) >= *time
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns true if a proposal that has already been extended
+    /// `extensions` times may be extended again.
+    pub fn can_extend(&self, extensions: u64) -> bool {
+        extensions < self.max_extensions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::mock_env;
+
+    #[test]
+    fn test_validate() {
+        let config = LateVoteExtensionConfig {
+            window: Duration::Height(10),
+            extension: Duration::Height(5),
+            max_extensions: 2,
+        };
+        assert_eq!(config.validate(&Duration::Height(100)), Ok(()));
+        assert_eq!(
+            config.validate(&Duration::Time(100)),
+            Err(LateVoteExtensionError::DurationUnitMismatch {})
+        );
+        assert_eq!(
+            config.validate(&Duration::Height(5)),
+            Err(LateVoteExtensionError::WindowTooLong {})
+        );
+        assert_eq!(
+            LateVoteExtensionConfig {
+                max_extensions: 0,
+                ..config
+            }
+            .validate(&Duration::Height(100)),
+            Err(LateVoteExtensionError::ZeroExtension {})
+        );
+    }
+
+    #[test]
+    fn test_is_in_window() {
+        let block = mock_env().block;
+        let config = LateVoteExtensionConfig {
+            window: Duration::Height(10),
+            extension: Duration::Height(5),
+            max_extensions: 2,
+        };
+        assert!(config.is_in_window(&Expiration::AtHeight(block.height + 10), &block));
+        assert!(!config.is_in_window(&Expiration::AtHeight(block.height + 11), &block));
+        assert!(!config.is_in_window(&Expiration::Never {}, &block));
+
+        assert!(config.can_extend(1));
+        assert!(!config.can_extend(2));
+    }
+}
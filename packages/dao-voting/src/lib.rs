@@ -1,13 +1,17 @@
 #![doc = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/README.md"))]
 
+pub mod condition;
+pub mod dependency;
 pub mod deposit;
 pub mod duration;
 pub mod error;
+pub mod late_vote_extension;
 pub mod multiple_choice;
 pub mod pre_propose;
 pub mod proposal;
 pub mod reply;
 pub mod status;
 pub mod threshold;
+pub mod turnout;
 pub mod veto;
 pub mod voting;
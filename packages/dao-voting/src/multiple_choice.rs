@@ -1,8 +1,9 @@
-use cosmwasm_std::{CosmosMsg, Empty, StdError, StdResult, Uint128};
+use cosmwasm_std::{CosmosMsg, Decimal, Empty, StdError, StdResult, Uint128};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use crate::threshold::{validate_quorum, PercentageThreshold, ThresholdError};
+use crate::threshold::{validate_quorum, PercentageThreshold, QuorumAdjustment, ThresholdError};
+use crate::turnout::TurnoutHistory;
 
 /// Maximum number of choices for multiple choice votes. Chosen
 /// in order to impose a bound on state / queries.
@@ -14,19 +15,60 @@ const NONE_OPTION_DESCRIPTION: &str = "None of the above";
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
 #[serde(rename_all = "snake_case")]
 pub enum VotingStrategy {
-    SingleChoice { quorum: PercentageThreshold },
+    SingleChoice {
+        quorum: PercentageThreshold,
+    },
+    /// Like `SingleChoice`, but the quorum follows the moving average
+    /// of turnout on recently completed proposals. Each new
+    /// proposal's quorum is set when it is created.
+    SingleChoiceDynamicQuorum {
+        min_quorum: Decimal,
+        max_quorum: Decimal,
+        adjustment: QuorumAdjustment,
+    },
 }
 
 impl VotingStrategy {
     pub fn validate(&self) -> Result<(), ThresholdError> {
         match self {
             VotingStrategy::SingleChoice { quorum } => validate_quorum(quorum),
+            VotingStrategy::SingleChoiceDynamicQuorum {
+                min_quorum,
+                max_quorum,
+                adjustment,
+            } => adjustment.validate(*min_quorum, *max_quorum),
         }
     }
 
+    /// Gets the strategy's quorum. Dynamic quorums are resolved when
+    /// proposals are created; should an unresolved one be used, its
+    /// max quorum applies.
     pub fn get_quorum(&self) -> PercentageThreshold {
         match self {
             VotingStrategy::SingleChoice { quorum } => *quorum,
+            VotingStrategy::SingleChoiceDynamicQuorum { max_quorum, .. } => {
+                PercentageThreshold::Percent(*max_quorum)
+            }
+        }
+    }
+
+    /// Resolves a dynamic quorum into a fixed `SingleChoice` quorum
+    /// using the turnout of recently completed proposals. Other
+    /// strategies are returned unchanged.
+    pub fn resolve(&self, turnout: &TurnoutHistory) -> VotingStrategy {
+        match self {
+            VotingStrategy::SingleChoiceDynamicQuorum {
+                min_quorum,
+                max_quorum,
+                adjustment,
+            } => VotingStrategy::SingleChoice {
+                quorum: PercentageThreshold::Percent(adjustment.quorum(
+                    *min_quorum,
+                    *max_quorum,
+                    turnout.average(adjustment.window),
+                )),
+            },
+            strategy => strategy.clone(),
         }
     }
 }
@@ -280,4 +322,48 @@ mod test {
         let mc_options = super::MultipleChoiceOptions { options };
         mc_options.into_checked().unwrap();
     }
+
+    #[test]
+    fn test_dynamic_quorum_strategy() {
+        let strategy = VotingStrategy::SingleChoiceDynamicQuorum {
+            min_quorum: Decimal::percent(10),
+            max_quorum: Decimal::percent(40),
+            adjustment: QuorumAdjustment {
+                window: 5,
+                factor: Decimal::one(),
+                initial_quorum: Some(Decimal::percent(25)),
+            },
+        };
+        strategy.validate().unwrap();
+
+        let mut turnout = TurnoutHistory::default();
+        assert_eq!(
+            strategy.resolve(&turnout),
+            VotingStrategy::SingleChoice {
+                quorum: PercentageThreshold::Percent(Decimal::percent(25))
+            }
+        );
+
+        turnout.record(Uint128::new(5), Uint128::new(100));
+        assert_eq!(
+            strategy.resolve(&turnout),
+            VotingStrategy::SingleChoice {
+                quorum: PercentageThreshold::Percent(Decimal::percent(10))
+            }
+        );
+
+        let invalid = VotingStrategy::SingleChoiceDynamicQuorum {
+            min_quorum: Decimal::percent(50),
+            max_quorum: Decimal::percent(40),
+            adjustment: QuorumAdjustment {
+                window: 5,
+                factor: Decimal::one(),
+                initial_quorum: None,
+            },
+        };
+        assert_eq!(
+            invalid.validate(),
+            Err(ThresholdError::InvalidQuorumBounds {})
+        );
+    }
 }
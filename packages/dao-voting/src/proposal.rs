@@ -2,6 +2,10 @@ use cosmwasm_std::{CosmosMsg, Empty};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use crate::condition::ExecutionCondition;
+use crate::dependency::ProposalDependency;
+use crate::multiple_choice::MultipleChoiceOptions;
+
 /// Default limit for proposal pagination.
 pub const DEFAULT_LIMIT: u64 = 30;
 pub const MAX_PROPOSAL_SIZE: u64 = 30_000;
@@ -34,4 +38,44 @@ pub struct SingleChoiceProposeMsg {
     /// pre-propose module is attached, this must be Some and will
     /// set the proposer of the proposal it creates.
     pub proposer: Option<String>,
+    /// Conditions that must hold when the proposal is executed. If
+    /// any does not hold, the proposal's messages are not executed.
+    #[serde(default)]
+    pub conditions: Vec<ExecutionCondition>,
+    /// Proposals, in this or other proposal modules, that must be
+    /// executed before the proposal may be executed.
+    #[serde(default)]
+    pub dependencies: Vec<ProposalDependency>,
+    /// Whether this is an emergency proposal. Emergency proposals
+    /// may only be created by the module's emergency proposers and
+    /// use its emergency configuration.
+    #[serde(default)]
+    pub emergency: bool,
+}
+
+/// The contents of a message to create a proposal in the multiple
+/// choice proposal module. Broken out for the same reasons as
+/// `SingleChoiceProposeMsg`.
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub struct MultipleChoiceProposeMsg {
+    /// The title of the proposal.
+    pub title: String,
+    /// A description of the proposal.
+    pub description: String,
+    /// The multiple choices.
+    pub choices: MultipleChoiceOptions,
+    /// The address creating the proposal. If no pre-propose
+    /// module is attached to this module this must always be None
+    /// as the proposer is the sender of the propose message. If a
+    /// pre-propose module is attached, this must be Some and will
+    /// set the proposer of the proposal it creates.
+    pub proposer: Option<String>,
+    /// Conditions that must hold when the proposal is executed.
+    #[serde(default)]
+    pub conditions: Vec<ExecutionCondition>,
+    /// Proposals, in this or other proposal modules, that must be
+    /// executed before the proposal may be executed.
+    #[serde(default)]
+    pub dependencies: Vec<ProposalDependency>,
 }
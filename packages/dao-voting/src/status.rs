@@ -18,11 +18,15 @@ pub enum Status {
     Closed,
     /// The proposal's execution failed.
     ExecutionFailed,
-    /// The proposal is timelocked. Only the configured vetoer
+    /// The proposal is timelocked. Only the configured vetoers
     /// can execute or veto until the timelock expires.
     VetoTimelock { expiration: Expiration },
     /// The proposal has been vetoed.
     Vetoed,
+    /// The proposal was withdrawn by its proposer during its review
+    /// period, before voting opened. A proposal deposit refund has
+    /// been issued if applicable.
+    Withdrawn,
 }
 
 impl std::fmt::Display for Status {
@@ -38,6 +42,7 @@ impl std::fmt::Display for Status {
                 write!(f, "veto_timelock_until_{:?}", expiration)
             }
             Status::Vetoed => write!(f, "vetoed"),
+            Status::Withdrawn => write!(f, "withdrawn"),
         }
     }
 }
@@ -3,6 +3,9 @@ use serde::{Serialize,Deserialize};
 use schemars::JsonSchema;
 
 
+use std::cmp::Ordering;
+
+use crate::turnout::MAX_TURNOUT_WINDOW;
 use thiserror::Error;
 
 /// The threshold of tokens that must be staked in order for this
@@ -66,8 +69,23 @@ pub enum ThresholdError {
 
     #[error("Not possible to reach required (passing) threshold")]
     UnreachableThreshold {},
+
+    #[error("Dynamic quorum bounds must satisfy min_quorum <= max_quorum <= 1")]
+    InvalidQuorumBounds {},
+
+    #[error(
+        "Dynamic quorum adjustment must have a non-zero factor and a window between 1 and {max}"
+    )]
+    InvalidQuorumAdjustment { max: u64 },
+
+    #[error("Composite thresholds must combine 2 to {max} non-composite thresholds")]
+    InvalidCompositeThreshold { max: usize },
 }
 
+/// The maximum number of thresholds a composite threshold may
+/// combine.
+pub const MAX_COMPOSITE_THRESHOLDS: usize = 4;
+
 /// A percentage of voting power that must vote yes for a proposal to
 /// pass. An example of why this is needed:
 ///
@@ -102,6 +120,22 @@ pub enum PercentageThreshold {
     Percent(Decimal),
 }
 
+impl PercentageThreshold {
+    /// Orders percentage thresholds by how hard they are to
+    /// reach. `Majority` requires strictly more than 50% and so is
+    /// stricter than `Percent(0.5)`, but less strict than any larger
+    /// percentage.
+    pub fn strictness_cmp(&self, other: &Self) -> Ordering {
+        fn rank(percentage: &PercentageThreshold) -> (Decimal, bool) {
+            match percentage {
+                PercentageThreshold::Majority {} => (Decimal::percent(50), true),
+                PercentageThreshold::Percent(percent) => (*percent, false),
+            }
+        }
+        rank(self).cmp(&rank(other))
+    }
+}
+
 /// The ways a proposal may reach its passing / failing threshold.
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
 #[serde(rename_all = "snake_case")]
@@ -124,6 +158,76 @@ pub enum Threshold {
     /// An absolute number of votes needed for something to cross the
     /// threshold. Useful for multisig style voting.
     AbsoluteCount { threshold: Uint128 },
+
+    /// Like `ThresholdQuorum`, but the quorum follows the moving
+    /// average of turnout on recently completed proposals. Each new
+    /// proposal's quorum is set when it is created, bounded by
+    /// `min_quorum` and `max_quorum`.
+    DynamicQuorum {
+        threshold: PercentageThreshold,
+        min_quorum: Decimal,
+        max_quorum: Decimal,
+        adjustment: QuorumAdjustment,
+    },
+
+    /// Combines several thresholds, all of which must be met for a
+    /// proposal to pass. For example, a majority of votes cast with a
+    /// 20% quorum and at least three yes votes. A proposal is rejected
+    /// as soon as any of the thresholds can no longer be met.
+    Composite { thresholds: Vec<Threshold> },
+}
+
+/// Describes how a dynamic quorum follows recent turnout.
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub struct QuorumAdjustment {
+    /// The number of most recently completed proposals whose
+    /// turnout is averaged.
+    pub window: u64,
+    /// The fraction of the average turnout used as the quorum. For
+    /// example, a factor of 0.8 sets the quorum to 80% of recent
+    /// average turnout.
+    pub factor: Decimal,
+    /// The quorum used until a proposal has completed. Must lie
+    /// between the quorum bounds. Defaults to `min_quorum`.
+    #[serde(default)]
+    pub initial_quorum: Option<Decimal>,
+}
+
+impl QuorumAdjustment {
+    /// Validates the adjustment and the quorum bounds it applies
+    /// within.
+    pub fn validate(&self, min_quorum: Decimal, max_quorum: Decimal) -> Result<(), ThresholdError> {
+        if min_quorum > max_quorum
+            || max_quorum > Decimal::one()
+            || self
+                .initial_quorum
+                .map_or(false, |initial| initial < min_quorum || initial > max_quorum)
+        {
+            return Err(ThresholdError::InvalidQuorumBounds {});
+        }
+        if self.factor.is_zero() || self.window == 0 || self.window > MAX_TURNOUT_WINDOW {
+            return Err(ThresholdError::InvalidQuorumAdjustment {
+                max: MAX_TURNOUT_WINDOW,
+            });
+        }
+        Ok(())
+    }
+
+    /// Computes the quorum from the average turnout of recent
+    /// proposals. Without any turnout history the initial quorum is
+    /// used.
+    pub fn quorum(
+        &self,
+        min_quorum: Decimal,
+        max_quorum: Decimal,
+        average_turnout: Option<Decimal>,
+    ) -> Decimal {
+        match average_turnout {
+            Some(turnout) => (turnout * self.factor).clamp(min_quorum, max_quorum),
+            None => self.initial_quorum.unwrap_or(min_quorum),
+        }
+    }
 }
 
 /// Asserts that the 0.0 < percent <= 1.0
@@ -155,12 +259,31 @@ pub fn validate_quorum(quorum: &PercentageThreshold) -> Result<(), ThresholdErro
     }
 }
 
+/// Combines the orderings of several components of a threshold.
+/// Returns None if some components are stricter and others less
+/// strict.
+fn combine_orderings(orderings: &[Ordering]) -> Option<Ordering> {
+    orderings
+        .iter()
+        .filter(|ordering| **ordering != Ordering::Equal)
+        .try_fold(Ordering::Equal, |combined, ordering| {
+            match (combined, *ordering) {
+                (Ordering::Equal, ordering) => Some(ordering),
+                (combined, ordering) if combined == ordering => Some(combined),
+                _ => None,
+            }
+        })
+}
+
 impl Threshold {
     /// Validates the threshold.
     ///
     /// - Quorums must never be over 100%.
     /// - Passing thresholds must never be over 100%, nor be 0%.
     /// - Absolute count thresholds must be non-zero.
+    /// - Dynamic quorum bounds must be ordered and at most 100%.
+    /// - Composite thresholds must combine between two and
+    ///   `MAX_COMPOSITE_THRESHOLDS` valid, non-composite thresholds.
     pub fn validate(&self) -> Result<(), ThresholdError> {
         match self {
             Threshold::AbsolutePercentage {
@@ -177,6 +300,118 @@ impl Threshold {
                     Ok(())
                 }
             }
+            Threshold::DynamicQuorum {
+                threshold,
+                min_quorum,
+                max_quorum,
+                adjustment,
+            } => {
+                validate_percentage(threshold)?;
+                adjustment.validate(*min_quorum, *max_quorum)
+            }
+            Threshold::Composite { thresholds } => {
+                if thresholds.len() < 2
+                    || thresholds.len() > MAX_COMPOSITE_THRESHOLDS
+                    || thresholds
+                        .iter()
+                        .any(|t| matches!(t, Threshold::Composite { .. }))
+                {
+                    return Err(ThresholdError::InvalidCompositeThreshold {
+                        max: MAX_COMPOSITE_THRESHOLDS,
+                    });
+                }
+                thresholds.iter().try_for_each(Threshold::validate)
+            }
+        }
+    }
+
+    /// Resolves a dynamic quorum into a fixed `ThresholdQuorum` using
+    /// the average turnout of recent proposals, including dynamic
+    /// quorums within a composite threshold. Other thresholds are
+    /// returned unchanged.
+    pub fn resolve(&self, average_turnout: Option<Decimal>) -> Threshold {
+        match self {
+            Threshold::DynamicQuorum {
+                threshold,
+                min_quorum,
+                max_quorum,
+                adjustment,
+            } => Threshold::ThresholdQuorum {
+                threshold: *threshold,
+                quorum: PercentageThreshold::Percent(adjustment.quorum(
+                    *min_quorum,
+                    *max_quorum,
+                    average_turnout,
+                )),
+            },
+            Threshold::Composite { thresholds } => Threshold::Composite {
+                thresholds: thresholds
+                    .iter()
+                    .map(|t| t.resolve(average_turnout))
+                    .collect(),
+            },
+            threshold => threshold.clone(),
+        }
+    }
+
+    /// Compares how hard two thresholds are to reach. Returns None if
+    /// the thresholds are of different kinds, or if one has a higher
+    /// passing threshold and the other a higher quorum.
+    pub fn strictness_cmp(&self, other: &Self) -> Option<Ordering> {
+        match (self, other) {
+            (
+                Threshold::AbsolutePercentage { percentage: a },
+                Threshold::AbsolutePercentage { percentage: b },
+            ) => Some(a.strictness_cmp(b)),
+            (
+                Threshold::ThresholdQuorum {
+                    threshold: threshold_a,
+                    quorum: quorum_a,
+                },
+                Threshold::ThresholdQuorum {
+                    threshold: threshold_b,
+                    quorum: quorum_b,
+                },
+            ) => combine_orderings(&[
+                threshold_a.strictness_cmp(threshold_b),
+                quorum_a.strictness_cmp(quorum_b),
+            ]),
+            (
+                Threshold::DynamicQuorum {
+                    threshold: threshold_a,
+                    min_quorum: min_a,
+                    max_quorum: max_a,
+                    ..
+                },
+                Threshold::DynamicQuorum {
+                    threshold: threshold_b,
+                    min_quorum: min_b,
+                    max_quorum: max_b,
+                    ..
+                },
+            ) => combine_orderings(&[
+                threshold_a.strictness_cmp(threshold_b),
+                min_a.cmp(min_b),
+                max_a.cmp(max_b),
+            ]),
+            (
+                Threshold::AbsoluteCount { threshold: a },
+                Threshold::AbsoluteCount { threshold: b },
+            ) => Some(a.cmp(b)),
+            // Composite thresholds are compared threshold by
+            // threshold, so must combine the same kinds of thresholds
+            // in the same order.
+            (Threshold::Composite { thresholds: a }, Threshold::Composite { thresholds: b })
+                if a.len() == b.len() =>
+            {
+                let orderings = a
+                    .iter()
+                    .zip(b)
+                    .map(|(a, b)| a.strictness_cmp(b))
+                    .collect::<Option<Vec<_>>>()?;
+                combine_orderings(&orderings)
+            }
+            _ => None,
         }
     }
 }
@@ -238,4 +473,160 @@ mod tests {
             ThresholdError::UnreachableThreshold {}
         );
     }
+
+    #[test]
+    fn test_threshold_strictness() {
+        let majority = PercentageThreshold::Majority {};
+        assert_eq!(p!(50).strictness_cmp(&majority), Ordering::Less);
+        assert_eq!(p!(51).strictness_cmp(&majority), Ordering::Greater);
+
+        let a = Threshold::ThresholdQuorum {
+            threshold: majority,
+            quorum: p!(20),
+        };
+        let b = Threshold::ThresholdQuorum {
+            threshold: p!(66),
+            quorum: p!(20),
+        };
+        let c = Threshold::ThresholdQuorum {
+            threshold: p!(66),
+            quorum: p!(10),
+        };
+        assert_eq!(b.strictness_cmp(&a), Some(Ordering::Greater));
+        assert_eq!(c.strictness_cmp(&b), Some(Ordering::Less));
+        assert_eq!(c.strictness_cmp(&a), None);
+        assert_eq!(
+            a.strictness_cmp(&Threshold::AbsolutePercentage {
+                percentage: majority
+            }),
+            None
+        );
+    }
+
+    #[test]
+    fn test_dynamic_quorum() {
+        let adjustment = QuorumAdjustment {
+            window: 5,
+            factor: Decimal::percent(80),
+            initial_quorum: None,
+        };
+        let t = Threshold::DynamicQuorum {
+            threshold: PercentageThreshold::Majority {},
+            min_quorum: Decimal::percent(10),
+            max_quorum: Decimal::percent(30),
+            adjustment: adjustment.clone(),
+        };
+        t.validate().unwrap();
+
+        let resolved = |turnout: Option<u64>| match t.resolve(turnout.map(Decimal::percent)) {
+            Threshold::ThresholdQuorum { quorum, .. } => quorum,
+            _ => unreachable!(),
+        };
+        assert_eq!(resolved(Some(25)), p!(20));
+        assert_eq!(resolved(Some(5)), p!(10));
+        assert_eq!(resolved(Some(90)), p!(30));
+        assert_eq!(resolved(None), p!(10));
+
+        let seeded = Threshold::DynamicQuorum {
+            threshold: PercentageThreshold::Majority {},
+            min_quorum: Decimal::percent(10),
+            max_quorum: Decimal::percent(30),
+            adjustment: QuorumAdjustment {
+                initial_quorum: Some(Decimal::percent(20)),
+                ..adjustment.clone()
+            },
+        };
+        seeded.validate().unwrap();
+        assert_eq!(
+            seeded.resolve(None),
+            Threshold::ThresholdQuorum {
+                threshold: PercentageThreshold::Majority {},
+                quorum: p!(20),
+            }
+        );
+        let out_of_bounds = Threshold::DynamicQuorum {
+            threshold: PercentageThreshold::Majority {},
+            min_quorum: Decimal::percent(10),
+            max_quorum: Decimal::percent(30),
+            adjustment: QuorumAdjustment {
+                initial_quorum: Some(Decimal::percent(40)),
+                ..adjustment.clone()
+            },
+        };
+        assert_eq!(
+            out_of_bounds.validate().unwrap_err(),
+            ThresholdError::InvalidQuorumBounds {}
+        );
+
+        let t = Threshold::DynamicQuorum {
+            threshold: PercentageThreshold::Majority {},
+            min_quorum: Decimal::percent(40),
+            max_quorum: Decimal::percent(30),
+            adjustment,
+        };
+        assert_eq!(
+            t.validate().unwrap_err(),
+            ThresholdError::InvalidQuorumBounds {}
+        );
+    }
+
+    #[test]
+    fn test_composite_threshold() {
+        let composite =
+            |majority: PercentageThreshold, quorum: u64, count: u128| Threshold::Composite {
+                thresholds: vec![
+                    Threshold::ThresholdQuorum {
+                        threshold: majority,
+                        quorum: p!(quorum),
+                    },
+                    Threshold::AbsoluteCount {
+                        threshold: Uint128::new(count),
+                    },
+                ],
+            };
+        let t = composite(PercentageThreshold::Majority {}, 20, 3);
+        t.validate().unwrap();
+
+        let invalid = |thresholds: Vec<Threshold>| {
+            assert_eq!(
+                Threshold::Composite { thresholds }.validate().unwrap_err(),
+                ThresholdError::InvalidCompositeThreshold {
+                    max: MAX_COMPOSITE_THRESHOLDS
+                }
+            )
+        };
+        invalid(vec![]);
+        invalid(vec![Threshold::AbsoluteCount {
+            threshold: Uint128::new(3),
+        }]);
+        invalid(vec![t.clone(), t.clone()]);
+        invalid(vec![
+            Threshold::AbsoluteCount {
+                threshold: Uint128::new(3),
+            };
+            MAX_COMPOSITE_THRESHOLDS + 1
+        ]);
+        assert_eq!(
+            composite(PercentageThreshold::Majority {}, 20, 0)
+                .validate()
+                .unwrap_err(),
+            ThresholdError::ZeroThreshold {}
+        );
+
+        assert_eq!(
+            composite(p!(60), 20, 3).strictness_cmp(&t),
+            Some(Ordering::Greater)
+        );
+        assert_eq!(
+            composite(PercentageThreshold::Majority {}, 10, 2).strictness_cmp(&t),
+            Some(Ordering::Less)
+        );
+        assert_eq!(composite(p!(60), 10, 3).strictness_cmp(&t), None);
+        assert_eq!(
+            t.strictness_cmp(&Threshold::AbsoluteCount {
+                threshold: Uint128::new(3)
+            }),
+            None
+        );
+    }
 }
@@ -0,0 +1,191 @@
+use cosmwasm_std::{Decimal, Uint128};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::threshold::{QuorumAdjustment, Threshold};
+
+/// The maximum number of completed proposals whose turnout is
+/// remembered, and so the largest window a dynamic quorum may average
+/// over.
+pub const MAX_TURNOUT_WINDOW: u64 = 50;
+
+/// The turnout of a proposal module's most recently completed
+/// proposals, used to compute dynamic quorums.
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug, Default)]
+#[serde(rename_all = "snake_case")]
+pub struct TurnoutHistory {
+    /// Turnout of recently completed proposals, oldest first.
+    pub recent: Vec<Decimal>,
+}
+
+impl TurnoutHistory {
+    /// Records the turnout of a completed proposal. Proposals with no
+    /// voting power are ignored.
+    pub fn record(&mut self, votes: Uint128, total_power: Uint128) {
+        if total_power.is_zero() {
+            return;
+        }
+        let turnout = Decimal::from_ratio(votes, total_power).min(Decimal::one());
+        self.recent.push(turnout);
+        if self.recent.len() as u64 > MAX_TURNOUT_WINDOW {
+            self.recent.remove(0);
+        }
+    }
+
+    /// The average turnout of the last `window` completed proposals,
+    /// or None if no proposals have completed.
+    pub fn average(&self, window: u64) -> Option<Decimal> {
+        let count = (window as usize).min(self.recent.len());
+        if count == 0 {
+            return None;
+        }
+        let sum = self
+            .recent
+            .iter()
+            .rev()
+            .take(count)
+            .fold(Decimal::zero(), |sum, turnout| sum + *turnout);
+        Some(sum * Decimal::from_ratio(1u64, count as u64))
+    }
+
+    /// Resolves a dynamic quorum threshold using the average turnout
+    /// over its adjustment window. Other thresholds are returned
+    /// unchanged.
+    pub fn resolve(&self, threshold: &Threshold) -> Threshold {
+        match threshold {
+            Threshold::DynamicQuorum { adjustment, .. } => {
+                threshold.resolve(self.average(adjustment.window))
+            }
+            Threshold::Composite { thresholds } => Threshold::Composite {
+                thresholds: thresholds.iter().map(|t| self.resolve(t)).collect(),
+            },
+            threshold => threshold.clone(),
+        }
+    }
+
+    /// Describes the quorum a proposal created with `threshold` would
+    /// have now. For composite thresholds, the first dynamic quorum
+    /// is described.
+    pub fn query(&self, threshold: &Threshold) -> DynamicQuorumResponse {
+        if let Threshold::Composite { thresholds } = threshold {
+            if let Some(dynamic) = thresholds
+                .iter()
+                .find(|t| matches!(t, Threshold::DynamicQuorum { .. }))
+            {
+                return self.query(dynamic);
+            }
+        }
+        match threshold {
+            Threshold::DynamicQuorum {
+                min_quorum,
+                max_quorum,
+                adjustment,
+                ..
+            } => self.query_quorum(*min_quorum, *max_quorum, adjustment),
+            _ => DynamicQuorumResponse {
+                quorum: None,
+                average_turnout: None,
+                samples: self.recent.len() as u64,
+            },
+        }
+    }
+
+    /// Describes the quorum computed from recent turnout with the
+    /// given bounds and adjustment.
+    pub fn query_quorum(
+        &self,
+        min_quorum: Decimal,
+        max_quorum: Decimal,
+        adjustment: &QuorumAdjustment,
+    ) -> DynamicQuorumResponse {
+        let average_turnout = self.average(adjustment.window);
+        DynamicQuorumResponse {
+            quorum: Some(adjustment.quorum(min_quorum, max_quorum, average_turnout)),
+            average_turnout,
+            samples: self.recent.len() as u64,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub struct DynamicQuorumResponse {
+    /// The quorum a proposal created now would have. None if the
+    /// module is not configured with a dynamic quorum.
+    pub quorum: Option<Decimal>,
+    /// The average turnout the quorum was computed from. None if no
+    /// proposals have completed yet.
+    pub average_turnout: Option<Decimal>,
+    /// The number of completed proposals whose turnout is
+    /// remembered.
+    pub samples: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_turnout_average() {
+        let mut history = TurnoutHistory::default();
+        assert_eq!(history.average(3), None);
+
+        history.record(Uint128::new(10), Uint128::new(100));
+        history.record(Uint128::new(30), Uint128::new(100));
+        history.record(Uint128::new(1), Uint128::zero());
+        assert_eq!(history.recent.len(), 2);
+        assert_eq!(history.average(3), Some(Decimal::percent(20)));
+        assert_eq!(history.average(1), Some(Decimal::percent(30)));
+
+        for _ in 0..MAX_TURNOUT_WINDOW {
+            history.record(Uint128::new(50), Uint128::new(100));
+        }
+        assert_eq!(history.recent.len() as u64, MAX_TURNOUT_WINDOW);
+        assert_eq!(
+            history.average(MAX_TURNOUT_WINDOW),
+            Some(Decimal::percent(50))
+        );
+    }
+
+    #[test]
+    fn test_turnout_resolve() {
+        use crate::threshold::PercentageThreshold;
+
+        let threshold = Threshold::DynamicQuorum {
+            threshold: PercentageThreshold::Majority {},
+            min_quorum: Decimal::percent(10),
+            max_quorum: Decimal::percent(40),
+            adjustment: QuorumAdjustment {
+                window: 2,
+                factor: Decimal::percent(50),
+                initial_quorum: None,
+            },
+        };
+        let mut history = TurnoutHistory::default();
+        assert_eq!(
+            history.query(&threshold),
+            DynamicQuorumResponse {
+                quorum: Some(Decimal::percent(10)),
+                average_turnout: None,
+                samples: 0,
+            }
+        );
+
+        history.record(Uint128::new(10), Uint128::new(100));
+        history.record(Uint128::new(50), Uint128::new(100));
+        history.record(Uint128::new(70), Uint128::new(100));
+        assert_eq!(
+            history.resolve(&threshold),
+            Threshold::ThresholdQuorum {
+                threshold: PercentageThreshold::Majority {},
+                quorum: PercentageThreshold::Percent(Decimal::percent(30)),
+            }
+        );
+
+        let fixed = Threshold::AbsoluteCount {
+            threshold: Uint128::new(1),
+        };
+        assert_eq!(history.resolve(&fixed), fixed);
+        assert_eq!(history.query(&fixed).quorum, None);
+    }
+}
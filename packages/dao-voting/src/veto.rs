@@ -1,4 +1,4 @@
-use cosmwasm_std::{Deps, MessageInfo, StdError};
+use cosmwasm_std::{Addr, Deps, MessageInfo, StdError};
 use schemars::JsonSchema;
 use secret_utils::Duration;
 use serde::{Deserialize, Serialize};
@@ -32,6 +32,23 @@ pub enum VetoError {
 
     #[error("Only vetoer can veto a proposal.")]
     Unauthorized {},
+
+    #[error("The veto threshold must be at least one and no more than the number of vetoers.")]
+    InvalidVetoThreshold {},
+
+    #[error("The veto council may not contain duplicate addresses.")]
+    DuplicateVetoer {},
+
+    #[error("This vetoer has already vetoed the proposal.")]
+    AlreadyVetoed {},
+
+    #[error("This vetoer has not vetoed the proposal.")]
+    NotVetoed {},
+
+    #[error(
+        "Members of the veto council have vetoed the proposal, so it can not be executed early."
+    )]
+    VetoesCast {},
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
@@ -42,6 +59,15 @@ pub struct VetoConfig {
     pub timelock_duration: Duration,
     /// The address able to veto proposals.
     pub vetoer: String,
+    /// Additional addresses able to veto proposals. Together with
+    /// `vetoer` they form the veto council.
+    #[serde(default)]
+    pub council: Vec<String>,
+    /// The number of council members that must veto a proposal for
+    /// it to be vetoed. Defaults to one, in which case any single
+    /// vetoer may veto a proposal.
+    #[serde(default = "default_veto_threshold")]
+    pub veto_threshold: u64,
     /// Whether or not the vetoer can execute a proposal early before the
     /// timelock duration has expired
     pub early_execute: bool,
@@ -49,10 +75,25 @@ pub struct VetoConfig {
     pub veto_before_passed: bool,
 }
 
+fn default_veto_threshold() -> u64 {
+    1
+}
+
 impl VetoConfig {
     pub fn validate(&self, deps: &Deps, max_voting_period: &Duration) -> Result<(), VetoError> {
-        // Validate vetoer address.
-        deps.api.addr_validate(&self.vetoer)?;
+        // Validate vetoer addresses.
+        let mut vetoers = self
+            .vetoers()
+            .map(|vetoer| deps.api.addr_validate(vetoer))
+            .collect::<Result<Vec<_>, _>>()?;
+        vetoers.sort();
+        vetoers.dedup();
+        if vetoers.len() != self.council.len() + 1 {
+            return Err(VetoError::DuplicateVetoer {});
+        }
+        if self.veto_threshold == 0 || self.veto_threshold > vetoers.len() as u64 {
+            return Err(VetoError::InvalidVetoThreshold {});
+        }
 
         // Validate duration units match voting period.
         match (self.timelock_duration, max_voting_period) {
@@ -73,15 +114,47 @@ impl VetoConfig {
         }
     }
 
-    /// Checks whether the message sender is the vetoer.
+    /// All addresses able to veto proposals.
+    pub fn vetoers(&self) -> impl Iterator<Item = &String> {
+        std::iter::once(&self.vetoer).chain(self.council.iter())
+    }
+
+    /// Whether `addr` is a member of the veto council.
+    pub fn is_vetoer(&self, addr: &Addr) -> bool {
+        self.vetoers().any(|vetoer| vetoer == addr.as_str())
+    }
+
+    /// Checks whether the message sender is a vetoer.
     pub fn check_is_vetoer(&self, info: &MessageInfo) -> Result<(), VetoError> {
-        if self.vetoer == info.sender {
+        if self.is_vetoer(&info.sender) {
             Ok(())
         } else {
             Err(VetoError::Unauthorized {})
         }
     }
 
+    /// Records `vetoer`'s veto in a proposal's `vetoes`. Returns true
+    /// if the veto threshold has been reached and the proposal is
+    /// vetoed.
+    pub fn cast_veto(&self, vetoes: &mut Vec<Addr>, vetoer: &Addr) -> Result<bool, VetoError> {
+        if vetoes.contains(vetoer) {
+            return Err(VetoError::AlreadyVetoed {});
+        }
+        vetoes.push(vetoer.clone());
+        Ok(vetoes.len() as u64 >= self.veto_threshold)
+    }
+
+    /// Checks whether a proposal with `vetoes` may be executed early
+    /// by a vetoer. With a council, early execution is only allowed
+    /// while no member has vetoed the proposal.
+    pub fn check_no_vetoes_cast(&self, vetoes: &[Addr]) -> Result<(), VetoError> {
+        if vetoes.is_empty() {
+            Ok(())
+        } else {
+            Err(VetoError::VetoesCast {})
+        }
+    }
+
     /// Checks whether veto_before_passed is enabled, errors if not
     pub fn check_veto_before_passed_enabled(&self) -> Result<(), VetoError> {
         if self.veto_before_passed {
@@ -91,3 +164,80 @@ impl VetoConfig {
         }
     }
 }
+
+/// Removes `vetoer`'s veto from a proposal's `vetoes`.
+pub fn withdraw_veto(vetoes: &mut Vec<Addr>, vetoer: &Addr) -> Result<(), VetoError> {
+    let len = vetoes.len();
+    vetoes.retain(|v| v != vetoer);
+    if vetoes.len() == len {
+        return Err(VetoError::NotVetoed {});
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::mock_dependencies;
+
+    fn council(veto_threshold: u64) -> VetoConfig {
+        VetoConfig {
+            timelock_duration: Duration::Height(10),
+            vetoer: "vetoer_a".to_string(),
+            council: vec!["vetoer_b".to_string(), "vetoer_c".to_string()],
+            early_execute: false,
+            veto_before_passed: false,
+            veto_threshold,
+        }
+    }
+
+    #[test]
+    fn test_validate_council() {
+        let deps = mock_dependencies();
+        let max = Duration::Height(100);
+        assert_eq!(council(2).validate(&deps.as_ref(), &max), Ok(()));
+        assert_eq!(
+            council(4).validate(&deps.as_ref(), &max),
+            Err(VetoError::InvalidVetoThreshold {})
+        );
+        assert_eq!(
+            council(0).validate(&deps.as_ref(), &max),
+            Err(VetoError::InvalidVetoThreshold {})
+        );
+        let mut duplicate = council(1);
+        duplicate.council.push("vetoer_a".to_string());
+        assert_eq!(
+            duplicate.validate(&deps.as_ref(), &max),
+            Err(VetoError::DuplicateVetoer {})
+        );
+    }
+
+    #[test]
+    fn test_cast_and_withdraw_vetoes() {
+        let config = council(2);
+        let (a, b) = (Addr::unchecked("vetoer_a"), Addr::unchecked("vetoer_b"));
+        assert!(config.is_vetoer(&b));
+        assert!(!config.is_vetoer(&Addr::unchecked("vetoer_d")));
+
+        let mut vetoes = vec![];
+        assert_eq!(config.cast_veto(&mut vetoes, &a), Ok(false));
+        assert_eq!(
+            config.cast_veto(&mut vetoes, &a),
+            Err(VetoError::AlreadyVetoed {})
+        );
+        assert_eq!(
+            config.check_no_vetoes_cast(&vetoes),
+            Err(VetoError::VetoesCast {})
+        );
+
+        assert_eq!(withdraw_veto(&mut vetoes, &a), Ok(()));
+        assert_eq!(withdraw_veto(&mut vetoes, &a), Err(VetoError::NotVetoed {}));
+
+        assert_eq!(config.cast_veto(&mut vetoes, &a), Ok(false));
+        assert_eq!(config.cast_veto(&mut vetoes, &b), Ok(true));
+
+        // Single vetoer configs are vetoed by one veto.
+        let mut vetoes = vec![];
+        assert_eq!(council(1).cast_veto(&mut vetoes, &b), Ok(true));
+    }
+}
@@ -4,7 +4,7 @@ use schemars::JsonSchema;
 use secret_utils::Duration;
 use serde::{Deserialize, Serialize};
 
-use crate::threshold::PercentageThreshold;
+use crate::threshold::{PercentageThreshold, Threshold};
 
 // We multiply by this when calculating needed_votes in order to round
 // up properly.
@@ -129,6 +129,107 @@ pub fn does_vote_count_fail(
     }
 }
 
+/// Returns true iff `threshold` is sure to be met by `votes` out of
+/// `total_power`, even if no future sequence of possible votes can
+/// cause it to fail. Once voting has `expired` a quorum's passing
+/// threshold is compared to the votes cast rather than to all
+/// possible votes.
+pub fn is_threshold_passed(
+    threshold: &Threshold,
+    votes: &Votes,
+    total_power: Uint128,
+    expired: bool,
+) -> bool {
+    match *threshold {
+        Threshold::AbsolutePercentage { percentage } => {
+            let options = total_power - votes.abstain;
+            does_vote_count_pass(votes.yes, options, percentage)
+        }
+        Threshold::ThresholdQuorum { threshold, quorum } => {
+            if !does_vote_count_pass(votes.total(), total_power, quorum) {
+                return false;
+            }
+            let options = if expired {
+                votes.total() - votes.abstain
+            } else {
+                total_power - votes.abstain
+            };
+            does_vote_count_pass(votes.yes, options, threshold)
+        }
+        Threshold::AbsoluteCount { threshold } => votes.yes >= threshold,
+        // A composite threshold is passed once all of its thresholds
+        // are.
+        Threshold::Composite { ref thresholds } => thresholds
+            .iter()
+            .all(|threshold| is_threshold_passed(threshold, votes, total_power, expired)),
+        // Dynamic quorums are resolved when proposals are created, so
+        // are never voted on directly.
+        Threshold::DynamicQuorum { .. } => false,
+    }
+}
+
+/// Returns true iff `threshold` can no longer be met by `votes` out
+/// of `total_power`.
+pub fn is_threshold_rejected(
+    threshold: &Threshold,
+    votes: &Votes,
+    total_power: Uint128,
+    expired: bool,
+) -> bool {
+    match *threshold {
+        Threshold::AbsolutePercentage { percentage } => {
+            let options = total_power - votes.abstain;
+            does_no_vote_fail(votes.no, options, percentage)
+        }
+        Threshold::ThresholdQuorum { threshold, quorum } => {
+            match (
+                does_vote_count_pass(votes.total(), total_power, quorum),
+                expired,
+            ) {
+                // Voting has closed without reaching quorum.
+                (false, true) => true,
+                // Voting has closed, so only the votes cast are
+                // considered.
+                (true, true) => {
+                    let options = votes.total() - votes.abstain;
+                    does_no_vote_fail(votes.no, options, threshold)
+                }
+                // Otherwise, all possible votes are considered.
+                (_, false) => {
+                    let options = total_power - votes.abstain;
+                    does_no_vote_fail(votes.no, options, threshold)
+                }
+            }
+        }
+        Threshold::AbsoluteCount { threshold } => {
+            // If all the outstanding votes voting yes would not cause
+            // the threshold to be met then it is rejected.
+            let outstanding_votes = total_power - votes.total();
+            votes.yes + outstanding_votes < threshold
+        }
+        // A composite threshold is rejected as soon as any of its
+        // thresholds can no longer be met.
+        Threshold::Composite { ref thresholds } => thresholds
+            .iter()
+            .any(|threshold| is_threshold_rejected(threshold, votes, total_power, expired)),
+        Threshold::DynamicQuorum { .. } => false,
+    }
+}
+
+/// Returns true iff `no_votes` are enough to fail `threshold` out of
+/// `options`.
+fn does_no_vote_fail(no_votes: Uint128, options: Uint128, threshold: PercentageThreshold) -> bool {
+    // With a 100% passing threshold inverting the threshold
+    // (`Decimal::one() - threshold`) gives a 0% requirement for no
+    // votes, which zero no votes do indeed meet. A single no vote
+    // fails such a threshold instead, and with no possible votes
+    // (zero voting power or all abstain) it can't be met at all.
+    if threshold == PercentageThreshold::Percent(Decimal::percent(100)) {
+        return options.is_zero() || !no_votes.is_zero();
+    }
+    does_vote_count_fail(no_votes, options, threshold)
+}
+
 impl Votes {
     /// Constructs an zero'd out votes struct.
     pub fn zero() -> Self {
@@ -256,6 +357,59 @@ pub fn validate_voting_period(
 mod test {
     use super::*;
 
+    #[test]
+    fn threshold_tally() {
+        let majority = PercentageThreshold::Majority {};
+        let quorum = Threshold::ThresholdQuorum {
+            threshold: majority,
+            quorum: PercentageThreshold::Percent(Decimal::percent(20)),
+        };
+        let total_power = Uint128::new(100);
+        let mut votes = Votes::with_yes(Uint128::new(15));
+
+        // Quorum not reached.
+        assert!(!is_threshold_passed(&quorum, &votes, total_power, false));
+        assert!(!is_threshold_rejected(&quorum, &votes, total_power, false));
+        assert!(is_threshold_rejected(&quorum, &votes, total_power, true));
+
+        // Once expired only the votes cast count.
+        votes.add_vote(Vote::No, Uint128::new(10));
+        assert!(!is_threshold_passed(&quorum, &votes, total_power, false));
+        assert!(is_threshold_passed(&quorum, &votes, total_power, true));
+
+        // Composite thresholds pass once all of their thresholds do,
+        // and are rejected once any is. The count can no longer be
+        // reached here even though the quorum has passed.
+        let composite = Threshold::Composite {
+            thresholds: vec![
+                quorum,
+                Threshold::AbsoluteCount {
+                    threshold: Uint128::new(95),
+                },
+            ],
+        };
+        assert!(!is_threshold_passed(&composite, &votes, total_power, true));
+        assert!(is_threshold_rejected(&composite, &votes, total_power, true));
+
+        // A single no vote fails a 100% threshold.
+        let unanimous = Threshold::AbsolutePercentage {
+            percentage: PercentageThreshold::Percent(Decimal::percent(100)),
+        };
+        assert!(is_threshold_rejected(&unanimous, &votes, total_power, false));
+        assert!(is_threshold_rejected(
+            &unanimous,
+            &Votes::zero(),
+            Uint128::zero(),
+            false
+        ));
+        assert!(!is_threshold_rejected(
+            &unanimous,
+            &Votes::zero(),
+            total_power,
+            false
+        ));
+    }
+
     #[test]
     fn count_votes() {
         let mut votes = Votes::with_yes(Uint128::new(5));